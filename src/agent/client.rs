@@ -0,0 +1,127 @@
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    domain::{
+        job::dto::{
+            AppendNodeJobLogsBody, AppendNodeJobLogsResponse, ClaimNodeJobsBody,
+            ClaimNodeJobsResponse, ReportNodeJobBody,
+        },
+        node::dto::{
+            NodeHeartbeatBody, NodeHeartbeatResponse, RegisterNodeBody, RegisterNodeResponse,
+        },
+    },
+    errors::{self, ErrorResponse},
+};
+
+/*
+agent가 batchman 서버의 /api/nodes API를 호출하는 HTTP 클라이언트입니다.
+*/
+#[derive(Clone)]
+pub struct ServerClient {
+    http: reqwest::Client,
+    server_url: String,
+    token: String,
+}
+
+impl ServerClient {
+    pub fn new(server_url: String, token: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            server_url,
+            token,
+        }
+    }
+
+    pub async fn register_node(
+        &self,
+        body: &RegisterNodeBody,
+    ) -> errors::Result<RegisterNodeResponse> {
+        self.post("/nodes/register", body).await
+    }
+
+    pub async fn heartbeat(
+        &self,
+        node_id: i64,
+        body: &NodeHeartbeatBody,
+    ) -> errors::Result<NodeHeartbeatResponse> {
+        self.post(&format!("/nodes/{}/heartbeat", node_id), body)
+            .await
+    }
+
+    pub async fn claim_jobs(
+        &self,
+        node_id: i64,
+        body: &ClaimNodeJobsBody,
+    ) -> errors::Result<ClaimNodeJobsResponse> {
+        self.post(&format!("/nodes/{}/claim", node_id), body).await
+    }
+
+    pub async fn report_job(
+        &self,
+        node_id: i64,
+        job_id: i64,
+        body: &ReportNodeJobBody,
+    ) -> errors::Result<()> {
+        self.post(&format!("/nodes/{}/jobs/{}/report", node_id, job_id), body)
+            .await
+    }
+
+    pub async fn append_job_logs(
+        &self,
+        node_id: i64,
+        job_id: i64,
+        body: &AppendNodeJobLogsBody,
+    ) -> errors::Result<AppendNodeJobLogsResponse> {
+        self.post(&format!("/nodes/{}/jobs/{}/logs", node_id, job_id), body)
+            .await
+    }
+
+    /*
+    서버가 돌려준 에러 코드 중 agent가 처리해야 하는 것은 같은 에러로 되돌리고, 나머지는 AgentRequestFailed로 감쌉니다.
+    */
+    async fn post<B: Serialize, R: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> errors::Result<R> {
+        let response = self
+            .http
+            .post(format!("{}/api{}", self.server_url, path))
+            .bearer_auth(&self.token)
+            .json(body)
+            .send()
+            .await
+            .map_err(|error| errors::Error::AgentRequestFailed(error.to_string()))?;
+
+        let status = response.status();
+
+        if status.is_success() {
+            return response
+                .json::<R>()
+                .await
+                .map_err(|error| errors::Error::AgentRequestFailed(error.to_string()));
+        }
+
+        let text = response.text().await.unwrap_or_default();
+
+        let Ok(error_response) = serde_json::from_str::<ErrorResponse>(&text) else {
+            return Err(errors::Error::AgentRequestFailed(format!(
+                "{} {}: {}",
+                path, status, text
+            )));
+        };
+
+        Err(match error_response.error_code.as_str() {
+            "NODE_NOT_FOUND" => errors::Error::NodeNotFound,
+            "JOB_NOT_ASSIGNED_TO_NODE" => errors::Error::JobNotAssignedToNode,
+            "INVALID_JOB_STATUS_TRANSITION" => {
+                errors::Error::InvalidJobStatusTransition(error_response.message)
+            }
+            "AGENT_UNAUTHORIZED" => errors::Error::AgentUnauthorized,
+            _ => errors::Error::AgentRequestFailed(format!(
+                "{} {}: {}",
+                path, status, error_response.message
+            )),
+        })
+    }
+}
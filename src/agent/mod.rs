@@ -0,0 +1,252 @@
+/*
+원격 실행 노드에서 동작하는 agent입니다.
+중앙 batchman 서버에 node로 등록한 뒤 heartbeat를 보내고, HTTP로 job을 claim하여 로컬 ContainerRepository로 실행합니다.
+job 상태 변경과 컨테이너 로그는 서버로 다시 보고합니다.
+*/
+pub mod client;
+pub mod runner;
+
+use std::collections::BTreeMap;
+
+use crate::{config::default_worker_id, errors};
+
+// agent가 동시에 실행하는 job의 기본 개수
+pub const DEFAULT_AGENT_CAPACITY: i32 = 5;
+
+pub const DEFAULT_SERVER_URL: &str = "http://localhost:13939";
+
+pub const DEFAULT_HEARTBEAT_INTERVAL_SECONDS: u64 = 5;
+pub const DEFAULT_CLAIM_RETRY_INTERVAL_SECONDS: u64 = 5;
+pub const DEFAULT_TRACK_INTERVAL_SECONDS: u64 = 2;
+pub const DEFAULT_LOG_BATCH_LINES: usize = 500;
+
+#[derive(Clone)]
+pub struct AgentConfig {
+    pub server_url: String,                // batchman server (BATCHMAN_SERVER_URL)
+    pub name: String, // node name (BATCHMAN_WORKER_ID, defaults to the host name)
+    pub labels: BTreeMap<String, String>, // advertised labels (BATCHMAN_AGENT_LABELS, "key=value,key=value")
+    pub capacity: i32,                    // max number of concurrent jobs (BATCHMAN_AGENT_CAPACITY)
+    pub token: String,                    // shared token (BATCHMAN_AGENT_TOKEN, required)
+    pub heartbeat_interval_seconds: u64, // must be shorter than the server's heartbeat timeout and lease (BATCHMAN_AGENT_HEARTBEAT_INTERVAL_SECONDS)
+    pub claim_retry_interval_seconds: u64, // wait before claiming again when no job was assigned or the server is unreachable (BATCHMAN_AGENT_CLAIM_RETRY_INTERVAL_SECONDS)
+    pub track_interval_seconds: u64, // how often running containers are inspected and their logs sent (BATCHMAN_AGENT_TRACK_INTERVAL_SECONDS)
+    pub log_batch_lines: usize, // max log lines sent in one request (BATCHMAN_AGENT_LOG_BATCH_LINES)
+}
+
+// 토큰이 로그에 남지 않도록 token은 설정 여부만 출력합니다.
+impl std::fmt::Debug for AgentConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AgentConfig")
+            .field("server_url", &self.server_url)
+            .field("name", &self.name)
+            .field("labels", &self.labels)
+            .field("capacity", &self.capacity)
+            .field("token", &"***")
+            .field(
+                "heartbeat_interval_seconds",
+                &self.heartbeat_interval_seconds,
+            )
+            .field(
+                "claim_retry_interval_seconds",
+                &self.claim_retry_interval_seconds,
+            )
+            .field("track_interval_seconds", &self.track_interval_seconds)
+            .field("log_batch_lines", &self.log_batch_lines)
+            .finish()
+    }
+}
+
+impl AgentConfig {
+    // https이거나 같은 호스트(loopback)의 서버인지 확인합니다.
+    pub fn is_secure_server_url(&self) -> bool {
+        let Some(rest) = self.server_url.strip_prefix("http://") else {
+            return self.server_url.starts_with("https://");
+        };
+
+        let host = rest.split('/').next().unwrap_or_default();
+        let host = match host.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => host,
+            _ => host,
+        };
+
+        matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+    }
+
+    pub fn from_env() -> errors::Result<Self> {
+        let server_url = std::env::var("BATCHMAN_SERVER_URL")
+            .unwrap_or_else(|_| DEFAULT_SERVER_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        let labels = match std::env::var("BATCHMAN_AGENT_LABELS") {
+            Ok(value) => parse_labels(&value)?,
+            Err(_) => BTreeMap::new(),
+        };
+
+        // 서버는 토큰 없이 agent API를 열지 않음
+        let token = std::env::var("BATCHMAN_AGENT_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| {
+                errors::Error::InvalidNode("BATCHMAN_AGENT_TOKEN must be set".to_string())
+            })?;
+
+        let capacity = match std::env::var("BATCHMAN_AGENT_CAPACITY") {
+            Ok(value) => value
+                .trim()
+                .parse::<i32>()
+                .ok()
+                .filter(|capacity| *capacity > 0)
+                .ok_or_else(|| {
+                    errors::Error::InvalidNode(format!(
+                        "BATCHMAN_AGENT_CAPACITY must be a positive integer (got {:?})",
+                        value
+                    ))
+                })?,
+            Err(_) => DEFAULT_AGENT_CAPACITY,
+        };
+
+        Ok(Self {
+            server_url,
+            name: default_worker_id(),
+            labels,
+            capacity,
+            token,
+            heartbeat_interval_seconds: positive_env(
+                "BATCHMAN_AGENT_HEARTBEAT_INTERVAL_SECONDS",
+                DEFAULT_HEARTBEAT_INTERVAL_SECONDS,
+            )?,
+            claim_retry_interval_seconds: positive_env(
+                "BATCHMAN_AGENT_CLAIM_RETRY_INTERVAL_SECONDS",
+                DEFAULT_CLAIM_RETRY_INTERVAL_SECONDS,
+            )?,
+            track_interval_seconds: positive_env(
+                "BATCHMAN_AGENT_TRACK_INTERVAL_SECONDS",
+                DEFAULT_TRACK_INTERVAL_SECONDS,
+            )?,
+            log_batch_lines: positive_env(
+                "BATCHMAN_AGENT_LOG_BATCH_LINES",
+                DEFAULT_LOG_BATCH_LINES as u64,
+            )? as usize,
+        })
+    }
+
+    /*
+    heartbeat 주기가 서버의 heartbeat timeout과 lease보다 짧은지 확인합니다.
+    그렇지 않으면 서버가 살아있는 node를 drain하거나, lease가 만료된 job을 다른 worker가 가져갑니다.
+    */
+    pub fn check_heartbeat_interval(
+        &self,
+        heartbeat_timeout_seconds: u64,
+        lease_duration_seconds: u64,
+    ) -> errors::Result<()> {
+        let limit = heartbeat_timeout_seconds.min(lease_duration_seconds);
+
+        if self.heartbeat_interval_seconds >= limit {
+            return Err(errors::Error::InvalidNode(format!(
+                "BATCHMAN_AGENT_HEARTBEAT_INTERVAL_SECONDS ({}) must be shorter than the server's node.heartbeat_timeout_seconds ({}) and runner.lease_duration_seconds ({})",
+                self.heartbeat_interval_seconds, heartbeat_timeout_seconds, lease_duration_seconds
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+// 양의 정수 환경 변수를 읽습니다. 설정되지 않았다면 default를 사용합니다.
+fn positive_env(name: &str, default: u64) -> errors::Result<u64> {
+    let Ok(value) = std::env::var(name) else {
+        return Ok(default);
+    };
+
+    value
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|value| *value > 0)
+        .ok_or_else(|| {
+            errors::Error::InvalidNode(format!(
+                "{} must be a positive integer (got {:?})",
+                name, value
+            ))
+        })
+}
+
+/*
+"gpu=true,zone=a" 형식의 라벨 목록을 파싱합니다.
+*/
+fn parse_labels(value: &str) -> errors::Result<BTreeMap<String, String>> {
+    let mut labels = BTreeMap::new();
+
+    for pair in value
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+    {
+        let Some((key, value)) = pair.split_once('=') else {
+            return Err(errors::Error::InvalidNode(format!(
+                "label must be key=value (got {:?})",
+                pair
+            )));
+        };
+
+        let key = key.trim();
+
+        if key.is_empty() {
+            return Err(errors::Error::InvalidNode(format!(
+                "label key must not be empty (got {:?})",
+                pair
+            )));
+        }
+
+        labels.insert(key.to_string(), value.trim().to_string());
+    }
+
+    Ok(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_labels() {
+        let labels = parse_labels("gpu=true, zone = a ,,").unwrap();
+
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels["gpu"], "true");
+        assert_eq!(labels["zone"], "a");
+
+        assert!(parse_labels("").unwrap().is_empty());
+        assert!(parse_labels("gpu").is_err());
+        assert!(parse_labels("=true").is_err());
+    }
+
+    #[test]
+    fn test_is_secure_server_url() {
+        let config = |server_url: &str| AgentConfig {
+            server_url: server_url.to_string(),
+            name: "node".to_string(),
+            labels: BTreeMap::new(),
+            capacity: 1,
+            token: "token".to_string(),
+            heartbeat_interval_seconds: DEFAULT_HEARTBEAT_INTERVAL_SECONDS,
+            claim_retry_interval_seconds: DEFAULT_CLAIM_RETRY_INTERVAL_SECONDS,
+            track_interval_seconds: DEFAULT_TRACK_INTERVAL_SECONDS,
+            log_batch_lines: DEFAULT_LOG_BATCH_LINES,
+        };
+
+        assert!(config("https://batchman.example.com").is_secure_server_url());
+        assert!(config("http://localhost:13939").is_secure_server_url());
+        assert!(config("http://127.0.0.1").is_secure_server_url());
+        assert!(config("http://[::1]:13939/").is_secure_server_url());
+        assert!(!config("http://batchman:13939").is_secure_server_url());
+        assert!(!config("http://localhost.example.com").is_secure_server_url());
+
+        let mut config = config("https://batchman.example.com");
+        assert!(config.check_heartbeat_interval(30, 600).is_ok());
+        config.heartbeat_interval_seconds = 30;
+        assert!(config.check_heartbeat_interval(30, 600).is_err());
+        assert!(config.check_heartbeat_interval(60, 20).is_err());
+    }
+}
@@ -0,0 +1,990 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicI64, Ordering},
+    },
+};
+
+use crate::{
+    domain::{
+        container::{
+            ContainerRepository,
+            dao::{
+                ContainerLogLine, InspectContainerParams, InspectImageParams, JOB_ID_LABEL,
+                ListContainersParams, PullImageParams, RunContainerParams,
+            },
+            repository::docker::ContainerDockerRepository,
+            stop_or_kill_container,
+        },
+        job::{
+            dto::{
+                AppendNodeJobLogsBody, ClaimNodeJobsBody, JobStatusDto, NodeJobAssignment,
+                NodeJobLogLine, ReportNodeJobBody,
+            },
+            entities::job::ExitReason,
+            service::{DEFAULT_STOP_GRACE_PERIOD_SECONDS, classify_exit},
+        },
+        node::dto::{CancelJobDto, NodeHeartbeatBody, RegisterNodeBody},
+        task_definition::entities::task_definition::ImagePullPolicy,
+    },
+    errors,
+};
+
+use super::{AgentConfig, client::ServerClient};
+
+/// agent가 실행 중인 job의 상태입니다.
+#[derive(Debug, Default)]
+struct AgentJobState {
+    container_id: Option<String>,
+    cancel_requested: bool, // the server asked to cancel the job
+    stop_requested: bool,   // the agent already asked docker to stop the container
+}
+
+pub struct Agent {
+    config: AgentConfig,
+    client: ServerClient,
+    container_repository: Arc<dyn ContainerRepository + Send + Sync>,
+    node_id: AtomicI64,
+    slots: Arc<tokio::sync::Semaphore>,
+    jobs: Mutex<BTreeMap<i64, AgentJobState>>,
+}
+
+/*
+agent를 서버에 등록하고, heartbeat/claim 루프를 실행합니다.
+재시작 전에 실행하던 컨테이너는 다시 추적하여 종료 상태와 로그를 보고합니다.
+*/
+pub async fn run_agent(config: AgentConfig) -> errors::Result<()> {
+    // claim 응답에 secret 값이 들어가므로 다른 호스트의 서버와는 TLS로 통신해야 함
+    if !config.is_secure_server_url() {
+        log::warn!(
+            "BATCHMAN_SERVER_URL {} is plain http; the agent token and job secrets are sent unencrypted. Use https (e.g. behind a TLS reverse proxy).",
+            config.server_url
+        );
+    }
+
+    let agent = Arc::new(Agent::new(config));
+
+    agent.register().await?;
+    agent.clone().adopt_containers().await;
+
+    tokio::join!(agent.clone().heartbeat_loop(), agent.clone().claim_loop());
+
+    Ok(())
+}
+
+impl Agent {
+    pub fn new(config: AgentConfig) -> Self {
+        Self::with_container_repository(config, Arc::new(ContainerDockerRepository::new()))
+    }
+
+    pub fn with_container_repository(
+        config: AgentConfig,
+        container_repository: Arc<dyn ContainerRepository + Send + Sync>,
+    ) -> Self {
+        Self {
+            client: ServerClient::new(config.server_url.clone(), config.token.clone()),
+            container_repository,
+            node_id: AtomicI64::new(0),
+            slots: Arc::new(tokio::sync::Semaphore::new(config.capacity.max(1) as usize)),
+            jobs: Mutex::new(BTreeMap::new()),
+            config,
+        }
+    }
+
+    fn node_id(&self) -> i64 {
+        self.node_id.load(Ordering::SeqCst)
+    }
+
+    /*
+    서버에 node로 등록합니다. 서버에 연결될 때까지 계속 재시도합니다.
+    heartbeat 주기가 서버의 heartbeat timeout이나 lease보다 길면 에러를 반환합니다.
+    */
+    async fn register(&self) -> errors::Result<()> {
+        loop {
+            let result = self
+                .client
+                .register_node(&RegisterNodeBody {
+                    name: self.config.name.clone(),
+                    labels: self.config.labels.clone(),
+                    capacity: self.config.capacity,
+                })
+                .await;
+
+            match result {
+                Ok(response) => {
+                    self.config.check_heartbeat_interval(
+                        response.heartbeat_timeout_seconds,
+                        response.lease_duration_seconds,
+                    )?;
+
+                    self.node_id.store(response.node_id, Ordering::SeqCst);
+                    log::info!(
+                        "Registered as node {} ({})",
+                        response.node_id,
+                        self.config.name
+                    );
+                    return Ok(());
+                }
+                Err(error) => {
+                    log::error!("Failed to register node: {}", error);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(
+                        self.config.claim_retry_interval_seconds,
+                    ))
+                    .await;
+                }
+            }
+        }
+    }
+
+    /*
+    batchman 라벨이 붙은 로컬 컨테이너를 다시 추적합니다.
+    이미 종료 처리된 job이라면 서버가 보고를 거부하므로 곧바로 추적을 멈춥니다.
+    */
+    async fn adopt_containers(self: Arc<Self>) {
+        let containers = match self
+            .container_repository
+            .list_containers(ListContainersParams {
+                label: JOB_ID_LABEL.to_string(),
+            })
+            .await
+        {
+            Ok(containers) => containers,
+            Err(error) => {
+                log::error!("Failed to list containers on startup: {}", error);
+                return;
+            }
+        };
+
+        for container in containers {
+            let Some(job_id) = container
+                .labels
+                .get(JOB_ID_LABEL)
+                .and_then(|job_id| job_id.parse::<i64>().ok())
+            else {
+                continue;
+            };
+
+            self.jobs.lock().unwrap().insert(
+                job_id,
+                AgentJobState {
+                    container_id: Some(container.container_id.clone()),
+                    ..Default::default()
+                },
+            );
+
+            // capacity를 넘더라도 이미 실행 중인 컨테이너는 추적해야 하므로 슬롯이 없어도 진행
+            let permit = self.slots.clone().try_acquire_owned().ok();
+            let agent = self.clone();
+
+            tokio::spawn(async move {
+                agent.track_job(job_id, container.container_id).await;
+                agent.jobs.lock().unwrap().remove(&job_id);
+                drop(permit);
+            });
+        }
+    }
+
+    async fn heartbeat_loop(self: Arc<Self>) {
+        loop {
+            let job_ids = self
+                .jobs
+                .lock()
+                .unwrap()
+                .keys()
+                .copied()
+                .collect::<Vec<_>>();
+
+            let body = NodeHeartbeatBody {
+                running_jobs: job_ids.len() as i32,
+                job_ids,
+            };
+
+            let result = self.client.heartbeat(self.node_id(), &body).await;
+
+            match result {
+                Ok(response) => {
+                    for cancel_job in response.cancel_jobs {
+                        self.handle_cancel(cancel_job);
+                    }
+                }
+                // 서버에서 node가 삭제된 경우 다시 등록
+                Err(errors::Error::NodeNotFound) => {
+                    if let Err(error) = self.register().await {
+                        log::error!("Failed to register node again: {}", error);
+                    }
+                }
+                Err(error) => log::error!("Failed to send heartbeat: {}", error),
+            }
+
+            tokio::time::sleep(tokio::time::Duration::from_secs(
+                self.config.heartbeat_interval_seconds,
+            ))
+            .await;
+        }
+    }
+
+    /*
+    서버가 취소를 요청한 job의 컨테이너를 종료합니다. 컨테이너가 아직 없다면 실행 직전에 취소합니다.
+    서버와 같이 재시도 후 kill로 강제 종료하고, 그래도 실패하면 다음 heartbeat의 취소 요청에서 다시 시도합니다.
+    */
+    fn handle_cancel(self: &Arc<Self>, cancel_job: CancelJobDto) {
+        let container_id = {
+            let mut jobs = self.jobs.lock().unwrap();
+
+            // 이 agent가 실행 중이지 않은 job (재시작 전에 컨테이너가 정리된 경우 등)
+            let Some(state) = jobs.get_mut(&cancel_job.job_id) else {
+                return;
+            };
+
+            state.cancel_requested = true;
+
+            if state.container_id.is_none() {
+                state.container_id = cancel_job.container_id;
+            }
+
+            if state.stop_requested {
+                return;
+            }
+
+            let Some(container_id) = state.container_id.clone() else {
+                return;
+            };
+
+            state.stop_requested = true;
+            container_id
+        };
+
+        let agent = self.clone();
+
+        tokio::spawn(async move {
+            let result = stop_or_kill_container(
+                agent.container_repository.as_ref(),
+                &container_id,
+                cancel_job.grace_period_seconds,
+            )
+            .await;
+
+            if let Err(error) = result {
+                log::error!(
+                    "Failed to stop container of job {}: {}",
+                    cancel_job.job_id,
+                    error
+                );
+
+                if let Some(state) = agent.jobs.lock().unwrap().get_mut(&cancel_job.job_id) {
+                    state.stop_requested = false;
+                }
+            }
+        });
+    }
+
+    async fn claim_loop(self: Arc<Self>) {
+        loop {
+            // 실행 슬롯이 하나 이상 빌 때까지 대기
+            let Ok(first_permit) = self.slots.clone().acquire_owned().await else {
+                break;
+            };
+            let available_slots = 1 + self.slots.available_permits();
+
+            let result = self
+                .client
+                .claim_jobs(
+                    self.node_id(),
+                    &ClaimNodeJobsBody {
+                        limit: available_slots as u64,
+                    },
+                )
+                .await;
+
+            let assignments = match result {
+                Ok(response) => response.jobs,
+                Err(error) => {
+                    log::error!("Failed to claim jobs: {}", error);
+                    Vec::new()
+                }
+            };
+
+            if assignments.is_empty() {
+                drop(first_permit);
+                tokio::time::sleep(tokio::time::Duration::from_secs(
+                    self.config.claim_retry_interval_seconds,
+                ))
+                .await;
+                continue;
+            }
+
+            let mut first_permit = Some(first_permit);
+
+            for assignment in assignments {
+                // job이 끝날 때까지 슬롯을 점유
+                let permit = match first_permit.take() {
+                    Some(permit) => permit,
+                    None => match self.slots.clone().acquire_owned().await {
+                        Ok(permit) => permit,
+                        Err(_) => break,
+                    },
+                };
+
+                self.jobs
+                    .lock()
+                    .unwrap()
+                    .entry(assignment.job_id)
+                    .or_default();
+
+                let agent = self.clone();
+
+                tokio::spawn(async move {
+                    let job_id = assignment.job_id;
+                    agent.run_job(assignment).await;
+                    agent.jobs.lock().unwrap().remove(&job_id);
+                    drop(permit);
+                });
+            }
+        }
+    }
+
+    /*
+    배정받은 job의 컨테이너를 실행하고 종료될 때까지 추적합니다. 실행에 실패하면 job을 Failed로 보고합니다.
+    */
+    async fn run_job(&self, assignment: NodeJobAssignment) {
+        let job_id = assignment.job_id;
+
+        match self.launch_job(assignment).await {
+            Ok(Some(container_id)) => self.track_job(job_id, container_id).await,
+            Ok(None) => {}
+            Err(error) => {
+                log::error!("Error processing job {}: {}", job_id, error);
+
+                let result = self
+                    .client
+                    .report_job(
+                        self.node_id(),
+                        job_id,
+                        &ReportNodeJobBody {
+                            status: JobStatusDto::Failed,
+                            reason: Some(error.to_string()),
+                            exit_reason: Some(ExitReason::RuntimeError),
+                            error_code: Some(error.error_code()),
+                            error_message: Some(error.to_string()),
+                            ..Default::default()
+                        },
+                    )
+                    .await;
+
+                if let Err(error) = result {
+                    log::error!("Error reporting job {} failure: {}", job_id, error);
+                }
+            }
+        }
+    }
+
+    /*
+    이미지를 준비하고 컨테이너를 실행합니다. 서버의 run_pending_job과 같은 순서로 상태를 보고합니다.
+    컨테이너 실행 전에 취소되었다면 None을 반환합니다.
+    */
+    async fn launch_job(&self, assignment: NodeJobAssignment) -> errors::Result<Option<String>> {
+        let job_id = assignment.job_id;
+        let task_definition = assignment.task_definition;
+        let image = task_definition.image.clone();
+
+        // 1. pull 정책에 따라 이미지 준비
+        let image_pull_policy = task_definition.image_pull_policy.unwrap_or_default();
+
+        let needs_pull = match image_pull_policy {
+            ImagePullPolicy::Always => true,
+            ImagePullPolicy::IfNotPresent | ImagePullPolicy::Never => {
+                let exists = self
+                    .container_repository
+                    .image_exists(InspectImageParams {
+                        image: image.clone(),
+                    })
+                    .await?;
+
+                if !exists && image_pull_policy == ImagePullPolicy::Never {
+                    return Err(errors::Error::ContainerImageNotPresent(image));
+                }
+
+                !exists
+            }
+        };
+
+        if needs_pull {
+            self.try_report_job(
+                job_id,
+                ReportNodeJobBody {
+                    status: JobStatusDto::Pulling,
+                    reason: Some(format!("Pulling image {}", image)),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+            self.container_repository
+                .pull_image(PullImageParams {
+                    image: image.clone(),
+                    auth: assignment.registry_auth,
+                })
+                .await?;
+
+            let starting = self
+                .try_report_job(
+                    job_id,
+                    ReportNodeJobBody {
+                        status: JobStatusDto::Starting,
+                        reason: Some("Image pulled".to_string()),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+
+            if !starting {
+                self.finalize_cancelled_job(job_id).await?;
+                return Ok(None);
+            }
+        }
+
+        // 2. heartbeat로 취소 요청을 받았다면 컨테이너를 실행하지 않음
+        if self.is_cancel_requested(job_id) {
+            self.finalize_cancelled_job(job_id).await?;
+            return Ok(None);
+        }
+
+        // 3. 컨테이너 실행 (이전에 실행한 컨테이너가 남아있다면 그것을 사용)
+        let existing_container = self
+            .container_repository
+            .list_containers(ListContainersParams {
+                label: format!("{}={}", JOB_ID_LABEL, job_id),
+            })
+            .await?
+            .into_iter()
+            .next();
+
+        let (container_id, running_reason) = match existing_container {
+            Some(container) => {
+                let reason = format!("Re-attached to container {}", container.container_id);
+                (container.container_id, reason)
+            }
+            None => {
+                let container_id = self
+                    .container_repository
+                    .run_container(RunContainerParams {
+                        job_id,
+                        task_definition: task_definition.clone(),
+                        secret_env: assignment.secret_env,
+                    })
+                    .await?
+                    .container_id;
+                let reason = format!(
+                    "Container {} started on node {}",
+                    container_id, self.config.name
+                );
+                (container_id, reason)
+            }
+        };
+
+        if let Some(state) = self.jobs.lock().unwrap().get_mut(&job_id) {
+            state.container_id = Some(container_id.clone());
+        }
+
+        // 4. job 상태를 Running으로 보고
+        let running = self
+            .try_report_job(
+                job_id,
+                ReportNodeJobBody {
+                    status: JobStatusDto::Running,
+                    reason: Some(running_reason),
+                    container_id: Some(container_id.clone()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        // 컨테이너 실행 중에 취소된 경우, 컨테이너를 종료하고 추적 중에 Cancelled로 보고
+        if !running {
+            if let Some(state) = self.jobs.lock().unwrap().get_mut(&job_id) {
+                state.stop_requested = true;
+            }
+
+            stop_or_kill_container(
+                self.container_repository.as_ref(),
+                &container_id,
+                task_definition
+                    .stop_grace_period_seconds
+                    .map(|seconds| seconds as u32)
+                    .unwrap_or(DEFAULT_STOP_GRACE_PERIOD_SECONDS),
+            )
+            .await?;
+        }
+
+        Ok(Some(container_id))
+    }
+
+    /*
+    컨테이너가 종료될 때까지 로그를 서버로 보내고, 종료되면 종료 사유를 분류하여 보고합니다.
+    */
+    async fn track_job(&self, job_id: i64, container_id: String) {
+        let mut log_offset = 0;
+
+        loop {
+            let inspect_result = self
+                .container_repository
+                .inspect_container(InspectContainerParams {
+                    container_id: container_id.clone(),
+                })
+                .await;
+
+            let inspect_result = match inspect_result {
+                Ok(inspect_result) => inspect_result,
+                Err(error) => {
+                    log::error!("Failed to inspect container of job {}: {}", job_id, error);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(
+                        self.config.track_interval_seconds,
+                    ))
+                    .await;
+                    continue;
+                }
+            };
+
+            // 종료 여부를 확인한 뒤에 로그를 보내야 마지막 로그까지 빠짐없이 전달됨
+            match self
+                .push_logs(job_id, &inspect_result.log_path, log_offset)
+                .await
+            {
+                Ok(next_offset) => log_offset = next_offset,
+                Err(errors::Error::JobNotAssignedToNode) => {
+                    log::warn!("Job {} is no longer assigned to this node", job_id);
+                    return;
+                }
+                Err(error) => log::warn!("Failed to send logs of job {}: {}", job_id, error),
+            }
+
+            if inspect_result.state.running
+                || inspect_result.state.restarting
+                || inspect_result.state.paused
+            {
+                tokio::time::sleep(tokio::time::Duration::from_secs(
+                    self.config.track_interval_seconds,
+                ))
+                .await;
+                continue;
+            }
+
+            let stop_requested = self
+                .jobs
+                .lock()
+                .unwrap()
+                .get(&job_id)
+                .is_some_and(|state| state.stop_requested || state.cancel_requested);
+
+            let outcome = classify_exit(&inspect_result.state, stop_requested);
+
+            let result = self
+                .client
+                .report_job(
+                    self.node_id(),
+                    job_id,
+                    &ReportNodeJobBody {
+                        status: outcome.status.into(),
+                        reason: Some(format!(
+                            "Container exited with code {} ({:?})",
+                            inspect_result.state.exit_code.unwrap_or(-1),
+                            outcome.exit_reason
+                        )),
+                        exit_code: inspect_result.state.exit_code,
+                        exit_reason: Some(outcome.exit_reason),
+                        error_message: outcome.error_message,
+                        // 종료 시각을 알 수 없는 경우 docker는 0001-01-01T00:00:00Z를 반환함
+                        finished_at: inspect_result
+                            .state
+                            .finished_at
+                            .filter(|finished_at| finished_at.timestamp() > 0),
+                        // Running 보고가 취소 요청과 겹쳐 거절되었다면 컨테이너 id가 여기서 기록됨
+                        container_id: Some(container_id.clone()),
+                        ..Default::default()
+                    },
+                )
+                .await;
+
+            match result {
+                // 이미 종료 처리되었거나 다른 worker가 가져간 job
+                Ok(()) | Err(errors::Error::InvalidJobStatusTransition(_)) => return,
+                Err(errors::Error::JobNotAssignedToNode) => return,
+                Err(error) => {
+                    // 서버에 보고할 수 있을 때까지 재시도
+                    log::error!("Failed to report exit of job {}: {}", job_id, error);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(
+                        self.config.track_interval_seconds,
+                    ))
+                    .await;
+                }
+            }
+        }
+    }
+
+    /*
+    docker 로그 파일에서 offset 이후의 줄을 읽어 서버로 보내고, 서버가 다음으로 기대하는 offset을 반환합니다.
+    */
+    async fn push_logs(&self, job_id: i64, log_path: &str, offset: u64) -> errors::Result<u64> {
+        let mut offset = offset;
+
+        loop {
+            let lines = crate::utils::read_lines_range(
+                log_path,
+                offset as usize,
+                self.config.log_batch_lines,
+            )?;
+
+            if lines.is_empty() {
+                return Ok(offset);
+            }
+
+            let line_count = lines.len();
+
+            let logs = lines
+                .into_iter()
+                .map(|line| {
+                    let line = serde_json::from_str::<ContainerLogLine>(&line)?;
+                    Ok(NodeJobLogLine {
+                        time: line.time,
+                        message: line.log,
+                    })
+                })
+                .collect::<errors::Result<Vec<_>>>()?;
+
+            let response = self
+                .client
+                .append_job_logs(
+                    self.node_id(),
+                    job_id,
+                    &AppendNodeJobLogsBody { offset, logs },
+                )
+                .await?;
+
+            // 서버에 이미 있는 로그였다면 서버가 알려준 위치부터 다시 보냄
+            let sent_all = response.next_offset == offset + line_count as u64;
+            offset = response.next_offset;
+
+            if sent_all && line_count < self.config.log_batch_lines {
+                return Ok(offset);
+            }
+        }
+    }
+
+    /*
+    상태 변경을 보고하고, 서버가 상태 전이 규칙에 따라 거부했거나 job이 더 이상 이 node에 배정되어 있지 않다면 false를 반환합니다.
+    */
+    async fn try_report_job(&self, job_id: i64, body: ReportNodeJobBody) -> errors::Result<bool> {
+        match self.client.report_job(self.node_id(), job_id, &body).await {
+            Ok(()) => Ok(true),
+            Err(
+                errors::Error::InvalidJobStatusTransition(_) | errors::Error::JobNotAssignedToNode,
+            ) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /*
+    컨테이너가 실행되기 전에 취소된 job을 Cancelled로 보고합니다.
+    */
+    async fn finalize_cancelled_job(&self, job_id: i64) -> errors::Result<()> {
+        self.try_report_job(
+            job_id,
+            ReportNodeJobBody {
+                status: JobStatusDto::Cancelled,
+                reason: Some("Cancelled before the container was started".to_string()),
+                exit_reason: Some(ExitReason::KilledByUser),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    fn is_cancel_requested(&self, job_id: i64) -> bool {
+        self.jobs
+            .lock()
+            .unwrap()
+            .get(&job_id)
+            .is_some_and(|state| state.cancel_requested)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agent::{
+            DEFAULT_CLAIM_RETRY_INTERVAL_SECONDS, DEFAULT_HEARTBEAT_INTERVAL_SECONDS,
+            DEFAULT_LOG_BATCH_LINES, DEFAULT_TRACK_INTERVAL_SECONDS,
+        },
+        config::Config,
+        context::{Context, SharedContext},
+        domain::{
+            container::repository::fake::FakeContainerRepository,
+            job::{
+                dao::ListJobsParams,
+                dto::{StopJobBody, StopJobRequest, SubmitJobBody, SubmitJobRequest},
+                entities::job::{self, JobStatus},
+            },
+            task_definition::dto::CreateDefinitionRequest,
+        },
+        routes,
+    };
+
+    const AGENT_TOKEN: &str = "agent-token";
+
+    /*
+    agent API를 제공하는 서버를 띄우고 agent 설정을 반환합니다. 서버의 백그라운드 루프는 실행하지 않습니다.
+    */
+    async fn spawn_server() -> (SharedContext, AgentConfig) {
+        let mut config = Config::default();
+        config.node.agent_token = Some(AGENT_TOKEN.to_string());
+
+        let context = Context::for_test(config, Arc::new(FakeContainerRepository::new())).await;
+
+        let router = axum::Router::new().nest(
+            "/api",
+            routes::api_router()
+                .into_router()
+                .layer(axum::Extension(context.clone())),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let agent_config = AgentConfig {
+            server_url: format!("http://{}", address),
+            name: "node-a".to_string(),
+            labels: BTreeMap::new(),
+            capacity: 2,
+            token: AGENT_TOKEN.to_string(),
+            heartbeat_interval_seconds: DEFAULT_HEARTBEAT_INTERVAL_SECONDS,
+            claim_retry_interval_seconds: DEFAULT_CLAIM_RETRY_INTERVAL_SECONDS,
+            track_interval_seconds: DEFAULT_TRACK_INTERVAL_SECONDS,
+            log_batch_lines: DEFAULT_LOG_BATCH_LINES,
+        };
+
+        (context, agent_config)
+    }
+
+    /*
+    spawn_server로 띄운 서버에, FakeContainerRepository로 컨테이너를 실행하는 agent를 등록합니다.
+    */
+    async fn spawn_agent(containers: Arc<FakeContainerRepository>) -> (SharedContext, Arc<Agent>) {
+        let (context, agent_config) = spawn_server().await;
+
+        let agent = Arc::new(Agent::with_container_repository(agent_config, containers));
+        agent.register().await.unwrap();
+
+        (context, agent)
+    }
+
+    async fn submit_job(context: &SharedContext) -> i64 {
+        let task_definition_id = context
+            .task_definition_service
+            .create_task_definition(CreateDefinitionRequest {
+                request_body: serde_json::from_value(serde_json::json!({
+                    "name": "task",
+                    "description": "task",
+                    "image": "alpine",
+                }))
+                .unwrap(),
+            })
+            .await
+            .unwrap()
+            .task_definition_id;
+
+        context
+            .job_service
+            .submit_job(SubmitJobRequest {
+                request_body: SubmitJobBody {
+                    task_definition_id,
+                    job_name: "job".to_string(),
+                    log_expire_after: None,
+                },
+                schedule_id: None,
+            })
+            .await
+            .unwrap()
+            .job_id
+    }
+
+    async fn get_job(context: &SharedContext, job_id: i64) -> job::Model {
+        context
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: vec![job_id],
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .pop()
+            .unwrap()
+    }
+
+    async fn stop_job(context: &SharedContext, job_id: i64) {
+        context
+            .job_service
+            .stop_job(StopJobRequest {
+                request_body: StopJobBody {
+                    job_id,
+                    reason: None,
+                    requested_by: None,
+                    grace_period_seconds: None,
+                },
+            })
+            .await
+            .unwrap();
+    }
+
+    // agent의 추적 주기(track_interval_seconds)와 종료 재시도 backoff를 기다릴 수 있도록 넉넉하게 대기
+    async fn wait_for_status(context: &SharedContext, job_id: i64, status: JobStatus) {
+        tokio::time::timeout(tokio::time::Duration::from_secs(20), async {
+            while get_job(context, job_id).await.status != status {
+                tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("job did not reach the status in time");
+    }
+
+    /*
+    agent가 job을 claim하여 컨테이너를 실행하고, Running과 컨테이너 종료 결과를 서버에 보고하는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_agent_claims_runs_and_reports_job() {
+        let containers = Arc::new(FakeContainerRepository::new());
+        let (context, agent) = spawn_agent(containers.clone()).await;
+        let job_id = submit_job(&context).await;
+
+        let claim_loop = tokio::spawn(agent.clone().claim_loop());
+
+        wait_for_status(&context, job_id, JobStatus::Running).await;
+
+        let container_id = format!("container-{}", job_id);
+        let job = get_job(&context, job_id).await;
+        assert_eq!(job.container_id.as_deref(), Some(container_id.as_str()));
+        assert_eq!(job.worker_id.as_deref(), Some("node-a"));
+        assert!(job.node_id.is_some());
+        assert_eq!(containers.state().pulled_images, vec!["alpine".to_string()]);
+        assert_eq!(containers.state().runs.len(), 1);
+
+        // 컨테이너가 종료되면 종료 결과를 보고
+        containers.state().running.insert(container_id, false);
+        wait_for_status(&context, job_id, JobStatus::Failed).await;
+
+        let job = get_job(&context, job_id).await;
+        assert_eq!(job.exit_code, Some(137));
+        assert_eq!(job.exit_reason, Some(ExitReason::NonZeroExit));
+        assert!(job.finished_at.is_some());
+
+        claim_loop.abort();
+    }
+
+    /*
+    컨테이너 실행 전에 취소된 job은 컨테이너를 실행하지 않고 Cancelled로 보고하는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_agent_cancels_job_before_container_exists() {
+        let containers = Arc::new(FakeContainerRepository::with_blocking_pulls());
+        let (context, agent) = spawn_agent(containers.clone()).await;
+        let job_id = submit_job(&context).await;
+
+        let claim_loop = tokio::spawn(agent.clone().claim_loop());
+
+        wait_for_status(&context, job_id, JobStatus::Pulling).await;
+
+        // 서버의 취소 요청이 heartbeat 응답으로 전달됨
+        stop_job(&context, job_id).await;
+        agent.handle_cancel(CancelJobDto {
+            job_id,
+            container_id: None,
+            grace_period_seconds: 1,
+        });
+        assert_eq!(containers.state().stop_calls, 0);
+
+        containers.release_pulls(1);
+        wait_for_status(&context, job_id, JobStatus::Cancelled).await;
+
+        let job = get_job(&context, job_id).await;
+        assert_eq!(job.exit_reason, Some(ExitReason::KilledByUser));
+        assert!(containers.state().runs.is_empty());
+
+        claim_loop.abort();
+    }
+
+    /*
+    실행 중인 컨테이너의 종료가 끝내 실패하면 다음 취소 요청에서 다시 종료하고, 종료되면 Cancelled로 보고하는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_agent_cancels_running_container_and_retries_failed_stop() {
+        let containers = Arc::new(FakeContainerRepository::new());
+        let (context, agent) = spawn_agent(containers.clone()).await;
+        let job_id = submit_job(&context).await;
+
+        let claim_loop = tokio::spawn(agent.clone().claim_loop());
+
+        wait_for_status(&context, job_id, JobStatus::Running).await;
+        stop_job(&context, job_id).await;
+
+        let cancel_job = CancelJobDto {
+            job_id,
+            container_id: Some(format!("container-{}", job_id)),
+            grace_period_seconds: 1,
+        };
+
+        // 1. stop과 kill이 모두 실패하면 stop_requested를 되돌림
+        {
+            let mut state = containers.state();
+            state.stop_failures = 3;
+            state.kill_failures = 3;
+        }
+        agent.handle_cancel(cancel_job.clone());
+
+        tokio::time::timeout(tokio::time::Duration::from_secs(10), async {
+            while agent.jobs.lock().unwrap()[&job_id].stop_requested {
+                tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("stop_requested was not cleared in time");
+
+        assert_eq!(containers.state().stop_calls, 3);
+        assert_eq!(containers.state().kill_calls, 3);
+        assert_eq!(
+            get_job(&context, job_id).await.status,
+            JobStatus::Cancelling
+        );
+
+        // 2. 다음 취소 요청에서 다시 종료하고, tracker가 Cancelled로 보고
+        agent.handle_cancel(cancel_job);
+        wait_for_status(&context, job_id, JobStatus::Cancelled).await;
+
+        let job = get_job(&context, job_id).await;
+        assert_eq!(job.exit_reason, Some(ExitReason::KilledByUser));
+        assert_eq!(containers.state().stop_calls, 4);
+
+        claim_loop.abort();
+    }
+
+    /*
+    heartbeat 주기가 서버의 heartbeat timeout(기본 30초)보다 짧지 않으면 등록하지 않는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_agent_rejects_heartbeat_interval_longer_than_server_timeout() {
+        let (_context, mut agent_config) = spawn_server().await;
+        agent_config.heartbeat_interval_seconds = 30;
+
+        let agent = Agent::with_container_repository(
+            agent_config,
+            Arc::new(FakeContainerRepository::new()),
+        );
+
+        assert!(matches!(
+            agent.register().await,
+            Err(errors::Error::InvalidNode(_))
+        ));
+    }
+}
@@ -1,76 +1,28 @@
-pub(crate) mod background;
-pub(crate) mod context;
-pub(crate) mod db;
-pub(crate) mod domain;
-pub(crate) mod errors;
-pub(crate) mod types;
-pub(crate) mod utils;
-pub(crate) mod web;
-
 use std::sync::Arc;
 
 use axum::{
     Extension, Router,
-    http::Uri,
-    response::Response,
-    routing::{delete, get, patch, post},
+    extract::OriginalUri,
+    http::{Method, Uri},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use batchman::{
+    background::{self, scheduler::ScheduleCDCEvent},
+    config::Config,
+    context::{self, SharedContext},
+    db::{self, migration},
+    domain::secret::cipher::SecretCipher,
+    errors, request_id, routes, web,
 };
-use background::scheduler::ScheduleCDCEvent;
-use context::SharedContext;
-use db::setup_schema;
+use tokio_util::sync::CancellationToken;
 
 pub fn app(context: SharedContext) -> Router {
-    let api_router = Router::new()
-        .route("/healthz", get(root))
-        .route("/database-check", get(database_check))
-        .route(
-            "/task-definitions",
-            get(domain::task_definition::routes::http::list_task_definitions),
-        )
-        .route(
-            "/task-definitions",
-            post(domain::task_definition::routes::http::create_task_definition),
-        )
-        .route(
-            "/task-definitions/{task_definition_id}",
-            patch(domain::task_definition::routes::http::patch_task_definition),
-        )
-        .route(
-            "/task-definitions/{task_definition_id}",
-            delete(domain::task_definition::routes::http::delete_task_definition),
-        )
-        .route("/jobs/submit", post(domain::job::routes::http::submit_job))
-        .route("/jobs/stop", post(domain::job::routes::http::stop_job))
-        .route("/jobs", get(domain::job::routes::http::list_jobs))
-        .route(
-            "/jobs/{job_id}/logs",
-            get(domain::job::routes::http::list_job_logs),
-        )
-        .route(
-            "/jobs/{job_id}/logs/count",
-            get(domain::job::routes::http::count_job_logs),
-        )
-        .route(
-            "/jobs/{job_id}/logs/tailing",
-            get(domain::job::routes::http::tail_job_logs),
-        )
-        .route(
-            "/schedules",
-            get(domain::schedule::routes::http::list_schedules),
-        )
-        .route(
-            "/schedules",
-            post(domain::schedule::routes::http::create_schedule),
-        )
-        .route(
-            "/schedules/{schedule_id}",
-            patch(domain::schedule::routes::http::patch_schedule),
-        )
-        .route(
-            "/schedules/{schedule_id}",
-            delete(domain::schedule::routes::http::delete_schedule),
-        )
-        .layer(Extension(context));
+    let api_router = routes::api_router()
+        .into_router()
+        .fallback(api_route_not_found)
+        .layer(Extension(context))
+        .layer(axum::middleware::from_fn(request_id::request_id_middleware));
 
     Router::new()
         // `GET /` goes to `root`
@@ -83,48 +35,513 @@ pub fn app(context: SharedContext) -> Router {
 
 #[tokio::main]
 async fn main() {
-    let connection = db::create_database_connection().await.unwrap();
-    setup_schema(&connection).await;
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let command = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    if !matches!(command.as_slice(), [] | ["migrate"] | ["migrate", "status"]) {
+        eprintln!("Usage: api [migrate [status]]");
+        std::process::exit(2);
+    }
+
+    let config = Config::load().expect("Failed to load configuration");
+
+    let connection = db::create_database_connection(&config.database)
+        .await
+        .unwrap();
+
+    match command.as_slice() {
+        ["migrate"] => return run_migrate_command(&connection).await,
+        ["migrate", "status"] => return print_migration_status(&connection).await,
+        _ => {}
+    }
+
+    // 데이터베이스가 이 버전보다 새로운 스키마라면 시작하지 않음
+    if config.database.auto_migrate {
+        migration::run_migrations(&connection)
+            .await
+            .expect("Failed to migrate database");
+    } else {
+        migration::check_schema_is_up_to_date(&connection)
+            .await
+            .expect("Database schema is not up to date");
+    }
+
+    let secret_cipher =
+        SecretCipher::from_config(&config.secret).expect("Failed to load secret master key");
+
+    if secret_cipher.is_none() {
+        log::warn!("secret.key (BATCHMAN_SECRET_KEY) is not set. Secrets are disabled.");
+    }
 
     let (schedule_cdc_sender, schedule_cdc_receiver) =
         tokio::sync::mpsc::channel::<ScheduleCDCEvent>(8);
 
+    let bind_address = config.server.bind_address.clone();
+
     let context = Arc::new(context::Context::new(
+        config,
         connection.clone(), // Pass cloned connection for context
         schedule_cdc_sender,
+        secret_cipher,
     ));
 
     let router = app(context.clone()); // Use the app function
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:13939")
-        .await
-        .unwrap();
+    let listener = tokio::net::TcpListener::bind(&bind_address).await.unwrap();
+
+    tokio::spawn(handle_shutdown_signal(context.shutdown_token.clone()));
+
+    // 백그라운드 루프가 정리를 마칠 때까지는 agent 보고 등을 받을 수 있도록 HTTP 서버를 유지
+    let background_stopped = CancellationToken::new();
 
     let (server, _) = tokio::join!(
-        axum::serve(listener, router), // Use the router from app()
-        background::start_background_loop(context, schedule_cdc_receiver),
+        axum::serve(listener, router) // Use the router from app()
+            .with_graceful_shutdown(background_stopped.clone().cancelled_owned()),
+        async {
+            background::start_background_loop(context, schedule_cdc_receiver).await;
+            background_stopped.cancel();
+        },
     );
 
     server.unwrap();
+
+    log::info!("Shutdown complete");
 }
 
-async fn root() -> &'static str {
-    "Hello, World!"
+/*
+`api migrate`: 적용하지 않은 마이그레이션을 적용하고 종료합니다.
+*/
+async fn run_migrate_command(connection: &sea_orm::DatabaseConnection) {
+    match migration::run_migrations(connection).await {
+        Ok(applied) if applied.is_empty() => println!("Database schema is up to date"),
+        Ok(applied) => {
+            for status in applied {
+                println!("Applied migration {} {}", status.version, status.name);
+            }
+        }
+        Err(error) => {
+            eprintln!("Failed to migrate database: {}", String::from(&error));
+            std::process::exit(1);
+        }
+    }
 }
 
-async fn database_check(Extension(state): Extension<SharedContext>) -> &'static str {
-    state.connection.ping().await.unwrap();
+/*
+`api migrate status`: 마이그레이션별 적용 여부를 출력합니다.
+*/
+async fn print_migration_status(connection: &sea_orm::DatabaseConnection) {
+    match migration::migration_status(connection).await {
+        Ok(statuses) => {
+            for status in statuses {
+                let applied_at = status
+                    .applied_at
+                    .map(|applied_at| applied_at.to_rfc3339())
+                    .unwrap_or("pending".to_string());
+
+                println!("{:>4} {:<24} {}", status.version, status.name, applied_at);
+            }
+        }
+        Err(error) => {
+            eprintln!("Failed to read migration status: {}", String::from(&error));
+            std::process::exit(1);
+        }
+    }
+}
+
+/*
+SIGTERM 또는 Ctrl-C를 받으면 shutdown_token을 취소합니다.
+정리 중에 한 번 더 받으면 기다리지 않고 바로 종료합니다.
+*/
+async fn handle_shutdown_signal(shutdown_token: CancellationToken) {
+    let mut terminate = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+        .expect("Failed to install SIGTERM handler");
+
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate.recv() => {}
+    }
+
+    log::info!("Shutdown requested, no longer accepting new jobs");
+    shutdown_token.cancel();
+
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate.recv() => {}
+    }
 
-    "OK"
+    log::warn!("Received a second shutdown signal, exiting immediately");
+    std::process::exit(1);
 }
 
-async fn fallback(uri: Uri) -> Response {
+// 등록되지 않은 API 경로도 다른 API 에러와 같은 JSON 본문(request_id 포함)으로 응답
+async fn api_route_not_found(method: Method, OriginalUri(uri): OriginalUri) -> errors::Error {
+    errors::Error::RouteNotFound(format!("{} {}", method, uri.path()))
+}
+
+async fn fallback(method: Method, uri: Uri) -> Response {
     if uri.path().starts_with("/api") {
-        return axum::response::Response::builder()
-            .status(axum::http::StatusCode::NOT_FOUND)
-            .body(axum::body::Body::from("Not Found"))
-            .unwrap();
+        return errors::Error::RouteNotFound(format!("{} {}", method, uri.path())).into_response();
     }
 
     web::index_html().await
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use batchman::{
+        config::{Config, DatabaseConfig},
+        context::{Context, SharedContext},
+        db::{self, migration},
+        domain::{
+            job::{
+                dao::{
+                    AppendJobLogsParams, ClaimPendingJobsParams, CreateJobParams, PatchJobParams,
+                    TransitionJobParams,
+                },
+                entities::job::JobStatus,
+            },
+            secret::cipher::SecretCipher,
+        },
+        routes,
+    };
+    use serde_json::{Value, json};
+
+    /*
+    in-memory SQLite를 사용하는 서버를 띄우고 주소를 반환합니다. 백그라운드 루프는 실행하지 않습니다.
+    */
+    async fn spawn_app(config: Config) -> String {
+        spawn_app_with_context(config).await.0
+    }
+
+    /*
+    spawn_app과 같지만, 테스트 데이터를 직접 넣을 수 있도록 서버의 context도 함께 반환합니다.
+    */
+    async fn spawn_app_with_context(mut config: Config) -> (String, SharedContext) {
+        config.database = DatabaseConfig {
+            url: "sqlite::memory:".to_string(),
+            // in-memory 데이터베이스는 커넥션마다 따로 생성되므로 하나만 사용
+            max_connections: 1,
+            min_connections: 1,
+            max_lifetime_seconds: 3600,
+            idle_timeout_seconds: 3600,
+            ..Default::default()
+        };
+
+        let connection = db::create_database_connection(&config.database)
+            .await
+            .unwrap();
+        migration::run_migrations(&connection).await.unwrap();
+
+        let (schedule_cdc_sender, _) = tokio::sync::mpsc::channel(8);
+        let context = Arc::new(Context::new(
+            config,
+            connection,
+            schedule_cdc_sender,
+            Some(SecretCipher::new(&[7; 32]).unwrap()),
+        ));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let app = super::app(context.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        (format!("http://{}/api", address), context)
+    }
+
+    async fn post(
+        base_url: &str,
+        path: &str,
+        token: Option<&str>,
+        body: Value,
+    ) -> (reqwest::StatusCode, String) {
+        let mut request = reqwest::Client::new()
+            .post(format!("{}{}", base_url, path))
+            .json(&body);
+
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await.unwrap();
+        (response.status(), response.text().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_agent_routes_require_token() {
+        const SECRET_VALUE: &str = "s3cr3t-value";
+        const TOKEN: &str = "agent-token";
+
+        // 토큰이 설정되지 않으면 agent API는 열리지 않음
+        let base_url = spawn_app(Config::default()).await;
+        let register_body = json!({ "name": "node-1", "labels": {}, "capacity": 1 });
+        let (status, body) = post(&base_url, "/nodes/register", None, register_body.clone()).await;
+        assert_eq!(status, 503, "{}", body);
+        assert!(body.contains("AGENT_TOKEN_NOT_CONFIGURED"));
+
+        let mut config = Config::default();
+        config.node.agent_token = Some(TOKEN.to_string());
+        let base_url = spawn_app(config).await;
+
+        let (status, body) = post(
+            &base_url,
+            "/secrets",
+            None,
+            json!({ "name": "db-password", "value": SECRET_VALUE }),
+        )
+        .await;
+        assert_eq!(status, 200, "{}", body);
+
+        let (status, body) = post(
+            &base_url,
+            "/task-definitions",
+            None,
+            json!({
+                "name": "task",
+                "description": "task",
+                "image": "alpine",
+                "secrets": { "DB_PASSWORD": "db-password" },
+            }),
+        )
+        .await;
+        assert_eq!(status, 200, "{}", body);
+        let task_definition_id =
+            serde_json::from_str::<Value>(&body).unwrap()["task_definition_id"].clone();
+
+        let (status, body) = post(
+            &base_url,
+            "/jobs/submit",
+            None,
+            json!({ "task_definition_id": task_definition_id, "job_name": "job" }),
+        )
+        .await;
+        assert_eq!(status, 200, "{}", body);
+
+        let (status, body) = post(&base_url, "/nodes/register", None, register_body.clone()).await;
+        assert_eq!(status, 401, "{}", body);
+
+        let (status, body) = post(&base_url, "/nodes/register", Some(TOKEN), register_body).await;
+        assert_eq!(status, 200, "{}", body);
+        let node_id = serde_json::from_str::<Value>(&body).unwrap()["node_id"].clone();
+        let claim_path = format!("/nodes/{}/claim", node_id);
+
+        for token in [None, Some("wrong-token")] {
+            let (status, body) = post(&base_url, &claim_path, token, json!({ "limit": 1 })).await;
+            assert_eq!(status, 401, "{}", body);
+            assert!(body.contains("AGENT_UNAUTHORIZED"));
+            assert!(!body.contains(SECRET_VALUE));
+        }
+
+        // 인증 실패한 요청은 job을 가져가지 않았으므로 올바른 토큰으로 claim할 수 있음
+        let (status, body) = post(&base_url, &claim_path, Some(TOKEN), json!({ "limit": 1 })).await;
+        assert_eq!(status, 200, "{}", body);
+        assert!(body.contains(SECRET_VALUE));
+    }
+
+    /*
+    등록되지 않은 API 경로는 request_id가 담긴 JSON 에러로, 그 외 경로는 웹 UI로 응답하는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_unknown_api_route_returns_json_error() {
+        let base_url = spawn_app(Config::default()).await;
+        let client = reqwest::Client::new();
+
+        for path in ["/unknown", "/jobs/1/unknown"] {
+            let response = client
+                .get(format!("{}{}", base_url, path))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), 404);
+
+            let request_id = response.headers()["x-request-id"]
+                .to_str()
+                .unwrap()
+                .to_string();
+            let body = response.json::<Value>().await.unwrap();
+            assert_eq!(body["error_code"], "ROUTE_NOT_FOUND");
+            assert_eq!(
+                body["message"],
+                format!("No API route for GET /api{}", path)
+            );
+            assert_eq!(body["request_id"], request_id.as_str());
+        }
+
+        let response = client
+            .get(base_url.replace("/api", "/jobs"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(response.text().await.unwrap().contains("<html"));
+    }
+
+    /*
+    offset 계산이 overflow되는 page_number는 모든 목록 조회에서 400 VALIDATION_FAILED로 응답하는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_list_rejects_overflowing_page_number() {
+        let base_url = spawn_app(Config::default()).await;
+        let client = reqwest::Client::new();
+
+        for path in [
+            "/jobs",
+            "/task-definitions",
+            "/schedules",
+            "/secrets",
+            "/registry-credentials",
+        ] {
+            let response = client
+                .get(format!("{}{}?page_number={}", base_url, path, u64::MAX))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), 400, "{}", path);
+
+            let body = response.json::<Value>().await.unwrap();
+            assert_eq!(body["error_code"], "VALIDATION_FAILED", "{}", path);
+            assert_eq!(body["details"][0]["field"], "page_number", "{}", path);
+        }
+    }
+
+    /*
+    문서의 모든 operation이 app()에서 같은 method로 route되는지 확인합니다.
+    path 파라미터는 1로 채우고, 405이거나 ROUTE_NOT_FOUND이면 route가 없는 것입니다.
+    */
+    #[tokio::test]
+    async fn test_every_documented_operation_is_routed() {
+        let base_url = spawn_app(Config::default()).await;
+        let client = reqwest::Client::new();
+
+        let operations = routes::api_router().operations().to_vec();
+        assert!(operations.iter().any(|operation| operation.path == "/jobs"));
+
+        for operation in operations {
+            let path = operation
+                .path
+                .split('/')
+                .map(|segment| match segment.starts_with('{') {
+                    true => "1",
+                    false => segment,
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+
+            let response = client
+                .request(operation.method.clone(), format!("{}{}", base_url, path))
+                .json(&json!({}))
+                .send()
+                .await
+                .unwrap();
+            let status = response.status();
+
+            // SSE 응답은 끝나지 않으므로 본문을 읽지 않음
+            let body = match status.is_success() && operation.path.ends_with("/tailing") {
+                true => String::new(),
+                false => response.text().await.unwrap(),
+            };
+
+            assert_ne!(status, 405, "{} {}", operation.method, operation.path);
+            assert!(
+                !body.contains("ROUTE_NOT_FOUND"),
+                "{} {} is documented but not routed",
+                operation.method,
+                operation.path
+            );
+        }
+    }
+
+    /*
+    tailing은 없는 job이면 JSON 에러로 응답하고, 끝난 job은 남은 로그를 이벤트로 모두 보낸 뒤 스트림을 닫는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_tail_job_logs_streams_logs_until_job_finishes() {
+        let (base_url, context) = spawn_app_with_context(Config::default()).await;
+        let client = reqwest::Client::new();
+
+        let response = client
+            .get(format!("{}/jobs/999/logs/tailing", base_url))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+        let body = response.json::<Value>().await.unwrap();
+        assert_eq!(body["error_code"], "JOB_NOT_FOUND");
+        assert!(body["request_id"].is_string());
+
+        // 원격 node가 실행하고 로그를 보낸 뒤 끝난 job
+        let job_id = context
+            .job_repository
+            .create_job(CreateJobParams {
+                name: "job".to_string(),
+                task_definition_id: 1,
+                status: JobStatus::Pending,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        context
+            .job_repository
+            .claim_pending_jobs(ClaimPendingJobsParams {
+                worker_id: "node-1".to_string(),
+                node_id: Some(1),
+                limit: 1,
+                excluded_task_definition_ids: vec![],
+                lease_duration: chrono::Duration::minutes(1),
+            })
+            .await
+            .unwrap();
+        context
+            .job_repository
+            .append_job_logs(AppendJobLogsParams {
+                job_id,
+                offset: 0,
+                lines: vec![
+                    (chrono::Utc::now(), "first\n".to_string()),
+                    (chrono::Utc::now(), "second\n".to_string()),
+                ],
+            })
+            .await
+            .unwrap();
+        for to_status in [JobStatus::Running, JobStatus::Succeeded] {
+            context
+                .job_repository
+                .transition_job(TransitionJobParams {
+                    to_status,
+                    reason: None,
+                    patch: PatchJobParams {
+                        job_id,
+                        exit_code: Some(0),
+                        ..Default::default()
+                    },
+                })
+                .await
+                .unwrap();
+        }
+
+        let response = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            client
+                .get(format!("{}/jobs/{}/logs/tailing", base_url, job_id))
+                .send(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body = tokio::time::timeout(std::time::Duration::from_secs(5), response.text())
+            .await
+            .expect("stream was not closed after the job finished")
+            .unwrap();
+        let logs = body
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .map(|data| serde_json::from_str::<Value>(data).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0]["index"], 0);
+        assert_eq!(logs[1]["message"], "second\n");
+    }
+}
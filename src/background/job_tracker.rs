@@ -1,59 +1,268 @@
+use tokio_util::sync::CancellationToken;
+
 use crate::{
     context::SharedContext,
     domain::job::{
-        dao::{ListJobsParams, PatchJobParams},
-        entities::job::JobStatus,
+        dao::{PatchJobParams, TransitionJobParams},
+        entities::job::{ExitReason, JobStatus},
     },
 };
 
-pub async fn start_status_tracker_loop(context: SharedContext) {
+/*
+실행 중인 job의 컨테이너 상태를 추적합니다.
+종료 시에는 실행 중인 job을 기다리는 동안에도 상태를 기록해야 하므로, 다른 루프와 달리 stop_token으로 멈춥니다.
+*/
+pub async fn start_status_tracker_loop(context: SharedContext, stop_token: CancellationToken) {
     let _ = tokio::spawn(async move {
-        loop {
+        let poll_interval =
+            tokio::time::Duration::from_secs(context.config.tracker.poll_interval_seconds);
+        let idle_poll_interval =
+            tokio::time::Duration::from_secs(context.config.tracker.idle_poll_interval_seconds);
+
+        while !stop_token.is_cancelled() {
+            // 이 worker가 실행한 job의 lease를 연장하고, 추적하는 worker가 없어진 job을 가져옴
             let running_jobs_result = context
-                .job_repository
-                .list_jobs(ListJobsParams {
-                    statuses: vec![JobStatus::Running],
-                    ..Default::default()
-                })
+                .job_service
+                .claim_running_jobs(
+                    &context.config.server.worker_id,
+                    context.config.runner.lease_duration(),
+                )
                 .await;
 
             let running_jobs = match running_jobs_result {
                 Ok(jobs) => jobs,
                 Err(error) => {
-                    println!("Error fetching running jobs: {:?}", error);
-                    // TODO: 대기시간을 config 설정 가능한 값으로 빼기
-                    tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+                    log::error!("Failed to fetch running jobs: {}", error);
+                    stop_token
+                        .run_until_cancelled(tokio::time::sleep(idle_poll_interval))
+                        .await;
                     continue;
                 }
             };
 
             if running_jobs.is_empty() {
-                // TODO: 대기시간을 config 설정 가능한 값으로 빼기
-                tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+                stop_token
+                    .run_until_cancelled(tokio::time::sleep(idle_poll_interval))
+                    .await;
                 continue;
             }
 
+            // 리소스 사용량 수집 실패는 job 실패로 처리하지 않음
+            if let Err(error) = context.job_service.sample_job_metrics(&running_jobs).await {
+                log::warn!("Failed to sample job metrics: {}", error);
+            }
+
             for running_job in running_jobs {
                 if let Err(error) = context.job_service.track_running_job(&running_job).await {
-                    println!("Error processing job: {:?}", error);
+                    log::error!("Failed to track job {}: {}", running_job.id, error);
 
                     let patch_result = context
                         .job_repository
-                        .patch_job(PatchJobParams {
-                            job_id: running_job.id,
-                            status: Some(JobStatus::Failed),
-                            ..Default::default()
+                        .transition_job(TransitionJobParams {
+                            to_status: JobStatus::Failed,
+                            reason: Some(error.to_string()),
+                            patch: PatchJobParams {
+                                job_id: running_job.id,
+                                exit_reason: Some(ExitReason::RuntimeError),
+                                finished_at: Some(chrono::Utc::now()),
+                                error_code: Some(error.error_code()),
+                                error_message: Some(error.to_string()),
+                                ..Default::default()
+                            },
                         })
                         .await;
 
                     if let Err(error) = patch_result {
-                        println!("Error updating job status: {:?}", error);
+                        log::error!(
+                            "Failed to update status of job {}: {}",
+                            running_job.id,
+                            error
+                        );
                     }
                 }
             }
 
-            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+            stop_token
+                .run_until_cancelled(tokio::time::sleep(poll_interval))
+                .await;
         }
     })
     .await;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        config::Config,
+        context::Context,
+        domain::{
+            container::repository::fake::FakeContainerRepository,
+            job::{
+                dao::{CreateJobParams, ListJobsParams},
+                entities,
+            },
+            task_definition::dto::CreateDefinitionRequest,
+        },
+    };
+
+    async fn create_job(
+        context: &SharedContext,
+        task_definition_id: i64,
+        status: JobStatus,
+        container_id: &str,
+        worker_id: &str,
+        lease_expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> i64 {
+        let job_id = context
+            .job_repository
+            .create_job(CreateJobParams {
+                name: container_id.to_string(),
+                task_definition_id,
+                status,
+                container_id: Some(container_id.to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        context
+            .job_repository
+            .patch_job(PatchJobParams {
+                job_id,
+                worker_id: Some(worker_id.to_string()),
+                lease_expires_at: Some(lease_expires_at),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        job_id
+    }
+
+    async fn get_job(context: &SharedContext, job_id: i64) -> entities::job::Model {
+        context
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: vec![job_id],
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .pop()
+            .unwrap()
+    }
+
+    /*
+    두 worker가 데이터베이스를 공유할 때, lease를 연장하며 추적 중인 다른 worker의 job은 건드리지 않고
+    lease가 만료된(worker가 죽은) job은 가져와서 추적하는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_tracker_ignores_jobs_of_other_workers() {
+        let containers = Arc::new(FakeContainerRepository::new());
+        let mut config = Config::default();
+        config.server.worker_id = "worker-a".to_string();
+        let context = Context::for_test(config, containers.clone()).await;
+
+        let task_definition_id = context
+            .task_definition_service
+            .create_task_definition(CreateDefinitionRequest {
+                request_body: serde_json::from_value(serde_json::json!({
+                    "name": "task",
+                    "description": "task",
+                    "image": "alpine",
+                }))
+                .unwrap(),
+            })
+            .await
+            .unwrap()
+            .task_definition_id;
+
+        let live_lease = chrono::Utc::now() + chrono::Duration::seconds(60);
+        let expired_lease = chrono::Utc::now() - chrono::Duration::seconds(1);
+
+        let own_job_id = create_job(
+            &context,
+            task_definition_id,
+            JobStatus::Running,
+            "container-a",
+            "worker-a",
+            live_lease,
+        )
+        .await;
+        let other_running_job_id = create_job(
+            &context,
+            task_definition_id,
+            JobStatus::Running,
+            "container-b",
+            "worker-b",
+            live_lease,
+        )
+        .await;
+        let other_cancelling_job_id = create_job(
+            &context,
+            task_definition_id,
+            JobStatus::Cancelling,
+            "container-c",
+            "worker-b",
+            live_lease,
+        )
+        .await;
+        let orphaned_job_id = create_job(
+            &context,
+            task_definition_id,
+            JobStatus::Running,
+            "container-d",
+            "worker-c",
+            expired_lease,
+        )
+        .await;
+
+        // 이 worker의 컨테이너만 존재하며, 이미 종료됨
+        {
+            let mut state = containers.state();
+            state.running.insert("container-a".to_string(), false);
+            state.running.insert("container-d".to_string(), true);
+        }
+
+        context
+            .job_service
+            .reconcile_jobs("worker-a", chrono::Duration::seconds(60))
+            .await
+            .unwrap();
+
+        let stop_token = CancellationToken::new();
+        let tracker = tokio::spawn(start_status_tracker_loop(
+            context.clone(),
+            stop_token.clone(),
+        ));
+
+        tokio::time::timeout(tokio::time::Duration::from_secs(5), async {
+            while !get_job(&context, own_job_id).await.status.is_terminal() {
+                tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("own job was not finished in time");
+
+        stop_token.cancel();
+        tracker.await.unwrap();
+
+        let other_running_job = get_job(&context, other_running_job_id).await;
+        assert_eq!(other_running_job.status, JobStatus::Running);
+        assert_eq!(other_running_job.worker_id.as_deref(), Some("worker-b"));
+        assert_eq!(
+            get_job(&context, other_cancelling_job_id).await.status,
+            JobStatus::Cancelling
+        );
+        assert_eq!(containers.state().stop_calls, 0);
+
+        // lease가 만료된 job은 이 worker가 가져와서 lease를 연장하며 추적
+        let orphaned_job = get_job(&context, orphaned_job_id).await;
+        assert_eq!(orphaned_job.status, JobStatus::Running);
+        assert_eq!(orphaned_job.worker_id.as_deref(), Some("worker-a"));
+        assert!(orphaned_job.lease_expires_at.unwrap() > chrono::Utc::now());
+    }
+}
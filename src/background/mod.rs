@@ -1,20 +1,211 @@
 pub mod job_tracker;
+pub mod node_monitor;
+pub mod retention;
 pub mod runner;
 pub mod scheduler;
 
 use std::sync::Arc;
 
 use scheduler::ScheduleCDCReceiver;
+use tokio_util::sync::CancellationToken;
 
-use crate::context;
+use crate::{
+    context,
+    domain::job::{dao::ListJobsParams, entities::job::JobStatus},
+};
 
+// 종료 시 실행 중인 job이 끝났는지 확인하는 주기
+const SHUTDOWN_JOB_POLL_INTERVAL_SECONDS: u64 = 2;
+
+/*
+백그라운드 루프를 실행합니다. context.shutdown_token이 취소되면 다음 순서로 정리한 뒤 반환합니다.
+1. 새 job을 가져오거나 제출하지 않고, 이미 가져온 job의 실행(이미지 pull, 컨테이너 시작)이 끝날 때까지 대기
+2. shutdown.job_wait_seconds가 설정되어 있다면 실행 중인 job이 끝날 때까지 최대 그 시간만큼 대기
+3. status tracker 종료
+*/
 pub async fn start_background_loop(
     context: Arc<context::Context>,
     schedule_cdc_receiver: ScheduleCDCReceiver,
 ) {
+    let tracker_stop_token = CancellationToken::new();
+
+    // job을 원격 agent에서만 실행하는 경우 이 서버에서는 컨테이너를 다루지 않음
+    if !context.config.runner.enabled {
+        tokio::join!(
+            async {
+                tokio::join!(
+                    scheduler::start_scheduler_loop(context.clone(), schedule_cdc_receiver),
+                    node_monitor::start_node_monitor_loop(context.clone()),
+                    retention::start_retention_loop(context.clone()),
+                );
+                tracker_stop_token.cancel();
+            },
+            job_tracker::start_status_tracker_loop(context.clone(), tracker_stop_token.clone()),
+        );
+        return;
+    }
+
+    // 재시작 전에 진행 중이던 job을 정리한 뒤에 runner/tracker를 시작
+    if let Err(error) = context
+        .job_service
+        .reconcile_jobs(
+            &context.config.server.worker_id,
+            context.config.runner.lease_duration(),
+        )
+        .await
+    {
+        log::error!("Failed to reconcile jobs on startup: {}", error);
+    }
+
     tokio::join!(
-        runner::start_runner_loop(context.clone()),
-        scheduler::start_scheduler_loop(context.clone(), schedule_cdc_receiver),
-        job_tracker::start_status_tracker_loop(context.clone()),
+        async {
+            tokio::join!(
+                runner::start_runner_loop(context.clone()),
+                scheduler::start_scheduler_loop(context.clone(), schedule_cdc_receiver),
+                node_monitor::start_node_monitor_loop(context.clone()),
+                retention::start_retention_loop(context.clone()),
+            );
+            wait_for_running_jobs(&context).await;
+            tracker_stop_token.cancel();
+        },
+        job_tracker::start_status_tracker_loop(context.clone(), tracker_stop_token.clone()),
     );
 }
+
+/*
+이 서버에서 실행 중인 job이 끝날 때까지 최대 shutdown.job_wait_seconds만큼 대기합니다.
+시간 안에 끝나지 않은 job의 컨테이너는 그대로 두며, 재시작 시 reconcile 과정에서 다시 추적합니다.
+*/
+async fn wait_for_running_jobs(context: &context::Context) {
+    if context.config.shutdown.job_wait_seconds == 0 {
+        return;
+    }
+
+    let deadline = tokio::time::Instant::now()
+        + tokio::time::Duration::from_secs(context.config.shutdown.job_wait_seconds);
+
+    loop {
+        let running_jobs_result = context
+            .job_repository
+            .list_jobs(ListJobsParams {
+                statuses: vec![JobStatus::Running, JobStatus::Cancelling],
+                ..Default::default()
+            })
+            .await;
+
+        let running_job_count = match running_jobs_result {
+            Ok(jobs) => jobs
+                .iter()
+                .filter(|job| {
+                    job.node_id.is_none()
+                        && job.worker_id.as_deref() == Some(&context.config.server.worker_id)
+                })
+                .count(),
+            Err(error) => {
+                log::error!("Error fetching running jobs: {:?}", error);
+                return;
+            }
+        };
+
+        if running_job_count == 0 {
+            return;
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            log::warn!(
+                "{} jobs are still running, they will be reconciled on the next start",
+                running_job_count
+            );
+            return;
+        }
+
+        log::info!("Waiting for {} running jobs to finish", running_job_count);
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(
+            SHUTDOWN_JOB_POLL_INTERVAL_SECONDS,
+        ))
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Config,
+        context::Context,
+        domain::{
+            container::repository::fake::FakeContainerRepository,
+            job::dao::{CreateJobParams, PatchJobParams},
+            task_definition::dto::CreateDefinitionRequest,
+        },
+    };
+
+    /*
+    shutdown.job_wait_seconds가 지나면 실행 중인 job이 남아 있어도 대기를 멈추는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_wait_for_running_jobs_stops_at_deadline() {
+        let mut config = Config::default();
+        config.server.worker_id = "worker-a".to_string();
+        config.shutdown.job_wait_seconds = 1;
+        let context = Context::for_test(config, Arc::new(FakeContainerRepository::new())).await;
+
+        let task_definition_id = context
+            .task_definition_service
+            .create_task_definition(CreateDefinitionRequest {
+                request_body: serde_json::from_value(serde_json::json!({
+                    "name": "task",
+                    "description": "task",
+                    "image": "alpine",
+                }))
+                .unwrap(),
+            })
+            .await
+            .unwrap()
+            .task_definition_id;
+
+        let job_id = context
+            .job_repository
+            .create_job(CreateJobParams {
+                name: "job".to_string(),
+                task_definition_id,
+                status: JobStatus::Running,
+                container_id: Some("container-1".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        context
+            .job_repository
+            .patch_job(PatchJobParams {
+                job_id,
+                worker_id: Some("worker-a".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        // deadline(1초)까지는 기다리고, 그 이후 첫 확인에서 반환
+        let started_at = tokio::time::Instant::now();
+        tokio::time::timeout(
+            tokio::time::Duration::from_secs(1 + SHUTDOWN_JOB_POLL_INTERVAL_SECONDS + 2),
+            wait_for_running_jobs(&context),
+        )
+        .await
+        .expect("wait_for_running_jobs did not stop at the deadline");
+        assert!(started_at.elapsed() >= tokio::time::Duration::from_secs(1));
+
+        let job = context
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: vec![job_id],
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(job.status, JobStatus::Running);
+    }
+}
@@ -0,0 +1,24 @@
+use crate::context::SharedContext;
+
+pub async fn start_node_monitor_loop(context: SharedContext) {
+    let _ = tokio::spawn(async move {
+        while !context.shutdown_token.is_cancelled() {
+            let result = context
+                .node_service
+                .drain_unresponsive_nodes(context.config.node.heartbeat_timeout())
+                .await;
+
+            if let Err(error) = result {
+                log::error!("Error draining unresponsive nodes: {:?}", error);
+            }
+
+            context
+                .shutdown_token
+                .run_until_cancelled(tokio::time::sleep(tokio::time::Duration::from_secs(
+                    context.config.node.monitor_interval_seconds,
+                )))
+                .await;
+        }
+    })
+    .await;
+}
@@ -0,0 +1,37 @@
+use crate::context::SharedContext;
+
+/*
+retention.enabled일 때 retention.interval_seconds마다 보관 기간이 지난 종료된 job을 job 테이블에서 옮깁니다.
+*/
+pub async fn start_retention_loop(context: SharedContext) {
+    if !context.config.retention.enabled {
+        return;
+    }
+
+    let _ = tokio::spawn(async move {
+        while !context.shutdown_token.is_cancelled() {
+            let result = context
+                .job_service
+                .remove_expired_jobs(&context.config.retention)
+                .await;
+
+            match result {
+                Ok(0) => {}
+                Ok(removed_count) => log::info!(
+                    "Moved {} jobs older than {} days out of the job table",
+                    removed_count,
+                    context.config.retention.job_retention_days
+                ),
+                Err(error) => log::error!("Error removing expired jobs: {:?}", error),
+            }
+
+            context
+                .shutdown_token
+                .run_until_cancelled(tokio::time::sleep(tokio::time::Duration::from_secs(
+                    context.config.retention.interval_seconds,
+                )))
+                .await;
+        }
+    })
+    .await;
+}
@@ -1,58 +1,269 @@
+use std::sync::Arc;
+
 use crate::{
     context::SharedContext,
     domain::job::{
-        dao::{ListJobsParams, PatchJobParams},
-        entities::job::JobStatus,
+        dao::{PatchJobParams, TransitionJobParams},
+        entities::{
+            self,
+            job::{ExitReason, JobStatus},
+        },
     },
 };
 
 pub async fn start_runner_loop(context: SharedContext) {
     let _ = tokio::spawn(async move {
+        // 동시에 실행(이미지 pull, 컨테이너 시작)을 진행할 수 있는 job의 최대 개수
+        let max_concurrent_launches = context.config.runner.max_concurrent_launches;
+        let idle_poll_interval =
+            tokio::time::Duration::from_secs(context.config.runner.idle_poll_interval_seconds);
+
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(max_concurrent_launches));
+
         loop {
+            // 실행 슬롯이 하나 이상 빌 때까지 대기. 종료 요청을 받으면 새 job을 가져오지 않음
+            let first_permit = tokio::select! {
+                biased;
+                _ = context.shutdown_token.cancelled() => break,
+                permit = semaphore.clone().acquire_owned() => match permit {
+                    Ok(permit) => permit,
+                    Err(_) => break,
+                },
+            };
+            let available_slots = 1 + semaphore.available_permits();
+
+            // 여러 worker가 동시에 실행되어도 같은 job을 중복으로 실행하지 않도록 원자적으로 가져옴
             let pending_jobs_result = context
-                .job_repository
-                .list_jobs(ListJobsParams {
-                    statuses: vec![JobStatus::Pending],
-                    limit: Some(5), // TODO: config 설정 가능한 값으로 빼기
-                    ..Default::default()
-                })
+                .job_service
+                .claim_local_jobs(
+                    &context.config.server.worker_id,
+                    available_slots as u64,
+                    context.config.runner.lease_duration(),
+                )
                 .await;
 
             let pending_jobs = match pending_jobs_result {
                 Ok(pending_jobs) => pending_jobs,
                 Err(error) => {
-                    println!("Error fetching pending jobs: {:?}", error);
-                    // TODO: 대기시간을 config 설정 가능한 값으로 빼기
-                    tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+                    drop(first_permit);
+                    log::error!("Error fetching pending jobs: {:?}", error);
+                    context
+                        .shutdown_token
+                        .run_until_cancelled(tokio::time::sleep(idle_poll_interval))
+                        .await;
                     continue;
                 }
             };
 
             if pending_jobs.is_empty() {
-                // TODO: 대기시간을 config 설정 가능한 값으로 빼기
-                tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+                drop(first_permit);
+                // 새 job이 제출되면 바로 깨어나고, 아니면 lease가 만료된 job을 위해 주기적으로 확인
+                tokio::select! {
+                    _ = context.job_submitted_notify.notified() => {}
+                    _ = tokio::time::sleep(idle_poll_interval) => {}
+                    _ = context.shutdown_token.cancelled() => {}
+                }
                 continue;
             }
 
+            let mut first_permit = Some(first_permit);
+
             for pending_job in pending_jobs {
-                if let Err(error) = context.job_service.run_pending_job(&pending_job).await {
-                    println!("Error processing job: {:?}", error);
-
-                    let patch_result = context
-                        .job_repository
-                        .patch_job(PatchJobParams {
-                            job_id: pending_job.id,
-                            status: Some(JobStatus::Failed),
-                            ..Default::default()
-                        })
-                        .await;
+                // claim한 개수만큼 슬롯이 남아있으므로 여기서는 대기하지 않음
+                let permit = match first_permit.take() {
+                    Some(permit) => permit,
+                    None => match semaphore.clone().acquire_owned().await {
+                        Ok(permit) => permit,
+                        Err(_) => break,
+                    },
+                };
 
-                    if let Err(error) = patch_result {
-                        println!("Error updating job status: {:?}", error);
-                    }
-                }
+                let context = context.clone();
+                tokio::spawn(async move {
+                    launch_job(&context, &pending_job).await;
+                    drop(permit);
+                });
             }
         }
+
+        // 이미 가져온 job의 실행(이미지 pull, 컨테이너 시작)이 끝날 때까지 대기
+        let launching_jobs = max_concurrent_launches - semaphore.available_permits();
+
+        if launching_jobs > 0 {
+            log::info!("Waiting for {} job launches to finish", launching_jobs);
+        }
+
+        let _ = semaphore.acquire_many(max_concurrent_launches as u32).await;
     })
     .await;
 }
+
+/*
+claim한 job 하나를 실행합니다. 실행에 실패하면 job을 Failed로 전이합니다.
+*/
+async fn launch_job(context: &SharedContext, pending_job: &entities::job::Model) {
+    if let Err(error) = context
+        .job_service
+        .run_pending_job(pending_job, context.config.runner.lease_duration())
+        .await
+    {
+        log::error!("Error processing job {}: {:?}", pending_job.id, error);
+
+        let patch_result = context
+            .job_repository
+            .transition_job(TransitionJobParams {
+                to_status: JobStatus::Failed,
+                reason: Some(error.to_string()),
+                patch: PatchJobParams {
+                    job_id: pending_job.id,
+                    exit_reason: Some(ExitReason::RuntimeError),
+                    finished_at: Some(chrono::Utc::now()),
+                    error_code: Some(error.error_code()),
+                    error_message: Some(error.to_string()),
+                    ..Default::default()
+                },
+            })
+            .await;
+
+        if let Err(error) = patch_result {
+            log::error!("Error updating job status: {:?}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Config,
+        context::Context,
+        domain::{
+            container::repository::fake::FakeContainerRepository,
+            job::dto::{SubmitJobBody, SubmitJobRequest},
+            task_definition::dto::CreateDefinitionRequest,
+        },
+    };
+
+    async fn wait_until(condition: impl Fn() -> bool) {
+        tokio::time::timeout(tokio::time::Duration::from_secs(5), async {
+            while !condition() {
+                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("condition was not met in time");
+    }
+
+    async fn submit_jobs(context: &SharedContext, count: usize) {
+        let task_definition_id = context
+            .task_definition_service
+            .create_task_definition(CreateDefinitionRequest {
+                request_body: serde_json::from_value(serde_json::json!({
+                    "name": "task",
+                    "description": "task",
+                    "image": "alpine",
+                }))
+                .unwrap(),
+            })
+            .await
+            .unwrap()
+            .task_definition_id;
+
+        for index in 0..count {
+            context
+                .job_service
+                .submit_job(SubmitJobRequest {
+                    request_body: SubmitJobBody {
+                        task_definition_id,
+                        job_name: format!("job-{}", index),
+                        log_expire_after: None,
+                    },
+                    schedule_id: None,
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    // 주기적인 확인으로는 테스트 시간 안에 깨어나지 않도록 idle poll 간격을 길게 설정
+    fn runner_config(max_concurrent_launches: usize) -> Config {
+        let mut config = Config::default();
+        config.runner.max_concurrent_launches = max_concurrent_launches;
+        config.runner.idle_poll_interval_seconds = 3600;
+        config
+    }
+
+    #[tokio::test]
+    async fn test_runner_wakes_up_on_submit() {
+        let containers = Arc::new(FakeContainerRepository::new());
+        let context = Context::for_test(runner_config(2), containers.clone()).await;
+        let runner = tokio::spawn(start_runner_loop(context.clone()));
+
+        // job이 없으므로 runner는 제출 알림을 기다림
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        assert!(containers.state().runs.is_empty());
+
+        submit_jobs(&context, 1).await;
+        wait_until(|| containers.state().runs.len() == 1).await;
+
+        context.shutdown_token.cancel();
+        runner.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_runner_bounds_concurrent_launches() {
+        let containers = Arc::new(FakeContainerRepository::with_blocking_pulls());
+        let context = Context::for_test(runner_config(2), containers.clone()).await;
+        let runner = tokio::spawn(start_runner_loop(context.clone()));
+
+        submit_jobs(&context, 5).await;
+
+        // pull이 끝나지 않으면 슬롯이 비지 않으므로 2개만 실행을 시작함
+        wait_until(|| containers.state().pulling == 2).await;
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        assert_eq!(containers.state().pulling, 2);
+        assert!(containers.state().runs.is_empty());
+
+        containers.release_pulls(5);
+        wait_until(|| containers.state().runs.len() == 5).await;
+        assert_eq!(containers.state().max_pulling, 2);
+
+        context.shutdown_token.cancel();
+        runner.await.unwrap();
+    }
+
+    /*
+    종료 요청을 받아도 이미 가져온 job의 실행은 끝까지 진행하여 상태와 컨테이너 id를 기록하는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_runner_drains_launches_on_shutdown() {
+        let containers = Arc::new(FakeContainerRepository::with_blocking_pulls());
+        let context = Context::for_test(runner_config(2), containers.clone()).await;
+        let runner = tokio::spawn(start_runner_loop(context.clone()));
+
+        submit_jobs(&context, 1).await;
+        wait_until(|| containers.state().pulling == 1).await;
+
+        // pull이 끝나지 않았으므로 종료 요청 후에도 runner는 반환하지 않음
+        context.shutdown_token.cancel();
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        assert!(!runner.is_finished());
+
+        containers.release_pulls(1);
+        tokio::time::timeout(tokio::time::Duration::from_secs(5), runner)
+            .await
+            .expect("runner did not finish after the launch")
+            .unwrap();
+
+        let job = context
+            .job_repository
+            .list_jobs(Default::default())
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(job.status, JobStatus::Running);
+        assert_eq!(job.container_id, Some(format!("container-{}", job.id)));
+        assert_eq!(containers.state().runs.len(), 1);
+    }
+}
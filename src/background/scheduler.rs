@@ -43,6 +43,9 @@ pub async fn start_scheduler_loop(
     mut receiver: tokio::sync::mpsc::Receiver<ScheduleCDCEvent>,
 ) {
     let _ = tokio::spawn(async move {
+        let idle_poll_interval =
+            tokio::time::Duration::from_secs(context.config.scheduler.idle_poll_interval_seconds);
+
         let mut schedules = context
             .schedule_repository
             .list_schedules(Default::default())
@@ -52,8 +55,8 @@ pub async fn start_scheduler_loop(
             .flat_map(ScheduleWithStates::try_from)
             .collect::<Vec<_>>();
 
-        // 스케줄링 루프
-        loop {
+        // 스케줄링 루프 (종료 요청을 받으면 더 이상 job을 제출하지 않음)
+        while !context.shutdown_token.is_cancelled() {
             let now = chrono::Utc::now();
 
             // 스케줄 데이터가 변경되면 스케줄을 다시 로드
@@ -71,7 +74,10 @@ pub async fn start_scheduler_loop(
 
             // 없으면 일단 적당히 대기
             if schedules.is_empty() {
-                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                context
+                    .shutdown_token
+                    .run_until_cancelled(tokio::time::sleep(idle_poll_interval))
+                    .await;
                 continue;
             }
 
@@ -85,6 +91,7 @@ pub async fn start_scheduler_loop(
                                 job_name: schedule.model.job_name.clone(),
                                 ..Default::default()
                             },
+                            schedule_id: Some(schedule.model.id),
                         })
                         .await
                     {
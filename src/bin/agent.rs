@@ -0,0 +1,10 @@
+use batchman::agent::{self, AgentConfig};
+
+#[tokio::main]
+async fn main() {
+    let config = AgentConfig::from_env().expect("Failed to load agent config");
+
+    agent::runner::run_agent(config)
+        .await
+        .expect("Agent stopped");
+}
@@ -0,0 +1,862 @@
+use std::collections::BTreeSet;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use crate::errors;
+
+// 설정 파일 경로를 지정하는 환경 변수 (없으면 현재 디렉터리의 batchman.toml을 사용)
+pub const CONFIG_PATH_ENV: &str = "BATCHMAN_CONFIG";
+const DEFAULT_CONFIG_PATH: &str = "batchman.toml";
+
+/*
+서버 설정입니다. TOML 설정 파일을 읽은 뒤 BATCHMAN_<SECTION>_<KEY> 환경 변수로 덮어씁니다.
+예: [runner] max_concurrent_launches = 10 은 BATCHMAN_RUNNER_MAX_CONCURRENT_LAUNCHES=10 으로 덮어쓸 수 있습니다.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub runner: RunnerConfig,
+    pub tracker: TrackerConfig,
+    pub scheduler: SchedulerConfig,
+    pub node: NodeConfig,
+    pub shutdown: ShutdownConfig,
+    pub retention: RetentionConfig,
+    pub secret: SecretConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub bind_address: String, // address the HTTP server listens on
+    pub worker_id: String,    // identifies this batchman instance when claiming jobs
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: "0.0.0.0:13939".to_string(),
+            worker_id: default_worker_id(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DatabaseConfig {
+    pub url: String, // database connection url
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub connect_timeout_seconds: u64,
+    pub acquire_timeout_seconds: u64,
+    pub idle_timeout_seconds: u64,
+    pub max_lifetime_seconds: u64,
+    pub auto_migrate: bool, // apply pending migrations on startup (false: refuse to start until `api migrate` runs)
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: "sqlite://./db.sqlite?mode=rwc".to_string(),
+            max_connections: 100,
+            min_connections: 5,
+            connect_timeout_seconds: 8,
+            acquire_timeout_seconds: 8,
+            idle_timeout_seconds: 8,
+            max_lifetime_seconds: 8,
+            auto_migrate: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunnerConfig {
+    pub enabled: bool,                  // false when jobs are only run by remote agents
+    pub max_concurrent_launches: usize, // max number of jobs pulling/starting at the same time
+    pub idle_poll_interval_seconds: u64, // pending job poll interval when no job was submitted
+    pub lease_duration_seconds: u64,    // how long a claim is valid without renewal
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_concurrent_launches: 5,
+            idle_poll_interval_seconds: 10,
+            lease_duration_seconds: 600,
+        }
+    }
+}
+
+impl RunnerConfig {
+    pub fn lease_duration(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.lease_duration_seconds as i64)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrackerConfig {
+    pub poll_interval_seconds: u64,      // running job poll interval
+    pub idle_poll_interval_seconds: u64, // poll interval when no job is running
+}
+
+impl Default for TrackerConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_seconds: 2,
+            idle_poll_interval_seconds: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    pub idle_poll_interval_seconds: u64, // poll interval when there is no schedule
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            idle_poll_interval_seconds: 5,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NodeConfig {
+    pub agent_token: Option<String>, // shared token agents must send; the agent API is disabled while unset
+    pub heartbeat_timeout_seconds: u64, // drain a node after this long without a heartbeat
+    pub monitor_interval_seconds: u64, // how often to look for nodes that missed heartbeats
+}
+
+// 토큰이 로그에 남지 않도록 agent_token은 설정 여부만 출력합니다.
+impl std::fmt::Debug for NodeConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeConfig")
+            .field("agent_token", &self.agent_token.as_ref().map(|_| "***"))
+            .field("heartbeat_timeout_seconds", &self.heartbeat_timeout_seconds)
+            .field("monitor_interval_seconds", &self.monitor_interval_seconds)
+            .finish()
+    }
+}
+
+impl Default for NodeConfig {
+    fn default() -> Self {
+        Self {
+            agent_token: None,
+            heartbeat_timeout_seconds: 30,
+            monitor_interval_seconds: 10,
+        }
+    }
+}
+
+impl NodeConfig {
+    pub fn heartbeat_timeout(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.heartbeat_timeout_seconds as i64)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownConfig {
+    pub job_wait_seconds: u64, // how long to wait for running jobs on shutdown (0: do not wait)
+}
+
+/*
+보관 기간이 지난 종료된 job을 job 테이블에서 옮기는 설정입니다.
+- archive: job_archive 테이블로 옮김
+- export: export_dir에 gzip으로 압축한 NDJSON 파일로 내보낸 뒤 삭제
+*/
+#[derive(Debug, Clone)]
+pub struct RetentionConfig {
+    pub enabled: bool,           // move old jobs out of the job table periodically
+    pub job_retention_days: u64, // jobs finished more than this many days ago are moved
+    pub mode: RetentionMode,
+    pub export_dir: String, // directory the NDJSON files are written to (export mode)
+    pub interval_seconds: u64, // how often to look for old jobs
+    pub batch_size: u64,    // max number of jobs moved in one transaction
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            job_retention_days: 30,
+            mode: RetentionMode::Archive,
+            export_dir: "./job-archive".to_string(),
+            interval_seconds: 3600,
+            batch_size: 500,
+        }
+    }
+}
+
+impl RetentionConfig {
+    pub fn retention_period(&self) -> chrono::Duration {
+        chrono::Duration::days(self.job_retention_days as i64)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionMode {
+    Archive,
+    Export,
+}
+
+// AES-256 마스터 키의 길이
+const SECRET_KEY_SIZE: usize = 32;
+
+#[derive(Clone, Default)]
+pub struct SecretConfig {
+    pub key: Option<String>, // base64-encoded 32 byte master key; secrets are disabled while unset
+}
+
+// 마스터 키가 로그에 남지 않도록 key는 설정 여부만 출력합니다.
+impl std::fmt::Debug for SecretConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretConfig")
+            .field("key", &self.key.as_ref().map(|_| "***"))
+            .finish()
+    }
+}
+
+impl SecretConfig {
+    // base64를 풀어낸 마스터 키입니다. 설정되지 않았다면 None을 반환합니다.
+    pub fn master_key(&self) -> errors::Result<Option<Vec<u8>>> {
+        let Some(encoded_key) = &self.key else {
+            return Ok(None);
+        };
+
+        let key = STANDARD.decode(encoded_key.trim()).map_err(|error| {
+            errors::Error::InvalidConfig(format!("secret.key must be base64: {}", error))
+        })?;
+
+        if key.len() != SECRET_KEY_SIZE {
+            return Err(errors::Error::InvalidConfig(format!(
+                "secret.key must be {} bytes after base64 decoding (got {} bytes)",
+                SECRET_KEY_SIZE,
+                key.len()
+            )));
+        }
+
+        Ok(Some(key))
+    }
+}
+
+impl Config {
+    /*
+    설정 파일과 환경 변수에서 설정을 읽고 검증합니다.
+    BATCHMAN_CONFIG로 지정한 파일이 없다면 에러이고, 기본 경로(batchman.toml)에 파일이 없다면 기본값을 사용합니다.
+    */
+    pub fn load() -> errors::Result<Self> {
+        let (path, required) = match std::env::var(CONFIG_PATH_ENV) {
+            Ok(path) => (path, true),
+            Err(_) => (DEFAULT_CONFIG_PATH.to_string(), false),
+        };
+
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound && !required => {
+                String::new()
+            }
+            Err(error) => {
+                return Err(errors::Error::InvalidConfig(format!(
+                    "failed to read {}: {}",
+                    path, error
+                )));
+            }
+        };
+
+        Self::from_toml_str(&content, |name| std::env::var(name).ok())
+    }
+
+    /*
+    TOML 문자열과 환경 변수 조회 함수로 설정을 만듭니다. 알 수 없는 키가 있으면 오타로 보고 에러를 반환합니다.
+    */
+    pub fn from_toml_str(
+        content: &str,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> errors::Result<Self> {
+        let document = content
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|error| errors::Error::InvalidConfig(error.to_string()))?;
+
+        let mut loader = ConfigLoader {
+            document,
+            env: &env,
+            known_keys: BTreeSet::new(),
+        };
+
+        let mut config = Config::default();
+
+        let server = &mut config.server;
+        loader.load("server", "bind_address", &[], &mut server.bind_address)?;
+        // 예전부터 사용하던 환경 변수 이름도 계속 지원
+        loader.load(
+            "server",
+            "worker_id",
+            &["BATCHMAN_WORKER_ID"],
+            &mut server.worker_id,
+        )?;
+
+        let database = &mut config.database;
+        loader.load("database", "url", &[], &mut database.url)?;
+        loader.load(
+            "database",
+            "max_connections",
+            &[],
+            &mut database.max_connections,
+        )?;
+        loader.load(
+            "database",
+            "min_connections",
+            &[],
+            &mut database.min_connections,
+        )?;
+        loader.load(
+            "database",
+            "connect_timeout_seconds",
+            &[],
+            &mut database.connect_timeout_seconds,
+        )?;
+        loader.load(
+            "database",
+            "acquire_timeout_seconds",
+            &[],
+            &mut database.acquire_timeout_seconds,
+        )?;
+        loader.load(
+            "database",
+            "idle_timeout_seconds",
+            &[],
+            &mut database.idle_timeout_seconds,
+        )?;
+        loader.load(
+            "database",
+            "max_lifetime_seconds",
+            &[],
+            &mut database.max_lifetime_seconds,
+        )?;
+        loader.load("database", "auto_migrate", &[], &mut database.auto_migrate)?;
+
+        let runner = &mut config.runner;
+        loader.load(
+            "runner",
+            "enabled",
+            &["BATCHMAN_LOCAL_RUNNER"],
+            &mut runner.enabled,
+        )?;
+        loader.load(
+            "runner",
+            "max_concurrent_launches",
+            &[],
+            &mut runner.max_concurrent_launches,
+        )?;
+        loader.load(
+            "runner",
+            "idle_poll_interval_seconds",
+            &[],
+            &mut runner.idle_poll_interval_seconds,
+        )?;
+        loader.load(
+            "runner",
+            "lease_duration_seconds",
+            &[],
+            &mut runner.lease_duration_seconds,
+        )?;
+
+        let tracker = &mut config.tracker;
+        loader.load(
+            "tracker",
+            "poll_interval_seconds",
+            &[],
+            &mut tracker.poll_interval_seconds,
+        )?;
+        loader.load(
+            "tracker",
+            "idle_poll_interval_seconds",
+            &[],
+            &mut tracker.idle_poll_interval_seconds,
+        )?;
+
+        loader.load(
+            "scheduler",
+            "idle_poll_interval_seconds",
+            &[],
+            &mut config.scheduler.idle_poll_interval_seconds,
+        )?;
+
+        let node = &mut config.node;
+        loader.load(
+            "node",
+            "agent_token",
+            &["BATCHMAN_AGENT_TOKEN"],
+            &mut node.agent_token,
+        )?;
+        loader.load(
+            "node",
+            "heartbeat_timeout_seconds",
+            &[],
+            &mut node.heartbeat_timeout_seconds,
+        )?;
+        loader.load(
+            "node",
+            "monitor_interval_seconds",
+            &[],
+            &mut node.monitor_interval_seconds,
+        )?;
+
+        loader.load(
+            "shutdown",
+            "job_wait_seconds",
+            &[],
+            &mut config.shutdown.job_wait_seconds,
+        )?;
+
+        let retention = &mut config.retention;
+        loader.load("retention", "enabled", &[], &mut retention.enabled)?;
+        loader.load(
+            "retention",
+            "job_retention_days",
+            &[],
+            &mut retention.job_retention_days,
+        )?;
+        loader.load("retention", "mode", &[], &mut retention.mode)?;
+        loader.load("retention", "export_dir", &[], &mut retention.export_dir)?;
+        loader.load(
+            "retention",
+            "interval_seconds",
+            &[],
+            &mut retention.interval_seconds,
+        )?;
+        loader.load("retention", "batch_size", &[], &mut retention.batch_size)?;
+
+        loader.load("secret", "key", &[], &mut config.secret.key)?;
+
+        loader.check_unknown_keys()?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /*
+    값의 범위와 서로 간의 관계를 검증합니다.
+    */
+    fn validate(&self) -> errors::Result<()> {
+        let invalid = |reason: String| Err(errors::Error::InvalidConfig(reason));
+
+        if self
+            .server
+            .bind_address
+            .parse::<std::net::SocketAddr>()
+            .is_err()
+        {
+            return invalid(format!(
+                "server.bind_address must be host:port (got {:?})",
+                self.server.bind_address
+            ));
+        }
+
+        if self.server.worker_id.trim().is_empty() {
+            return invalid("server.worker_id must not be empty".to_string());
+        }
+
+        if self.database.url.trim().is_empty() {
+            return invalid("database.url must not be empty".to_string());
+        }
+
+        // sea-orm이 지원하는 backend만 허용
+        if !["sqlite:", "postgres:", "postgresql:", "mysql:"]
+            .iter()
+            .any(|scheme| self.database.url.starts_with(scheme))
+        {
+            return invalid(
+                "database.url must start with sqlite:, postgres: or mysql:".to_string(),
+            );
+        }
+
+        if self.database.max_connections == 0 {
+            return invalid("database.max_connections must be at least 1".to_string());
+        }
+
+        if self.database.min_connections > self.database.max_connections {
+            return invalid(
+                "database.min_connections must not exceed database.max_connections".to_string(),
+            );
+        }
+
+        let positive_values = [
+            (
+                "database.connect_timeout_seconds",
+                self.database.connect_timeout_seconds,
+            ),
+            (
+                "database.acquire_timeout_seconds",
+                self.database.acquire_timeout_seconds,
+            ),
+            (
+                "database.idle_timeout_seconds",
+                self.database.idle_timeout_seconds,
+            ),
+            (
+                "database.max_lifetime_seconds",
+                self.database.max_lifetime_seconds,
+            ),
+            (
+                "runner.max_concurrent_launches",
+                self.runner.max_concurrent_launches as u64,
+            ),
+            (
+                "runner.idle_poll_interval_seconds",
+                self.runner.idle_poll_interval_seconds,
+            ),
+            (
+                "runner.lease_duration_seconds",
+                self.runner.lease_duration_seconds,
+            ),
+            (
+                "tracker.poll_interval_seconds",
+                self.tracker.poll_interval_seconds,
+            ),
+            (
+                "tracker.idle_poll_interval_seconds",
+                self.tracker.idle_poll_interval_seconds,
+            ),
+            (
+                "scheduler.idle_poll_interval_seconds",
+                self.scheduler.idle_poll_interval_seconds,
+            ),
+            (
+                "node.heartbeat_timeout_seconds",
+                self.node.heartbeat_timeout_seconds,
+            ),
+            (
+                "node.monitor_interval_seconds",
+                self.node.monitor_interval_seconds,
+            ),
+            (
+                "retention.job_retention_days",
+                self.retention.job_retention_days,
+            ),
+            (
+                "retention.interval_seconds",
+                self.retention.interval_seconds,
+            ),
+            ("retention.batch_size", self.retention.batch_size),
+        ];
+
+        for (name, value) in positive_values {
+            if value == 0 {
+                return invalid(format!("{} must be at least 1", name));
+            }
+        }
+
+        // heartbeat로 lease를 연장하므로, lease가 drain보다 먼저 만료되면 살아있는 node의 job을 다른 worker가 가져감
+        if self.runner.lease_duration_seconds <= self.node.heartbeat_timeout_seconds {
+            return invalid(
+                "runner.lease_duration_seconds must be greater than node.heartbeat_timeout_seconds"
+                    .to_string(),
+            );
+        }
+
+        // tracker가 매 주기마다 실행 중인 job의 lease를 연장하므로, 그보다 먼저 만료되면 살아있는 worker의 job을 다른 worker가 가져감
+        if self.runner.lease_duration_seconds <= self.tracker.poll_interval_seconds {
+            return invalid(
+                "runner.lease_duration_seconds must be greater than tracker.poll_interval_seconds"
+                    .to_string(),
+            );
+        }
+
+        if self.retention.mode == RetentionMode::Export
+            && self.retention.export_dir.trim().is_empty()
+        {
+            return invalid(
+                "retention.export_dir must not be empty when retention.mode is export".to_string(),
+            );
+        }
+
+        self.secret.master_key()?;
+
+        Ok(())
+    }
+}
+
+/*
+job claim에 사용할 worker id의 기본값입니다. 재시작 후에도 같은 값을 사용하도록 호스트 이름을 사용합니다.
+같은 호스트에서 여러 인스턴스를 실행한다면 BATCHMAN_WORKER_ID를 각각 다르게 설정해야 합니다.
+*/
+pub fn default_worker_id() -> String {
+    if let Ok(worker_id) = std::env::var("BATCHMAN_WORKER_ID")
+        && !worker_id.trim().is_empty()
+    {
+        return worker_id.trim().to_string();
+    }
+
+    std::fs::read_to_string("/etc/hostname")
+        .ok()
+        .map(|hostname| hostname.trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .unwrap_or_else(|| "batchman".to_string())
+}
+
+struct ConfigLoader<'a> {
+    document: toml_edit::DocumentMut,
+    env: &'a dyn Fn(&str) -> Option<String>,
+    known_keys: BTreeSet<String>, // section.key loaded so far
+}
+
+impl ConfigLoader<'_> {
+    /*
+    [section] key 값을 읽어서 target에 덮어씁니다. 우선순위는 환경 변수 > 설정 파일 > 기본값입니다.
+    legacy_env_names는 BATCHMAN_<SECTION>_<KEY>가 없을 때 확인하는 예전 환경 변수 이름입니다.
+    */
+    fn load<T: ConfigValue>(
+        &mut self,
+        section: &str,
+        key: &str,
+        legacy_env_names: &[&str],
+        target: &mut T,
+    ) -> errors::Result<()> {
+        let name = format!("{}.{}", section, key);
+
+        if let Some(item) = self.document.get(section).and_then(|table| table.get(key)) {
+            *target = T::from_toml(item).ok_or_else(|| {
+                errors::Error::InvalidConfig(format!("{} must be {}", name, T::EXPECTED))
+            })?;
+        }
+
+        let env_name = format!("BATCHMAN_{}_{}", section.to_uppercase(), key.to_uppercase());
+
+        let env_value = std::iter::once(env_name.as_str())
+            .chain(legacy_env_names.iter().copied())
+            .find_map(|env_name| {
+                (self.env)(env_name)
+                    .filter(|value| !value.trim().is_empty())
+                    .map(|value| (env_name, value))
+            });
+
+        if let Some((env_name, value)) = env_value {
+            *target = T::from_env(value.trim()).ok_or_else(|| {
+                errors::Error::InvalidConfig(format!("{} must be {}", env_name, T::EXPECTED))
+            })?;
+        }
+
+        self.known_keys.insert(name);
+
+        Ok(())
+    }
+
+    fn check_unknown_keys(&self) -> errors::Result<()> {
+        for (section, item) in self.document.iter() {
+            let Some(table) = item.as_table_like() else {
+                return Err(errors::Error::InvalidConfig(format!(
+                    "unknown key: {}",
+                    section
+                )));
+            };
+
+            for (key, _) in table.iter() {
+                let name = format!("{}.{}", section, key);
+
+                if !self.known_keys.contains(&name) {
+                    return Err(errors::Error::InvalidConfig(format!(
+                        "unknown key: {}",
+                        name
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+trait ConfigValue: Sized {
+    const EXPECTED: &'static str; // used in error messages
+    fn from_toml(item: &toml_edit::Item) -> Option<Self>;
+    fn from_env(value: &str) -> Option<Self>;
+}
+
+impl ConfigValue for String {
+    const EXPECTED: &'static str = "a string";
+
+    fn from_toml(item: &toml_edit::Item) -> Option<Self> {
+        item.as_str().map(str::to_string)
+    }
+
+    fn from_env(value: &str) -> Option<Self> {
+        Some(value.to_string())
+    }
+}
+
+impl ConfigValue for Option<String> {
+    const EXPECTED: &'static str = "a string";
+
+    fn from_toml(item: &toml_edit::Item) -> Option<Self> {
+        item.as_str()
+            .map(|value| Some(value.to_string()).filter(|value| !value.is_empty()))
+    }
+
+    fn from_env(value: &str) -> Option<Self> {
+        Some(Some(value.to_string()))
+    }
+}
+
+impl ConfigValue for RetentionMode {
+    const EXPECTED: &'static str = "\"archive\" or \"export\"";
+
+    fn from_toml(item: &toml_edit::Item) -> Option<Self> {
+        item.as_str().and_then(Self::from_env)
+    }
+
+    fn from_env(value: &str) -> Option<Self> {
+        match value {
+            "archive" => Some(RetentionMode::Archive),
+            "export" => Some(RetentionMode::Export),
+            _ => None,
+        }
+    }
+}
+
+impl ConfigValue for bool {
+    const EXPECTED: &'static str = "true or false";
+
+    fn from_toml(item: &toml_edit::Item) -> Option<Self> {
+        item.as_bool()
+    }
+
+    fn from_env(value: &str) -> Option<Self> {
+        match value {
+            "true" | "1" => Some(true),
+            "false" | "0" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_integer_config_value {
+    ($($integer:ty),*) => {
+        $(
+            impl ConfigValue for $integer {
+                const EXPECTED: &'static str = "a non-negative integer";
+
+                fn from_toml(item: &toml_edit::Item) -> Option<Self> {
+                    item.as_integer().and_then(|value| Self::try_from(value).ok())
+                }
+
+                fn from_env(value: &str) -> Option<Self> {
+                    value.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_config_value!(u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn load(content: &str, env: &[(&str, &str)]) -> errors::Result<Config> {
+        let env = env
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<BTreeMap<_, _>>();
+
+        Config::from_toml_str(content, |name| env.get(name).cloned())
+    }
+
+    #[test]
+    fn test_config_file_and_env_overrides() {
+        let content = r#"
+            [server]
+            bind_address = "127.0.0.1:8080"
+            worker_id = "file-worker"
+
+            [runner]
+            max_concurrent_launches = 3
+            enabled = false
+
+            [scheduler]
+            idle_poll_interval_seconds = 7
+        "#;
+
+        let config = load(content, &[]).unwrap();
+        assert_eq!(config.server.bind_address, "127.0.0.1:8080");
+        assert_eq!(config.server.worker_id, "file-worker");
+        assert_eq!(config.runner.max_concurrent_launches, 3);
+        assert!(!config.runner.enabled);
+        assert_eq!(config.runner.lease_duration_seconds, 600);
+        assert_eq!(config.scheduler.idle_poll_interval_seconds, 7);
+
+        let config = load(
+            content,
+            &[
+                ("BATCHMAN_RUNNER_MAX_CONCURRENT_LAUNCHES", "8"),
+                ("BATCHMAN_LOCAL_RUNNER", "true"),
+                ("BATCHMAN_WORKER_ID", "legacy-worker"),
+                ("BATCHMAN_SERVER_WORKER_ID", "env-worker"),
+                ("BATCHMAN_SCHEDULER_IDLE_POLL_INTERVAL_SECONDS", "1"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(config.runner.max_concurrent_launches, 8);
+        assert_eq!(config.scheduler.idle_poll_interval_seconds, 1);
+        assert!(config.runner.enabled);
+        assert_eq!(config.server.worker_id, "env-worker");
+    }
+
+    #[test]
+    fn test_config_rejects_invalid_values() {
+        assert!(load("[runner]\nmax_concurent_launches = 3", &[]).is_err());
+        assert!(load("[runner]\nmax_concurrent_launches = -1", &[]).is_err());
+        assert!(load("[runner]\nmax_concurrent_launches = 0", &[]).is_err());
+        assert!(load("[server]\nbind_address = \"localhost\"", &[]).is_err());
+        assert!(load("", &[("BATCHMAN_DATABASE_MAX_CONNECTIONS", "many")]).is_err());
+        assert!(load("[node]\nheartbeat_timeout_seconds = 900", &[]).is_err());
+        assert!(load("[tracker]\npoll_interval_seconds = 600", &[]).is_err());
+        assert!(load("[scheduler]\nidle_poll_interval_seconds = 0", &[]).is_err());
+        assert!(load("[database]\nurl = \"db.sqlite\"", &[]).is_err());
+        assert!(load("[retention]\nmode = \"delete\"", &[]).is_err());
+        assert!(load("[retention]\nmode = \"export\"\nexport_dir = \"\"", &[]).is_err());
+        assert!(load("[secret]\nkey = \"not base64!\"", &[]).is_err());
+        assert!(load("", &[("BATCHMAN_SECRET_KEY", "c2hvcnQ=")]).is_err());
+    }
+
+    #[test]
+    fn test_secret_key() {
+        let key = STANDARD.encode([7u8; 32]);
+
+        let config = load("", &[]).unwrap();
+        assert_eq!(config.secret.master_key().unwrap(), None);
+
+        let config = load(&format!("[secret]\nkey = \"{}\"", key), &[]).unwrap();
+        assert_eq!(config.secret.master_key().unwrap(), Some(vec![7u8; 32]));
+
+        let env_key = STANDARD.encode([8u8; 32]);
+        let config = load(
+            &format!("[secret]\nkey = \"{}\"", key),
+            &[("BATCHMAN_SECRET_KEY", env_key.as_str())],
+        )
+        .unwrap();
+        assert_eq!(config.secret.master_key().unwrap(), Some(vec![8u8; 32]));
+    }
+
+    #[test]
+    fn test_debug_hides_secrets() {
+        let key = STANDARD.encode([7u8; 32]);
+        let config = load(
+            &format!(
+                "[secret]\nkey = \"{}\"\n[node]\nagent_token = \"agent-token\"",
+                key
+            ),
+            &[],
+        )
+        .unwrap();
+
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains(&key));
+        assert!(!debug.contains("agent-token"));
+        assert!(debug.contains("***"));
+    }
+}
@@ -1,29 +1,48 @@
 use std::sync::Arc;
 
 use sea_orm::DatabaseConnection;
+use tokio_util::sync::CancellationToken;
 
 use crate::{
     background::scheduler::ScheduleCDCEvent,
+    config::Config,
+    db,
     domain::{
         self,
         job::JobRepository,
+        node::{NodeRepository, NodeService},
+        registry_credential::{RegistryCredentialRepository, RegistryCredentialService},
         schedule::ScheduleRepository,
+        secret::{SecretRepository, SecretService, cipher::SecretCipher},
         task_definition::{TaskDefinitionRepository, TaskDefinitionService},
+        unit_of_work::UnitOfWorkFactory,
     },
 };
 
 pub struct Context {
     pub connection: DatabaseConnection,
 
+    pub config: Config, // settings loaded from batchman.toml and BATCHMAN_* environment variables
+
+    pub shutdown_token: CancellationToken, // cancelled on SIGTERM/Ctrl-C
+
     pub schedule_cdc_sender: tokio::sync::mpsc::Sender<ScheduleCDCEvent>,
+    pub job_submitted_notify: Arc<tokio::sync::Notify>, // wakes the runner when a job is submitted
 
     pub task_definition_repository: Arc<dyn TaskDefinitionRepository + Send + Sync>,
     pub job_repository: Arc<dyn JobRepository + Send + Sync>,
     pub schedule_repository: Arc<dyn ScheduleRepository + Send + Sync>,
+    pub secret_repository: Arc<dyn SecretRepository + Send + Sync>,
+    pub registry_credential_repository: Arc<dyn RegistryCredentialRepository + Send + Sync>,
+    pub node_repository: Arc<dyn NodeRepository + Send + Sync>,
+    pub unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>, // groups repository writes into one transaction
 
     pub task_definition_service: Box<dyn TaskDefinitionService + Send + Sync>,
     pub job_service: Box<dyn domain::job::JobService + Send + Sync>,
     pub schedule_service: Box<dyn domain::schedule::ScheduleService + Send + Sync>,
+    pub secret_service: Arc<dyn SecretService + Send + Sync>,
+    pub registry_credential_service: Arc<dyn RegistryCredentialService + Send + Sync>,
+    pub node_service: Box<dyn NodeService + Send + Sync>,
 
     pub docker_repository: Arc<dyn domain::container::ContainerRepository + Send + Sync>,
 }
@@ -32,8 +51,29 @@ pub type SharedContext = Arc<Context>;
 
 impl Context {
     pub fn new(
+        config: Config,
+        connection: DatabaseConnection,
+        schedule_cdc_sender: tokio::sync::mpsc::Sender<ScheduleCDCEvent>,
+        secret_cipher: Option<SecretCipher>,
+    ) -> Self {
+        Self::with_container_repository(
+            config,
+            connection,
+            schedule_cdc_sender,
+            secret_cipher,
+            Arc::new(domain::container::repository::docker::ContainerDockerRepository::new()),
+        )
+    }
+
+    /*
+    컨테이너 실행에 docker CLI 대신 다른 ContainerRepository를 사용합니다. (테스트 등)
+    */
+    pub fn with_container_repository(
+        config: Config,
         connection: DatabaseConnection,
         schedule_cdc_sender: tokio::sync::mpsc::Sender<ScheduleCDCEvent>,
+        secret_cipher: Option<SecretCipher>,
+        docker_repository: Arc<dyn domain::container::ContainerRepository + Send + Sync>,
     ) -> Self {
         let task_definition_repository = Arc::new(
             domain::task_definition::repository::TaskDefinitionSeaOrmRepository::new(
@@ -49,30 +89,118 @@ impl Context {
             domain::schedule::repository::ScheduleSeaOrmRepository::new(connection.clone()),
         );
 
-        let docker_repository =
-            Arc::new(domain::container::repository::docker::ContainerDockerRepository::new());
+        let unit_of_work_factory = Arc::new(db::unit_of_work::SeaOrmUnitOfWorkFactory::new(
+            connection.clone(),
+        ));
+
+        let secret_repository = Arc::new(domain::secret::repository::SecretSeaOrmRepository::new(
+            connection.clone(),
+        ));
+
+        let secret_service = Arc::new(domain::secret::service::SecretServiceImpl::new(
+            secret_repository.clone(),
+            unit_of_work_factory.clone(),
+            secret_cipher.clone(),
+        ));
+
+        let registry_credential_repository = Arc::new(
+            domain::registry_credential::repository::RegistryCredentialSeaOrmRepository::new(
+                connection.clone(),
+            ),
+        );
+
+        let registry_credential_service = Arc::new(
+            domain::registry_credential::service::RegistryCredentialServiceImpl::new(
+                registry_credential_repository.clone(),
+                secret_cipher,
+            ),
+        );
+
+        let node_repository = Arc::new(domain::node::repository::NodeSeaOrmRepository::new(
+            connection.clone(),
+        ));
+
+        let job_submitted_notify = Arc::new(tokio::sync::Notify::new());
 
         Self {
             connection: connection.clone(),
+            config,
+            shutdown_token: CancellationToken::new(),
             schedule_cdc_sender,
+            job_submitted_notify: job_submitted_notify.clone(),
             task_definition_repository: task_definition_repository.clone(),
             job_repository: job_repository.clone(),
             schedule_repository: schedule_repository.clone(),
+            secret_repository,
+            registry_credential_repository,
+            node_repository: node_repository.clone(),
+            unit_of_work_factory: unit_of_work_factory.clone(),
             task_definition_service: Box::new(
                 domain::task_definition::service::TaskDefinitionServiceImpl::new(
                     task_definition_repository.clone(),
+                    unit_of_work_factory.clone(),
                 ),
             ),
             schedule_service: Box::new(domain::schedule::service::ScheduleServiceImpl::new(
                 schedule_repository,
                 task_definition_repository.clone(),
             )),
+            node_service: Box::new(domain::node::service::NodeServiceImpl::new(
+                node_repository.clone(),
+                job_repository.clone(),
+                task_definition_repository.clone(),
+            )),
             job_service: Box::new(domain::job::service::JobServiceImpl::new(
                 job_repository,
                 task_definition_repository,
                 docker_repository.clone(),
+                secret_service.clone(),
+                registry_credential_service.clone(),
+                node_repository,
+                unit_of_work_factory,
+                job_submitted_notify,
             )),
+            secret_service,
+            registry_credential_service,
             docker_repository,
         }
     }
 }
+
+#[cfg(test)]
+impl Context {
+    /*
+    in-memory SQLite와 주어진 ContainerRepository를 사용하는 테스트용 Context를 만듭니다.
+    */
+    pub async fn for_test(
+        mut config: Config,
+        docker_repository: Arc<dyn domain::container::ContainerRepository + Send + Sync>,
+    ) -> SharedContext {
+        config.database = crate::config::DatabaseConfig {
+            url: "sqlite::memory:".to_string(),
+            // in-memory 데이터베이스는 커넥션마다 따로 생성되므로 하나만 사용
+            max_connections: 1,
+            min_connections: 1,
+            idle_timeout_seconds: 3600,
+            max_lifetime_seconds: 3600,
+            ..Default::default()
+        };
+
+        let connection = crate::db::create_database_connection(&config.database)
+            .await
+            .expect("Failed to connect test database");
+        crate::db::migration::run_migrations(&connection)
+            .await
+            .expect("Failed to run migrations");
+
+        let (schedule_cdc_sender, _) = tokio::sync::mpsc::channel(8);
+
+        Arc::new(Self::with_container_repository(
+            config,
+            connection,
+            schedule_cdc_sender,
+            Some(SecretCipher::new(&[7; 32]).expect("32 byte key")),
+            docker_repository,
+        ))
+    }
+}
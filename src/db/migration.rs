@@ -0,0 +1,461 @@
+mod m0001_create_tables;
+mod m0002_add_missing_columns;
+mod m0003_convert_legacy_data;
+mod m0004_add_job_listing_indexes;
+mod m0005_add_job_schedule_id;
+mod m0006_add_job_archive;
+
+use chrono::Utc;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DeriveIden,
+    TransactionTrait, sea_query,
+};
+
+use crate::errors;
+
+// 여러 인스턴스가 동시에 시작할 때 Postgres에서 마이그레이션을 하나씩 적용하기 위한 advisory lock id
+const POSTGRES_MIGRATION_LOCK_ID: i64 = 0x6261_7463_686d_616e;
+
+/*
+스키마 변경 하나입니다. version 순서대로 한 번씩 적용되고, 적용한 version은 schema_migration 테이블에 기록됩니다.
+배포된 마이그레이션은 수정하지 않고, 스키마를 바꿀 때는 새 version의 마이그레이션을 추가합니다.
+*/
+#[async_trait::async_trait]
+pub trait Migration: Send + Sync {
+    fn version(&self) -> i64;
+    fn name(&self) -> &'static str;
+    async fn up(&self, db: &DatabaseTransaction) -> errors::Result<()>;
+}
+
+/*
+이 버전의 batchman이 아는 마이그레이션 목록입니다. (version 오름차순)
+*/
+pub fn migrations() -> Vec<Box<dyn Migration>> {
+    vec![
+        Box::new(m0001_create_tables::CreateTables),
+        Box::new(m0002_add_missing_columns::AddMissingColumns),
+        Box::new(m0003_convert_legacy_data::ConvertLegacyData),
+        Box::new(m0004_add_job_listing_indexes::AddJobListingIndexes),
+        Box::new(m0005_add_job_schedule_id::AddJobScheduleId),
+        Box::new(m0006_add_job_archive::AddJobArchive),
+    ]
+}
+
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub applied_at: Option<chrono::DateTime<Utc>>, // None: not applied yet
+}
+
+#[derive(DeriveIden)]
+enum SchemaMigration {
+    Table,
+    Version,
+    Name,
+    AppliedAt,
+}
+
+/*
+마이그레이션별 적용 여부를 반환합니다.
+이 버전이 모르는(더 새로운 버전의 batchman이 적용한) 마이그레이션이 기록되어 있다면 UnknownSchemaVersion 에러를 반환합니다.
+*/
+pub async fn migration_status(db: &DatabaseConnection) -> errors::Result<Vec<MigrationStatus>> {
+    create_schema_migration_table(db).await?;
+
+    let applied_migrations = list_applied_migrations(db).await?;
+    let migrations = migrations();
+
+    if let Some((unknown_version, _)) = applied_migrations.iter().find(|(version, _)| {
+        !migrations
+            .iter()
+            .any(|migration| migration.version() == *version)
+    }) {
+        return Err(errors::Error::UnknownSchemaVersion(*unknown_version));
+    }
+
+    let statuses = migrations
+        .iter()
+        .map(|migration| MigrationStatus {
+            version: migration.version(),
+            name: migration.name().to_string(),
+            applied_at: applied_migrations
+                .iter()
+                .find(|(version, _)| *version == migration.version())
+                .map(|(_, applied_at)| *applied_at),
+        })
+        .collect();
+
+    Ok(statuses)
+}
+
+/*
+아직 적용하지 않은 마이그레이션을 순서대로 적용하고, 이번에 적용한 마이그레이션을 반환합니다.
+마이그레이션마다 트랜잭션 안에서 적용하고 기록합니다. (MySQL은 DDL이 바로 커밋되므로 중간에 실패하면 직접 확인해야 합니다)
+*/
+pub async fn run_migrations(db: &DatabaseConnection) -> errors::Result<Vec<MigrationStatus>> {
+    let statuses = migration_status(db).await?;
+
+    let mut applied = Vec::new();
+
+    for migration in migrations() {
+        let already_applied = statuses
+            .iter()
+            .any(|status| status.version == migration.version() && status.applied_at.is_some());
+
+        if already_applied {
+            continue;
+        }
+
+        let transaction = db.begin().await?;
+
+        if transaction.get_database_backend() == DbBackend::Postgres {
+            transaction
+                .execute_unprepared(&format!(
+                    "SELECT pg_advisory_xact_lock({})",
+                    POSTGRES_MIGRATION_LOCK_ID
+                ))
+                .await?;
+        }
+
+        // 잠금을 기다리는 동안 다른 인스턴스가 먼저 적용했다면 건너뜀
+        let applied_migrations = list_applied_migrations(&transaction).await?;
+
+        if applied_migrations
+            .iter()
+            .any(|(version, _)| *version == migration.version())
+        {
+            transaction.rollback().await?;
+            continue;
+        }
+
+        log::info!(
+            "Applying migration {} {}",
+            migration.version(),
+            migration.name()
+        );
+
+        migration.up(&transaction).await?;
+
+        let applied_at = Utc::now();
+
+        let insert_statement = sea_query::Query::insert()
+            .into_table(SchemaMigration::Table)
+            .columns([
+                SchemaMigration::Version,
+                SchemaMigration::Name,
+                SchemaMigration::AppliedAt,
+            ])
+            .values_panic([
+                migration.version().into(),
+                migration.name().into(),
+                applied_at.into(),
+            ])
+            .to_owned();
+
+        transaction
+            .execute(transaction.get_database_backend().build(&insert_statement))
+            .await?;
+
+        transaction.commit().await?;
+
+        applied.push(MigrationStatus {
+            version: migration.version(),
+            name: migration.name().to_string(),
+            applied_at: Some(applied_at),
+        });
+    }
+
+    Ok(applied)
+}
+
+/*
+모든 마이그레이션이 적용되어 있는지 확인합니다.
+적용하지 않은 마이그레이션이 있다면 PendingMigrations 에러를 반환합니다.
+*/
+pub async fn check_schema_is_up_to_date(db: &DatabaseConnection) -> errors::Result<()> {
+    let pending_versions = migration_status(db)
+        .await?
+        .into_iter()
+        .filter(|status| status.applied_at.is_none())
+        .map(|status| status.version.to_string())
+        .collect::<Vec<_>>();
+
+    if !pending_versions.is_empty() {
+        return Err(errors::Error::PendingMigrations(
+            pending_versions.join(", "),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn create_schema_migration_table(db: &DatabaseConnection) -> errors::Result<()> {
+    use sea_query::ColumnDef;
+
+    let create_table_statement = sea_query::Table::create()
+        .table(SchemaMigration::Table)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(SchemaMigration::Version)
+                .big_integer()
+                .not_null()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(SchemaMigration::Name).string().not_null())
+        .col(
+            ColumnDef::new(SchemaMigration::AppliedAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .to_owned();
+
+    db.execute(db.get_database_backend().build(&create_table_statement))
+        .await?;
+
+    Ok(())
+}
+
+async fn list_applied_migrations<C>(db: &C) -> errors::Result<Vec<(i64, chrono::DateTime<Utc>)>>
+where
+    C: ConnectionTrait,
+{
+    let select_statement = sea_query::Query::select()
+        .columns([SchemaMigration::Version, SchemaMigration::AppliedAt])
+        .from(SchemaMigration::Table)
+        .order_by(SchemaMigration::Version, sea_query::Order::Asc)
+        .to_owned();
+
+    let rows = db
+        .query_all(db.get_database_backend().build(&select_statement))
+        .await?;
+
+    let mut applied_migrations = Vec::new();
+
+    for row in rows {
+        let version: i64 = row.try_get("", "version")?;
+        let applied_at: chrono::DateTime<Utc> = row.try_get("", "applied_at")?;
+
+        applied_migrations.push((version, applied_at));
+    }
+
+    Ok(applied_migrations)
+}
+
+/*
+SQLite와 MySQL은 ADD COLUMN IF NOT EXISTS를 지원하지 않으므로, 테이블 정보를 조회해서 컬럼이 없을 때만 추가합니다.
+*/
+async fn add_column_if_not_exists<T>(
+    db: &DatabaseTransaction,
+    table: T,
+    mut column_def: sea_query::ColumnDef,
+) -> errors::Result<()>
+where
+    T: sea_query::Iden + 'static,
+{
+    let database_backend = db.get_database_backend();
+
+    let table_name = table.to_string();
+    let column_name = column_def.get_column_name();
+
+    let count_query = match database_backend {
+        DbBackend::Sqlite => "SELECT COUNT(*) AS count FROM pragma_table_info(?) WHERE name = ?",
+        DbBackend::Postgres => {
+            "SELECT COUNT(*) AS count FROM information_schema.columns \
+             WHERE table_schema = current_schema() AND table_name = $1 AND column_name = $2"
+        }
+        DbBackend::MySql => {
+            "SELECT COUNT(*) AS count FROM information_schema.columns \
+             WHERE table_schema = DATABASE() AND table_name = ? AND column_name = ?"
+        }
+    };
+
+    let count = query_count(
+        db,
+        count_query,
+        [table_name.clone().into(), column_name.clone().into()],
+    )
+    .await?;
+
+    if count > 0 {
+        return Ok(());
+    }
+
+    log::info!("Adding column {}.{}", table_name, column_name);
+
+    let alter_table_statement = sea_query::Table::alter()
+        .table(table)
+        .add_column(&mut column_def)
+        .to_owned();
+
+    db.execute(database_backend.build(&alter_table_statement))
+        .await?;
+
+    Ok(())
+}
+
+/*
+MySQL은 CREATE INDEX IF NOT EXISTS를 지원하지 않으므로, 인덱스가 있는지 조회해서 없을 때만 만듭니다.
+다른 backend는 statement의 if_not_exists를 그대로 사용합니다.
+*/
+async fn create_index_if_not_exists(
+    db: &DatabaseTransaction,
+    create_index_statement: sea_query::IndexCreateStatement,
+    index_name: &str,
+) -> errors::Result<()> {
+    let database_backend = db.get_database_backend();
+
+    if database_backend == DbBackend::MySql {
+        let count = query_count(
+            db,
+            "SELECT COUNT(*) AS count FROM information_schema.statistics \
+             WHERE table_schema = DATABASE() AND index_name = ?",
+            [index_name.into()],
+        )
+        .await?;
+
+        if count > 0 {
+            return Ok(());
+        }
+    }
+
+    db.execute(database_backend.build(&create_index_statement))
+        .await?;
+
+    Ok(())
+}
+
+/*
+"count" 컬럼 하나를 반환하는 쿼리를 실행합니다.
+*/
+async fn query_count<I>(db: &DatabaseTransaction, sql: &str, values: I) -> errors::Result<i64>
+where
+    I: IntoIterator<Item = sea_orm::Value>,
+{
+    let row = db
+        .query_one(sea_orm::Statement::from_sql_and_values(
+            db.get_database_backend(),
+            sql,
+            values,
+        ))
+        .await?;
+
+    let count: i64 = match row {
+        Some(row) => row.try_get("", "count")?,
+        None => 0,
+    };
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DatabaseConfig;
+
+    async fn connect_sqlite_memory() -> DatabaseConnection {
+        crate::db::create_database_connection(&DatabaseConfig {
+            url: "sqlite::memory:".to_string(),
+            max_connections: 1,
+            min_connections: 1,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to connect test database")
+    }
+
+    #[tokio::test]
+    async fn test_migrations_upgrade_unversioned_database() {
+        let db = connect_sqlite_memory().await;
+
+        // 마이그레이션 도입 전 버전이 만든 job 테이블 (이후에 추가된 컬럼이 없고, 인덱스 하나는 직접 만들어 둠)
+        db.execute_unprepared(
+            r#"CREATE TABLE "job" (
+                "id" integer NOT NULL PRIMARY KEY AUTOINCREMENT,
+                "name" varchar NOT NULL,
+                "task_definition_id" bigint NOT NULL,
+                "status" varchar(1) NOT NULL,
+                "submited_at" timestamp_with_timezone_text,
+                "started_at" timestamp_with_timezone_text,
+                "finished_at" timestamp_with_timezone_text,
+                "container_type" varchar(50) NOT NULL,
+                "container_id" varchar,
+                "exit_code" integer,
+                "error_message" varchar,
+                "log_expire_after" timestamp_with_timezone_text,
+                "log_expired" boolean NOT NULL,
+                "created_at" timestamp_with_timezone_text NOT NULL
+            );
+            CREATE INDEX "job_created_at" ON "job" ("created_at");
+            INSERT INTO "job" ("name", "task_definition_id", "status", "container_type", "exit_code", "log_expired", "created_at")
+            VALUES ('old', 1, 'Finished', 'Docker', 0, false, '2025-01-01T00:00:00Z');
+            INSERT INTO "job" ("name", "task_definition_id", "status", "container_type", "container_id", "log_expired", "created_at")
+            VALUES ('running', 1, 'Running', 'Docker', 'container-1', false, '2025-01-01T00:00:00Z');"#,
+        )
+        .await
+        .expect("Failed to create legacy table");
+
+        let applied = run_migrations(&db).await.expect("Failed to run migrations");
+        assert_eq!(applied.len(), migrations().len());
+
+        let row = db
+            .query_one(sea_orm::Statement::from_string(
+                DbBackend::Sqlite,
+                r#"SELECT "status", "exit_reason", "worker_id" FROM "job" WHERE "name" = 'old'"#,
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.try_get::<String>("", "status").unwrap(), "Succeeded");
+        assert_eq!(
+            row.try_get::<Option<String>>("", "exit_reason").unwrap(),
+            Some("Succeeded".to_string())
+        );
+        assert_eq!(
+            row.try_get::<Option<String>>("", "worker_id").unwrap(),
+            None
+        );
+
+        // 진행 중이던 job은 만료된 lease로 채워서 다음에 시작한 worker가 가져가도록 함
+        let row = db
+            .query_one(sea_orm::Statement::from_string(
+                DbBackend::Sqlite,
+                r#"SELECT "worker_id", "lease_expires_at" FROM "job" WHERE "name" = 'running'"#,
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            row.try_get::<Option<String>>("", "worker_id").unwrap(),
+            Some("legacy".to_string())
+        );
+        assert!(
+            row.try_get::<Option<chrono::DateTime<chrono::Utc>>>("", "lease_expires_at")
+                .unwrap()
+                .is_some_and(|lease| lease <= chrono::Utc::now())
+        );
+
+        assert!(check_schema_is_up_to_date(&db).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_migrations_refuse_unknown_schema_version() {
+        let db = connect_sqlite_memory().await;
+
+        run_migrations(&db).await.expect("Failed to run migrations");
+
+        // 더 새로운 버전의 batchman이 적용한 마이그레이션
+        db.execute_unprepared(
+            "INSERT INTO schema_migration (version, name, applied_at) \
+             VALUES (9999, 'future', '2030-01-01T00:00:00Z')",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            run_migrations(&db).await.unwrap_err(),
+            errors::Error::UnknownSchemaVersion(9999)
+        );
+        assert!(check_schema_is_up_to_date(&db).await.is_err());
+    }
+}
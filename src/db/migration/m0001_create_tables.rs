@@ -0,0 +1,588 @@
+use sea_orm::{ConnectionTrait, DatabaseTransaction, DeriveIden, sea_query};
+use sea_query::{ColumnDef, Index, Table, TableCreateStatement};
+
+use super::{Migration, create_index_if_not_exists};
+use crate::errors;
+
+/*
+마이그레이션을 도입하기 전의 스키마로 테이블과 인덱스를 만듭니다.
+마이그레이션 이전 버전에서 만든 데이터베이스라면 이미 있는 테이블과 인덱스는 건너뜁니다.
+이후 엔티티가 바뀌어도 이 마이그레이션의 결과가 바뀌지 않도록, 엔티티가 아니라 컬럼을 직접 정의합니다.
+*/
+pub struct CreateTables;
+
+#[async_trait::async_trait]
+impl Migration for CreateTables {
+    fn version(&self) -> i64 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "create_tables"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> errors::Result<()> {
+        let database_backend = db.get_database_backend();
+
+        for create_table_statement in [
+            task_definition_table(),
+            secret_table(),
+            registry_credential_table(),
+            job_table(),
+            job_metric_table(),
+            job_event_table(),
+            job_log_table(),
+            node_table(),
+            schedule_table(),
+        ] {
+            db.execute(database_backend.build(&create_table_statement))
+                .await?;
+        }
+
+        create_index_if_not_exists(
+            db,
+            Index::create()
+                .unique()
+                .if_not_exists()
+                .name("task_definition_name_unique")
+                .table(TaskDefinition::Table)
+                .col(TaskDefinition::Name)
+                .col(TaskDefinition::Version)
+                .to_owned(),
+            "task_definition_name_unique",
+        )
+        .await?;
+
+        create_index_if_not_exists(
+            db,
+            Index::create()
+                .if_not_exists()
+                .name("job_metric_job_id_sampled_at")
+                .table(JobMetric::Table)
+                .col(JobMetric::JobId)
+                .col(JobMetric::SampledAt)
+                .to_owned(),
+            "job_metric_job_id_sampled_at",
+        )
+        .await?;
+
+        create_index_if_not_exists(
+            db,
+            Index::create()
+                .if_not_exists()
+                .name("job_event_job_id_created_at")
+                .table(JobEvent::Table)
+                .col(JobEvent::JobId)
+                .col(JobEvent::CreatedAt)
+                .to_owned(),
+            "job_event_job_id_created_at",
+        )
+        .await?;
+
+        create_index_if_not_exists(
+            db,
+            Index::create()
+                .unique()
+                .if_not_exists()
+                .name("job_log_job_id_line_index_unique")
+                .table(JobLog::Table)
+                .col(JobLog::JobId)
+                .col(JobLog::LineIndex)
+                .to_owned(),
+            "job_log_job_id_line_index_unique",
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn id_column<T: sea_query::IntoIden>(name: T) -> ColumnDef {
+    ColumnDef::new(name)
+        .big_integer()
+        .not_null()
+        .auto_increment()
+        .primary_key()
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+pub(super) enum TaskDefinition {
+    Table,
+    Id,
+    Name,
+    Description,
+    Version,
+    Image,
+    ImagePullPolicy,
+    Command,
+    Args,
+    Env,
+    Secrets,
+    MemoryLimit,
+    MemoryReservation,
+    MemorySwap,
+    NanoCpus,
+    CpuLimit,
+    CpusetCpus,
+    Entrypoint,
+    WorkingDir,
+    User,
+    Network,
+    Mounts,
+    Labels,
+    ShmSize,
+    Ulimits,
+    StopGracePeriodSeconds,
+    PlacementConstraints,
+    CreatedAt,
+    Enabled,
+    IsLatest,
+}
+
+fn task_definition_table() -> TableCreateStatement {
+    Table::create()
+        .table(TaskDefinition::Table)
+        .if_not_exists()
+        .col(id_column(TaskDefinition::Id))
+        .col(ColumnDef::new(TaskDefinition::Name).string().not_null())
+        .col(
+            ColumnDef::new(TaskDefinition::Description)
+                .text()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(TaskDefinition::Version)
+                .big_integer()
+                .not_null(),
+        )
+        .col(ColumnDef::new(TaskDefinition::Image).string().not_null())
+        .col(
+            ColumnDef::new(TaskDefinition::ImagePullPolicy)
+                .string_len(20)
+                .null(),
+        )
+        .col(ColumnDef::new(TaskDefinition::Command).json().null())
+        .col(ColumnDef::new(TaskDefinition::Args).json().null())
+        .col(ColumnDef::new(TaskDefinition::Env).json().null())
+        .col(ColumnDef::new(TaskDefinition::Secrets).json().null())
+        .col(
+            ColumnDef::new(TaskDefinition::MemoryLimit)
+                .big_integer()
+                .null(),
+        )
+        .col(
+            ColumnDef::new(TaskDefinition::MemoryReservation)
+                .big_integer()
+                .null(),
+        )
+        .col(
+            ColumnDef::new(TaskDefinition::MemorySwap)
+                .big_integer()
+                .null(),
+        )
+        .col(
+            ColumnDef::new(TaskDefinition::NanoCpus)
+                .big_integer()
+                .null(),
+        )
+        .col(
+            ColumnDef::new(TaskDefinition::CpuLimit)
+                .big_integer()
+                .null(),
+        )
+        .col(ColumnDef::new(TaskDefinition::CpusetCpus).string().null())
+        .col(ColumnDef::new(TaskDefinition::Entrypoint).json().null())
+        .col(ColumnDef::new(TaskDefinition::WorkingDir).string().null())
+        .col(ColumnDef::new(TaskDefinition::User).string().null())
+        .col(ColumnDef::new(TaskDefinition::Network).string().null())
+        .col(ColumnDef::new(TaskDefinition::Mounts).json().null())
+        .col(ColumnDef::new(TaskDefinition::Labels).json().null())
+        .col(ColumnDef::new(TaskDefinition::ShmSize).big_integer().null())
+        .col(ColumnDef::new(TaskDefinition::Ulimits).json().null())
+        .col(
+            ColumnDef::new(TaskDefinition::StopGracePeriodSeconds)
+                .big_integer()
+                .null(),
+        )
+        .col(
+            ColumnDef::new(TaskDefinition::PlacementConstraints)
+                .json()
+                .null(),
+        )
+        .col(
+            ColumnDef::new(TaskDefinition::CreatedAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .col(ColumnDef::new(TaskDefinition::Enabled).boolean().not_null())
+        .col(
+            ColumnDef::new(TaskDefinition::IsLatest)
+                .boolean()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum Secret {
+    Table,
+    Id,
+    Name,
+    Description,
+    EncryptedValue,
+    CreatedAt,
+    UpdatedAt,
+}
+
+fn secret_table() -> TableCreateStatement {
+    Table::create()
+        .table(Secret::Table)
+        .if_not_exists()
+        .col(id_column(Secret::Id))
+        .col(
+            ColumnDef::new(Secret::Name)
+                .string()
+                .not_null()
+                .unique_key(),
+        )
+        .col(ColumnDef::new(Secret::Description).text().not_null())
+        .col(ColumnDef::new(Secret::EncryptedValue).text().not_null())
+        .col(
+            ColumnDef::new(Secret::CreatedAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(Secret::UpdatedAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum RegistryCredential {
+    Table,
+    Id,
+    Registry,
+    Username,
+    EncryptedPassword,
+    CreatedAt,
+    UpdatedAt,
+}
+
+fn registry_credential_table() -> TableCreateStatement {
+    Table::create()
+        .table(RegistryCredential::Table)
+        .if_not_exists()
+        .col(id_column(RegistryCredential::Id))
+        .col(
+            ColumnDef::new(RegistryCredential::Registry)
+                .string()
+                .not_null()
+                .unique_key(),
+        )
+        .col(
+            ColumnDef::new(RegistryCredential::Username)
+                .string()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(RegistryCredential::EncryptedPassword)
+                .text()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(RegistryCredential::CreatedAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(RegistryCredential::UpdatedAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+pub(super) enum Job {
+    Table,
+    Id,
+    Name,
+    TaskDefinitionId,
+    Status,
+    SubmitedAt,
+    StartedAt,
+    FinishedAt,
+    ContainerType,
+    ContainerId,
+    ExitCode,
+    ExitReason,
+    ErrorCode,
+    ErrorMessage,
+    StopRequestedAt,
+    CancelReason,
+    CancelledBy,
+    WorkerId,
+    LeaseExpiresAt,
+    NodeId,
+    PeakMemoryBytes,
+    CpuSeconds,
+    LogExpireAfter,
+    LogExpired,
+    CreatedAt,
+}
+
+fn job_table() -> TableCreateStatement {
+    Table::create()
+        .table(Job::Table)
+        .if_not_exists()
+        .col(id_column(Job::Id))
+        .col(ColumnDef::new(Job::Name).string().not_null())
+        .col(
+            ColumnDef::new(Job::TaskDefinitionId)
+                .big_integer()
+                .not_null(),
+        )
+        .col(ColumnDef::new(Job::Status).string_len(20).not_null())
+        .col(
+            ColumnDef::new(Job::SubmitedAt)
+                .timestamp_with_time_zone()
+                .null(),
+        )
+        .col(
+            ColumnDef::new(Job::StartedAt)
+                .timestamp_with_time_zone()
+                .null(),
+        )
+        .col(
+            ColumnDef::new(Job::FinishedAt)
+                .timestamp_with_time_zone()
+                .null(),
+        )
+        .col(ColumnDef::new(Job::ContainerType).string_len(50).not_null())
+        .col(ColumnDef::new(Job::ContainerId).string().null())
+        .col(ColumnDef::new(Job::ExitCode).integer().null())
+        .col(ColumnDef::new(Job::ExitReason).string_len(20).null())
+        .col(ColumnDef::new(Job::ErrorCode).string().null())
+        .col(ColumnDef::new(Job::ErrorMessage).text().null())
+        .col(
+            ColumnDef::new(Job::StopRequestedAt)
+                .timestamp_with_time_zone()
+                .null(),
+        )
+        .col(ColumnDef::new(Job::CancelReason).text().null())
+        .col(ColumnDef::new(Job::CancelledBy).string().null())
+        .col(ColumnDef::new(Job::WorkerId).string().null())
+        .col(
+            ColumnDef::new(Job::LeaseExpiresAt)
+                .timestamp_with_time_zone()
+                .null(),
+        )
+        .col(ColumnDef::new(Job::NodeId).big_integer().null())
+        .col(ColumnDef::new(Job::PeakMemoryBytes).big_integer().null())
+        .col(ColumnDef::new(Job::CpuSeconds).double().null())
+        .col(
+            ColumnDef::new(Job::LogExpireAfter)
+                .timestamp_with_time_zone()
+                .null(),
+        )
+        .col(ColumnDef::new(Job::LogExpired).boolean().not_null())
+        .col(
+            ColumnDef::new(Job::CreatedAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum JobMetric {
+    Table,
+    Id,
+    JobId,
+    SampledAt,
+    CpuPercent,
+    CpuSeconds,
+    MemoryUsageBytes,
+    MemoryLimitBytes,
+    Pids,
+}
+
+fn job_metric_table() -> TableCreateStatement {
+    Table::create()
+        .table(JobMetric::Table)
+        .if_not_exists()
+        .col(id_column(JobMetric::Id))
+        .col(ColumnDef::new(JobMetric::JobId).big_integer().not_null())
+        .col(
+            ColumnDef::new(JobMetric::SampledAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .col(ColumnDef::new(JobMetric::CpuPercent).double().not_null())
+        .col(ColumnDef::new(JobMetric::CpuSeconds).double().not_null())
+        .col(
+            ColumnDef::new(JobMetric::MemoryUsageBytes)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(JobMetric::MemoryLimitBytes)
+                .big_integer()
+                .not_null(),
+        )
+        .col(ColumnDef::new(JobMetric::Pids).big_integer().not_null())
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum JobEvent {
+    Table,
+    Id,
+    JobId,
+    FromStatus,
+    ToStatus,
+    Reason,
+    CreatedAt,
+}
+
+fn job_event_table() -> TableCreateStatement {
+    Table::create()
+        .table(JobEvent::Table)
+        .if_not_exists()
+        .col(id_column(JobEvent::Id))
+        .col(ColumnDef::new(JobEvent::JobId).big_integer().not_null())
+        .col(ColumnDef::new(JobEvent::FromStatus).string_len(20).null())
+        .col(ColumnDef::new(JobEvent::ToStatus).string_len(20).not_null())
+        .col(ColumnDef::new(JobEvent::Reason).text().null())
+        .col(
+            ColumnDef::new(JobEvent::CreatedAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum JobLog {
+    Table,
+    Id,
+    JobId,
+    LineIndex,
+    Time,
+    Message,
+}
+
+fn job_log_table() -> TableCreateStatement {
+    Table::create()
+        .table(JobLog::Table)
+        .if_not_exists()
+        .col(id_column(JobLog::Id))
+        .col(ColumnDef::new(JobLog::JobId).big_integer().not_null())
+        .col(ColumnDef::new(JobLog::LineIndex).big_integer().not_null())
+        .col(
+            ColumnDef::new(JobLog::Time)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .col(ColumnDef::new(JobLog::Message).text().not_null())
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+pub(super) enum Node {
+    Table,
+    Id,
+    Name,
+    Labels,
+    Capacity,
+    RunningJobs,
+    Status,
+    DrainedAt,
+    RegisteredAt,
+    LastHeartbeatAt,
+}
+
+fn node_table() -> TableCreateStatement {
+    Table::create()
+        .table(Node::Table)
+        .if_not_exists()
+        .col(id_column(Node::Id))
+        .col(ColumnDef::new(Node::Name).string().not_null().unique_key())
+        .col(ColumnDef::new(Node::Labels).json().not_null())
+        .col(ColumnDef::new(Node::Capacity).integer().not_null())
+        .col(ColumnDef::new(Node::RunningJobs).integer().not_null())
+        .col(
+            ColumnDef::new(Node::Status)
+                .string_len(20)
+                .not_null()
+                .default("Active"),
+        )
+        .col(
+            ColumnDef::new(Node::DrainedAt)
+                .timestamp_with_time_zone()
+                .null(),
+        )
+        .col(
+            ColumnDef::new(Node::RegisteredAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(Node::LastHeartbeatAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum Schedule {
+    Table,
+    Id,
+    Name,
+    JobName,
+    CronExpression,
+    TaskDefinitionId,
+    Command,
+    Timezone,
+    TimezoneOffset,
+    Enabled,
+    CreatedAt,
+    LastTriggeredAt,
+    LogRetainDays,
+}
+
+fn schedule_table() -> TableCreateStatement {
+    Table::create()
+        .table(Schedule::Table)
+        .if_not_exists()
+        .col(id_column(Schedule::Id))
+        .col(ColumnDef::new(Schedule::Name).string().not_null())
+        .col(ColumnDef::new(Schedule::JobName).string().not_null())
+        .col(ColumnDef::new(Schedule::CronExpression).string().not_null())
+        .col(
+            ColumnDef::new(Schedule::TaskDefinitionId)
+                .big_integer()
+                .not_null(),
+        )
+        .col(ColumnDef::new(Schedule::Command).text().null())
+        .col(ColumnDef::new(Schedule::Timezone).string().null())
+        .col(ColumnDef::new(Schedule::TimezoneOffset).integer().null())
+        .col(ColumnDef::new(Schedule::Enabled).boolean().not_null())
+        .col(
+            ColumnDef::new(Schedule::CreatedAt)
+                .timestamp_with_time_zone()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(Schedule::LastTriggeredAt)
+                .timestamp_with_time_zone()
+                .null(),
+        )
+        .col(ColumnDef::new(Schedule::LogRetainDays).integer().null())
+        .to_owned()
+}
@@ -0,0 +1,146 @@
+use sea_orm::{DatabaseTransaction, sea_query};
+use sea_query::ColumnDef;
+
+use super::{
+    Migration, add_column_if_not_exists,
+    m0001_create_tables::{Job, Node, TaskDefinition},
+};
+use crate::errors;
+
+/*
+마이그레이션을 도입하기 전에는 시작할 때마다 없는 컬럼을 추가했습니다.
+그 사이의 어느 버전에서 만든 데이터베이스라도 create_tables와 같은 스키마가 되도록 빠진 컬럼을 추가합니다.
+새로 만든 데이터베이스라면 아무것도 하지 않습니다.
+*/
+pub struct AddMissingColumns;
+
+#[async_trait::async_trait]
+impl Migration for AddMissingColumns {
+    fn version(&self) -> i64 {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "add_missing_columns"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> errors::Result<()> {
+        let task_definition_columns = [
+            ColumnDef::new(TaskDefinition::Secrets)
+                .json()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::Entrypoint)
+                .json()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::WorkingDir)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::User)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::Network)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::Mounts)
+                .json()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::Labels)
+                .json()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::ShmSize)
+                .big_integer()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::Ulimits)
+                .json()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::MemoryReservation)
+                .big_integer()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::MemorySwap)
+                .big_integer()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::NanoCpus)
+                .big_integer()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::CpusetCpus)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::ImagePullPolicy)
+                .string_len(20)
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::StopGracePeriodSeconds)
+                .big_integer()
+                .null()
+                .to_owned(),
+            ColumnDef::new(TaskDefinition::PlacementConstraints)
+                .json()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in task_definition_columns {
+            add_column_if_not_exists(db, TaskDefinition::Table, column).await?;
+        }
+
+        let job_columns = [
+            ColumnDef::new(Job::ErrorCode).string().null().to_owned(),
+            ColumnDef::new(Job::ExitReason)
+                .string_len(20)
+                .null()
+                .to_owned(),
+            ColumnDef::new(Job::StopRequestedAt)
+                .timestamp_with_time_zone()
+                .null()
+                .to_owned(),
+            ColumnDef::new(Job::PeakMemoryBytes)
+                .big_integer()
+                .null()
+                .to_owned(),
+            ColumnDef::new(Job::CpuSeconds).double().null().to_owned(),
+            ColumnDef::new(Job::CancelReason).text().null().to_owned(),
+            ColumnDef::new(Job::CancelledBy).string().null().to_owned(),
+            ColumnDef::new(Job::WorkerId).string().null().to_owned(),
+            ColumnDef::new(Job::LeaseExpiresAt)
+                .timestamp_with_time_zone()
+                .null()
+                .to_owned(),
+            ColumnDef::new(Job::NodeId).big_integer().null().to_owned(),
+        ];
+
+        for column in job_columns {
+            add_column_if_not_exists(db, Job::Table, column).await?;
+        }
+
+        let node_columns = [
+            ColumnDef::new(Node::Status)
+                .string_len(20)
+                .not_null()
+                .default("Active")
+                .to_owned(),
+            ColumnDef::new(Node::DrainedAt)
+                .timestamp_with_time_zone()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in node_columns {
+            add_column_if_not_exists(db, Node::Table, column).await?;
+        }
+
+        Ok(())
+    }
+}
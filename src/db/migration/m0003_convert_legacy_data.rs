@@ -0,0 +1,188 @@
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseTransaction, DbBackend, EntityTrait, QueryFilter,
+    Statement, sea_query,
+};
+
+use super::Migration;
+use crate::{domain, errors};
+
+/*
+예전 버전에서 저장한 데이터를 현재 형식으로 변환합니다.
+- task_definition의 command/args/env: 공백/콤마로 구분된 문자열 -> JSON
+- job의 Finished 상태: exit code에 따라 Succeeded/Failed
+- 진행 중인 job의 worker_id: 만료된 lease를 가진 LEGACY_WORKER_ID로 채워 다음에 시작한 worker가 가져가도록 함
+*/
+pub struct ConvertLegacyData;
+
+#[async_trait::async_trait]
+impl Migration for ConvertLegacyData {
+    fn version(&self) -> i64 {
+        3
+    }
+
+    fn name(&self) -> &'static str {
+        "convert_legacy_data"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> errors::Result<()> {
+        // 예전 형식의 command/args/env는 SQLite를 사용하던 버전에만 있음
+        if db.get_database_backend() == DbBackend::Sqlite {
+            migrate_legacy_task_definition_fields(db).await?;
+        }
+
+        migrate_legacy_job_statuses(db).await?;
+        migrate_legacy_job_workers(db).await?;
+
+        Ok(())
+    }
+}
+
+// worker를 기록하기 전에 실행된 job에 채우는 worker id
+const LEGACY_WORKER_ID: &str = "legacy";
+
+/*
+worker_id가 없던 버전에서 시작한 진행 중인 job은 어느 worker도 추적하지 않습니다.
+이미 만료된 lease로 채워서 Starting/Pulling job은 다시 claim되고, Running/Cancelling job은 tracker가 가져가도록 합니다.
+*/
+async fn migrate_legacy_job_workers(db: &DatabaseTransaction) -> errors::Result<()> {
+    use domain::job::entities::job::{Column, Entity, JobStatus};
+
+    let result = Entity::update_many()
+        .col_expr(Column::WorkerId, sea_query::Expr::value(LEGACY_WORKER_ID))
+        .col_expr(
+            Column::LeaseExpiresAt,
+            sea_query::Expr::value(chrono::Utc::now()),
+        )
+        .filter(Column::WorkerId.is_null())
+        .filter(Column::NodeId.is_null())
+        .filter(Column::Status.is_in([
+            JobStatus::Starting,
+            JobStatus::Pulling,
+            JobStatus::Running,
+            JobStatus::Cancelling,
+        ]))
+        .exec(db)
+        .await?;
+
+    if result.rows_affected > 0 {
+        log::info!(
+            "Assigned {} in-flight legacy jobs to worker {}",
+            result.rows_affected,
+            LEGACY_WORKER_ID
+        );
+    }
+
+    Ok(())
+}
+
+/*
+기존 Finished 상태를 exit code에 따라 Succeeded/Failed로 변환합니다.
+*/
+async fn migrate_legacy_job_statuses(db: &DatabaseTransaction) -> errors::Result<()> {
+    use domain::job::entities::job::{Column, Entity, ExitReason, JobStatus};
+
+    let succeeded = Entity::update_many()
+        .col_expr(Column::Status, sea_query::Expr::value(JobStatus::Succeeded))
+        .col_expr(
+            Column::ExitReason,
+            sea_query::Expr::value(ExitReason::Succeeded),
+        )
+        .filter(sea_query::Expr::col(Column::Status).eq("Finished"))
+        .filter(Column::ExitCode.eq(0))
+        .exec(db)
+        .await?;
+
+    let failed = Entity::update_many()
+        .col_expr(Column::Status, sea_query::Expr::value(JobStatus::Failed))
+        .col_expr(
+            Column::ExitReason,
+            sea_query::Expr::value(ExitReason::NonZeroExit),
+        )
+        .filter(sea_query::Expr::col(Column::Status).eq("Finished"))
+        .exec(db)
+        .await?;
+
+    if succeeded.rows_affected + failed.rows_affected > 0 {
+        log::info!(
+            "Migrated legacy Finished jobs: {} succeeded, {} failed",
+            succeeded.rows_affected,
+            failed.rows_affected
+        );
+    }
+
+    Ok(())
+}
+
+/*
+task_definition의 command/args/env 컬럼은 예전에는 공백/콤마로 구분된 문자열이었습니다.
+JSON 형식(문자열 배열, key/value 객체)이 아닌 행을 찾아서 변환합니다.
+*/
+async fn migrate_legacy_task_definition_fields(db: &DatabaseTransaction) -> errors::Result<()> {
+    use std::collections::BTreeMap;
+
+    use domain::task_definition::entities::task_definition::{
+        ArgumentList, Column, Entity, EnvironmentVariables,
+    };
+
+    let database_backend = db.get_database_backend();
+
+    let rows = db
+        .query_all(Statement::from_string(
+            database_backend,
+            "SELECT id, command, args, env FROM task_definition",
+        ))
+        .await?;
+
+    for row in rows {
+        let id: i64 = row.try_get("", "id")?;
+        let command: Option<String> = row.try_get("", "command")?;
+        let args: Option<String> = row.try_get("", "args")?;
+        let env: Option<String> = row.try_get("", "env")?;
+
+        let mut update_query = Entity::update_many().filter(Column::Id.eq(id));
+        let mut need_update = false;
+
+        if let Some(command) = command
+            && serde_json::from_str::<Vec<String>>(&command).is_err()
+        {
+            let command = ArgumentList::from_legacy_command(&command);
+            update_query = update_query.col_expr(
+                Column::Command,
+                sea_query::Expr::value(serde_json::to_string(&command)?),
+            );
+            need_update = true;
+        }
+
+        if let Some(args) = args
+            && serde_json::from_str::<Vec<String>>(&args).is_err()
+        {
+            let args = ArgumentList::from_legacy_args(&args);
+            update_query = update_query.col_expr(
+                Column::Args,
+                sea_query::Expr::value(serde_json::to_string(&args)?),
+            );
+            need_update = true;
+        }
+
+        if let Some(env) = env
+            && serde_json::from_str::<BTreeMap<String, String>>(&env).is_err()
+        {
+            let env = EnvironmentVariables::from_legacy_env(&env);
+            update_query = update_query.col_expr(
+                Column::Env,
+                sea_query::Expr::value(serde_json::to_string(&env)?),
+            );
+            need_update = true;
+        }
+
+        if need_update {
+            log::info!(
+                "Migrating legacy command/args/env of task_definition {}",
+                id
+            );
+            update_query.exec(db).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use sea_orm::{DatabaseTransaction, sea_query};
+use sea_query::Index;
+
+use super::{Migration, create_index_if_not_exists, m0001_create_tables::Job};
+use crate::errors;
+
+/*
+job 목록 조회와 runner/tracker의 상태별 polling에 사용하는 인덱스를 추가합니다.
+- (status, id): 상태로 거른 뒤 id 순서로 정렬/cursor 조회
+- task_definition_id: task definition별 job 조회
+- created_at: 생성 시각 범위 조회
+*/
+pub struct AddJobListingIndexes;
+
+#[async_trait::async_trait]
+impl Migration for AddJobListingIndexes {
+    fn version(&self) -> i64 {
+        4
+    }
+
+    fn name(&self) -> &'static str {
+        "add_job_listing_indexes"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> errors::Result<()> {
+        create_index_if_not_exists(
+            db,
+            Index::create()
+                .if_not_exists()
+                .name("job_status_id")
+                .table(Job::Table)
+                .col(Job::Status)
+                .col(Job::Id)
+                .to_owned(),
+            "job_status_id",
+        )
+        .await?;
+
+        create_index_if_not_exists(
+            db,
+            Index::create()
+                .if_not_exists()
+                .name("job_task_definition_id")
+                .table(Job::Table)
+                .col(Job::TaskDefinitionId)
+                .to_owned(),
+            "job_task_definition_id",
+        )
+        .await?;
+
+        create_index_if_not_exists(
+            db,
+            Index::create()
+                .if_not_exists()
+                .name("job_created_at")
+                .table(Job::Table)
+                .col(Job::CreatedAt)
+                .to_owned(),
+            "job_created_at",
+        )
+        .await?;
+
+        Ok(())
+    }
+}
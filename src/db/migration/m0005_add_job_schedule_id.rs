@@ -0,0 +1,51 @@
+use sea_orm::{ConnectionTrait, DatabaseTransaction, DeriveIden, sea_query};
+use sea_query::{ColumnDef, Index, Table};
+
+use super::Migration;
+use crate::errors;
+
+#[derive(DeriveIden)]
+enum Job {
+    Table,
+    ScheduleId,
+}
+
+/*
+스케줄이 제출한 job을 스케줄별로 조회할 수 있도록 job에 schedule_id를 추가합니다.
+이전에 스케줄이 제출한 job은 어느 스케줄인지 알 수 없으므로 NULL로 남습니다.
+*/
+pub struct AddJobScheduleId;
+
+#[async_trait::async_trait]
+impl Migration for AddJobScheduleId {
+    fn version(&self) -> i64 {
+        5
+    }
+
+    fn name(&self) -> &'static str {
+        "add_job_schedule_id"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> errors::Result<()> {
+        let database_backend = db.get_database_backend();
+
+        let add_column_statement = Table::alter()
+            .table(Job::Table)
+            .add_column(ColumnDef::new(Job::ScheduleId).big_integer().null())
+            .to_owned();
+
+        db.execute(database_backend.build(&add_column_statement))
+            .await?;
+
+        let create_index_statement = Index::create()
+            .name("job_schedule_id")
+            .table(Job::Table)
+            .col(Job::ScheduleId)
+            .to_owned();
+
+        db.execute(database_backend.build(&create_index_statement))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use sea_orm::{ConnectionTrait, DatabaseTransaction, DeriveIden, sea_query};
+use sea_query::{ColumnDef, Index, Table};
+
+use super::Migration;
+use crate::errors;
+
+#[derive(DeriveIden)]
+enum JobArchive {
+    Table,
+    Id,
+    Name,
+    TaskDefinitionId,
+    ScheduleId,
+    Status,
+    ExitCode,
+    SubmitedAt,
+    StartedAt,
+    FinishedAt,
+    CreatedAt,
+    ArchivedAt,
+    Record,
+}
+
+#[derive(DeriveIden)]
+enum RemovedJobCount {
+    Table,
+    TaskDefinitionId,
+    Status,
+    JobCount,
+    UpdatedAt,
+}
+
+/*
+보관 기간이 지난 job을 옮기기 위한 테이블을 추가합니다.
+- job_archive: job 테이블에서 옮긴 job (조회에 쓰는 컬럼과 job 전체를 담은 JSON)
+- removed_job_count: job 테이블에서 빠진 job 수를 task definition/상태별로 누적 (대시보드 집계용)
+*/
+pub struct AddJobArchive;
+
+#[async_trait::async_trait]
+impl Migration for AddJobArchive {
+    fn version(&self) -> i64 {
+        6
+    }
+
+    fn name(&self) -> &'static str {
+        "add_job_archive"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> errors::Result<()> {
+        let database_backend = db.get_database_backend();
+
+        // id는 job 테이블의 id를 그대로 사용
+        let create_job_archive_statement = Table::create()
+            .table(JobArchive::Table)
+            .col(
+                ColumnDef::new(JobArchive::Id)
+                    .big_integer()
+                    .not_null()
+                    .primary_key(),
+            )
+            .col(ColumnDef::new(JobArchive::Name).string().not_null())
+            .col(
+                ColumnDef::new(JobArchive::TaskDefinitionId)
+                    .big_integer()
+                    .not_null(),
+            )
+            .col(ColumnDef::new(JobArchive::ScheduleId).big_integer().null())
+            .col(ColumnDef::new(JobArchive::Status).string_len(20).not_null())
+            .col(ColumnDef::new(JobArchive::ExitCode).integer().null())
+            .col(
+                ColumnDef::new(JobArchive::SubmitedAt)
+                    .timestamp_with_time_zone()
+                    .null(),
+            )
+            .col(
+                ColumnDef::new(JobArchive::StartedAt)
+                    .timestamp_with_time_zone()
+                    .null(),
+            )
+            .col(
+                ColumnDef::new(JobArchive::FinishedAt)
+                    .timestamp_with_time_zone()
+                    .null(),
+            )
+            .col(
+                ColumnDef::new(JobArchive::CreatedAt)
+                    .timestamp_with_time_zone()
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(JobArchive::ArchivedAt)
+                    .timestamp_with_time_zone()
+                    .not_null(),
+            )
+            .col(ColumnDef::new(JobArchive::Record).text().not_null())
+            .to_owned();
+
+        let create_removed_job_count_statement = Table::create()
+            .table(RemovedJobCount::Table)
+            .col(
+                ColumnDef::new(RemovedJobCount::TaskDefinitionId)
+                    .big_integer()
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(RemovedJobCount::Status)
+                    .string_len(20)
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(RemovedJobCount::JobCount)
+                    .big_integer()
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(RemovedJobCount::UpdatedAt)
+                    .timestamp_with_time_zone()
+                    .not_null(),
+            )
+            .primary_key(
+                Index::create()
+                    .col(RemovedJobCount::TaskDefinitionId)
+                    .col(RemovedJobCount::Status),
+            )
+            .to_owned();
+
+        db.execute(database_backend.build(&create_job_archive_statement))
+            .await?;
+        db.execute(database_backend.build(&create_removed_job_count_statement))
+            .await?;
+
+        for create_index_statement in [
+            Index::create()
+                .name("job_archive_task_definition_id")
+                .table(JobArchive::Table)
+                .col(JobArchive::TaskDefinitionId)
+                .to_owned(),
+            Index::create()
+                .name("job_archive_finished_at")
+                .table(JobArchive::Table)
+                .col(JobArchive::FinishedAt)
+                .to_owned(),
+        ] {
+            db.execute(database_backend.build(&create_index_statement))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
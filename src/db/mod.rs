@@ -1,19 +1,22 @@
+pub mod migration;
+pub mod unit_of_work;
+
 use std::time::Duration;
 
-use sea_orm::{
-    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend, Schema, sea_query,
-};
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
 
-use crate::{domain, errors};
+use crate::{config::DatabaseConfig, errors};
 
-pub async fn create_database_connection() -> errors::Result<DatabaseConnection> {
-    let mut opt = ConnectOptions::new("sqlite://./db.sqlite?mode=rwc");
-    opt.max_connections(100)
-        .min_connections(5)
-        .connect_timeout(Duration::from_secs(8))
-        .acquire_timeout(Duration::from_secs(8))
-        .idle_timeout(Duration::from_secs(8))
-        .max_lifetime(Duration::from_secs(8))
+pub async fn create_database_connection(
+    config: &DatabaseConfig,
+) -> errors::Result<DatabaseConnection> {
+    let mut opt = ConnectOptions::new(config.url.clone());
+    opt.max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .connect_timeout(Duration::from_secs(config.connect_timeout_seconds))
+        .acquire_timeout(Duration::from_secs(config.acquire_timeout_seconds))
+        .idle_timeout(Duration::from_secs(config.idle_timeout_seconds))
+        .max_lifetime(Duration::from_secs(config.max_lifetime_seconds))
         .sqlx_logging(true)
         .sqlx_logging_level(log::LevelFilter::Info);
 
@@ -22,118 +25,341 @@ pub async fn create_database_connection() -> errors::Result<DatabaseConnection>
     Ok(db)
 }
 
-pub async fn setup_schema(db: &DatabaseConnection) {
-    // Setup Schema helper
-    let schema = Schema::new(DbBackend::Sqlite);
+#[cfg(test)]
+mod tests {
+    use sea_orm::{
+        ActiveModelTrait, ConnectionTrait, DbBackend, EntityTrait, NotSet, Set, sea_query,
+    };
 
-    // task_definition table generate
-    {
-        let mut create_table_statement =
-            schema.create_table_from_entity(entities::task_definition::Entity);
+    use super::{unit_of_work::SeaOrmUnitOfWorkFactory, *};
+    use crate::domain::{
+        job::{
+            JobRepository,
+            dao::{
+                AppendJobLogsParams, ClaimPendingJobsParams, CreateJobParams, ListJobsParams,
+                PatchJobParams, RemoveJobsParams, TransitionJobParams,
+            },
+            entities::{self, job::JobStatus},
+            repository::seaorm::JobSeaOrmRepository,
+        },
+        task_definition::{
+            TaskDefinitionRepository, dao::ListTaskDefinitionsParams,
+            repository::seaorm::TaskDefinitionSeaOrmRepository,
+        },
+        unit_of_work::UnitOfWorkFactory,
+    };
 
-        create_table_statement.if_not_exists();
+    /*
+    SQLite(in-memory)와, 환경 변수로 주소가 주어진 Postgres/MySQL 테스트 데이터베이스에 연결합니다.
+    예: BATCHMAN_TEST_POSTGRES_URL=postgres://postgres@127.0.0.1/batchman_test
+    Postgres/MySQL 데이터베이스의 테이블은 테스트 시작 시 모두 삭제됩니다.
+    */
+    async fn connect_test_databases() -> Vec<DatabaseConnection> {
+        let mut configs = vec![DatabaseConfig {
+            url: "sqlite::memory:".to_string(),
+            // in-memory 데이터베이스는 커넥션마다 따로 생성되므로 하나만 사용
+            max_connections: 1,
+            min_connections: 1,
+            ..Default::default()
+        }];
 
-        let database_backend = db.get_database_backend();
+        for env_name in ["BATCHMAN_TEST_POSTGRES_URL", "BATCHMAN_TEST_MYSQL_URL"] {
+            if let Ok(url) = std::env::var(env_name) {
+                configs.push(DatabaseConfig {
+                    url,
+                    ..Default::default()
+                });
+            }
+        }
 
-        // Execute create table statement
-        db.execute(database_backend.build(&create_table_statement))
-            .await
-            .expect("Failed to create table");
+        let mut connections = Vec::new();
 
-        // create index
+        for config in configs {
+            let db = create_database_connection(&config)
+                .await
+                .expect("Failed to connect test database");
 
-        use domain::task_definition::entities;
-        let create_unique_index_query = sea_query::Index::create()
-            .unique()
-            .if_not_exists()
-            .name("task_definition_name_unique")
-            .table(entities::task_definition::Entity)
-            .col(entities::task_definition::Column::Name)
-            .col(entities::task_definition::Column::Version)
-            .to_owned();
+            if db.get_database_backend() != DbBackend::Sqlite {
+                drop_all_tables(&db).await;
+            }
 
-        db.execute(database_backend.build(&create_unique_index_query))
-            .await
-            .expect("Failed to create index");
-    }
-
-    // job table generate
-    {
-        use domain::job::entities;
-        let mut create_table_statement = schema.create_table_from_entity(entities::job::Entity);
+            connections.push(db);
+        }
 
-        create_table_statement.if_not_exists();
+        connections
+    }
 
+    async fn drop_all_tables(db: &DatabaseConnection) {
         let database_backend = db.get_database_backend();
 
-        // Execute create table statement
-        db.execute(database_backend.build(&create_table_statement))
-            .await
-            .expect("Failed to create table");
-
-        // add columes
-        {
-            // use sea_orm::sea_query::ColumnDef;
-            // use sea_orm::sea_query::TableAlterStatement;
-
-            // let alter_table_statement = TableAlterStatement::new()
-            //     .table(entities::job::Entity)
-            //     // .add_column_if_not_exists(
-            //     //     ColumnDef::new(entities::job::Column::ContainerType)
-            //     //         .default("Docker")
-            //     //         .not_null(),
-            //     // )
-            //     .add_column_if_not_exists(
-            //         ColumnDef::new(entities::job::Column::LogExpireAfter)
-            //             .date_time()
-            //             .null(),
-            //     )
-            //     // .add_column_if_not_exists(
-            //     //     ColumnDef::new(entities::job::Column::LogExpired)
-            //     //         .boolean()
-            //     //         .default(false)
-            //     //         .not_null(),
-            //     // )
-            //     .to_owned();
-
-            // db.execute(database_backend.build(&alter_table_statement))
-            //     .await
-            //     .expect("Failed to alter table");
+        for table_name in [
+            "schema_migration",
+            "task_definition",
+            "secret",
+            "registry_credential",
+            "job",
+            "job_metric",
+            "job_event",
+            "job_log",
+            "job_archive",
+            "removed_job_count",
+            "node",
+            "schedule",
+        ] {
+            let drop_table_statement = sea_query::Table::drop()
+                .table(sea_query::Alias::new(table_name))
+                .if_exists()
+                .to_owned();
+
+            db.execute(database_backend.build(&drop_table_statement))
+                .await
+                .expect("Failed to drop table");
         }
     }
 
-    // schedule table generate
-    {
-        use domain::schedule::entities;
-        let mut create_table_statement =
-            schema.create_table_from_entity(entities::schedule::Entity);
+    #[tokio::test]
+    async fn test_schema_and_repositories_on_each_backend() {
+        use crate::domain::task_definition::entities::task_definition;
 
-        create_table_statement.if_not_exists();
+        for db in connect_test_databases().await {
+            let database_backend = db.get_database_backend();
 
-        let database_backend = db.get_database_backend();
+            let applied = migration::run_migrations(&db)
+                .await
+                .expect("Failed to run migrations");
+            assert_eq!(applied.len(), migration::migrations().len());
+
+            // 이미 적용한 마이그레이션은 다시 적용하지 않음
+            let applied = migration::run_migrations(&db)
+                .await
+                .expect("Failed to run migrations");
+            assert!(applied.is_empty(), "{database_backend:?}");
 
-        // Execute create table statement
-        db.execute(database_backend.build(&create_table_statement))
+            let task_definition_id = task_definition::ActiveModel {
+                id: NotSet,
+                name: Set("test".to_string()),
+                description: Set("d".repeat(1000)),
+                version: Set(1),
+                image: Set("alpine".to_string()),
+                memory_limit: Set(Some(u32::MAX.into())),
+                created_at: Set(chrono::Utc::now()),
+                enabled: Set(true),
+                is_latest: Set(true),
+                ..Default::default()
+            }
+            .insert(&db)
             .await
-            .expect("Failed to create table");
-
-        // add columes
-        {
-            // use sea_orm::sea_query::ColumnDef;
-            // use sea_orm::sea_query::TableAlterStatement;
-
-            // let alter_table_statement = TableAlterStatement::new()
-            //     .table(entities::schedule::Entity)
-            //     .add_column_if_not_exists(
-            //         ColumnDef::new(entities::schedule::Column::LogRetainDays)
-            //             .integer()
-            //             .null(),
-            //     )
-            //     .to_owned();
-
-            // db.execute(database_backend.build(&alter_table_statement))
-            //     .await
-            //     .expect("Failed to alter table");
+            .expect("Failed to insert task definition")
+            .id;
+
+            let task_definition_repository = TaskDefinitionSeaOrmRepository::new(db.clone());
+
+            let task_definitions = task_definition_repository
+                .list_task_definitions(ListTaskDefinitionsParams {
+                    task_definition_ids: vec![task_definition_id],
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to list task definitions");
+            assert_eq!(
+                task_definitions[0].memory_limit,
+                Some(u32::MAX.into()),
+                "{database_backend:?}"
+            );
+
+            let job_repository = JobSeaOrmRepository::new(db.clone());
+
+            for i in 0..3 {
+                job_repository
+                    .create_job(CreateJobParams {
+                        name: format!("job-{i}"),
+                        task_definition_id,
+                        status: JobStatus::Pending,
+                        submited_at: Some(chrono::Utc::now()),
+                        ..Default::default()
+                    })
+                    .await
+                    .expect("Failed to create job");
+            }
+
+            // 두 worker가 동시에 claim해도 같은 job을 중복으로 가져가지 않아야 함
+            let claim = |worker_id: &str| {
+                job_repository.claim_pending_jobs(ClaimPendingJobsParams {
+                    worker_id: worker_id.to_string(),
+                    node_id: None,
+                    limit: 3,
+                    excluded_task_definition_ids: vec![],
+                    lease_duration: chrono::Duration::minutes(10),
+                })
+            };
+            let (claimed_a, claimed_b) = tokio::join!(claim("worker-a"), claim("worker-b"));
+
+            let mut claimed_job_ids = claimed_a
+                .expect("Failed to claim jobs")
+                .into_iter()
+                .chain(claimed_b.expect("Failed to claim jobs"))
+                .map(|job| job.id)
+                .collect::<Vec<_>>();
+            claimed_job_ids.sort();
+            claimed_job_ids.dedup();
+            assert_eq!(claimed_job_ids.len(), 3, "{database_backend:?}");
+
+            // 최근 job부터 정렬되고, after_id 이후의 job만 조회되어야 함
+            let list_job_ids = |after_id: Option<i64>| {
+                job_repository.list_jobs(ListJobsParams {
+                    limit: Some(2),
+                    after_id,
+                    ..Default::default()
+                })
+            };
+            let first_page = list_job_ids(None).await.expect("Failed to list jobs");
+            let second_page = list_job_ids(Some(first_page[1].id))
+                .await
+                .expect("Failed to list jobs");
+            assert_eq!(
+                first_page
+                    .iter()
+                    .chain(&second_page)
+                    .map(|job| job.id)
+                    .collect::<Vec<_>>(),
+                claimed_job_ids.iter().rev().copied().collect::<Vec<_>>(),
+                "{database_backend:?}"
+            );
+
+            let job_id = claimed_job_ids[0];
+
+            job_repository
+                .patch_job(PatchJobParams {
+                    job_id,
+                    error_message: Some("e".repeat(1000)),
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to patch job");
+
+            let next_offset = job_repository
+                .append_job_logs(AppendJobLogsParams {
+                    job_id,
+                    offset: 0,
+                    lines: vec![
+                        (chrono::Utc::now(), "l".repeat(1000)),
+                        (chrono::Utc::now(), "done\n".to_string()),
+                    ],
+                })
+                .await
+                .expect("Failed to append job logs");
+            assert_eq!(next_offset, 2, "{database_backend:?}");
+
+            let events = job_repository
+                .list_job_events(job_id)
+                .await
+                .expect("Failed to list job events");
+            assert_eq!(events.len(), 2, "{database_backend:?}");
+
+            // commit하지 않은 unit of work의 변경은 job_event까지 함께 rollback되어야 함
+            let unit_of_work_factory = SeaOrmUnitOfWorkFactory::new(db.clone());
+            let transition_to_running = || TransitionJobParams {
+                to_status: JobStatus::Running,
+                reason: None,
+                patch: PatchJobParams {
+                    job_id,
+                    container_id: Some("container".to_string()),
+                    ..Default::default()
+                },
+            };
+
+            let unit_of_work = unit_of_work_factory
+                .begin()
+                .await
+                .expect("Failed to begin unit of work");
+            unit_of_work
+                .job_repository()
+                .transition_job(transition_to_running())
+                .await
+                .expect("Failed to transition job");
+            drop(unit_of_work);
+
+            let job = job_repository
+                .list_jobs(ListJobsParams {
+                    job_ids: vec![job_id],
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to list jobs")
+                .remove(0);
+            assert_eq!(job.status, JobStatus::Starting, "{database_backend:?}");
+            assert_eq!(job.container_id, None, "{database_backend:?}");
+
+            let unit_of_work = unit_of_work_factory
+                .begin()
+                .await
+                .expect("Failed to begin unit of work");
+            unit_of_work
+                .job_repository()
+                .transition_job(transition_to_running())
+                .await
+                .expect("Failed to transition job");
+            unit_of_work
+                .commit()
+                .await
+                .expect("Failed to commit unit of work");
+
+            let events = job_repository
+                .list_job_events(job_id)
+                .await
+                .expect("Failed to list job events");
+            assert_eq!(events.len(), 3, "{database_backend:?}");
+            assert_eq!(events[2].to_status, JobStatus::Running);
+
+            // 종료된 job만 job_archive로 옮기고, 옮긴 수는 상태별 집계에 남아야 함
+            job_repository
+                .transition_job(TransitionJobParams {
+                    to_status: JobStatus::Succeeded,
+                    reason: None,
+                    patch: PatchJobParams {
+                        job_id,
+                        finished_at: Some(chrono::Utc::now()),
+                        ..Default::default()
+                    },
+                })
+                .await
+                .expect("Failed to transition job");
+
+            let removed_count = job_repository
+                .remove_jobs(RemoveJobsParams {
+                    job_ids: claimed_job_ids.clone(),
+                    archive: true,
+                })
+                .await
+                .expect("Failed to remove jobs");
+            assert_eq!(removed_count, 1, "{database_backend:?}");
+
+            let archived_job = entities::job_archive::Entity::find_by_id(job_id)
+                .one(&db)
+                .await
+                .expect("Failed to find archived job")
+                .expect("Job was not archived");
+            assert_eq!(archived_job.status, JobStatus::Succeeded);
+            assert!(archived_job.record.contains("\"container\""));
+
+            let events = job_repository
+                .list_job_events(job_id)
+                .await
+                .expect("Failed to list job events");
+            assert!(events.is_empty(), "{database_backend:?}");
+
+            let counts = job_repository
+                .count_jobs_by_status(vec![task_definition_id])
+                .await
+                .expect("Failed to count jobs");
+            let count_of = |status: JobStatus| {
+                counts
+                    .iter()
+                    .find(|count| count.status == status)
+                    .map(|count| (count.job_count, count.removed_count))
+            };
+            assert_eq!(count_of(JobStatus::Succeeded), Some((0, 1)));
+            assert_eq!(count_of(JobStatus::Starting), Some((2, 0)));
         }
     }
 }
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr, ExecResult,
+    QueryResult, Statement, TransactionTrait,
+};
+
+use crate::{
+    domain::{
+        job::{JobRepository, repository::JobSeaOrmRepository},
+        secret::{SecretRepository, repository::SecretSeaOrmRepository},
+        task_definition::{TaskDefinitionRepository, repository::TaskDefinitionSeaOrmRepository},
+        unit_of_work::{UnitOfWork, UnitOfWorkFactory},
+    },
+    errors,
+};
+
+/*
+repository가 쿼리를 실행할 대상입니다.
+평소에는 커넥션 풀을, unit of work 안에서는 공유하는 트랜잭션을 사용합니다.
+*/
+#[derive(Debug, Clone)]
+pub enum DatabaseExecutor {
+    Connection(DatabaseConnection),
+    Transaction(Arc<DatabaseTransaction>),
+}
+
+impl DatabaseExecutor {
+    /*
+    트랜잭션을 시작합니다. 이미 트랜잭션 안이라면 savepoint를 사용하는 중첩 트랜잭션이 됩니다.
+    */
+    pub async fn begin(&self) -> Result<DatabaseTransaction, DbErr> {
+        match self {
+            DatabaseExecutor::Connection(connection) => connection.begin().await,
+            DatabaseExecutor::Transaction(transaction) => transaction.begin().await,
+        }
+    }
+}
+
+impl From<DatabaseConnection> for DatabaseExecutor {
+    fn from(connection: DatabaseConnection) -> Self {
+        DatabaseExecutor::Connection(connection)
+    }
+}
+
+#[async_trait::async_trait]
+impl ConnectionTrait for DatabaseExecutor {
+    fn get_database_backend(&self) -> DbBackend {
+        match self {
+            DatabaseExecutor::Connection(connection) => connection.get_database_backend(),
+            DatabaseExecutor::Transaction(transaction) => transaction.get_database_backend(),
+        }
+    }
+
+    async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        match self {
+            DatabaseExecutor::Connection(connection) => connection.execute(stmt).await,
+            DatabaseExecutor::Transaction(transaction) => transaction.execute(stmt).await,
+        }
+    }
+
+    async fn execute_unprepared(&self, sql: &str) -> Result<ExecResult, DbErr> {
+        match self {
+            DatabaseExecutor::Connection(connection) => connection.execute_unprepared(sql).await,
+            DatabaseExecutor::Transaction(transaction) => transaction.execute_unprepared(sql).await,
+        }
+    }
+
+    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        match self {
+            DatabaseExecutor::Connection(connection) => connection.query_one(stmt).await,
+            DatabaseExecutor::Transaction(transaction) => transaction.query_one(stmt).await,
+        }
+    }
+
+    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        match self {
+            DatabaseExecutor::Connection(connection) => connection.query_all(stmt).await,
+            DatabaseExecutor::Transaction(transaction) => transaction.query_all(stmt).await,
+        }
+    }
+
+    fn support_returning(&self) -> bool {
+        match self {
+            DatabaseExecutor::Connection(connection) => connection.support_returning(),
+            DatabaseExecutor::Transaction(transaction) => transaction.support_returning(),
+        }
+    }
+}
+
+pub struct SeaOrmUnitOfWorkFactory {
+    pub connection: DatabaseConnection,
+}
+
+impl SeaOrmUnitOfWorkFactory {
+    pub fn new(connection: DatabaseConnection) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait::async_trait]
+impl UnitOfWorkFactory for SeaOrmUnitOfWorkFactory {
+    async fn begin(&self) -> errors::Result<Box<dyn UnitOfWork + Send + Sync>> {
+        let transaction = Arc::new(self.connection.begin().await?);
+
+        Ok(Box::new(SeaOrmUnitOfWork {
+            task_definition_repository: TaskDefinitionSeaOrmRepository {
+                connection: DatabaseExecutor::Transaction(transaction.clone()),
+            },
+            job_repository: JobSeaOrmRepository {
+                connection: DatabaseExecutor::Transaction(transaction.clone()),
+            },
+            secret_repository: SecretSeaOrmRepository {
+                connection: DatabaseExecutor::Transaction(transaction.clone()),
+            },
+            transaction,
+        }))
+    }
+}
+
+pub struct SeaOrmUnitOfWork {
+    transaction: Arc<DatabaseTransaction>,
+    task_definition_repository: TaskDefinitionSeaOrmRepository,
+    job_repository: JobSeaOrmRepository,
+    secret_repository: SecretSeaOrmRepository,
+}
+
+#[async_trait::async_trait]
+impl UnitOfWork for SeaOrmUnitOfWork {
+    fn task_definition_repository(&self) -> &(dyn TaskDefinitionRepository + Send + Sync) {
+        &self.task_definition_repository
+    }
+
+    fn job_repository(&self) -> &(dyn JobRepository + Send + Sync) {
+        &self.job_repository
+    }
+
+    fn secret_repository(&self) -> &(dyn SecretRepository + Send + Sync) {
+        &self.secret_repository
+    }
+
+    async fn commit(self: Box<Self>) -> errors::Result<()> {
+        let SeaOrmUnitOfWork {
+            transaction,
+            task_definition_repository,
+            job_repository,
+            secret_repository,
+        } = *self;
+
+        // repository가 들고 있던 참조를 먼저 놓아야 트랜잭션을 꺼낼 수 있음
+        drop(task_definition_repository);
+        drop(job_repository);
+        drop(secret_repository);
+
+        let transaction = Arc::try_unwrap(transaction)
+            .map_err(|_| DbErr::Custom("transaction is still in use".to_string()))?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use dao::{
-    InspectContainerParams, InspectContainerResult, KillContainerParams, RemoveContainerParams,
-    RunContainerParams, RunContainerResult, StopContainerParams,
+    ContainerStatsParams, ContainerStatsResult, ContainerSummary, InspectContainerParams,
+    InspectContainerResult, InspectImageParams, KillContainerParams, ListContainersParams,
+    PullImageParams, RemoveContainerParams, RunContainerParams, RunContainerResult,
+    StopContainerParams,
 };
 use serde::Serialize;
 
@@ -25,6 +27,24 @@ pub trait ContainerRepository {
         params: InspectContainerParams,
     ) -> errors::Result<InspectContainerResult>;
 
+    /// 실행 중인 컨테이너들의 현재 CPU/메모리 사용량을 한 번에 조회합니다. 그 사이 삭제된 컨테이너는 결과에서 빠집니다.
+    async fn stats(
+        &self,
+        params: ContainerStatsParams,
+    ) -> errors::Result<Vec<ContainerStatsResult>>;
+
+    /// 이미지가 로컬에 존재하는지 확인합니다.
+    async fn image_exists(&self, params: InspectImageParams) -> errors::Result<bool>;
+
+    /// 레지스트리에서 이미지를 받아옵니다. auth가 있다면 해당 인증 정보를 사용합니다.
+    async fn pull_image(&self, params: PullImageParams) -> errors::Result<()>;
+
+    /// 종료된 컨테이너를 포함하여 라벨이 일치하는 컨테이너 목록을 조회합니다.
+    async fn list_containers(
+        &self,
+        params: ListContainersParams,
+    ) -> errors::Result<Vec<ContainerSummary>>;
+
     async fn run_container(
         &self,
         task_definition: RunContainerParams,
@@ -36,3 +56,80 @@ pub trait ContainerRepository {
 
     async fn remove_container(&self, params: RemoveContainerParams) -> errors::Result<()>;
 }
+
+/*
+컨테이너를 종료합니다. docker stop은 grace period가 지나면 SIGKILL을 보냅니다.
+stop이 계속 실패하거나, 성공했는데도 컨테이너가 남아 있다면 kill로 강제 종료합니다.
+각 단계는 docker daemon의 일시적인 오류에 대비해 backoff하며 재시도합니다.
+*/
+pub async fn stop_or_kill_container(
+    container_repository: &(dyn ContainerRepository + Send + Sync),
+    container_id: &str,
+    timeout_seconds: u32,
+) -> errors::Result<()> {
+    let stop_result = retry_with_backoff("stop container", || {
+        container_repository.stop_container(StopContainerParams {
+            container_id: container_id.to_string(),
+            timeout_seconds,
+        })
+    })
+    .await;
+
+    if stop_result.is_ok() {
+        let inspect_result = container_repository
+            .inspect_container(InspectContainerParams {
+                container_id: container_id.to_string(),
+            })
+            .await;
+
+        match inspect_result {
+            Ok(result) if result.state.running => {
+                log::warn!(
+                    "Container {} is still running after the grace period, killing it",
+                    container_id
+                );
+            }
+            _ => return Ok(()),
+        }
+    }
+
+    retry_with_backoff("kill container", || {
+        container_repository.kill_container(KillContainerParams {
+            container_id: container_id.to_string(),
+        })
+    })
+    .await
+}
+
+// 컨테이너 종료 요청의 재시도 횟수와 첫 대기 시간 (매번 두 배)
+const STOP_CONTAINER_ATTEMPTS: u32 = 3;
+const STOP_CONTAINER_BACKOFF: tokio::time::Duration = tokio::time::Duration::from_millis(500);
+
+// 이미 없는 컨테이너는 종료된 것으로 봄
+async fn retry_with_backoff<F, Fut>(action: &str, mut operation: F) -> errors::Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = errors::Result<()>>,
+{
+    let mut backoff = STOP_CONTAINER_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        match operation().await {
+            Ok(()) | Err(errors::Error::ContainerNotFound) => return Ok(()),
+            Err(error) if attempt >= STOP_CONTAINER_ATTEMPTS => return Err(error),
+            Err(error) => {
+                log::warn!(
+                    "Failed to {} (attempt {}/{}): {}",
+                    action,
+                    attempt,
+                    STOP_CONTAINER_ATTEMPTS,
+                    error
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
 
 use crate::domain;
 
@@ -268,9 +270,88 @@ pub struct ContainerState {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+/// docker json-file 로그 드라이버가 기록하는 로그 한 줄입니다.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContainerLogLine {
+    pub log: String,
+    pub time: chrono::DateTime<chrono::Utc>,
+}
+
+// batchman이 실행한 컨테이너에 job id를 기록하는 라벨 (재시작 시 job과 컨테이너를 다시 연결하는 데 사용)
+pub const JOB_ID_LABEL: &str = "batchman.job_id";
+
+#[derive(Clone)]
 pub struct RunContainerParams {
+    pub job_id: i64, // job id (recorded as the JOB_ID_LABEL label)
     pub task_definition: domain::task_definition::entities::task_definition::Model,
+    pub secret_env: BTreeMap<String, String>, // decrypted secret values (environment variable name -> value)
+}
+
+// secret 값이 로그에 남지 않도록 secret_env는 이름만 출력합니다.
+impl std::fmt::Debug for RunContainerParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunContainerParams")
+            .field("job_id", &self.job_id)
+            .field("task_definition", &self.task_definition)
+            .field("secret_env", &self.secret_env.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ContainerStatsParams {
+    pub container_ids: Vec<String>, // sampled together in one call
+}
+
+/// 컨테이너의 현재 리소스 사용량 스냅샷입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerStatsResult {
+    pub container_id: String,
+    pub cpu_percent: f64,               // host CPU usage (100 = one full core)
+    pub cpu_usage_seconds: Option<f64>, // cumulative CPU time from the container cgroup (None when it can't be read)
+    pub memory_usage_bytes: i64,        // current memory usage
+    pub memory_limit_bytes: i64,        // memory limit (host memory when unlimited)
+    pub pids: i64,                      // number of processes
+}
+
+#[derive(Debug, Clone)]
+pub struct InspectImageParams {
+    pub image: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PullImageParams {
+    pub image: String,
+    pub auth: Option<RegistryAuth>, // private registry credential
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RegistryAuth {
+    pub registry: String, // registry host (example: ghcr.io, docker.io)
+    pub username: String,
+    pub password: String, // decrypted password or access token
+}
+
+// 비밀번호가 로그에 남지 않도록 password는 출력하지 않습니다.
+impl std::fmt::Debug for RegistryAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegistryAuth")
+            .field("registry", &self.registry)
+            .field("username", &self.username)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListContainersParams {
+    pub label: String, // only containers with this label (key or key=value)
+}
+
+#[derive(Debug, Clone)]
+pub struct ContainerSummary {
+    pub container_id: String, // full container id
+    pub state: String,        // created, running, exited, ...
+    pub labels: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone)]
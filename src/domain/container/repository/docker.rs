@@ -1,25 +1,139 @@
-use std::process::Command;
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    os::unix::fs::{DirBuilderExt, OpenOptionsExt},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use tokio::process::Command;
 
 use crate::{
-    domain::container::{
-        ContainerRepository,
-        dao::{
-            InspectContainerParams, InspectContainerResult, KillContainerParams,
-            RemoveContainerParams, RunContainerParams, RunContainerResult, StopContainerParams,
+    domain::{
+        container::{
+            ContainerRepository,
+            dao::{
+                ContainerStatsParams, ContainerStatsResult, ContainerSummary,
+                InspectContainerParams, InspectContainerResult, InspectImageParams, JOB_ID_LABEL,
+                KillContainerParams, ListContainersParams, PullImageParams, RegistryAuth,
+                RemoveContainerParams, RunContainerParams, RunContainerResult, StopContainerParams,
+            },
         },
+        registry_credential::service::DEFAULT_REGISTRY,
+        task_definition::entities,
     },
     errors,
 };
 
 const DOCKER_PATH: &str = "docker";
 
-#[derive(Debug, Clone)]
+/*
+docker CLI로 컨테이너를 다루는 ContainerRepository입니다.
+docker 명령은 tokio::process로 실행하므로, pull처럼 오래 걸리는 명령도 async 런타임의 worker 스레드를 막지 않습니다.
+*/
+#[derive(Debug, Clone, Default)]
 pub struct ContainerDockerRepository {}
 
 impl ContainerDockerRepository {
     pub fn new() -> Self {
         Self {}
     }
+
+    /*
+    소유자만 읽을 수 있는 임시 디렉토리(0700)를 만들고, 그 안에 contents를 담은 파일(0600)을 씁니다.
+    인증 정보나 secret 값을 docker CLI에 넘길 때 사용하며, 사용한 뒤에는 remove_private_dir로 삭제해야 합니다.
+    */
+    fn write_private_file(
+        prefix: &str,
+        file_name: &str,
+        contents: &[u8],
+    ) -> errors::Result<PathBuf> {
+        static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+        let nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+        let dir = std::env::temp_dir().join(format!(
+            "batchman-{}-{}-{}-{}",
+            prefix,
+            std::process::id(),
+            nanos,
+            SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        std::fs::DirBuilder::new().mode(0o700).create(&dir)?;
+
+        let write_result = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(dir.join(file_name))
+            .and_then(|mut file| file.write_all(contents));
+
+        if let Err(error) = write_result {
+            let _ = std::fs::remove_dir_all(&dir);
+            return Err(error.into());
+        }
+
+        Ok(dir)
+    }
+
+    // 내용이 디스크에 남지 않도록 명령 결과와 관계없이 삭제
+    async fn remove_private_dir(dir: &Path) {
+        if let Err(error) = tokio::fs::remove_dir_all(dir).await {
+            log::warn!("Failed to remove temporary directory {:?}: {}", dir, error);
+        }
+    }
+
+    /*
+    pull에만 사용할 임시 docker 설정 디렉토리를 생성합니다.
+    `docker login`은 사용자 전역 설정(~/.docker/config.json)을 변경하므로 사용하지 않고,
+    인증 정보만 담긴 config.json을 만들어 `docker --config <dir> pull`로 전달합니다.
+    */
+    fn write_docker_config(auth: &RegistryAuth) -> errors::Result<PathBuf> {
+        // Docker Hub 인증 정보는 예전 index 주소를 키로 사용함
+        let server_address = match auth.registry.as_str() {
+            DEFAULT_REGISTRY => "https://index.docker.io/v1/",
+            registry => registry,
+        };
+
+        let config = serde_json::json!({
+            "auths": {
+                server_address: {
+                    "auth": STANDARD.encode(format!("{}:{}", auth.username, auth.password)),
+                }
+            }
+        });
+
+        Self::write_private_file(
+            "docker-config",
+            "config.json",
+            config.to_string().as_bytes(),
+        )
+    }
+
+    async fn run_pull(image: &str, config_dir: Option<&Path>) -> errors::Result<()> {
+        let mut command = Command::new(DOCKER_PATH);
+
+        if let Some(config_dir) = config_dir {
+            command.arg("--config");
+            command.arg(config_dir);
+        }
+
+        command.arg("pull");
+        command.arg("--quiet");
+        command.arg(image);
+
+        let output = command.output().await?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(errors::Error::ContainerFailedToPullImage(
+                error.trim().to_string(),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -33,7 +147,7 @@ impl ContainerRepository for ContainerDockerRepository {
         command.arg("inspect");
         command.arg(params.container_id);
 
-        let output = command.output()?;
+        let output = command.output().await?;
 
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -57,6 +171,161 @@ impl ContainerRepository for ContainerDockerRepository {
         Ok(inspect_response[0].clone())
     }
 
+    async fn stats(
+        &self,
+        params: ContainerStatsParams,
+    ) -> errors::Result<Vec<ContainerStatsResult>> {
+        let mut container_ids = params.container_ids;
+
+        // 목록 중 하나라도 삭제되었다면 docker stats 전체가 실패하므로, 그 컨테이너를 빼고 한 번 더 시도
+        for _ in 0..2 {
+            if container_ids.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let output = Command::new(DOCKER_PATH)
+                .arg("stats")
+                .arg("--no-stream")
+                .arg("--format")
+                .arg("{{json .}}")
+                .args(&container_ids)
+                .output()
+                .await?;
+
+            if output.status.success() {
+                let mut results = String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(parse_docker_stats)
+                    .collect::<errors::Result<Vec<_>>>()?;
+
+                for result in &mut results {
+                    result.cpu_usage_seconds = read_cgroup_cpu_usage(&result.container_id).await;
+                }
+
+                return Ok(results);
+            }
+
+            let error = String::from_utf8_lossy(&output.stderr);
+            let missing_container_ids = parse_missing_containers(&error);
+
+            if missing_container_ids.is_empty() {
+                return Err(errors::Error::ContainerFailedToGetStats(error.to_string()));
+            }
+
+            container_ids.retain(|container_id| !missing_container_ids.contains(container_id));
+        }
+
+        Err(errors::Error::ContainerFailedToGetStats(
+            "containers kept disappearing while sampling".to_string(),
+        ))
+    }
+
+    async fn image_exists(&self, params: InspectImageParams) -> errors::Result<bool> {
+        let mut command = Command::new(DOCKER_PATH);
+
+        command.arg("image");
+        command.arg("inspect");
+        command.arg("--format");
+        command.arg("{{.Id}}");
+        command.arg(&params.image);
+
+        let output = command.output().await?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+
+            if error.contains("No such image") {
+                return Ok(false);
+            }
+
+            return Err(errors::Error::ContainerFailedToInspect(error.to_string()));
+        }
+
+        Ok(true)
+    }
+
+    async fn pull_image(&self, params: PullImageParams) -> errors::Result<()> {
+        let Some(auth) = &params.auth else {
+            return Self::run_pull(&params.image, None).await;
+        };
+
+        let config_dir = Self::write_docker_config(auth)?;
+
+        let result = Self::run_pull(&params.image, Some(&config_dir)).await;
+
+        Self::remove_private_dir(&config_dir).await;
+
+        result
+    }
+
+    async fn list_containers(
+        &self,
+        params: ListContainersParams,
+    ) -> errors::Result<Vec<ContainerSummary>> {
+        let output = Command::new(DOCKER_PATH)
+            .arg("ps")
+            .arg("--all")
+            .arg("--no-trunc")
+            .arg("--filter")
+            .arg(format!("label={}", params.label))
+            .arg("--format")
+            .arg("{{.ID}}")
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(errors::Error::ContainerFailedToList(error.to_string()));
+        }
+
+        let mut container_ids = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        // docker ps의 Labels는 ','로 이어 붙인 문자열이라 값에 ','나 '='가 있으면 구분할 수 없으므로,
+        // 라벨은 docker inspect에서 JSON 객체로 읽음
+        // 그 사이 삭제된 컨테이너가 있으면 inspect 전체가 실패하므로, 그 컨테이너를 빼고 한 번 더 시도
+        for _ in 0..2 {
+            if container_ids.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let output = Command::new(DOCKER_PATH)
+                .arg("container")
+                .arg("inspect")
+                .arg("--format")
+                .arg(CONTAINER_SUMMARY_FORMAT)
+                .args(&container_ids)
+                .output()
+                .await?;
+
+            if output.status.success() {
+                return String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(parse_container_summary)
+                    .collect();
+            }
+
+            let error = String::from_utf8_lossy(&output.stderr);
+            let missing_container_ids = parse_missing_containers(&error);
+
+            if missing_container_ids.is_empty() {
+                return Err(errors::Error::ContainerFailedToList(error.to_string()));
+            }
+
+            container_ids.retain(|container_id| !missing_container_ids.contains(container_id));
+        }
+
+        Err(errors::Error::ContainerFailedToList(
+            "containers kept disappearing while listing".to_string(),
+        ))
+    }
+
     async fn run_container(
         &self,
         params: RunContainerParams,
@@ -71,6 +340,10 @@ impl ContainerRepository for ContainerDockerRepository {
         command.arg("run");
         command.arg("-d");
 
+        // 이미지는 pull 정책에 따라 run 이전 단계에서 준비하므로 run에서 암묵적으로 받지 않음
+        command.arg("--pull");
+        command.arg("never");
+
         // log 드라이버 설정
         // 참조: https://docs.docker.com/engine/logging/configure/
         command.arg("--log-driver");
@@ -82,59 +355,167 @@ impl ContainerRepository for ContainerDockerRepository {
             command.arg(format!("{}m", memory_limit));
         }
 
+        if let Some(memory_reservation) = task_definition.memory_reservation {
+            command.arg("--memory-reservation");
+            command.arg(format!("{}m", memory_reservation));
+        }
+
+        if let Some(memory_swap) = task_definition.memory_swap {
+            command.arg("--memory-swap");
+            command.arg(match memory_swap {
+                -1 => "-1".to_string(),
+                memory_swap => format!("{}m", memory_swap),
+            });
+        }
+
         // CPU 제한 설정
-        if let Some(cpu_limit) = task_definition.cpu_limit {
+        // --cpus는 CFS quota 기반의 hard limit이고, --cpu-shares는 경합 시에만 적용되는 상대 가중치
+        if let Some(nano_cpus) = task_definition.nano_cpus {
+            command.arg("--cpus");
+            command.arg(format!(
+                "{}",
+                entities::task_definition::nano_cpus_to_cpus(nano_cpus)
+            ));
+        }
+
+        if let Some(cpu_shares) = task_definition.cpu_shares {
             command.arg("--cpu-shares");
-            command.arg(format!("{}", cpu_limit));
+            command.arg(format!("{}", cpu_shares));
         }
 
-        // 환경 변수 설정
-        if let Some(env_vars) = &task_definition.env {
-            let env_vars = env_vars.trim();
-            if !env_vars.is_empty() {
-                for env_var in env_vars.split(',') {
-                    let env_var = env_var.trim();
-                    if !env_var.is_empty() {
-                        command.arg("-e");
-                        command.arg(env_var);
-                    }
+        if let Some(cpuset_cpus) = &task_definition.cpuset_cpus {
+            command.arg("--cpuset-cpus");
+            command.arg(cpuset_cpus);
+        }
+
+        // 작업 디렉토리, 사용자, 네트워크 설정
+        if let Some(working_dir) = &task_definition.working_dir {
+            command.arg("--workdir");
+            command.arg(working_dir);
+        }
+
+        if let Some(user) = &task_definition.user {
+            command.arg("--user");
+            command.arg(user);
+        }
+
+        if let Some(network) = &task_definition.network {
+            command.arg("--network");
+            command.arg(network);
+        }
+
+        // 공유 메모리(/dev/shm) 크기 설정
+        if let Some(shm_size) = task_definition.shm_size {
+            command.arg("--shm-size");
+            command.arg(format!("{}m", shm_size));
+        }
+
+        // docker stop 시 SIGKILL 전까지 대기할 시간
+        if let Some(stop_grace_period_seconds) = task_definition.stop_grace_period_seconds {
+            command.arg("--stop-timeout");
+            command.arg(stop_grace_period_seconds.to_string());
+        }
+
+        // 마운트 설정
+        // 참조: https://docs.docker.com/engine/storage/bind-mounts/
+        if let Some(mounts) = &task_definition.mounts {
+            for mount in mounts.0.iter() {
+                let mut mount_option = format!("type={}", mount.mount_type.as_str());
+
+                if let Some(source) = &mount.source {
+                    mount_option.push_str(&format!(",source={}", source));
                 }
+
+                mount_option.push_str(&format!(",target={}", mount.target));
+
+                if mount.read_only {
+                    mount_option.push_str(",readonly");
+                }
+
+                command.arg("--mount");
+                command.arg(mount_option);
             }
         }
 
-        command.arg(image_name);
+        // 라벨 설정
+        if let Some(labels) = &task_definition.labels {
+            for (key, value) in labels.0.iter() {
+                command.arg("--label");
+                command.arg(format!("{}={}", key, value));
+            }
+        }
 
-        // CMD 설정
-        if let Some(cmd) = &task_definition.command {
-            let cmd = cmd.trim();
-            if !cmd.is_empty() {
-                // split 로직 고도화 필요
-                let command_list = cmd
-                    .split(' ')
-                    .filter(|s| !s.trim().is_empty())
-                    .map(|s| s.to_string())
-                    .collect::<Vec<String>>();
-
-                for cmd in command_list {
-                    command.arg(cmd);
+        // 재시작 시 job과 컨테이너를 다시 연결할 수 있도록 job id를 라벨로 기록
+        command.arg("--label");
+        command.arg(format!("{}={}", JOB_ID_LABEL, params.job_id));
+
+        // ulimit 설정
+        if let Some(ulimits) = &task_definition.ulimits {
+            for ulimit in ulimits.0.iter() {
+                command.arg("--ulimit");
+                command.arg(format!("{}={}:{}", ulimit.name, ulimit.soft, ulimit.hard));
+            }
+        }
+
+        // entrypoint 설정
+        // docker run --entrypoint는 실행 파일 하나만 받으므로, 나머지 argv는 CMD 앞에 붙여서 전달합니다.
+        let mut entrypoint_args: &[String] = &[];
+        if let Some(entrypoint) = &task_definition.entrypoint {
+            command.arg("--entrypoint");
+            match entrypoint.0.split_first() {
+                Some((executable, rest)) => {
+                    command.arg(executable);
+                    entrypoint_args = rest;
+                }
+                // 빈 배열이면 이미지의 기본 entrypoint를 제거
+                None => {
+                    command.arg("");
                 }
             }
         }
 
+        // 환경 변수 설정
+        if let Some(env) = &task_definition.env {
+            for (key, value) in env.0.iter() {
+                command.arg("-e");
+                command.arg(format!("{}={}", key, value));
+            }
+        }
+
+        // secret 환경 변수 설정
+        // 값이 프로세스 인자(ps 등)로 노출되지 않도록 0600 권한의 --env-file로 전달하고, 실행 후 바로 삭제합니다.
+        // docker CLI 자신의 환경 변수(PATH, DOCKER_HOST 등)에는 넣지 않습니다.
+        let secret_env_dir = if params.secret_env.is_empty() {
+            None
+        } else {
+            let env_file = secret_env_file(&params.secret_env)?;
+            let dir = Self::write_private_file("secret-env", "secret.env", env_file.as_bytes())?;
+            command.arg("--env-file");
+            command.arg(dir.join("secret.env"));
+            Some(dir)
+        };
+
+        command.arg(image_name);
+
+        command.args(entrypoint_args.iter());
+
+        // CMD 설정 (argv를 그대로 전달)
+        if let Some(cmd) = &task_definition.command {
+            command.args(cmd.0.iter());
+        }
+
         // Arguments 전달
         if let Some(args) = &task_definition.args {
-            let args = args.trim();
-            if !args.is_empty() {
-                for arg in args.split(',') {
-                    let arg = arg.trim();
-                    if !arg.is_empty() {
-                        command.arg(arg);
-                    }
-                }
-            }
+            command.args(args.0.iter());
+        }
+
+        let output = command.output().await;
+
+        if let Some(dir) = &secret_env_dir {
+            Self::remove_private_dir(dir).await;
         }
 
-        let output = command.output()?;
+        let output = output?;
 
         if !output.status.success() {
             return Err(errors::Error::ContainerFailedToStart(
@@ -164,7 +545,7 @@ impl ContainerRepository for ContainerDockerRepository {
         command.arg("kill");
         command.arg(&params.container_id);
 
-        let output = command.output()?;
+        let output = command.output().await?;
 
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -204,7 +585,7 @@ impl ContainerRepository for ContainerDockerRepository {
         command.arg(params.timeout_seconds.to_string());
         command.arg(&params.container_id);
 
-        let output = command.output()?;
+        let output = command.output().await?;
 
         // If stop succeeded, return success
         if output.status.success() {
@@ -214,7 +595,7 @@ impl ContainerRepository for ContainerDockerRepository {
         // If stop failed for a reason other than "container not found", log the error
         let error = String::from_utf8_lossy(&output.stderr);
         if !error.contains("No such container") {
-            eprintln!("Warning: Failed to gracefully stop container: {}", error);
+            log::warn!("Failed to gracefully stop container: {}", error);
         }
 
         // Fall back to kill
@@ -245,7 +626,7 @@ impl ContainerRepository for ContainerDockerRepository {
 
         command.arg(&params.container_id);
 
-        let output = command.output()?;
+        let output = command.output().await?;
 
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -260,3 +641,285 @@ impl ContainerRepository for ContainerDockerRepository {
         Ok(())
     }
 }
+
+/*
+docker --env-file 형식(한 줄에 NAME=value)으로 secret 환경 변수를 만듭니다.
+env file은 줄 단위로 읽히므로 줄바꿈이 들어간 값은 전달할 수 없습니다.
+*/
+fn secret_env_file(secret_env: &BTreeMap<String, String>) -> errors::Result<String> {
+    let mut env_file = String::new();
+
+    for (name, value) in secret_env {
+        if value.contains(['\n', '\r', '\0']) {
+            return Err(errors::Error::ContainerFailedToStart(format!(
+                "value of secret environment variable {} contains a line break",
+                name
+            )));
+        }
+
+        env_file.push_str(name);
+        env_file.push('=');
+        env_file.push_str(value);
+        env_file.push('\n');
+    }
+
+    Ok(env_file)
+}
+
+// docker inspect로 컨테이너 한 개당 한 줄의 JSON을 출력 (라벨이 없으면 Labels는 null)
+const CONTAINER_SUMMARY_FORMAT: &str =
+    r#"{"ID":{{json .Id}},"State":{{json .State.Status}},"Labels":{{json .Config.Labels}}}"#;
+
+/*
+CONTAINER_SUMMARY_FORMAT으로 출력한 `docker container inspect` 결과의 한 줄을 파싱합니다.
+예: {"ID":"3f2a...","State":"running","Labels":{"batchman.job_id":"12","app":"web"}}
+*/
+fn parse_container_summary(raw: &str) -> errors::Result<ContainerSummary> {
+    #[derive(serde::Deserialize)]
+    struct DockerContainerSummary {
+        #[serde(rename = "ID")]
+        id: String,
+        #[serde(rename = "State")]
+        state: String,
+        #[serde(rename = "Labels", default)]
+        labels: Option<BTreeMap<String, String>>,
+    }
+
+    let summary: DockerContainerSummary = serde_json::from_str(raw)?;
+
+    Ok(ContainerSummary {
+        container_id: summary.id,
+        state: summary.state,
+        labels: summary.labels.unwrap_or_default(),
+    })
+}
+
+/*
+`docker stats --format "{{json .}}"` 출력의 한 줄을 파싱합니다. Container는 명령에 넘긴 컨테이너 id입니다.
+예: {"Container":"3f2a...","CPUPerc":"12.50%","MemUsage":"1.5MiB / 7.6GiB","PIDs":"3", ...}
+*/
+fn parse_docker_stats(raw: &str) -> errors::Result<ContainerStatsResult> {
+    #[derive(serde::Deserialize)]
+    struct DockerStats {
+        #[serde(rename = "Container")]
+        container: String,
+        #[serde(rename = "CPUPerc")]
+        cpu_percent: String,
+        #[serde(rename = "MemUsage")]
+        memory_usage: String,
+        #[serde(rename = "PIDs")]
+        pids: String,
+    }
+
+    let stats: DockerStats = serde_json::from_str(raw)?;
+
+    let invalid = |field: &str, value: &str| {
+        errors::Error::ContainerFailedToGetStats(format!("unexpected {}: {:?}", field, value))
+    };
+
+    let cpu_percent = stats
+        .cpu_percent
+        .trim()
+        .trim_end_matches('%')
+        .parse::<f64>()
+        .map_err(|_| invalid("CPUPerc", &stats.cpu_percent))?;
+
+    let (memory_usage, memory_limit) = stats
+        .memory_usage
+        .split_once('/')
+        .ok_or_else(|| invalid("MemUsage", &stats.memory_usage))?;
+
+    let memory_usage_bytes =
+        parse_docker_size(memory_usage).ok_or_else(|| invalid("MemUsage", &stats.memory_usage))?;
+    let memory_limit_bytes =
+        parse_docker_size(memory_limit).ok_or_else(|| invalid("MemUsage", &stats.memory_usage))?;
+
+    // 종료 직전의 컨테이너는 "--"를 반환할 수 있음
+    let pids = stats.pids.trim().parse::<i64>().unwrap_or_default();
+
+    Ok(ContainerStatsResult {
+        container_id: stats.container,
+        cpu_percent,
+        cpu_usage_seconds: None,
+        memory_usage_bytes,
+        memory_limit_bytes,
+        pids,
+    })
+}
+
+// "Error response from daemon: No such container: 3f2a..." 에서 컨테이너 id를 꺼냄
+fn parse_missing_containers(error: &str) -> Vec<String> {
+    error
+        .lines()
+        .filter_map(|line| line.split_once("No such container: "))
+        .map(|(_, container_id)| container_id.trim().to_string())
+        .collect()
+}
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/*
+컨테이너 cgroup의 누적 CPU 시간(초)을 읽습니다.
+docker stats는 순간 사용률만 제공하므로, docker daemon과 같은 호스트에서 실행 중이면 cgroup 파일에서 정확한 누적값을 얻습니다.
+cgroup v2(systemd, cgroupfs driver)와 v1의 기본 경로를 확인하고, 읽을 수 없으면(원격 daemon 등) None을 반환합니다.
+*/
+async fn read_cgroup_cpu_usage(container_id: &str) -> Option<f64> {
+    let cpu_stat_paths = [
+        format!("{CGROUP_ROOT}/system.slice/docker-{container_id}.scope/cpu.stat"),
+        format!("{CGROUP_ROOT}/docker/{container_id}/cpu.stat"),
+    ];
+
+    for path in cpu_stat_paths {
+        if let Ok(cpu_stat) = tokio::fs::read_to_string(&path).await {
+            return parse_cgroup_cpu_stat(&cpu_stat);
+        }
+    }
+
+    // cgroup v1: 나노초 단위
+    let cpuacct_usage_paths = [
+        format!("{CGROUP_ROOT}/cpu,cpuacct/docker/{container_id}/cpuacct.usage"),
+        format!("{CGROUP_ROOT}/cpuacct/docker/{container_id}/cpuacct.usage"),
+        format!("{CGROUP_ROOT}/cpu,cpuacct/system.slice/docker-{container_id}.scope/cpuacct.usage"),
+    ];
+
+    for path in cpuacct_usage_paths {
+        if let Ok(usage) = tokio::fs::read_to_string(&path).await {
+            return usage
+                .trim()
+                .parse::<u64>()
+                .ok()
+                .map(|nanoseconds| nanoseconds as f64 / 1e9);
+        }
+    }
+
+    None
+}
+
+// cgroup v2 cpu.stat의 usage_usec (마이크로초)
+fn parse_cgroup_cpu_stat(cpu_stat: &str) -> Option<f64> {
+    cpu_stat
+        .lines()
+        .find_map(|line| line.strip_prefix("usage_usec "))
+        .and_then(|usage| usage.trim().parse::<u64>().ok())
+        .map(|microseconds| microseconds as f64 / 1e6)
+}
+
+/// "1.5MiB", "512kB", "0B" 같은 docker 크기 표기를 바이트로 변환합니다.
+fn parse_docker_size(size: &str) -> Option<i64> {
+    let size = size.trim();
+    let unit_start = size.find(|c: char| c.is_ascii_alphabetic())?;
+    let (value, unit) = size.split_at(unit_start);
+
+    let multiplier: f64 = match unit {
+        "B" => 1.0,
+        "kB" | "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+
+    let value = value.trim().parse::<f64>().ok()?;
+
+    Some((value * multiplier).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_docker_stats() {
+        let stats = parse_docker_stats(
+            r#"{"BlockIO":"0B / 0B","CPUPerc":"150.25%","Container":"3f2a9c","MemUsage":"1.5MiB / 2GiB","PIDs":"3"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            stats,
+            ContainerStatsResult {
+                container_id: "3f2a9c".to_string(),
+                cpu_percent: 150.25,
+                cpu_usage_seconds: None,
+                memory_usage_bytes: 1572864,
+                memory_limit_bytes: 2147483648,
+                pids: 3,
+            }
+        );
+
+        assert_eq!(
+            parse_missing_containers(
+                "Error response from daemon: No such container: 3f2a9c\nother error"
+            ),
+            vec!["3f2a9c".to_string()]
+        );
+
+        assert_eq!(
+            parse_cgroup_cpu_stat("usage_usec 2500000\nuser_usec 2000000\nsystem_usec 500000\n"),
+            Some(2.5)
+        );
+        assert_eq!(parse_cgroup_cpu_stat("nr_periods 0\n"), None);
+    }
+
+    #[test]
+    fn test_parse_container_summary() {
+        // 라벨 값에 ','나 '='가 있어도 그대로 읽음
+        let summary = parse_container_summary(
+            r#"{"ID":"3f2a9c","State":"exited","Labels":{"batchman.job_id":"12","app":"web,api","query":"a=b"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(summary.container_id, "3f2a9c");
+        assert_eq!(summary.state, "exited");
+        assert_eq!(
+            summary.labels.get(JOB_ID_LABEL).map(String::as_str),
+            Some("12")
+        );
+        assert_eq!(
+            summary.labels.get("app").map(String::as_str),
+            Some("web,api")
+        );
+        assert_eq!(summary.labels.get("query").map(String::as_str), Some("a=b"));
+
+        let summary =
+            parse_container_summary(r#"{"ID":"3f2a9c","State":"running","Labels":null}"#).unwrap();
+        assert!(summary.labels.is_empty());
+    }
+
+    #[test]
+    fn test_secret_env_file() {
+        let secret_env = BTreeMap::from([
+            ("DB_PASSWORD".to_string(), "p=ss word ".to_string()),
+            ("TOKEN".to_string(), "abc".to_string()),
+        ]);
+        assert_eq!(
+            secret_env_file(&secret_env).unwrap(),
+            "DB_PASSWORD=p=ss word \nTOKEN=abc\n"
+        );
+
+        let secret_env = BTreeMap::from([("KEY".to_string(), "line1\nline2".to_string())]);
+        assert!(secret_env_file(&secret_env).is_err());
+
+        let dir =
+            ContainerDockerRepository::write_private_file("test", "secret.env", b"A=1\n").unwrap();
+        let mode = |path: &Path| {
+            std::os::unix::fs::PermissionsExt::mode(&std::fs::metadata(path).unwrap().permissions())
+                & 0o777
+        };
+        assert_eq!(mode(&dir), 0o700);
+        assert_eq!(mode(&dir.join("secret.env")), 0o600);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_docker_size() {
+        assert_eq!(parse_docker_size("0B"), Some(0));
+        assert_eq!(parse_docker_size(" 512kB "), Some(512000));
+        assert_eq!(parse_docker_size("1.5GiB"), Some(1610612736));
+        assert_eq!(parse_docker_size("12"), None);
+    }
+}
@@ -0,0 +1,217 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use crate::{
+    domain::container::{
+        ContainerRepository,
+        dao::{
+            ContainerState, ContainerStatsParams, ContainerStatsResult, ContainerSummary,
+            InspectContainerParams, InspectContainerResult, InspectImageParams,
+            KillContainerParams, ListContainersParams, PullImageParams, RemoveContainerParams,
+            RunContainerParams, RunContainerResult, StopContainerParams,
+        },
+    },
+    errors,
+};
+
+/*
+docker 없이 service/runner를 테스트하기 위한 ContainerRepository입니다.
+컨테이너는 메모리에만 존재하고, 호출 기록과 실패 횟수를 테스트에서 확인하고 조정할 수 있습니다.
+*/
+#[derive(Debug, Default)]
+pub struct FakeContainerRepository {
+    pub state: Mutex<FakeContainerState>,
+    pull_gate: Option<tokio::sync::Semaphore>, // when set, each pull waits for a permit
+}
+
+#[derive(Debug, Default)]
+pub struct FakeContainerState {
+    pub pulling: usize,     // pulls in progress
+    pub max_pulling: usize, // most pulls in progress at the same time
+    pub pulled_images: Vec<String>,
+    pub runs: Vec<RunContainerParams>,
+    pub running: BTreeMap<String, bool>, // container id -> running
+    pub stop_calls: usize,
+    pub stop_failures: usize, // number of upcoming stop_container calls that fail
+    pub stop_ignored: bool,   // stop_container succeeds but leaves the container running
+    pub kill_calls: usize,
+    pub kill_failures: usize, // number of upcoming kill_container calls that fail
+    pub stats_calls: usize,
+    pub cpu_usage_seconds: Option<f64>, // cgroup CPU time returned by stats (None: not readable)
+}
+
+impl FakeContainerRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // pull이 release_pulls로 허용될 때까지 대기하도록 합니다.
+    pub fn with_blocking_pulls() -> Self {
+        Self {
+            pull_gate: Some(tokio::sync::Semaphore::new(0)),
+            ..Default::default()
+        }
+    }
+
+    pub fn release_pulls(&self, count: usize) {
+        if let Some(pull_gate) = &self.pull_gate {
+            pull_gate.add_permits(count);
+        }
+    }
+
+    pub fn state(&self) -> std::sync::MutexGuard<'_, FakeContainerState> {
+        self.state.lock().unwrap()
+    }
+}
+
+fn container_state(running: bool) -> ContainerState {
+    ContainerState {
+        status: if running { "running" } else { "exited" }.to_string(),
+        running,
+        paused: false,
+        restarting: false,
+        oom_killed: false,
+        dead: false,
+        exit_code: Some(if running { 0 } else { 137 }),
+        started_at: None,
+        finished_at: None,
+        error: Some(String::new()),
+    }
+}
+
+#[async_trait::async_trait]
+impl ContainerRepository for FakeContainerRepository {
+    async fn inspect_container(
+        &self,
+        params: InspectContainerParams,
+    ) -> errors::Result<InspectContainerResult> {
+        let running = *self
+            .state()
+            .running
+            .get(&params.container_id)
+            .ok_or(errors::Error::ContainerNotFound)?;
+
+        Ok(InspectContainerResult {
+            state: container_state(running),
+            log_path: String::new(),
+        })
+    }
+
+    async fn stats(
+        &self,
+        params: ContainerStatsParams,
+    ) -> errors::Result<Vec<ContainerStatsResult>> {
+        let mut state = self.state();
+        state.stats_calls += 1;
+
+        Ok(params
+            .container_ids
+            .into_iter()
+            .filter(|container_id| state.running.contains_key(container_id))
+            .map(|container_id| ContainerStatsResult {
+                container_id,
+                cpu_percent: 50.0,
+                cpu_usage_seconds: state.cpu_usage_seconds,
+                memory_usage_bytes: 1024,
+                memory_limit_bytes: 4096,
+                pids: 1,
+            })
+            .collect())
+    }
+
+    async fn image_exists(&self, params: InspectImageParams) -> errors::Result<bool> {
+        Ok(self.state().pulled_images.contains(&params.image))
+    }
+
+    async fn pull_image(&self, params: PullImageParams) -> errors::Result<()> {
+        {
+            let mut state = self.state();
+            state.pulling += 1;
+            state.max_pulling = state.max_pulling.max(state.pulling);
+        }
+
+        if let Some(pull_gate) = &self.pull_gate {
+            pull_gate
+                .acquire()
+                .await
+                .expect("pull gate is never closed")
+                .forget();
+        }
+
+        let mut state = self.state();
+        state.pulling -= 1;
+        state.pulled_images.push(params.image);
+
+        Ok(())
+    }
+
+    async fn list_containers(
+        &self,
+        _params: ListContainersParams,
+    ) -> errors::Result<Vec<ContainerSummary>> {
+        Ok(Vec::new())
+    }
+
+    async fn run_container(
+        &self,
+        params: RunContainerParams,
+    ) -> errors::Result<RunContainerResult> {
+        let container_id = format!("container-{}", params.job_id);
+
+        let mut state = self.state();
+        state.running.insert(container_id.clone(), true);
+        state.runs.push(params);
+
+        Ok(RunContainerResult { container_id })
+    }
+
+    async fn kill_container(&self, params: KillContainerParams) -> errors::Result<()> {
+        let mut state = self.state();
+        state.kill_calls += 1;
+
+        if state.kill_failures > 0 {
+            state.kill_failures -= 1;
+            return Err(errors::Error::ContainerFailedToKill(
+                "docker daemon is not responding".to_string(),
+            ));
+        }
+
+        match state.running.get_mut(&params.container_id) {
+            Some(running) => {
+                *running = false;
+                Ok(())
+            }
+            None => Err(errors::Error::ContainerNotFound),
+        }
+    }
+
+    async fn stop_container(&self, params: StopContainerParams) -> errors::Result<()> {
+        let mut state = self.state();
+        state.stop_calls += 1;
+
+        if state.stop_failures > 0 {
+            state.stop_failures -= 1;
+            return Err(errors::Error::ContainerFailedToKill(
+                "docker daemon is not responding".to_string(),
+            ));
+        }
+
+        if state.stop_ignored {
+            return Ok(());
+        }
+
+        match state.running.get_mut(&params.container_id) {
+            Some(running) => {
+                *running = false;
+                Ok(())
+            }
+            None => Err(errors::Error::ContainerNotFound),
+        }
+    }
+
+    async fn remove_container(&self, params: RemoveContainerParams) -> errors::Result<()> {
+        match self.state().running.remove(&params.container_id) {
+            Some(_) => Ok(()),
+            None => Err(errors::Error::ContainerNotFound),
+        }
+    }
+}
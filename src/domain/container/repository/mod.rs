@@ -1 +1,3 @@
 pub mod docker;
+#[cfg(test)]
+pub mod fake;
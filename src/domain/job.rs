@@ -9,8 +9,13 @@ use dao::*;
 use dto::{ListJobsRequest, StopJobRequest, SubmitJobRequest};
 
 use crate::{
+    config::RetentionConfig,
     domain::job::dto::{
-        CountJobLogsRequest, CountJobLogsResponse, ListJobLogsRequest, SubmitJobResponse,
+        AppendNodeJobLogsRequest, AppendNodeJobLogsResponse, ClaimNodeJobsRequest,
+        ClaimNodeJobsResponse, CountJobLogsRequest, CountJobLogsResponse, JobRecordDto,
+        JobStatsRequest, JobStatsResponse, ListJobEventsRequest, ListJobEventsResponse,
+        ListJobLogsRequest, ListJobMetricsRequest, ListJobMetricsResponse, PurgeJobsRequest,
+        PurgeJobsResponse, ReportNodeJobRequest, SubmitJobResponse,
     },
     errors,
 };
@@ -20,7 +25,50 @@ pub trait JobRepository {
     async fn list_jobs(&self, params: ListJobsParams) -> errors::Result<Vec<entities::job::Model>>;
     async fn count_jobs(&self, params: ListJobsParams) -> errors::Result<u64>;
     async fn create_job(&self, params: CreateJobParams) -> errors::Result<i64>;
+    /// 상태를 제외한 job 필드를 변경합니다. 상태 변경은 transition_job을 사용해야 합니다.
     async fn patch_job(&self, params: PatchJobParams) -> errors::Result<()>;
+    /// 상태 전이 규칙을 검사한 뒤 job 상태를 변경하고 job_event를 기록합니다.
+    /// 허용되지 않는 전이라면 InvalidJobStatusTransition 에러를 반환합니다.
+    async fn transition_job(&self, params: TransitionJobParams) -> errors::Result<()>;
+    async fn list_job_events(&self, job_id: i64)
+    -> errors::Result<Vec<entities::job_event::Model>>;
+    /// 실행할 job을 원자적으로 가져옵니다. 여러 worker가 동시에 호출해도 같은 job을 중복으로 가져가지 않습니다.
+    async fn claim_pending_jobs(
+        &self,
+        params: ClaimPendingJobsParams,
+    ) -> errors::Result<Vec<entities::job::Model>>;
+    /// claim한 job의 lease를 연장합니다. 다른 worker가 이미 가져갔다면 false를 반환합니다.
+    async fn renew_job_lease(&self, params: RenewJobLeaseParams) -> errors::Result<bool>;
+    /// lease가 없거나 만료된 로컬 job을 가져옵니다. 그 사이 다른 worker가 먼저 가져갔다면 false를 반환합니다.
+    async fn adopt_job(&self, params: AdoptJobParams) -> errors::Result<bool>;
+
+    /// 원격 node가 보낸 로그를 저장하고, 다음에 받을 줄의 index(저장된 줄 수)를 반환합니다.
+    async fn append_job_logs(&self, params: AppendJobLogsParams) -> errors::Result<u64>;
+    async fn list_job_log_lines(
+        &self,
+        params: ListJobLogLinesParams,
+    ) -> errors::Result<Vec<entities::job_log::Model>>;
+    async fn count_job_log_lines(&self, job_id: i64) -> errors::Result<u64>;
+
+    async fn create_job_metric(&self, params: CreateJobMetricParams) -> errors::Result<()>;
+    async fn list_job_metrics(
+        &self,
+        params: ListJobMetricsParams,
+    ) -> errors::Result<Vec<entities::job_metric::Model>>;
+    async fn summarize_job_metrics(&self, job_id: i64) -> errors::Result<JobMetricsSummary>;
+
+    /// job과 함께 지워지는 이벤트, 리소스 샘플, agent 로그를 모아 보관/내보내기용 기록을 만듭니다.
+    async fn list_job_records(
+        &self,
+        jobs: &[entities::job::Model],
+    ) -> errors::Result<Vec<JobRecordDto>>;
+    /// 종료된 job을 한 트랜잭션으로 삭제(또는 job_archive로 이동)하고, 삭제한 job 수를 반환합니다.
+    async fn remove_jobs(&self, params: RemoveJobsParams) -> errors::Result<u64>;
+    /// task definition/상태별 job 수를 반환합니다. job 테이블에서 삭제된 job 수도 함께 반환합니다.
+    async fn count_jobs_by_status(
+        &self,
+        task_definition_ids: Vec<i64>,
+    ) -> errors::Result<Vec<JobStatusCount>>;
 }
 
 #[async_trait::async_trait]
@@ -28,8 +76,28 @@ pub trait JobService {
     async fn submit_job(&self, params: SubmitJobRequest) -> errors::Result<SubmitJobResponse>;
     async fn stop_job(&self, params: StopJobRequest) -> errors::Result<()>;
     async fn list_jobs(&self, params: ListJobsRequest) -> errors::Result<dto::ListJobsResponse>;
-    async fn run_pending_job(&self, pending_job: &entities::job::Model) -> errors::Result<()>;
+    /// claim한 job을 실행합니다. 이미지 pull 이후 lease_duration만큼 lease를 연장합니다.
+    async fn run_pending_job(
+        &self,
+        pending_job: &entities::job::Model,
+        lease_duration: chrono::Duration,
+    ) -> errors::Result<()>;
     async fn track_running_job(&self, job: &entities::job::Model) -> errors::Result<()>;
+    /// 실행 중인 job들의 리소스 사용량을 한 번의 stats 호출로 샘플링하여 저장합니다.
+    async fn sample_job_metrics(&self, jobs: &[entities::job::Model]) -> errors::Result<()>;
+    /// 이 worker가 추적할 로컬 Running/Cancelling job을 반환합니다.
+    /// 자신의 job은 lease를 연장하고, lease가 없거나 만료된 job은 가져와서 함께 반환합니다.
+    async fn claim_running_jobs(
+        &self,
+        worker_id: &str,
+        lease_duration: chrono::Duration,
+    ) -> errors::Result<Vec<entities::job::Model>>;
+    /// 시작 시 한 번 호출되어, 재시작 전에 진행 중이던 job과 batchman 라벨이 붙은 컨테이너를 맞춥니다.
+    async fn reconcile_jobs(
+        &self,
+        worker_id: &str,
+        lease_duration: chrono::Duration,
+    ) -> errors::Result<()>;
     async fn list_job_logs(
         &self,
         request: ListJobLogsRequest,
@@ -38,4 +106,39 @@ pub trait JobService {
         &self,
         job_id: CountJobLogsRequest,
     ) -> errors::Result<CountJobLogsResponse>;
+    async fn list_job_metrics(
+        &self,
+        request: ListJobMetricsRequest,
+    ) -> errors::Result<ListJobMetricsResponse>;
+    async fn list_job_events(
+        &self,
+        request: ListJobEventsRequest,
+    ) -> errors::Result<ListJobEventsResponse>;
+
+    /// 이 서버에서 실행할 job을 claim합니다. placement constraint가 있는 task definition의 job은 원격 node에 맡깁니다.
+    async fn claim_local_jobs(
+        &self,
+        worker_id: &str,
+        limit: u64,
+        lease_duration: chrono::Duration,
+    ) -> errors::Result<Vec<entities::job::Model>>;
+    /// 원격 node의 남은 capacity만큼 job을 claim하고, 실행에 필요한 task definition과 secret을 함께 반환합니다.
+    async fn claim_node_jobs(
+        &self,
+        request: ClaimNodeJobsRequest,
+    ) -> errors::Result<ClaimNodeJobsResponse>;
+    /// 원격 node가 보고한 job 상태 변경을 적용합니다.
+    async fn report_node_job(&self, request: ReportNodeJobRequest) -> errors::Result<()>;
+    /// 원격 node가 보낸 job 로그를 저장합니다.
+    async fn append_node_job_logs(
+        &self,
+        request: AppendNodeJobLogsRequest,
+    ) -> errors::Result<AppendNodeJobLogsResponse>;
+
+    /// 조건에 맞는 종료된 job을 job 테이블에서 삭제하거나 job_archive로 옮깁니다.
+    async fn purge_jobs(&self, request: PurgeJobsRequest) -> errors::Result<PurgeJobsResponse>;
+    /// task definition별 job 수를 반환합니다. job 테이블에서 빠진 job도 포함합니다.
+    async fn get_job_stats(&self, request: JobStatsRequest) -> errors::Result<JobStatsResponse>;
+    /// 보관 기간이 지난 종료된 job을 retention.mode에 따라 옮기고, 옮긴 job 수를 반환합니다.
+    async fn remove_expired_jobs(&self, retention: &RetentionConfig) -> errors::Result<u64>;
 }
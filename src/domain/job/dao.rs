@@ -1,6 +1,9 @@
 use chrono::Utc;
 
-use super::entities::job::JobStatus;
+use super::entities::{
+    self,
+    job::{ExitReason, JobStatus},
+};
 
 #[derive(Debug, Default)]
 pub struct CreateJobParams {
@@ -14,27 +17,161 @@ pub struct CreateJobParams {
     pub exit_code: Option<i32>,                          // batch exit code
     pub error_message: Option<String>,                   // batch error message
     pub log_expire_after: Option<chrono::DateTime<Utc>>, // log expire time
+    pub schedule_id: Option<i64>,                        // schedule that submitted the job
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct PatchJobParams {
-    pub job_id: i64,                                // job id
-    pub name: Option<String>,                       // job name
-    pub task_definition_id: Option<i64>,            // task definition id
-    pub status: Option<JobStatus>,                  // job status
-    pub submited_at: Option<chrono::DateTime<Utc>>, // job submited time
-    pub started_at: Option<chrono::DateTime<Utc>>,  // job started time
-    pub finished_at: Option<chrono::DateTime<Utc>>, // job finished time
-    pub container_id: Option<String>,               // batch container id (docker container id)
-    pub exit_code: Option<i32>,                     // batch exit code
-    pub error_message: Option<String>,              // batch error message
+    pub job_id: i64,                                      // job id
+    pub name: Option<String>,                             // job name
+    pub task_definition_id: Option<i64>,                  // task definition id
+    pub submited_at: Option<chrono::DateTime<Utc>>,       // job submited time
+    pub started_at: Option<chrono::DateTime<Utc>>,        // job started time
+    pub finished_at: Option<chrono::DateTime<Utc>>,       // job finished time
+    pub container_id: Option<String>, // batch container id (docker container id)
+    pub exit_code: Option<i32>,       // batch exit code
+    pub exit_reason: Option<ExitReason>, // why the job reached a terminal status
+    pub error_code: Option<String>,   // error code of the failure
+    pub error_message: Option<String>, // batch error message
+    pub stop_requested_at: Option<chrono::DateTime<Utc>>, // time the user requested to stop the job
+    pub cancel_reason: Option<String>, // reason given when the job was cancelled
+    pub cancelled_by: Option<String>, // user who cancelled the job
+    pub worker_id: Option<String>,    // worker that claimed the job
+    pub lease_expires_at: Option<chrono::DateTime<Utc>>, // claim lease expiry
+    pub peak_memory_bytes: Option<i64>, // peak memory usage observed while running
+    pub cpu_seconds: Option<f64>,     // total CPU seconds used while running
 }
 
+/*
+job 상태를 전이합니다. 상태는 JobStatus::can_transition_to 규칙을 따라야 하며,
+상태와 함께 변경할 필드는 patch에 담습니다. 종료 상태로 전이할 때 patch.finished_at이 없다면 현재 시각을 기록합니다.
+*/
 #[derive(Debug, Default)]
+pub struct TransitionJobParams {
+    pub to_status: JobStatus,   // next status
+    pub reason: Option<String>, // why the transition happened (recorded in job_event)
+    pub patch: PatchJobParams, // fields updated together with the status (job_id is taken from here)
+}
+
+/*
+Pending job과 lease가 만료된 Starting/Pulling job을 최대 limit개 가져와 worker_id로 claim합니다.
+claim된 job은 Starting 상태가 되며, lease_duration 동안 다른 worker가 가져가지 않습니다.
+*/
+#[derive(Debug, Clone)]
+pub struct ClaimPendingJobsParams {
+    pub worker_id: String,                      // worker claiming the jobs
+    pub node_id: Option<i64>, // remote node claiming the jobs (None: the api server itself)
+    pub limit: u64,           // max number of jobs to claim
+    pub excluded_task_definition_ids: Vec<i64>, // task definitions whose placement constraints the claimer does not satisfy
+    pub lease_duration: chrono::Duration,       // how long the claim is valid without renewal
+}
+
+#[derive(Debug, Clone)]
+pub struct RenewJobLeaseParams {
+    pub job_id: i64,
+    pub worker_id: String, // lease is renewed only while this worker still holds it
+    pub lease_expires_at: chrono::DateTime<Utc>,
+}
+
+/*
+lease가 없거나 만료된 로컬(node_id가 없는) job을 worker_id로 가져옵니다.
+worker id가 바뀌었거나 죽은 worker가 실행하던 Running/Cancelling job을 다른 worker가 이어서 추적할 때 사용합니다.
+*/
+#[derive(Debug, Clone)]
+pub struct AdoptJobParams {
+    pub job_id: i64,
+    pub worker_id: String, // worker taking over the job
+    pub lease_expires_at: chrono::DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct ListJobsParams {
     pub job_ids: Vec<i64>,
     pub statuses: Vec<JobStatus>,
+    pub node_ids: Vec<i64>,
+    pub task_definition_ids: Vec<i64>,
+    pub schedule_ids: Vec<i64>,
     pub limit: Option<u64>,
     pub offset: Option<u64>,
+    pub after_id: Option<i64>, // only jobs listed after this id (only valid when sorted by id)
     pub contains_name: Option<String>,
+    pub container_id_prefix: Option<String>, // matches full and short docker container ids
+    pub exit_code: Option<i32>,
+    pub submitted_at: TimeRange,
+    pub started_at: TimeRange,
+    pub finished_at: TimeRange,
+    pub sort_by: Option<entities::job::Column>, // None: id
+    pub ascending: bool,                        // false: newest (largest) first
+}
+
+/*
+종료된 job을 job 테이블에서 삭제합니다. job의 이벤트/로그/메트릭도 함께 삭제하고,
+삭제한 job 수를 removed_job_count에 더합니다. 종료 상태가 아닌 job은 건너뜁니다.
+*/
+#[derive(Debug, Default, Clone)]
+pub struct RemoveJobsParams {
+    pub job_ids: Vec<i64>,
+    pub archive: bool, // true: copy the jobs into job_archive before deleting them
+}
+
+/*
+task definition/상태별 job 수입니다.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobStatusCount {
+    pub task_definition_id: i64,
+    pub status: JobStatus,
+    pub job_count: u64,     // jobs still in the job table
+    pub removed_count: u64, // jobs archived, exported or purged from the job table
+}
+
+/*
+시각 범위 조건입니다. 양 끝을 모두 포함하며, 비어있는 쪽은 제한하지 않습니다.
+*/
+#[derive(Debug, Default, Clone)]
+pub struct TimeRange {
+    pub from: Option<chrono::DateTime<Utc>>,
+    pub to: Option<chrono::DateTime<Utc>>,
+}
+
+/*
+원격 node가 보낸 로그를 offset 위치부터 이어 붙입니다. 이미 저장된 줄은 건너뛰므로 같은 로그를 다시 보내도 안전합니다.
+*/
+#[derive(Debug, Default)]
+pub struct AppendJobLogsParams {
+    pub job_id: i64,
+    pub offset: u64, // index of the first line in lines
+    pub lines: Vec<(chrono::DateTime<Utc>, String)>, // (time, message)
+}
+
+#[derive(Debug, Default)]
+pub struct ListJobLogLinesParams {
+    pub job_id: i64,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct CreateJobMetricParams {
+    pub job_id: i64,
+    pub sampled_at: chrono::DateTime<Utc>,
+    pub cpu_percent: f64,
+    pub cpu_seconds: f64, // cumulative CPU seconds up to this sample
+    pub memory_usage_bytes: i64,
+    pub memory_limit_bytes: i64,
+    pub pids: i64,
+}
+
+#[derive(Debug, Default)]
+pub struct ListJobMetricsParams {
+    pub job_id: i64,
+    pub since: Option<chrono::DateTime<Utc>>,
+    pub limit: Option<u64>,
+    pub latest_first: bool, // true: 최신 샘플부터 조회
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct JobMetricsSummary {
+    pub peak_memory_bytes: Option<i64>,
+    pub cpu_seconds: Option<f64>,
 }
@@ -1,26 +1,57 @@
+use std::collections::BTreeMap;
+
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
-use crate::domain::container::ContainerType;
+use crate::{
+    domain::{
+        container::{ContainerType, dao::RegistryAuth},
+        job::entities::job::{ExitReason, JobStatus},
+        task_definition::entities::task_definition,
+    },
+    openapi::schema::{schema_enum, schema_object},
+    validation::{MAX_NAME_LENGTH, MAX_PAGE_SIZE, Validate, Validator},
+};
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
 pub enum JobStatusDto {
     #[default]
     Pending,
+    Pulling,
     Starting,
     Running,
-    Finished,
+    Succeeded,
     Failed,
+    Cancelling,
+    Cancelled,
 }
 
 impl From<super::entities::job::JobStatus> for JobStatusDto {
     fn from(status: super::entities::job::JobStatus) -> Self {
         match status {
             super::entities::job::JobStatus::Pending => JobStatusDto::Pending,
+            super::entities::job::JobStatus::Pulling => JobStatusDto::Pulling,
             super::entities::job::JobStatus::Starting => JobStatusDto::Starting,
             super::entities::job::JobStatus::Running => JobStatusDto::Running,
-            super::entities::job::JobStatus::Finished => JobStatusDto::Finished,
+            super::entities::job::JobStatus::Succeeded => JobStatusDto::Succeeded,
             super::entities::job::JobStatus::Failed => JobStatusDto::Failed,
+            super::entities::job::JobStatus::Cancelling => JobStatusDto::Cancelling,
+            super::entities::job::JobStatus::Cancelled => JobStatusDto::Cancelled,
+        }
+    }
+}
+
+impl From<JobStatusDto> for JobStatus {
+    fn from(status: JobStatusDto) -> Self {
+        match status {
+            JobStatusDto::Pending => JobStatus::Pending,
+            JobStatusDto::Pulling => JobStatus::Pulling,
+            JobStatusDto::Starting => JobStatus::Starting,
+            JobStatusDto::Running => JobStatus::Running,
+            JobStatusDto::Succeeded => JobStatus::Succeeded,
+            JobStatusDto::Failed => JobStatus::Failed,
+            JobStatusDto::Cancelling => JobStatus::Cancelling,
+            JobStatusDto::Cancelled => JobStatus::Cancelled,
         }
     }
 }
@@ -38,7 +69,16 @@ pub struct JobDto {
     pub container_type: ContainerType,
     pub container_id: Option<String>,
     pub exit_code: Option<i32>,
+    pub exit_reason: Option<ExitReason>,
+    pub peak_memory_bytes: Option<i64>,
+    pub cpu_seconds: Option<f64>,
+    pub error_code: Option<String>,
     pub error_message: Option<String>,
+    pub stop_requested_at: Option<chrono::DateTime<Utc>>,
+    pub cancel_reason: Option<String>,
+    pub cancelled_by: Option<String>,
+    pub worker_id: Option<String>,
+    pub schedule_id: Option<i64>,
     pub created_at: chrono::DateTime<Utc>,
     pub log_expire_after: Option<chrono::DateTime<Utc>>,
     pub log_expired: bool,
@@ -56,7 +96,16 @@ impl From<super::entities::job::Model> for JobDto {
             finished_at: model.finished_at,
             container_id: model.container_id,
             exit_code: model.exit_code,
+            exit_reason: model.exit_reason,
+            peak_memory_bytes: model.peak_memory_bytes,
+            cpu_seconds: model.cpu_seconds,
+            error_code: model.error_code,
             error_message: model.error_message,
+            stop_requested_at: model.stop_requested_at,
+            cancel_reason: model.cancel_reason,
+            cancelled_by: model.cancelled_by,
+            worker_id: model.worker_id,
+            schedule_id: model.schedule_id,
             created_at: model.created_at,
             container_type: model.container_type,
             log_expire_after: model.log_expire_after,
@@ -76,6 +125,7 @@ pub struct SubmitJobBody {
 #[derive(Debug, Clone)]
 pub struct SubmitJobRequest {
     pub request_body: SubmitJobBody,
+    pub schedule_id: Option<i64>, // set when the scheduler submits the job
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -86,6 +136,9 @@ pub struct SubmitJobResponse {
 #[derive(Deserialize, Debug, Clone)]
 pub struct StopJobBody {
     pub job_id: i64,
+    pub reason: Option<String>,            // why the job is cancelled
+    pub requested_by: Option<String>,      // who cancelled the job
+    pub grace_period_seconds: Option<u32>, // SIGTERM -> SIGKILL grace period (overrides task definition)
 }
 
 #[derive(Debug, Clone)]
@@ -93,13 +146,60 @@ pub struct StopJobRequest {
     pub request_body: StopJobBody,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, Default)]
 pub struct ListJobsQuery {
     pub page_number: Option<u64>,
     pub page_size: Option<u64>,
+    pub after_id: Option<i64>, // cursor: next_cursor of the previous page (page_number is ignored)
     pub job_id: Option<i64>,
-    pub status: Option<String>,
+    pub status: Option<String>, // comma separated statuses (example: Running,Failed)
     pub contains_name: Option<String>,
+    pub task_definition_id: Option<i64>,
+    pub task_definition_name: Option<String>, // jobs of every version of the task definition
+    pub schedule_id: Option<i64>,
+    pub exit_code: Option<i32>,
+    pub container_id: Option<String>, // full or short (prefix) container id
+    pub submitted_after: Option<chrono::DateTime<Utc>>, // RFC 3339, inclusive
+    pub submitted_before: Option<chrono::DateTime<Utc>>,
+    pub started_after: Option<chrono::DateTime<Utc>>,
+    pub started_before: Option<chrono::DateTime<Utc>>,
+    pub finished_after: Option<chrono::DateTime<Utc>>,
+    pub finished_before: Option<chrono::DateTime<Utc>>,
+    pub sort_by: Option<JobSortBy>, // default: id
+    pub order: Option<SortOrder>,   // default: desc
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobSortBy {
+    Id,
+    Name,
+    Status,
+    SubmittedAt,
+    StartedAt,
+    FinishedAt,
+    CreatedAt,
+}
+
+impl From<JobSortBy> for super::entities::job::Column {
+    fn from(sort_by: JobSortBy) -> Self {
+        match sort_by {
+            JobSortBy::Id => super::entities::job::Column::Id,
+            JobSortBy::Name => super::entities::job::Column::Name,
+            JobSortBy::Status => super::entities::job::Column::Status,
+            JobSortBy::SubmittedAt => super::entities::job::Column::SubmitedAt,
+            JobSortBy::StartedAt => super::entities::job::Column::StartedAt,
+            JobSortBy::FinishedAt => super::entities::job::Column::FinishedAt,
+            JobSortBy::CreatedAt => super::entities::job::Column::CreatedAt,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
 }
 
 #[derive(Debug, Clone)]
@@ -111,6 +211,74 @@ pub struct ListJobsRequest {
 pub struct ListJobsResponse {
     pub jobs: Vec<JobDto>,
     pub total_count: u64,
+    pub next_cursor: Option<i64>, // after_id of the next page (None: last page or not sorted by id)
+}
+
+/*
+조건에 맞는 종료된 job을 job 테이블에서 삭제합니다. 실수로 모든 job을 지우지 않도록 statuses 외의 조건이 하나 이상 필요합니다.
+*/
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct PurgeJobsBody {
+    pub statuses: Option<Vec<JobStatusDto>>, // terminal statuses only (default: Succeeded, Failed and Cancelled)
+    pub task_definition_id: Option<i64>,
+    pub schedule_id: Option<i64>,
+    pub finished_before: Option<chrono::DateTime<Utc>>, // RFC 3339, inclusive
+    #[serde(default)]
+    pub archive: bool,             // true: move the jobs into job_archive instead of deleting them
+    #[serde(default)]
+    pub dry_run: bool,             // true: only count the matching jobs
+}
+
+#[derive(Debug, Clone)]
+pub struct PurgeJobsRequest {
+    pub request_body: PurgeJobsBody,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PurgeJobsResponse {
+    pub matched_count: u64, // jobs matching the filter
+    pub purged_count: u64,  // jobs removed from the job table (0 on dry run)
+}
+
+/*
+보관(archive)하거나 내보낸(export) job 한 개의 기록입니다.
+job 테이블에서 빼낼 때 함께 지워지는 상태 이벤트, 리소스 샘플, agent 로그를 포함합니다.
+로컬에서 실행한 job의 로그는 docker 로그 파일에 있으므로 포함되지 않습니다.
+*/
+#[derive(Serialize, Debug, Clone)]
+pub struct JobRecordDto {
+    #[serde(flatten)]
+    pub job: JobDto,
+    pub events: Vec<JobEventDto>,   // status changes, oldest first
+    pub metrics: Vec<JobMetricDto>, // resource samples, oldest first
+    pub logs: Vec<JobLogDto>,       // log lines sent by the agent (remote jobs)
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct JobStatsQuery {
+    pub task_definition_id: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct JobStatsRequest {
+    pub request_query: JobStatsQuery,
+}
+
+/*
+task definition별 job 수입니다. 보관 기간이 지나 job 테이블에서 빠진 job도 포함합니다.
+*/
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct TaskDefinitionJobStatsDto {
+    pub task_definition_id: i64,
+    pub task_definition_name: Option<String>,
+    pub total_count: u64,
+    pub status_counts: BTreeMap<String, u64>, // status -> number of jobs
+    pub removed_count: u64, // jobs archived, exported or purged (included in the counts above)
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct JobStatsResponse {
+    pub task_definitions: Vec<TaskDefinitionJobStatsDto>,
 }
 
 #[derive(Debug, Clone)]
@@ -132,6 +300,16 @@ pub struct JobLogDto {
     pub message: String,
 }
 
+impl From<super::entities::job_log::Model> for JobLogDto {
+    fn from(model: super::entities::job_log::Model) -> Self {
+        Self {
+            index: model.line_index as usize,
+            time: model.time,
+            message: model.message,
+        }
+    }
+}
+
 #[derive(Serialize, Debug, Clone)]
 pub struct ListJobLogsResponse {
     pub logs: Vec<JobLogDto>,
@@ -146,3 +324,421 @@ pub struct CountJobLogsRequest {
 pub struct CountJobLogsResponse {
     pub count: usize,
 }
+
+#[derive(Debug, Clone)]
+pub struct ListJobMetricsRequest {
+    pub job_id: i64,
+    pub query: ListJobMetricsQuery,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ListJobMetricsQuery {
+    pub since: Option<chrono::DateTime<Utc>>, // oldest samples taken after this time (default: the newest samples)
+    pub limit: Option<u64>,                   // max number of samples (default 1000, at most 1000)
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct JobMetricDto {
+    pub sampled_at: chrono::DateTime<Utc>,
+    pub cpu_percent: f64,
+    pub cpu_seconds: f64,
+    pub memory_usage_bytes: i64,
+    pub memory_limit_bytes: i64,
+    pub pids: i64,
+}
+
+impl From<super::entities::job_metric::Model> for JobMetricDto {
+    fn from(model: super::entities::job_metric::Model) -> Self {
+        Self {
+            sampled_at: model.sampled_at,
+            cpu_percent: model.cpu_percent,
+            cpu_seconds: model.cpu_seconds,
+            memory_usage_bytes: model.memory_usage_bytes,
+            memory_limit_bytes: model.memory_limit_bytes,
+            pids: model.pids,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListJobEventsRequest {
+    pub job_id: i64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct JobEventDto {
+    pub id: i64,
+    pub from_status: Option<JobStatusDto>,
+    pub to_status: JobStatusDto,
+    pub reason: Option<String>,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+impl From<super::entities::job_event::Model> for JobEventDto {
+    fn from(model: super::entities::job_event::Model) -> Self {
+        Self {
+            id: model.id,
+            from_status: model.from_status.map(JobStatusDto::from),
+            to_status: model.to_status.into(),
+            reason: model.reason,
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ListJobEventsResponse {
+    pub events: Vec<JobEventDto>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ListJobMetricsResponse {
+    pub metrics: Vec<JobMetricDto>,
+    pub peak_memory_bytes: Option<i64>,
+    pub cpu_seconds: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ClaimNodeJobsBody {
+    pub limit: u64, // max number of jobs to claim (capped by the free capacity of the node)
+}
+
+#[derive(Debug, Clone)]
+pub struct ClaimNodeJobsRequest {
+    pub node_id: i64,
+    pub lease_duration: chrono::Duration, // how long the claim is valid without a heartbeat
+    pub request_body: ClaimNodeJobsBody,
+}
+
+/// node에 배정된 job과 실행에 필요한 정보입니다.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NodeJobAssignment {
+    pub job_id: i64,
+    pub task_definition: task_definition::Model,
+    pub secret_env: BTreeMap<String, String>, // decrypted secret values (environment variable name -> value)
+    pub registry_auth: Option<RegistryAuth>,  // credential of the registry the image belongs to
+}
+
+// secret 값이 로그에 남지 않도록 secret_env는 이름만 출력합니다.
+impl std::fmt::Debug for NodeJobAssignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeJobAssignment")
+            .field("job_id", &self.job_id)
+            .field("task_definition", &self.task_definition)
+            .field("secret_env", &self.secret_env.keys().collect::<Vec<_>>())
+            .field("registry_auth", &self.registry_auth)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ClaimNodeJobsResponse {
+    pub jobs: Vec<NodeJobAssignment>,
+}
+
+/// node가 실행한 job의 상태 변경을 보고합니다. 상태 전이 규칙에 어긋나면 409로 거부됩니다.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ReportNodeJobBody {
+    pub status: JobStatusDto,
+    pub reason: Option<String>,       // recorded in the job event history
+    pub container_id: Option<String>, // container started on the node
+    pub exit_code: Option<i32>,
+    pub exit_reason: Option<ExitReason>,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+    pub finished_at: Option<chrono::DateTime<Utc>>, // defaults to now for terminal statuses
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportNodeJobRequest {
+    pub node_id: i64,
+    pub job_id: i64,
+    pub request_body: ReportNodeJobBody,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NodeJobLogLine {
+    pub time: chrono::DateTime<Utc>,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AppendNodeJobLogsBody {
+    pub offset: u64, // index of the first line in logs
+    pub logs: Vec<NodeJobLogLine>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AppendNodeJobLogsRequest {
+    pub node_id: i64,
+    pub job_id: i64,
+    pub request_body: AppendNodeJobLogsBody,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AppendNodeJobLogsResponse {
+    pub next_offset: u64, // index of the next line the server expects
+}
+
+impl Validate for SubmitJobBody {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .min("task_definition_id", self.task_definition_id, 1)
+            .not_blank("job_name", &self.job_name)
+            .max_length("job_name", &self.job_name, MAX_NAME_LENGTH);
+    }
+}
+
+impl Validate for StopJobBody {
+    fn validate(&self, validator: &mut Validator) {
+        validator.min("job_id", self.job_id, 1);
+    }
+}
+
+impl Validate for ListJobsQuery {
+    fn validate(&self, validator: &mut Validator) {
+        validator.pagination(self.page_number, self.page_size);
+    }
+}
+
+impl Validate for PurgeJobsBody {
+    fn validate(&self, _validator: &mut Validator) {}
+}
+
+impl Validate for JobStatsQuery {
+    fn validate(&self, _validator: &mut Validator) {}
+}
+
+impl Validate for ListJobLogsQuery {
+    fn validate(&self, _validator: &mut Validator) {}
+}
+
+impl Validate for ListJobMetricsQuery {
+    fn validate(&self, validator: &mut Validator) {
+        validator.optional_range("limit", self.limit, 1, MAX_PAGE_SIZE as u64);
+    }
+}
+
+impl Validate for ClaimNodeJobsBody {
+    fn validate(&self, _validator: &mut Validator) {}
+}
+
+impl Validate for ReportNodeJobBody {
+    fn validate(&self, _validator: &mut Validator) {}
+}
+
+impl Validate for AppendNodeJobLogsBody {
+    fn validate(&self, _validator: &mut Validator) {}
+}
+
+schema_object!(JobDto {
+    id,
+    name,
+    task_definition_id,
+    task_definition_name,
+    status,
+    submited_at,
+    started_at,
+    finished_at,
+    container_type,
+    container_id,
+    exit_code,
+    exit_reason,
+    peak_memory_bytes,
+    cpu_seconds,
+    error_code,
+    error_message,
+    stop_requested_at,
+    cancel_reason,
+    cancelled_by,
+    worker_id,
+    schedule_id,
+    created_at,
+    log_expire_after,
+    log_expired,
+});
+
+schema_object!(JobRecordDto {
+    #[flatten]
+    job,
+    events,
+    metrics,
+    logs,
+});
+
+schema_object!(SubmitJobBody {
+    task_definition_id,
+    job_name,
+    log_expire_after,
+});
+
+schema_object!(SubmitJobResponse { job_id });
+
+schema_object!(StopJobBody {
+    job_id,
+    reason,
+    requested_by,
+    grace_period_seconds,
+});
+
+schema_object!(ListJobsQuery {
+    page_number,
+    page_size,
+    after_id,
+    job_id,
+    status,
+    contains_name,
+    task_definition_id,
+    task_definition_name,
+    schedule_id,
+    exit_code,
+    container_id,
+    submitted_after,
+    submitted_before,
+    started_after,
+    started_before,
+    finished_after,
+    finished_before,
+    sort_by,
+    order,
+});
+
+schema_object!(ListJobsResponse {
+    jobs,
+    total_count,
+    next_cursor,
+});
+
+schema_object!(PurgeJobsBody {
+    statuses,
+    task_definition_id,
+    schedule_id,
+    finished_before,
+    #[optional]
+    archive,
+    #[optional]
+    dry_run,
+});
+
+schema_object!(PurgeJobsResponse {
+    matched_count,
+    purged_count,
+});
+
+schema_object!(JobStatsQuery { task_definition_id });
+
+schema_object!(TaskDefinitionJobStatsDto {
+    task_definition_id,
+    task_definition_name,
+    total_count,
+    status_counts,
+    removed_count,
+});
+
+schema_object!(JobStatsResponse { task_definitions });
+
+schema_object!(ListJobLogsQuery { offset, limit });
+
+schema_object!(JobLogDto {
+    index,
+    time,
+    message,
+});
+
+schema_object!(ListJobLogsResponse { logs });
+
+schema_object!(CountJobLogsResponse { count });
+
+schema_object!(ListJobMetricsQuery { since, limit });
+
+schema_object!(JobMetricDto {
+    sampled_at,
+    cpu_percent,
+    cpu_seconds,
+    memory_usage_bytes,
+    memory_limit_bytes,
+    pids,
+});
+
+schema_object!(JobEventDto {
+    id,
+    from_status,
+    to_status,
+    reason,
+    created_at,
+});
+
+schema_object!(ListJobEventsResponse { events });
+
+schema_object!(ListJobMetricsResponse {
+    metrics,
+    peak_memory_bytes,
+    cpu_seconds,
+});
+
+schema_object!(ClaimNodeJobsBody { limit });
+
+schema_object!(NodeJobAssignment {
+    job_id,
+    task_definition,
+    secret_env,
+    registry_auth,
+});
+
+schema_object!(ClaimNodeJobsResponse { jobs });
+
+schema_object!(ReportNodeJobBody {
+    status,
+    reason,
+    container_id,
+    exit_code,
+    exit_reason,
+    error_code,
+    error_message,
+    finished_at,
+});
+
+schema_object!(NodeJobLogLine { time, message });
+
+schema_object!(AppendNodeJobLogsBody { offset, logs });
+
+schema_object!(AppendNodeJobLogsResponse { next_offset });
+
+schema_object!(RegistryAuth {
+    registry,
+    username,
+    password,
+});
+
+schema_enum!(JobStatusDto {
+    Pending,
+    Pulling,
+    Starting,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelling,
+    Cancelled
+});
+
+schema_enum!(JobSortBy {
+    Id,
+    Name,
+    Status,
+    SubmittedAt,
+    StartedAt,
+    FinishedAt,
+    CreatedAt
+});
+
+schema_enum!(SortOrder { Asc, Desc });
+
+schema_enum!(ExitReason {
+    Succeeded,
+    NonZeroExit,
+    OomKilled,
+    KilledByUser,
+    RuntimeError
+});
+
+schema_enum!(ContainerType { Docker });
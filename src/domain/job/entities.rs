@@ -1 +1,6 @@
 pub mod job;
+pub mod job_archive;
+pub mod job_event;
+pub mod job_log;
+pub mod job_metric;
+pub mod removed_job_count;
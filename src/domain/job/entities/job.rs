@@ -1,25 +1,101 @@
 use chrono::Utc;
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::domain::container::ContainerType;
 
 #[derive(EnumIter, DeriveActiveEnum, Clone, Debug, PartialEq, Eq, Default)]
-#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(1))")]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
 pub enum JobStatus {
     #[sea_orm(string_value = "Pending")]
     #[default]
     Pending,
+    #[sea_orm(string_value = "Pulling")]
+    Pulling,
     #[sea_orm(string_value = "Starting")]
     Starting,
     #[sea_orm(string_value = "Running")]
     Running,
-    #[sea_orm(string_value = "Finished")]
-    Finished,
+    #[sea_orm(string_value = "Succeeded")]
+    Succeeded,
     #[sea_orm(string_value = "Failed")]
     Failed,
+    #[sea_orm(string_value = "Cancelling")]
+    Cancelling,
+    #[sea_orm(string_value = "Cancelled")]
+    Cancelled,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+impl JobStatus {
+    /*
+    job 상태 전이 규칙입니다. 여기에 없는 전이는 모두 거부됩니다.
+    - Pending -> Starting (worker가 claim) | Failed | Cancelled
+    - Starting -> Pulling | Running | Failed | Cancelling
+    - Pulling -> Starting (pull 완료) | Failed | Cancelling
+    - Running -> Succeeded | Failed | Cancelling
+    - Cancelling -> Cancelled | Succeeded | Failed (컨테이너가 종료 요청 전에 끝난 경우)
+    - Succeeded, Failed, Cancelled는 종료 상태로, 더 이상 전이하지 않음
+    lease 만료로 다른 worker가 다시 가져가는 경우는 can_be_claimed를 따릅니다.
+    */
+    pub fn can_transition_to(&self, next: &JobStatus) -> bool {
+        use JobStatus::*;
+
+        matches!(
+            (self, next),
+            (Pending, Starting | Failed | Cancelled)
+                | (Starting, Pulling | Running | Failed | Cancelling)
+                | (Pulling, Starting | Failed | Cancelling)
+                | (Running, Succeeded | Failed | Cancelling)
+                | (Cancelling, Cancelled | Succeeded | Failed)
+        )
+    }
+
+    /*
+    worker가 claim하여 Starting으로 가져갈 수 있는지 확인합니다.
+    - Pending: 항상 가져갈 수 있음
+    - Starting, Pulling: 컨테이너가 아직 실행되지 않았으므로 lease가 만료된 경우에만 다시 가져감 (reclaim)
+    - 그 외 상태는 컨테이너가 실행 중이거나 종료되었으므로 가져가지 않음
+    */
+    pub fn can_be_claimed(&self, lease_expired: bool) -> bool {
+        match self {
+            JobStatus::Pending => true,
+            JobStatus::Starting | JobStatus::Pulling => lease_expired,
+            _ => false,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Succeeded | JobStatus::Failed | JobStatus::Cancelled
+        )
+    }
+}
+
+/*
+job이 종료된 이유입니다.
+- Succeeded: exit code 0으로 정상 종료
+- NonZeroExit: 0이 아닌 exit code로 종료
+- OomKilled: 메모리 제한을 초과하여 강제 종료
+- KilledByUser: 사용자의 중지(취소) 요청으로 종료
+- RuntimeError: 이미지 pull 실패, 컨테이너 실행/조회 실패 등 런타임 오류
+*/
+#[derive(EnumIter, DeriveActiveEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+pub enum ExitReason {
+    #[sea_orm(string_value = "Succeeded")]
+    Succeeded,
+    #[sea_orm(string_value = "NonZeroExit")]
+    NonZeroExit,
+    #[sea_orm(string_value = "OomKilled")]
+    OomKilled,
+    #[sea_orm(string_value = "KilledByUser")]
+    KilledByUser,
+    #[sea_orm(string_value = "RuntimeError")]
+    RuntimeError,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "job")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -37,7 +113,22 @@ pub struct Model {
     pub container_type: ContainerType,
     pub container_id: Option<String>, // batch container id (docker container id)
     pub exit_code: Option<i32>,       // batch exit code
+    pub exit_reason: Option<ExitReason>, // why the job reached a terminal status
+    pub error_code: Option<String>,   // error code of the failure (example: FAILED_TO_PULL_IMAGE)
+    #[sea_orm(column_type = "Text", nullable)]
     pub error_message: Option<String>, // batch error message
+    pub stop_requested_at: Option<chrono::DateTime<Utc>>, // time the user requested to stop the job
+    #[sea_orm(column_type = "Text", nullable)]
+    pub cancel_reason: Option<String>, // reason given when the job was cancelled
+    pub cancelled_by: Option<String>, // user who cancelled the job
+
+    pub worker_id: Option<String>, // worker that claimed the job
+    pub lease_expires_at: Option<chrono::DateTime<Utc>>, // claim is released to other workers after this time
+    pub node_id: Option<i64>, // remote node running the job (None: run by the api server itself)
+    pub schedule_id: Option<i64>, // schedule that submitted the job (None: submitted through the API)
+
+    pub peak_memory_bytes: Option<i64>, // peak memory usage observed while running
+    pub cpu_seconds: Option<f64>,       // total CPU seconds used while running
 
     pub log_expire_after: Option<chrono::DateTime<Utc>>, // log expire time
     pub log_expired: bool,                               // log expired
@@ -49,3 +140,45 @@ pub struct Model {
 pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::Iterable;
+
+    use super::*;
+
+    #[test]
+    fn test_job_status_transitions() {
+        assert!(JobStatus::Pending.can_transition_to(&JobStatus::Starting));
+        assert!(JobStatus::Starting.can_transition_to(&JobStatus::Pulling));
+        assert!(JobStatus::Pending.can_transition_to(&JobStatus::Cancelled));
+        assert!(JobStatus::Running.can_transition_to(&JobStatus::Cancelling));
+        assert!(JobStatus::Cancelling.can_transition_to(&JobStatus::Cancelled));
+
+        assert!(!JobStatus::Pending.can_transition_to(&JobStatus::Running));
+        assert!(!JobStatus::Pending.can_transition_to(&JobStatus::Pulling));
+        assert!(!JobStatus::Running.can_transition_to(&JobStatus::Running));
+        assert!(!JobStatus::Cancelling.can_transition_to(&JobStatus::Starting));
+
+        // lease가 만료된 Starting/Pulling job만 다시 가져갈 수 있음
+        assert!(JobStatus::Pending.can_be_claimed(false));
+        assert!(JobStatus::Starting.can_be_claimed(true));
+        assert!(JobStatus::Pulling.can_be_claimed(true));
+        assert!(!JobStatus::Starting.can_be_claimed(false));
+        assert!(!JobStatus::Pulling.can_be_claimed(false));
+        assert!(!JobStatus::Running.can_be_claimed(true));
+        assert!(!JobStatus::Cancelling.can_be_claimed(true));
+
+        // 종료 상태에서는 어떤 상태로도 전이할 수 없음
+        for status in [
+            JobStatus::Succeeded,
+            JobStatus::Failed,
+            JobStatus::Cancelled,
+        ] {
+            assert!(status.is_terminal());
+            for next in JobStatus::iter() {
+                assert!(!status.can_transition_to(&next));
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+use super::job::JobStatus;
+
+/*
+보관 기간이 지나 job 테이블에서 옮긴 job입니다.
+조회 조건에 쓰는 컬럼만 따로 두고, job 전체와 이벤트, 리소스 샘플, agent 로그는 record에 JSON(JobRecordDto)으로 저장합니다.
+*/
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "job_archive")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: i64, // id of the job in the job table
+    pub name: String, // job name
+
+    pub task_definition_id: i64,  // task definition id
+    pub schedule_id: Option<i64>, // schedule that submitted the job
+    pub status: JobStatus,        // terminal status of the job
+    pub exit_code: Option<i32>,   // batch exit code
+
+    pub submited_at: Option<chrono::DateTime<Utc>>, // job submited time
+    pub started_at: Option<chrono::DateTime<Utc>>,  // job started time
+    pub finished_at: Option<chrono::DateTime<Utc>>, // job finished time
+    pub created_at: chrono::DateTime<Utc>,          // job created time
+    pub archived_at: chrono::DateTime<Utc>,         // time the job was moved here
+
+    #[sea_orm(column_type = "Text")]
+    pub record: String, // the whole job with its events, metrics and agent logs as JSON
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,27 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+use super::job::JobStatus;
+
+/*
+job 상태 전이 이력입니다. 상태가 바뀔 때마다 한 행씩 기록됩니다.
+*/
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "job_event")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64, // primary key
+    pub job_id: i64, // job id
+
+    pub from_status: Option<JobStatus>, // status before the transition (None when the job is created)
+    pub to_status: JobStatus,           // status after the transition
+    #[sea_orm(column_type = "Text", nullable)]
+    pub reason: Option<String>, // why the transition happened
+
+    pub created_at: chrono::DateTime<Utc>, // transition time
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
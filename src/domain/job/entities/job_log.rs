@@ -0,0 +1,23 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+/*
+원격 node에서 실행된 job의 로그입니다. 로컬에서 실행된 job의 로그는 docker 로그 파일에서 직접 읽습니다.
+*/
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "job_log")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64, // primary key
+    pub job_id: i64,     // job id
+    pub line_index: i64, // 0-based line number within the job log
+
+    pub time: chrono::DateTime<Utc>, // time the line was written by the container
+    #[sea_orm(column_type = "Text")]
+    pub message: String, // log line (including the trailing newline)
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
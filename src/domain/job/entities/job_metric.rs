@@ -0,0 +1,26 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+/*
+실행 중인 job 컨테이너의 리소스 사용량 샘플입니다. job tracker가 주기적으로 기록합니다.
+*/
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "job_metric")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64, // primary key
+    pub job_id: i64, // job id
+
+    pub sampled_at: chrono::DateTime<Utc>, // sample time
+
+    pub cpu_percent: f64,        // host CPU usage (100 = one full core)
+    pub cpu_seconds: f64, // cumulative CPU seconds (from the cgroup, estimated from cpu_percent when unreadable)
+    pub memory_usage_bytes: i64, // memory usage
+    pub memory_limit_bytes: i64, // memory limit (host memory when unlimited)
+    pub pids: i64,        // number of processes
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
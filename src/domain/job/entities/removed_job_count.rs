@@ -0,0 +1,24 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+use super::job::JobStatus;
+
+/*
+job 테이블에서 빠진(보관/내보내기/삭제된) job 수를 task definition과 상태별로 누적합니다.
+job 테이블의 집계에 이 값을 더하면 지금까지 실행된 전체 job 수가 됩니다.
+*/
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "removed_job_count")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub task_definition_id: i64, // task definition id
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub status: JobStatus, // terminal status of the removed jobs
+    pub job_count: i64, // number of removed jobs
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
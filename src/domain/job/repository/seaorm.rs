@@ -1,47 +1,75 @@
+use std::collections::BTreeMap;
+
 use sea_orm::{
-    ActiveModelTrait,
+    ActiveEnum, ActiveModelTrait,
     ActiveValue::{NotSet, Set},
-    ColumnTrait, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, QuerySelect,
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, IntoActiveModel, Order, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect,
+    sea_query::Expr,
 };
 
 use crate::{
-    domain::job::{
-        JobRepository,
-        dao::{CreateJobParams, ListJobsParams, PatchJobParams},
-        entities,
+    db::unit_of_work::DatabaseExecutor,
+    domain::{
+        container::ContainerType,
+        job::{
+            JobRepository,
+            dao::{
+                AdoptJobParams, AppendJobLogsParams, ClaimPendingJobsParams, CreateJobMetricParams,
+                CreateJobParams, JobMetricsSummary, JobStatusCount, ListJobLogLinesParams,
+                ListJobMetricsParams, ListJobsParams, PatchJobParams, RemoveJobsParams,
+                RenewJobLeaseParams, TransitionJobParams,
+            },
+            dto::{JobDto, JobEventDto, JobLogDto, JobMetricDto, JobRecordDto},
+            entities,
+        },
     },
     errors,
 };
 
+// 상태 전이 중 다른 작업과 경합이 발생했을 때 재시도하는 횟수
+const TRANSITION_RETRY_COUNT: usize = 3;
+
 pub struct JobSeaOrmRepository {
-    pub connection: sea_orm::DatabaseConnection,
+    pub connection: DatabaseExecutor,
 }
 
 impl JobSeaOrmRepository {
     pub fn new(connection: sea_orm::DatabaseConnection) -> Self {
-        Self { connection }
+        Self {
+            connection: connection.into(),
+        }
     }
 }
 
 #[async_trait::async_trait]
 impl JobRepository for JobSeaOrmRepository {
     async fn list_jobs(&self, params: ListJobsParams) -> errors::Result<Vec<entities::job::Model>> {
-        let mut find_job_query = entities::job::Entity::find();
+        use entities::job::Column;
 
-        if !params.job_ids.is_empty() {
-            find_job_query = find_job_query.filter(entities::job::Column::Id.is_in(params.job_ids));
-        }
+        let order = if params.ascending {
+            Order::Asc
+        } else {
+            Order::Desc
+        };
 
-        if !params.statuses.is_empty() {
-            find_job_query =
-                find_job_query.filter(entities::job::Column::Status.is_in(params.statuses));
+        let mut find_job_query = filter_jobs(entities::job::Entity::find(), &params);
+
+        if let Some(after_id) = params.after_id {
+            find_job_query = find_job_query.filter(if params.ascending {
+                Column::Id.gt(after_id)
+            } else {
+                Column::Id.lt(after_id)
+            });
         }
 
-        if let Some(contains_name) = &params.contains_name {
-            find_job_query =
-                find_job_query.filter(entities::job::Column::Name.contains(contains_name));
+        if let Some(sort_by) = params.sort_by {
+            find_job_query = find_job_query.order_by(sort_by, order.clone());
         }
 
+        // 같은 값끼리는 id 순서로 정렬하여 페이지가 겹치거나 빠지지 않도록 함
+        find_job_query = find_job_query.order_by(Column::Id, order);
+
         if let Some(limit) = params.limit {
             find_job_query = find_job_query.limit(limit);
         }
@@ -66,14 +94,40 @@ impl JobRepository for JobSeaOrmRepository {
             finished_at: Set(params.finished_at),
             container_id: Set(params.container_id),
             exit_code: Set(params.exit_code),
+            exit_reason: Set(None),
+            error_code: Set(None),
             error_message: Set(params.error_message),
+            stop_requested_at: Set(None),
+            cancel_reason: Set(None),
+            cancelled_by: Set(None),
+            worker_id: Set(None),
+            lease_expires_at: Set(None),
+            node_id: Set(None),
+            schedule_id: Set(params.schedule_id),
+            peak_memory_bytes: Set(None),
+            cpu_seconds: Set(None),
             log_expire_after: Set(params.log_expire_after),
             log_expired: Set(false),
             created_at: Set(chrono::Utc::now()),
-            ..Default::default()
+            container_type: Set(ContainerType::Docker),
         };
 
-        let model = new_job.into_active_model().insert(&self.connection).await?;
+        let transaction = self.connection.begin().await?;
+
+        let model = new_job.into_active_model().insert(&transaction).await?;
+
+        entities::job_event::ActiveModel {
+            id: NotSet,
+            job_id: Set(model.id),
+            from_status: Set(None),
+            to_status: Set(model.status.clone()),
+            reason: Set(Some("Job submitted".to_string())),
+            created_at: Set(model.created_at),
+        }
+        .insert(&transaction)
+        .await?;
+
+        transaction.commit().await?;
 
         Ok(model.id)
     }
@@ -86,67 +140,756 @@ impl JobRepository for JobSeaOrmRepository {
 
         let mut model = job.into_active_model();
 
-        if let Some(name) = params.name {
-            model.name = Set(name);
+        apply_patch_params(&mut model, params);
+
+        model.update(&self.connection).await?;
+
+        Ok(())
+    }
+
+    async fn transition_job(&self, params: TransitionJobParams) -> errors::Result<()> {
+        let job_id = params.patch.job_id;
+
+        // 읽은 이후 다른 작업이 상태를 바꿨다면 다시 읽어서 전이 가능 여부를 재확인
+        for _ in 0..TRANSITION_RETRY_COUNT {
+            let job = entities::job::Entity::find_by_id(job_id)
+                .one(&self.connection)
+                .await?
+                .ok_or_else(|| errors::Error::JobNotFound)?;
+
+            if !job.status.can_transition_to(&params.to_status) {
+                return Err(errors::Error::InvalidJobStatusTransition(format!(
+                    "{:?} -> {:?}",
+                    job.status, params.to_status
+                )));
+            }
+
+            let mut model = entities::job::ActiveModel {
+                status: Set(params.to_status.clone()),
+                ..Default::default()
+            };
+
+            apply_patch_params(&mut model, params.patch.clone());
+
+            // 종료 상태의 job은 항상 종료 시각을 가지도록, 주어지지 않으면 전이 시각을 기록
+            if params.to_status.is_terminal() && params.patch.finished_at.is_none() {
+                model.finished_at = Set(Some(chrono::Utc::now()));
+            }
+
+            let transaction = self.connection.begin().await?;
+
+            // 상태 확인과 변경을 하나의 UPDATE 문으로 처리하여 다른 작업과의 경합을 방지
+            let result = entities::job::Entity::update_many()
+                .set(model)
+                .filter(entities::job::Column::Id.eq(job_id))
+                .filter(entities::job::Column::Status.eq(job.status.clone()))
+                .exec(&transaction)
+                .await?;
+
+            if result.rows_affected == 0 {
+                transaction.rollback().await?;
+                continue;
+            }
+
+            entities::job_event::ActiveModel {
+                id: NotSet,
+                job_id: Set(job_id),
+                from_status: Set(Some(job.status)),
+                to_status: Set(params.to_status.clone()),
+                reason: Set(params.reason.clone()),
+                created_at: Set(chrono::Utc::now()),
+            }
+            .insert(&transaction)
+            .await?;
+
+            transaction.commit().await?;
+
+            return Ok(());
+        }
+
+        Err(errors::Error::InvalidJobStatusTransition(format!(
+            "job {} status changed concurrently",
+            job_id
+        )))
+    }
+
+    async fn claim_pending_jobs(
+        &self,
+        params: ClaimPendingJobsParams,
+    ) -> errors::Result<Vec<entities::job::Model>> {
+        use entities::job::{Column, JobStatus};
+
+        let now = chrono::Utc::now();
+        let lease_expires_at = now + params.lease_duration;
+
+        let transaction = self.connection.begin().await?;
+
+        let mut candidates_query = entities::job::Entity::find();
+
+        // placement constraint를 만족하지 않는 task definition의 job은 가져가지 않음
+        if !params.excluded_task_definition_ids.is_empty() {
+            candidates_query = candidates_query
+                .filter(Column::TaskDefinitionId.is_not_in(params.excluded_task_definition_ids));
+        }
+
+        // Pending job과, claim한 worker가 lease를 갱신하지 못한(죽은) Starting/Pulling job
+        let candidates = candidates_query
+            .filter(
+                Condition::any()
+                    .add(Column::Status.eq(JobStatus::Pending))
+                    .add(
+                        Condition::all()
+                            .add(Column::Status.is_in([JobStatus::Starting, JobStatus::Pulling]))
+                            .add(Column::LeaseExpiresAt.lt(now)),
+                    ),
+            )
+            .order_by_asc(Column::Id)
+            .limit(params.limit)
+            .all(&transaction)
+            .await?;
+
+        let mut claimed_jobs = Vec::new();
+
+        for job in candidates {
+            let lease_expired = job.lease_expires_at.is_some_and(|lease| lease < now);
+            if !job.status.can_be_claimed(lease_expired) {
+                continue;
+            }
+
+            let model = entities::job::ActiveModel {
+                status: Set(JobStatus::Starting),
+                started_at: Set(Some(now)),
+                worker_id: Set(Some(params.worker_id.clone())),
+                lease_expires_at: Set(Some(lease_expires_at)),
+                node_id: Set(params.node_id),
+                ..Default::default()
+            };
+
+            // 조회 이후 다른 worker가 먼저 가져갔다면 상태나 lease가 바뀌었으므로 건너뜀
+            let mut update_query = entities::job::Entity::update_many()
+                .set(model)
+                .filter(Column::Id.eq(job.id))
+                .filter(Column::Status.eq(job.status.clone()));
+
+            // 먼저 가져간 worker가 lease를 새로 설정했다면 만료 조건에서 걸러짐
+            if job.status != JobStatus::Pending {
+                update_query = update_query.filter(Column::LeaseExpiresAt.lt(now));
+            }
+
+            let result = update_query.exec(&transaction).await?;
+
+            if result.rows_affected == 0 {
+                continue;
+            }
+
+            let reason = match &job.worker_id {
+                Some(previous_worker_id) if job.status != JobStatus::Pending => format!(
+                    "Lease of worker {} expired, reclaimed by worker {}",
+                    previous_worker_id, params.worker_id
+                ),
+                _ => format!("Claimed by worker {}", params.worker_id),
+            };
+
+            entities::job_event::ActiveModel {
+                id: NotSet,
+                job_id: Set(job.id),
+                from_status: Set(Some(job.status.clone())),
+                to_status: Set(JobStatus::Starting),
+                reason: Set(Some(reason)),
+                created_at: Set(now),
+            }
+            .insert(&transaction)
+            .await?;
+
+            claimed_jobs.push(entities::job::Model {
+                status: JobStatus::Starting,
+                started_at: Some(now),
+                worker_id: Some(params.worker_id.clone()),
+                lease_expires_at: Some(lease_expires_at),
+                node_id: params.node_id,
+                ..job
+            });
+        }
+
+        transaction.commit().await?;
+
+        Ok(claimed_jobs)
+    }
+
+    async fn renew_job_lease(&self, params: RenewJobLeaseParams) -> errors::Result<bool> {
+        let model = entities::job::ActiveModel {
+            lease_expires_at: Set(Some(params.lease_expires_at)),
+            ..Default::default()
+        };
+
+        let result = entities::job::Entity::update_many()
+            .set(model)
+            .filter(entities::job::Column::Id.eq(params.job_id))
+            .filter(entities::job::Column::WorkerId.eq(params.worker_id))
+            .exec(&self.connection)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn adopt_job(&self, params: AdoptJobParams) -> errors::Result<bool> {
+        use entities::job::Column;
+
+        let model = entities::job::ActiveModel {
+            worker_id: Set(Some(params.worker_id)),
+            lease_expires_at: Set(Some(params.lease_expires_at)),
+            ..Default::default()
+        };
+
+        // 조회 이후 다른 worker가 먼저 가져갔다면 lease가 새로 설정되었으므로 만료 조건에서 걸러짐
+        let result = entities::job::Entity::update_many()
+            .set(model)
+            .filter(Column::Id.eq(params.job_id))
+            .filter(Column::NodeId.is_null())
+            .filter(
+                Condition::any()
+                    .add(Column::WorkerId.is_null())
+                    .add(Column::LeaseExpiresAt.is_null())
+                    .add(Column::LeaseExpiresAt.lt(chrono::Utc::now())),
+            )
+            .exec(&self.connection)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn append_job_logs(&self, params: AppendJobLogsParams) -> errors::Result<u64> {
+        let transaction = self.connection.begin().await?;
+
+        let stored_count = entities::job_log::Entity::find()
+            .filter(entities::job_log::Column::JobId.eq(params.job_id))
+            .count(&transaction)
+            .await?;
+
+        // 중간이 빠진 로그는 받지 않고, agent가 stored_count부터 다시 보내도록 함
+        if params.offset > stored_count {
+            transaction.rollback().await?;
+            return Ok(stored_count);
         }
 
-        if let Some(task_definition_id) = params.task_definition_id {
-            model.task_definition_id = Set(task_definition_id);
+        let new_lines = params
+            .lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| (params.offset + i as u64, line))
+            .filter(|(line_index, _)| *line_index >= stored_count)
+            .map(
+                |(line_index, (time, message))| entities::job_log::ActiveModel {
+                    id: NotSet,
+                    job_id: Set(params.job_id),
+                    line_index: Set(line_index as i64),
+                    time: Set(time),
+                    message: Set(message),
+                },
+            )
+            .collect::<Vec<_>>();
+
+        let next_offset = stored_count + new_lines.len() as u64;
+
+        if !new_lines.is_empty() {
+            entities::job_log::Entity::insert_many(new_lines)
+                .exec(&transaction)
+                .await?;
         }
 
-        if let Some(status) = params.status {
-            model.status = Set(status);
+        transaction.commit().await?;
+
+        Ok(next_offset)
+    }
+
+    async fn list_job_log_lines(
+        &self,
+        params: ListJobLogLinesParams,
+    ) -> errors::Result<Vec<entities::job_log::Model>> {
+        let lines = entities::job_log::Entity::find()
+            .filter(entities::job_log::Column::JobId.eq(params.job_id))
+            .filter(entities::job_log::Column::LineIndex.gte(params.offset as i64))
+            .order_by_asc(entities::job_log::Column::LineIndex)
+            .limit(params.limit)
+            .all(&self.connection)
+            .await?;
+
+        Ok(lines)
+    }
+
+    async fn count_job_log_lines(&self, job_id: i64) -> errors::Result<u64> {
+        let count = entities::job_log::Entity::find()
+            .filter(entities::job_log::Column::JobId.eq(job_id))
+            .count(&self.connection)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn list_job_events(
+        &self,
+        job_id: i64,
+    ) -> errors::Result<Vec<entities::job_event::Model>> {
+        let events = entities::job_event::Entity::find()
+            .filter(entities::job_event::Column::JobId.eq(job_id))
+            .order_by_asc(entities::job_event::Column::CreatedAt)
+            .order_by_asc(entities::job_event::Column::Id)
+            .all(&self.connection)
+            .await?;
+
+        Ok(events)
+    }
+
+    async fn count_jobs(&self, params: ListJobsParams) -> errors::Result<u64> {
+        let count = filter_jobs(entities::job::Entity::find(), &params)
+            .count(&self.connection)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn create_job_metric(&self, params: CreateJobMetricParams) -> errors::Result<()> {
+        let new_metric = entities::job_metric::ActiveModel {
+            id: NotSet,
+            job_id: Set(params.job_id),
+            sampled_at: Set(params.sampled_at),
+            cpu_percent: Set(params.cpu_percent),
+            cpu_seconds: Set(params.cpu_seconds),
+            memory_usage_bytes: Set(params.memory_usage_bytes),
+            memory_limit_bytes: Set(params.memory_limit_bytes),
+            pids: Set(params.pids),
+        };
+
+        new_metric.insert(&self.connection).await?;
+
+        Ok(())
+    }
+
+    async fn list_job_metrics(
+        &self,
+        params: ListJobMetricsParams,
+    ) -> errors::Result<Vec<entities::job_metric::Model>> {
+        let mut query = entities::job_metric::Entity::find()
+            .filter(entities::job_metric::Column::JobId.eq(params.job_id));
+
+        if let Some(since) = params.since {
+            query = query.filter(entities::job_metric::Column::SampledAt.gt(since));
         }
 
-        if let Some(submited_at) = params.submited_at {
-            model.submited_at = Set(Some(submited_at));
+        query = if params.latest_first {
+            query.order_by_desc(entities::job_metric::Column::SampledAt)
+        } else {
+            query.order_by_asc(entities::job_metric::Column::SampledAt)
+        };
+
+        if let Some(limit) = params.limit {
+            query = query.limit(limit);
         }
 
-        if let Some(started_at) = params.started_at {
-            model.started_at = Set(Some(started_at));
+        let metrics = query.all(&self.connection).await?;
+
+        Ok(metrics)
+    }
+
+    async fn summarize_job_metrics(&self, job_id: i64) -> errors::Result<JobMetricsSummary> {
+        let peak_memory_bytes: Option<i64> = entities::job_metric::Entity::find()
+            .select_only()
+            .column_as(
+                entities::job_metric::Column::MemoryUsageBytes.max(),
+                "peak_memory_bytes",
+            )
+            .filter(entities::job_metric::Column::JobId.eq(job_id))
+            .into_tuple::<Option<i64>>()
+            .one(&self.connection)
+            .await?
+            .flatten();
+
+        // cpu_seconds는 누적값이므로 가장 마지막 샘플의 값이 전체 사용량
+        let cpu_seconds = entities::job_metric::Entity::find()
+            .filter(entities::job_metric::Column::JobId.eq(job_id))
+            .order_by_desc(entities::job_metric::Column::SampledAt)
+            .one(&self.connection)
+            .await?
+            .map(|metric| metric.cpu_seconds);
+
+        Ok(JobMetricsSummary {
+            peak_memory_bytes,
+            cpu_seconds,
+        })
+    }
+
+    async fn list_job_records(
+        &self,
+        jobs: &[entities::job::Model],
+    ) -> errors::Result<Vec<JobRecordDto>> {
+        job_records(&self.connection, jobs).await
+    }
+
+    async fn remove_jobs(&self, params: RemoveJobsParams) -> errors::Result<u64> {
+        use entities::job::{Column, JobStatus};
+
+        if params.job_ids.is_empty() {
+            return Ok(0);
         }
 
-        if let Some(finished_at) = params.finished_at {
-            model.finished_at = Set(Some(finished_at));
+        let terminal_statuses = [
+            JobStatus::Succeeded,
+            JobStatus::Failed,
+            JobStatus::Cancelled,
+        ];
+
+        let transaction = self.connection.begin().await?;
+
+        let jobs = entities::job::Entity::find()
+            .filter(Column::Id.is_in(params.job_ids))
+            .filter(Column::Status.is_in(terminal_statuses.clone()))
+            .all(&transaction)
+            .await?;
+
+        if jobs.is_empty() {
+            return Ok(0);
         }
 
-        if let Some(container_id) = params.container_id {
-            model.container_id = Set(Some(container_id));
+        let job_ids = jobs.iter().map(|job| job.id).collect::<Vec<_>>();
+        let now = chrono::Utc::now();
+
+        if params.archive {
+            // 함께 지워지는 이벤트, 리소스 샘플, agent 로그도 record에 남김
+            let records = job_records(&transaction, &jobs).await?;
+
+            let archived_jobs = jobs
+                .iter()
+                .zip(records)
+                .map(|(job, record)| {
+                    Ok(entities::job_archive::ActiveModel {
+                        id: Set(job.id),
+                        name: Set(job.name.clone()),
+                        task_definition_id: Set(job.task_definition_id),
+                        schedule_id: Set(job.schedule_id),
+                        status: Set(job.status.clone()),
+                        exit_code: Set(job.exit_code),
+                        submited_at: Set(job.submited_at),
+                        started_at: Set(job.started_at),
+                        finished_at: Set(job.finished_at),
+                        created_at: Set(job.created_at),
+                        archived_at: Set(now),
+                        record: Set(serde_json::to_string(&record)?),
+                    })
+                })
+                .collect::<errors::Result<Vec<_>>>()?;
+
+            entities::job_archive::Entity::insert_many(archived_jobs)
+                .exec(&transaction)
+                .await?;
         }
 
-        if let Some(exit_code) = params.exit_code {
-            model.exit_code = Set(Some(exit_code));
+        entities::job_event::Entity::delete_many()
+            .filter(entities::job_event::Column::JobId.is_in(job_ids.clone()))
+            .exec(&transaction)
+            .await?;
+
+        entities::job_log::Entity::delete_many()
+            .filter(entities::job_log::Column::JobId.is_in(job_ids.clone()))
+            .exec(&transaction)
+            .await?;
+
+        entities::job_metric::Entity::delete_many()
+            .filter(entities::job_metric::Column::JobId.is_in(job_ids.clone()))
+            .exec(&transaction)
+            .await?;
+
+        entities::job::Entity::delete_many()
+            .filter(Column::Id.is_in(job_ids))
+            .filter(Column::Status.is_in(terminal_statuses))
+            .exec(&transaction)
+            .await?;
+
+        // 삭제한 job 수를 task definition/상태별로 누적
+        let mut removed_counts = BTreeMap::<(i64, String), (JobStatus, i64)>::new();
+        for job in &jobs {
+            removed_counts
+                .entry((job.task_definition_id, job.status.to_value()))
+                .or_insert((job.status.clone(), 0))
+                .1 += 1;
         }
 
-        if let Some(error_message) = params.error_message {
-            model.error_message = Set(Some(error_message));
+        for ((task_definition_id, _), (status, count)) in removed_counts {
+            let result = entities::removed_job_count::Entity::update_many()
+                .col_expr(
+                    entities::removed_job_count::Column::JobCount,
+                    Expr::col(entities::removed_job_count::Column::JobCount).add(count),
+                )
+                .col_expr(
+                    entities::removed_job_count::Column::UpdatedAt,
+                    Expr::value(now),
+                )
+                .filter(
+                    entities::removed_job_count::Column::TaskDefinitionId.eq(task_definition_id),
+                )
+                .filter(entities::removed_job_count::Column::Status.eq(status.clone()))
+                .exec(&transaction)
+                .await?;
+
+            if result.rows_affected == 0 {
+                entities::removed_job_count::ActiveModel {
+                    task_definition_id: Set(task_definition_id),
+                    status: Set(status),
+                    job_count: Set(count),
+                    updated_at: Set(now),
+                }
+                .insert(&transaction)
+                .await?;
+            }
         }
 
-        model.update(&self.connection).await?;
+        transaction.commit().await?;
 
-        Ok(())
+        Ok(jobs.len() as u64)
     }
 
-    async fn count_jobs(&self, params: ListJobsParams) -> errors::Result<u64> {
-        let mut count_job_query = entities::job::Entity::find();
+    async fn count_jobs_by_status(
+        &self,
+        task_definition_ids: Vec<i64>,
+    ) -> errors::Result<Vec<JobStatusCount>> {
+        use entities::job::{Column, JobStatus};
+
+        let mut job_count_query = entities::job::Entity::find()
+            .select_only()
+            .column(Column::TaskDefinitionId)
+            .column(Column::Status)
+            .column_as(Column::Id.count(), "job_count")
+            .group_by(Column::TaskDefinitionId)
+            .group_by(Column::Status);
+
+        let mut removed_count_query = entities::removed_job_count::Entity::find();
+
+        if !task_definition_ids.is_empty() {
+            job_count_query =
+                job_count_query.filter(Column::TaskDefinitionId.is_in(task_definition_ids.clone()));
+            removed_count_query = removed_count_query.filter(
+                entities::removed_job_count::Column::TaskDefinitionId.is_in(task_definition_ids),
+            );
+        }
+
+        let job_counts = job_count_query
+            .into_tuple::<(i64, JobStatus, i64)>()
+            .all(&self.connection)
+            .await?;
+
+        let removed_counts = removed_count_query.all(&self.connection).await?;
+
+        // JobStatus는 정렬할 수 없으므로 문자열 값을 키로 사용
+        let mut counts = BTreeMap::<(i64, String), JobStatusCount>::new();
+
+        for (task_definition_id, status, job_count) in job_counts {
+            counts
+                .entry((task_definition_id, status.to_value()))
+                .or_insert_with(|| JobStatusCount {
+                    task_definition_id,
+                    status,
+                    job_count: 0,
+                    removed_count: 0,
+                })
+                .job_count += job_count as u64;
+        }
 
-        if !params.job_ids.is_empty() {
-            count_job_query =
-                count_job_query.filter(entities::job::Column::Id.is_in(params.job_ids));
+        for removed_count in removed_counts {
+            counts
+                .entry((
+                    removed_count.task_definition_id,
+                    removed_count.status.to_value(),
+                ))
+                .or_insert_with(|| JobStatusCount {
+                    task_definition_id: removed_count.task_definition_id,
+                    status: removed_count.status,
+                    job_count: 0,
+                    removed_count: 0,
+                })
+                .removed_count += removed_count.job_count as u64;
         }
 
-        if !params.statuses.is_empty() {
-            count_job_query =
-                count_job_query.filter(entities::job::Column::Status.is_in(params.statuses));
+        Ok(counts.into_values().collect())
+    }
+}
+
+/*
+ListJobsParams의 조건(정렬/페이지 제외)을 조회 쿼리에 적용합니다. list_jobs와 count_jobs가 같은 조건을 사용합니다.
+*/
+fn filter_jobs(
+    mut query: sea_orm::Select<entities::job::Entity>,
+    params: &ListJobsParams,
+) -> sea_orm::Select<entities::job::Entity> {
+    use entities::job::Column;
+
+    if !params.job_ids.is_empty() {
+        query = query.filter(Column::Id.is_in(params.job_ids.clone()));
+    }
+
+    if !params.statuses.is_empty() {
+        query = query.filter(Column::Status.is_in(params.statuses.clone()));
+    }
+
+    if !params.node_ids.is_empty() {
+        query = query.filter(Column::NodeId.is_in(params.node_ids.clone()));
+    }
+
+    if !params.task_definition_ids.is_empty() {
+        query = query.filter(Column::TaskDefinitionId.is_in(params.task_definition_ids.clone()));
+    }
+
+    if !params.schedule_ids.is_empty() {
+        query = query.filter(Column::ScheduleId.is_in(params.schedule_ids.clone()));
+    }
+
+    if let Some(contains_name) = &params.contains_name {
+        query = query.filter(Column::Name.contains(contains_name));
+    }
+
+    if let Some(container_id_prefix) = &params.container_id_prefix {
+        query = query.filter(Column::ContainerId.starts_with(container_id_prefix));
+    }
+
+    if let Some(exit_code) = params.exit_code {
+        query = query.filter(Column::ExitCode.eq(exit_code));
+    }
+
+    for (column, range) in [
+        (Column::SubmitedAt, &params.submitted_at),
+        (Column::StartedAt, &params.started_at),
+        (Column::FinishedAt, &params.finished_at),
+    ] {
+        if let Some(from) = range.from {
+            query = query.filter(column.gte(from));
         }
 
-        if let Some(contains_name) = &params.contains_name {
-            count_job_query =
-                count_job_query.filter(entities::job::Column::Name.contains(contains_name));
+        if let Some(to) = range.to {
+            query = query.filter(column.lte(to));
         }
+    }
 
-        let count = count_job_query.count(&self.connection).await?;
+    query
+}
 
-        Ok(count)
+fn apply_patch_params(model: &mut entities::job::ActiveModel, params: PatchJobParams) {
+    if let Some(name) = params.name {
+        model.name = Set(name);
     }
+
+    if let Some(task_definition_id) = params.task_definition_id {
+        model.task_definition_id = Set(task_definition_id);
+    }
+
+    if let Some(submited_at) = params.submited_at {
+        model.submited_at = Set(Some(submited_at));
+    }
+
+    if let Some(started_at) = params.started_at {
+        model.started_at = Set(Some(started_at));
+    }
+
+    if let Some(finished_at) = params.finished_at {
+        model.finished_at = Set(Some(finished_at));
+    }
+
+    if let Some(container_id) = params.container_id {
+        model.container_id = Set(Some(container_id));
+    }
+
+    if let Some(exit_code) = params.exit_code {
+        model.exit_code = Set(Some(exit_code));
+    }
+
+    if let Some(exit_reason) = params.exit_reason {
+        model.exit_reason = Set(Some(exit_reason));
+    }
+
+    if let Some(stop_requested_at) = params.stop_requested_at {
+        model.stop_requested_at = Set(Some(stop_requested_at));
+    }
+
+    if let Some(peak_memory_bytes) = params.peak_memory_bytes {
+        model.peak_memory_bytes = Set(Some(peak_memory_bytes));
+    }
+
+    if let Some(cpu_seconds) = params.cpu_seconds {
+        model.cpu_seconds = Set(Some(cpu_seconds));
+    }
+
+    if let Some(error_code) = params.error_code {
+        model.error_code = Set(Some(error_code));
+    }
+
+    if let Some(error_message) = params.error_message {
+        model.error_message = Set(Some(error_message));
+    }
+
+    if let Some(cancel_reason) = params.cancel_reason {
+        model.cancel_reason = Set(Some(cancel_reason));
+    }
+
+    if let Some(cancelled_by) = params.cancelled_by {
+        model.cancelled_by = Set(Some(cancelled_by));
+    }
+
+    if let Some(worker_id) = params.worker_id {
+        model.worker_id = Set(Some(worker_id));
+    }
+
+    if let Some(lease_expires_at) = params.lease_expires_at {
+        model.lease_expires_at = Set(Some(lease_expires_at));
+    }
+}
+
+/*
+job마다 이벤트, 리소스 샘플, agent 로그를 모아 JobRecordDto를 만듭니다. jobs와 같은 순서로 반환합니다.
+*/
+async fn job_records(
+    connection: &impl ConnectionTrait,
+    jobs: &[entities::job::Model],
+) -> errors::Result<Vec<JobRecordDto>> {
+    let job_ids = jobs.iter().map(|job| job.id).collect::<Vec<_>>();
+
+    let mut events = BTreeMap::<i64, Vec<JobEventDto>>::new();
+    for event in entities::job_event::Entity::find()
+        .filter(entities::job_event::Column::JobId.is_in(job_ids.clone()))
+        .order_by_asc(entities::job_event::Column::CreatedAt)
+        .order_by_asc(entities::job_event::Column::Id)
+        .all(connection)
+        .await?
+    {
+        events.entry(event.job_id).or_default().push(event.into());
+    }
+
+    let mut metrics = BTreeMap::<i64, Vec<JobMetricDto>>::new();
+    for metric in entities::job_metric::Entity::find()
+        .filter(entities::job_metric::Column::JobId.is_in(job_ids.clone()))
+        .order_by_asc(entities::job_metric::Column::SampledAt)
+        .all(connection)
+        .await?
+    {
+        metrics
+            .entry(metric.job_id)
+            .or_default()
+            .push(metric.into());
+    }
+
+    let mut logs = BTreeMap::<i64, Vec<JobLogDto>>::new();
+    for line in entities::job_log::Entity::find()
+        .filter(entities::job_log::Column::JobId.is_in(job_ids))
+        .order_by_asc(entities::job_log::Column::LineIndex)
+        .all(connection)
+        .await?
+    {
+        logs.entry(line.job_id).or_default().push(line.into());
+    }
+
+    Ok(jobs
+        .iter()
+        .map(|job| JobRecordDto {
+            job: JobDto::from(job.clone()),
+            events: events.remove(&job.id).unwrap_or_default(),
+            metrics: metrics.remove(&job.id).unwrap_or_default(),
+            logs: logs.remove(&job.id).unwrap_or_default(),
+        })
+        .collect())
 }
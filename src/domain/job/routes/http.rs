@@ -2,153 +2,293 @@ use std::convert::Infallible;
 
 use axum::{
     Extension, Json,
-    body::Body,
-    extract::{Path, Query},
-    http::Response,
     response::{
-        self, IntoResponse, Sse,
+        Sse,
         sse::{Event, KeepAlive},
     },
 };
 
 use crate::{
     context::SharedContext,
-    domain::job::dto::{
-        CountJobLogsRequest, ListJobLogsQuery, ListJobLogsRequest, ListJobsQuery, ListJobsRequest,
-        StopJobBody, StopJobRequest, SubmitJobBody, SubmitJobRequest,
+    domain::job::{
+        dao::ListJobsParams,
+        dto::{
+            CountJobLogsRequest, CountJobLogsResponse, JobLogDto, JobStatsQuery, JobStatsRequest,
+            JobStatsResponse, ListJobEventsRequest, ListJobEventsResponse, ListJobLogsQuery,
+            ListJobLogsRequest, ListJobLogsResponse, ListJobMetricsQuery, ListJobMetricsRequest,
+            ListJobMetricsResponse, ListJobsQuery, ListJobsRequest, ListJobsResponse,
+            PurgeJobsBody, PurgeJobsRequest, PurgeJobsResponse, StopJobBody, StopJobRequest,
+            SubmitJobBody, SubmitJobRequest, SubmitJobResponse,
+        },
     },
+    errors, request_id,
+    validation::{ValidJson, ValidPath, ValidQuery},
 };
 
 pub async fn submit_job(
     Extension(context): Extension<SharedContext>,
-    Json(body): Json<SubmitJobBody>,
-) -> response::Response {
-    let job_id = context
-        .job_service
-        .submit_job(SubmitJobRequest { request_body: body })
-        .await;
-
-    match job_id {
-        Ok(job_id) => Json(job_id).into_response(),
-        Err(error) => Response::builder()
-            .status(500)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
+    ValidJson(body): ValidJson<SubmitJobBody>,
+) -> errors::Result<Json<SubmitJobResponse>> {
+    // 종료 중에는 새 job을 받지 않음
+    if context.shutdown_token.is_cancelled() {
+        return Err(errors::Error::ShuttingDown);
     }
+
+    let response = context
+        .job_service
+        .submit_job(SubmitJobRequest {
+            request_body: body,
+            schedule_id: None,
+        })
+        .await?;
+
+    Ok(Json(response))
 }
 
 pub async fn stop_job(
     Extension(context): Extension<SharedContext>,
-    Json(body): Json<StopJobBody>,
-) -> response::Response {
-    let result = context
+    ValidJson(body): ValidJson<StopJobBody>,
+) -> errors::Result<Json<()>> {
+    context
         .job_service
         .stop_job(StopJobRequest { request_body: body })
-        .await;
-
-    match result {
-        Ok(_) => Json(()).into_response(),
-        Err(error) => Response::builder()
-            .status(500)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+        .await?;
+
+    Ok(Json(()))
 }
 
 pub async fn list_jobs(
     Extension(context): Extension<SharedContext>,
-    Query(query): Query<ListJobsQuery>,
-) -> response::Response {
-    let result = context
+    ValidQuery(query): ValidQuery<ListJobsQuery>,
+) -> errors::Result<Json<ListJobsResponse>> {
+    let response = context
         .job_service
         .list_jobs(ListJobsRequest {
             request_query: query,
         })
-        .await;
-
-    match result {
-        Ok(response) => Json(response).into_response(),
-        Err(error) => Response::builder()
-            .status(500)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+        .await?;
+
+    Ok(Json(response))
 }
 
 pub async fn list_job_logs(
-    Path(job_id): Path<i64>,
+    ValidPath(job_id): ValidPath<i64>,
     Extension(context): Extension<SharedContext>,
-    Query(query): Query<ListJobLogsQuery>,
-) -> response::Response {
-    let result = context
+    ValidQuery(query): ValidQuery<ListJobLogsQuery>,
+) -> errors::Result<Json<ListJobLogsResponse>> {
+    let response = context
         .job_service
         .list_job_logs(ListJobLogsRequest { job_id, query })
-        .await;
-
-    match result {
-        Ok(response) => Json(response).into_response(),
-        Err(crate::errors::Error::JobLogExpired) => Response::builder()
-            .status(410) // Gone
-            .body(Body::new(
-                crate::errors::Error::JobLogExpired.into_json_response(),
-            ))
-            .unwrap(),
-        Err(error) => Response::builder()
-            .status(500)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+        .await?;
+
+    Ok(Json(response))
 }
 
 pub async fn count_job_logs(
-    Path(job_id): Path<i64>,
+    ValidPath(job_id): ValidPath<i64>,
     Extension(context): Extension<SharedContext>,
-) -> response::Response {
-    let result = context
+) -> errors::Result<Json<CountJobLogsResponse>> {
+    let response = context
         .job_service
         .count_job_logs(CountJobLogsRequest { job_id })
-        .await;
-
-    match result {
-        Ok(response) => Json(response).into_response(),
-        Err(crate::errors::Error::JobLogExpired) => Response::builder()
-            .status(410) // Gone
-            .body(Body::new(
-                crate::errors::Error::JobLogExpired.into_json_response(),
-            ))
-            .unwrap(),
-        Err(error) => Response::builder()
-            .status(500)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+        .await?;
+
+    Ok(Json(response))
 }
 
-// UNCOMPLETE:
+pub async fn list_job_metrics(
+    ValidPath(job_id): ValidPath<i64>,
+    Extension(context): Extension<SharedContext>,
+    ValidQuery(query): ValidQuery<ListJobMetricsQuery>,
+) -> errors::Result<Json<ListJobMetricsResponse>> {
+    let response = context
+        .job_service
+        .list_job_metrics(ListJobMetricsRequest { job_id, query })
+        .await?;
+
+    Ok(Json(response))
+}
+
+/*
+job 로그를 server-sent events로 전달합니다. 이벤트 하나에 로그 한 줄(JobLogDto)을 JSON으로 담고, job이 끝나고 남은 로그를 모두 보내면 스트림을 닫습니다.
+job이 없거나 로그가 만료되었다면 다른 API와 같은 JSON 에러로 응답하고,
+스트리밍 중에 실패하면 error 이벤트에 에러 본문을 담아 보낸 뒤 스트림을 닫습니다.
+*/
 pub async fn tail_job_logs(
-    Path(_job_id): Path<i64>,
-    Extension(_context): Extension<SharedContext>,
-) -> Sse<impl futures_util::Stream<Item = Result<Event, Infallible>>> {
-    use futures_util::stream::{self};
-    use std::time::Duration;
-    use tokio_stream::StreamExt as _;
-
-    // let result = context
-    //     .job_service
-    //     .list_job_logs(ListJobLogsRequest { job_id })
-    //     .await;
-
-    let stream = stream::repeat_with(|| Event::default().data("hi!"))
-        .map(Ok)
-        .throttle(Duration::from_secs(1));
-
-    Sse::new(stream).keep_alive(KeepAlive::default())
-
-    // match result {
-    //     Ok(response) => unimplemented!(),
-    //     Err(error) => Response::builder()
-    //         .status(500)
-    //         .body(Body::new(error.into_json_response()))
-    //         .unwrap(),
-    // }
+    ValidPath(job_id): ValidPath<i64>,
+    Extension(context): Extension<SharedContext>,
+) -> errors::Result<Sse<impl futures_util::Stream<Item = Result<Event, Infallible>>>> {
+    use futures_util::StreamExt as _;
+
+    // 스트림을 열기 전에 조회해서, 실패하면 일반 에러 응답으로 돌려줌
+    let first_logs = context
+        .job_service
+        .list_job_logs(ListJobLogsRequest {
+            job_id,
+            query: ListJobLogsQuery {
+                offset: 0,
+                limit: TAIL_JOB_LOGS_BATCH_LINES,
+            },
+        })
+        .await?
+        .logs;
+
+    let state = TailJobLogsState {
+        context,
+        job_id,
+        offset: first_logs.len(),
+        // 스트림은 요청 처리가 끝난 뒤에 polling되므로 request id를 미리 꺼내 둠
+        request_id: request_id::current(),
+        first_logs: Some(first_logs),
+        closed: false,
+    };
+
+    let stream = futures_util::stream::unfold(state, |mut state| async move {
+        let events = state.next_events().await?;
+
+        Some((
+            futures_util::stream::iter(events.into_iter().map(Ok)),
+            state,
+        ))
+    })
+    .flatten();
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+// 새 로그를 다시 조회하기 전에 기다리는 시간
+const TAIL_JOB_LOGS_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+// 한 번에 조회하는 로그 줄 수
+const TAIL_JOB_LOGS_BATCH_LINES: usize = 1000;
+
+struct TailJobLogsState {
+    context: SharedContext,
+    job_id: i64,
+    offset: usize,
+    request_id: Option<String>,
+    first_logs: Option<Vec<JobLogDto>>,
+    closed: bool,
+}
+
+impl TailJobLogsState {
+    /*
+    다음에 보낼 이벤트를 반환합니다. 스트림을 닫아야 하면 None입니다.
+    */
+    async fn next_events(&mut self) -> Option<Vec<Event>> {
+        if self.closed {
+            return None;
+        }
+
+        if let Some(logs) = self.first_logs.take() {
+            return Some(logs.iter().map(log_event).collect());
+        }
+
+        loop {
+            // 로그를 조회하기 전에 상태를 확인해야, 종료 직전에 쓰인 로그까지 보낸 뒤 닫을 수 있음
+            let finished = match self.is_job_finished().await {
+                Ok(finished) => finished,
+                Err(error) => return Some(vec![self.close_with_error(error)]),
+            };
+
+            let logs = match self
+                .context
+                .job_service
+                .list_job_logs(ListJobLogsRequest {
+                    job_id: self.job_id,
+                    query: ListJobLogsQuery {
+                        offset: self.offset,
+                        limit: TAIL_JOB_LOGS_BATCH_LINES,
+                    },
+                })
+                .await
+            {
+                Ok(response) => response.logs,
+                Err(error) => return Some(vec![self.close_with_error(error)]),
+            };
+
+            self.offset += logs.len();
+
+            if !logs.is_empty() {
+                return Some(logs.iter().map(log_event).collect());
+            }
+
+            if finished {
+                return None;
+            }
+
+            tokio::time::sleep(TAIL_JOB_LOGS_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn is_job_finished(&self) -> errors::Result<bool> {
+        let job = self
+            .context
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: vec![self.job_id],
+                ..Default::default()
+            })
+            .await?
+            .pop()
+            .ok_or(errors::Error::JobNotFound)?;
+
+        Ok(job.status.is_terminal())
+    }
+
+    fn close_with_error(&mut self, error: errors::Error) -> Event {
+        self.closed = true;
+
+        let response = errors::ErrorResponse {
+            request_id: self.request_id.clone(),
+            ..errors::ErrorResponse::from(error)
+        };
+
+        Event::default()
+            .event("error")
+            .json_data(response)
+            .unwrap_or_default()
+    }
+}
+
+fn log_event(log: &JobLogDto) -> Event {
+    Event::default().json_data(log).unwrap_or_default()
+}
+
+pub async fn list_job_events(
+    ValidPath(job_id): ValidPath<i64>,
+    Extension(context): Extension<SharedContext>,
+) -> errors::Result<Json<ListJobEventsResponse>> {
+    let response = context
+        .job_service
+        .list_job_events(ListJobEventsRequest { job_id })
+        .await?;
+
+    Ok(Json(response))
+}
+
+pub async fn purge_jobs(
+    Extension(context): Extension<SharedContext>,
+    ValidJson(body): ValidJson<PurgeJobsBody>,
+) -> errors::Result<Json<PurgeJobsResponse>> {
+    let response = context
+        .job_service
+        .purge_jobs(PurgeJobsRequest { request_body: body })
+        .await?;
+
+    Ok(Json(response))
+}
+
+pub async fn get_job_stats(
+    Extension(context): Extension<SharedContext>,
+    ValidQuery(query): ValidQuery<JobStatsQuery>,
+) -> errors::Result<Json<JobStatsResponse>> {
+    let response = context
+        .job_service
+        .get_job_stats(JobStatsRequest {
+            request_query: query,
+        })
+        .await?;
+
+    Ok(Json(response))
 }
@@ -0,0 +1,41 @@
+use crate::openapi::router::ApiRouter;
+
+use super::http;
+
+pub fn router() -> ApiRouter {
+    ApiRouter::new("jobs")
+        .post("/jobs/submit", "Submit a job", http::submit_job)
+        .post("/jobs/stop", "Cancel a job", http::stop_job)
+        .get("/jobs", "List jobs", http::list_jobs)
+        .post(
+            "/jobs/purge",
+            "Archive or delete finished jobs",
+            http::purge_jobs,
+        )
+        .get(
+            "/jobs/stats",
+            "Job counts per task definition",
+            http::get_job_stats,
+        )
+        .get("/jobs/{job_id}/logs", "List job logs", http::list_job_logs)
+        .get(
+            "/jobs/{job_id}/logs/count",
+            "Count job log lines",
+            http::count_job_logs,
+        )
+        .get(
+            "/jobs/{job_id}/metrics",
+            "List job resource usage samples",
+            http::list_job_metrics,
+        )
+        .get(
+            "/jobs/{job_id}/events",
+            "List job status changes",
+            http::list_job_events,
+        )
+        .get(
+            "/jobs/{job_id}/logs/tailing",
+            "Stream job logs (server-sent events)",
+            http::tail_job_logs,
+        )
+}
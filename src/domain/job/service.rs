@@ -1,408 +1,3169 @@
-use std::sync::Arc;
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use sea_orm::{ActiveEnum, Iterable};
 
 use crate::{
+    config::{RetentionConfig, RetentionMode},
     domain::{
         container::{
             ContainerRepository,
-            dao::{InspectContainerParams, RunContainerParams, StopContainerParams},
+            dao::{
+                ContainerLogLine, ContainerState, ContainerStatsParams, ContainerStatsResult,
+                ContainerSummary, InspectContainerParams, InspectImageParams, JOB_ID_LABEL,
+                ListContainersParams, PullImageParams, RemoveContainerParams, RunContainerParams,
+                StopContainerParams,
+            },
+            stop_or_kill_container,
         },
         job::dto::{
-            CountJobLogsRequest, CountJobLogsResponse, JobLogDto, ListJobLogsRequest,
-            ListJobLogsResponse, SubmitJobResponse,
+            AppendNodeJobLogsRequest, AppendNodeJobLogsResponse, ClaimNodeJobsRequest,
+            ClaimNodeJobsResponse, CountJobLogsRequest, CountJobLogsResponse, JobEventDto,
+            JobLogDto, JobMetricDto, JobStatsRequest, JobStatsResponse, ListJobEventsRequest,
+            ListJobEventsResponse, ListJobLogsRequest, ListJobLogsResponse, ListJobMetricsRequest,
+            ListJobMetricsResponse, NodeJobAssignment, PurgeJobsRequest, PurgeJobsResponse,
+            ReportNodeJobRequest, SubmitJobResponse, TaskDefinitionJobStatsDto,
         },
-        task_definition::{TaskDefinitionRepository, dao::ListTaskDefinitionsParams},
+        node::{NodeRepository, dao::ListNodesParams, entities::node::NodeStatus},
+        registry_credential::RegistryCredentialService,
+        secret::SecretService,
+        task_definition::{
+            TaskDefinitionRepository, dao::ListTaskDefinitionsParams,
+            entities::task_definition::ImagePullPolicy,
+        },
+        unit_of_work::UnitOfWorkFactory,
     },
-    errors,
+    errors, utils,
 };
 
 use super::{
     JobRepository, JobService,
-    dao::{CreateJobParams, ListJobsParams, PatchJobParams},
-    dto::{JobDto, ListJobsRequest, ListJobsResponse, StopJobRequest, SubmitJobRequest},
-    entities::{self, job::JobStatus},
+    dao::{
+        AdoptJobParams, AppendJobLogsParams, ClaimPendingJobsParams, CreateJobMetricParams,
+        CreateJobParams, ListJobLogLinesParams, ListJobMetricsParams, ListJobsParams,
+        PatchJobParams, RemoveJobsParams, RenewJobLeaseParams, TimeRange, TransitionJobParams,
+    },
+    dto::{
+        JobDto, JobRecordDto, JobSortBy, ListJobsRequest, ListJobsResponse, SortOrder,
+        StopJobRequest, SubmitJobRequest,
+    },
+    entities::{
+        self,
+        job::{ExitReason, JobStatus},
+    },
 };
 
+// task definition에 stop_grace_period_seconds가 없을 때 SIGKILL 전까지 대기하는 시간
+pub const DEFAULT_STOP_GRACE_PERIOD_SECONDS: u32 = 10;
+
+// metrics 조회에서 limit이 없을 때 반환하는 샘플 수
+const DEFAULT_JOB_METRICS_LIMIT: u64 = 1000;
+
+// purge 요청에서 한 트랜잭션으로 삭제하는 job 수
+const PURGE_BATCH_SIZE: u64 = 500;
+
+const TERMINAL_JOB_STATUSES: [JobStatus; 3] = [
+    JobStatus::Succeeded,
+    JobStatus::Failed,
+    JobStatus::Cancelled,
+];
+
+/*
+job 테이블에서 빼낸 job을 어디에 남길지 정합니다.
+*/
+enum RemovedJobDestination<'a> {
+    Delete,
+    Archive,          // job_archive 테이블
+    Export(&'a Path), // 디렉터리에 NDJSON(gzip) 파일로
+}
+
 pub struct JobServiceImpl {
     pub job_repository: Arc<dyn JobRepository + Send + Sync>,
     pub task_definition_repository: Arc<dyn TaskDefinitionRepository + Send + Sync>,
     pub container_repository: Arc<dyn ContainerRepository + Send + Sync>,
+    pub secret_service: Arc<dyn SecretService + Send + Sync>,
+    pub registry_credential_service: Arc<dyn RegistryCredentialService + Send + Sync>,
+    pub node_repository: Arc<dyn NodeRepository + Send + Sync>,
+    pub unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+    pub job_submitted_notify: Arc<tokio::sync::Notify>, // 새 job이 제출되면 runner를 깨우기 위한 알림
 }
 
 impl JobServiceImpl {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         job_repository: Arc<dyn JobRepository + Send + Sync>,
         task_definition_repository: Arc<dyn TaskDefinitionRepository + Send + Sync>,
         container_repository: Arc<dyn ContainerRepository + Send + Sync>,
+        secret_service: Arc<dyn SecretService + Send + Sync>,
+        registry_credential_service: Arc<dyn RegistryCredentialService + Send + Sync>,
+        node_repository: Arc<dyn NodeRepository + Send + Sync>,
+        unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+        job_submitted_notify: Arc<tokio::sync::Notify>,
     ) -> Self {
         Self {
             job_repository,
             task_definition_repository,
             container_repository,
+            secret_service,
+            registry_credential_service,
+            node_repository,
+            unit_of_work_factory,
+            job_submitted_notify,
         }
     }
-}
-
-#[async_trait::async_trait]
-impl JobService for JobServiceImpl {
-    async fn submit_job(&self, params: SubmitJobRequest) -> errors::Result<SubmitJobResponse> {
-        let task_definitions = self
-            .task_definition_repository
-            .list_task_definitions(ListTaskDefinitionsParams {
-                task_definition_ids: vec![params.request_body.task_definition_id],
-                ..Default::default()
-            })
-            .await?;
 
-        if task_definitions.is_empty() {
-            return Err(errors::Error::TaskDefinitionNotFound);
-        };
+    /*
+    job의 CPU 누적 사용 시간을 구합니다.
+    cgroup 값을 읽을 수 있으면 그대로 사용하고, 아니면 직전 샘플(첫 샘플은 job 시작 시각) 이후의 평균 사용률에 시간을 곱해 추정합니다.
+    */
+    async fn cumulative_cpu_seconds(
+        &self,
+        job: &entities::job::Model,
+        stats: &ContainerStatsResult,
+        sampled_at: chrono::DateTime<chrono::Utc>,
+    ) -> errors::Result<f64> {
+        if let Some(cpu_usage_seconds) = stats.cpu_usage_seconds {
+            return Ok(cpu_usage_seconds);
+        }
 
-        let new_job_id = self
+        let last_metric = self
             .job_repository
-            .create_job(CreateJobParams {
-                name: params.request_body.job_name.clone(),
-                task_definition_id: params.request_body.task_definition_id,
-                status: entities::job::JobStatus::Pending,
-                submited_at: Some(chrono::Utc::now()),
-                log_expire_after: params.request_body.log_expire_after,
+            .list_job_metrics(ListJobMetricsParams {
+                job_id: job.id,
+                limit: Some(1),
+                latest_first: true,
                 ..Default::default()
             })
-            .await?;
-
-        Ok(SubmitJobResponse { job_id: new_job_id })
-    }
+            .await?
+            .pop();
+
+        let (since, cpu_seconds, cpu_percent) = match last_metric {
+            Some(last_metric) => (
+                last_metric.sampled_at,
+                last_metric.cpu_seconds,
+                (last_metric.cpu_percent + stats.cpu_percent) / 2.0,
+            ),
+            None => (job.started_at.unwrap_or(sampled_at), 0.0, stats.cpu_percent),
+        };
 
-    async fn stop_job(&self, params: StopJobRequest) -> errors::Result<()> {
-        let job_id = params.request_body.job_id;
+        let elapsed_seconds = (sampled_at - since).num_milliseconds().max(0) as f64 / 1000.0;
 
-        let mut jobs = self
-            .job_repository
-            .list_jobs(ListJobsParams {
-                job_ids: vec![job_id],
-                ..Default::default()
-            })
-            .await?;
+        Ok(cpu_seconds + cpu_percent / 100.0 * elapsed_seconds)
+    }
 
-        let Some(job) = jobs.pop() else {
-            return Err(errors::Error::JobNotFound);
+    /*
+    task definition의 pull 정책에 따라 이미지를 준비합니다.
+    pull이 필요한 경우 job 상태를 Pulling으로 변경한 뒤, 레지스트리 인증 정보가 있다면 함께 사용합니다.
+    pull을 했다면 true를 반환합니다.
+    */
+    async fn prepare_image(
+        &self,
+        job_id: i64,
+        image: &str,
+        image_pull_policy: ImagePullPolicy,
+    ) -> errors::Result<bool> {
+        let needs_pull = match image_pull_policy {
+            ImagePullPolicy::Always => true,
+            ImagePullPolicy::IfNotPresent | ImagePullPolicy::Never => {
+                let exists = self
+                    .container_repository
+                    .image_exists(InspectImageParams {
+                        image: image.to_string(),
+                    })
+                    .await?;
+
+                if !exists && image_pull_policy == ImagePullPolicy::Never {
+                    return Err(errors::Error::ContainerImageNotPresent(image.to_string()));
+                }
+
+                !exists
+            }
         };
 
-        if job.status == JobStatus::Finished {
-            return Err(errors::Error::JobAlreadyFinished);
+        if !needs_pull {
+            return Ok(false);
         }
 
-        if job.status == JobStatus::Failed {
-            return Err(errors::Error::JobAlreadyFailed);
-        }
+        // 그 사이 취소 요청이 들어왔다면 상태를 덮어쓰지 않음 (pull 이후 Starting 전환 시점에 취소 처리)
+        self.try_transition_job(TransitionJobParams {
+            to_status: JobStatus::Pulling,
+            reason: Some(format!("Pulling image {}", image)),
+            patch: PatchJobParams {
+                job_id,
+                ..Default::default()
+            },
+        })
+        .await?;
 
-        let Some(container_id) = job.container_id.as_ref() else {
-            return Err(errors::Error::JobHasNoContainerID);
-        };
+        let auth = self
+            .registry_credential_service
+            .resolve_registry_auth(image)
+            .await?;
 
         self.container_repository
-            .stop_container(StopContainerParams {
-                container_id: container_id.clone(),
-                timeout_seconds: 3,
+            .pull_image(PullImageParams {
+                image: image.to_string(),
+                auth,
             })
             .await?;
 
-        Ok(())
+        Ok(true)
     }
 
-    async fn run_pending_job(&self, pending_job: &entities::job::Model) -> errors::Result<()> {
-        // TODO: 리소스 제한이나 실행 제한 등에 걸리지 않는지 확인 (차후 개발)
-
-        // 1. job 상태를 START로 변경
-        self.job_repository
-            .patch_job(PatchJobParams {
-                job_id: pending_job.id,
-                status: Some(JobStatus::Starting),
-                started_at: Some(chrono::Utc::now()),
+    /*
+    Cancelling 상태의 job을 Cancelled로 종료 처리합니다.
+    컨테이너가 실행되기 전에 취소된 job의 마무리에 사용합니다.
+    */
+    async fn finalize_cancelled_job(&self, job_id: i64) -> errors::Result<()> {
+        self.try_transition_job(TransitionJobParams {
+            to_status: JobStatus::Cancelled,
+            reason: Some("Cancelled before the container was started".to_string()),
+            patch: PatchJobParams {
+                job_id,
+                exit_reason: Some(ExitReason::KilledByUser),
+                finished_at: Some(chrono::Utc::now()),
                 ..Default::default()
-            })
-            .await?;
+            },
+        })
+        .await?;
 
-        // 2. 컨테이너 실행을 위해 task definition을 가져옴
-        let mut task_definitions = self
+        Ok(())
+    }
+
+    /*
+    job 상태 전이를 시도하고, 현재 상태에서 허용되지 않는 전이라면 false를 반환합니다.
+    취소 요청 등으로 상태가 먼저 바뀌었을 수 있는 곳에서 사용합니다.
+    */
+    async fn try_transition_job(&self, params: TransitionJobParams) -> errors::Result<bool> {
+        match self.job_repository.transition_job(params).await {
+            Ok(()) => Ok(true),
+            Err(errors::Error::InvalidJobStatusTransition(_)) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /*
+    job의 task definition에 설정된 종료 grace period를 반환합니다. 설정이 없다면 기본값을 사용합니다.
+    */
+    async fn stop_grace_period_seconds(&self, job: &entities::job::Model) -> errors::Result<u32> {
+        let grace_period_seconds = self
             .task_definition_repository
             .list_task_definitions(ListTaskDefinitionsParams {
-                task_definition_ids: vec![pending_job.task_definition_id],
+                task_definition_ids: vec![job.task_definition_id],
                 ..Default::default()
             })
-            .await?;
-
-        let Some(task_definition) = task_definitions.pop() else {
-            return Err(errors::Error::JobNotFound);
-        };
-
-        // 3. 컨테이너 실행
-        let container_id = self
-            .container_repository
-            .run_container(RunContainerParams {
-                task_definition: task_definition.clone(),
-            })
             .await?
-            .container_id;
-
-        // 4. 컨테이너 정보를 job에 업데이트, job 상태를 RUNNING으로 변경
-        self.job_repository
-            .patch_job(PatchJobParams {
-                job_id: pending_job.id,
-                container_id: Some(container_id.clone()),
-                status: Some(JobStatus::Running),
-                ..Default::default()
-            })
-            .await?;
+            .pop()
+            .and_then(|task_definition| task_definition.stop_grace_period_seconds)
+            .map(|seconds| seconds as u32);
 
-        Ok(())
+        Ok(grace_period_seconds.unwrap_or(DEFAULT_STOP_GRACE_PERIOD_SECONDS))
     }
 
-    async fn track_running_job(&self, job: &entities::job::Model) -> errors::Result<()> {
-        let Some(container_id) = &job.container_id else {
-            return Err(errors::Error::ContainerIDNotFound);
-        };
+    /*
+    grace period 동안 호출자를 붙잡지 않도록 백그라운드에서 컨테이너를 종료합니다.
+    종료된 컨테이너는 tracker가 확인하여 job을 마무리합니다.
+    컨테이너를 끝내 종료하지 못하면 Cancelling에 머무르지 않도록 job을 RuntimeError로 실패 처리합니다.
+    */
+    fn spawn_stop_container(&self, job_id: i64, container_id: String, timeout_seconds: u32) {
+        let container_repository = self.container_repository.clone();
+        let job_repository = self.job_repository.clone();
+
+        tokio::spawn(async move {
+            let result = stop_or_kill_container(
+                container_repository.as_ref(),
+                &container_id,
+                timeout_seconds,
+            )
+            .await;
+
+            let Err(error) = result else {
+                return;
+            };
+
+            log::error!("Failed to stop container of job {}: {}", job_id, error);
+
+            let transition_result = job_repository
+                .transition_job(TransitionJobParams {
+                    to_status: JobStatus::Failed,
+                    reason: Some(format!("Failed to stop the container: {}", error)),
+                    patch: PatchJobParams {
+                        job_id,
+                        exit_reason: Some(ExitReason::RuntimeError),
+                        finished_at: Some(chrono::Utc::now()),
+                        error_code: Some(error.error_code()),
+                        error_message: Some(error.to_string()),
+                        ..Default::default()
+                    },
+                })
+                .await;
 
-        let inspect_result = self
+            match transition_result {
+                // 그 사이 컨테이너가 종료되어 tracker가 job을 마무리한 경우
+                Ok(()) | Err(errors::Error::InvalidJobStatusTransition(_)) => {}
+                Err(error) => log::error!("Failed to fail job {}: {}", job_id, error),
+            }
+        });
+    }
+
+    /*
+    job에 기록되지 않은 batchman 컨테이너를 강제로 삭제합니다. 실패하더라도 다음 재시작 시 다시 시도되므로 로그만 남깁니다.
+    */
+    async fn remove_orphan_container(&self, container_id: &str) {
+        let result = self
             .container_repository
-            .inspect_container(InspectContainerParams {
-                container_id: container_id.clone(),
+            .remove_container(RemoveContainerParams {
+                container_id: container_id.to_string(),
+                force: true,
+                remove_volumes: false,
+                remove_links: false,
             })
-            .await?;
-
-        // 1. 컨테이너가 여전히 실행 중인 경우, 아무 작업도 하지 않음
-        if inspect_result.state.running {
-            return Ok(());
+            .await;
+
+        match result {
+            Ok(()) => log::info!("Removed orphan container {}", container_id),
+            Err(error) => {
+                log::warn!(
+                    "Failed to remove orphan container {}: {}",
+                    container_id,
+                    error
+                )
+            }
         }
+    }
 
-        // 2. 컨테이너가 모종의 이유로 조기 종료(실패)했을 경우 처리
-        if inspect_result.state.dead {
-            self.job_repository
-                .patch_job(PatchJobParams {
-                    job_id: job.id,
-                    status: Some(entities::job::JobStatus::Failed),
-                    error_message: Some(format!(
-                        "Container is dead: {}",
-                        inspect_result.state.error.unwrap_or_default()
-                    )),
-                    ..Default::default()
-                })
-                .await?;
+    /*
+    재시작 전에 진행 중이던 job 하나를 실제 컨테이너 상태와 맞춥니다.
+    containers는 해당 job id 라벨이 붙은 컨테이너 목록입니다.
+    - Pulling/Starting: 컨테이너가 있다면 다시 연결하여 Running으로, 없다면 Failed로 처리
+    - Cancelling: 컨테이너가 있다면 종료를 다시 요청하고, 없다면 Cancelled로 처리
+    - Running: 기록된 컨테이너가 없다면 다시 연결 (컨테이너 종료 여부는 tracker가 확인)
+    */
+    async fn reconcile_job(
+        &self,
+        job: &entities::job::Model,
+        containers: Vec<ContainerSummary>,
+    ) -> errors::Result<()> {
+        // 기록된 컨테이너가 있으면 그것을, 없다면 라벨로 찾은 컨테이너(실행 중인 것 우선)에 다시 연결
+        let container_id = match &job.container_id {
+            Some(container_id) => Some(container_id.clone()),
+            None => containers
+                .iter()
+                .find(|container| container.state == "running")
+                .or(containers.first())
+                .map(|container| container.container_id.clone()),
+        };
 
-            return Ok(());
+        // 연결되지 않은 나머지 컨테이너는 중복 실행된 것이므로 정리
+        for container in containers.iter() {
+            if Some(&container.container_id) != container_id.as_ref() {
+                self.remove_orphan_container(&container.container_id).await;
+            }
         }
 
-        // 3. 컨테이너가 종료되었을 경우 종료 처리
-        if let Some(finished_at) = inspect_result.state.finished_at {
-            self.job_repository
+        // 컨테이너 id 기록과 상태 전이가 함께 반영되도록 하나의 트랜잭션으로 처리
+        let unit_of_work = self.unit_of_work_factory.begin().await?;
+        let job_repository = unit_of_work.job_repository();
+
+        if job.container_id.is_none()
+            && let Some(container_id) = &container_id
+        {
+            job_repository
                 .patch_job(PatchJobParams {
                     job_id: job.id,
-                    status: Some(entities::job::JobStatus::Finished),
-                    finished_at: Some(finished_at),
-                    exit_code: inspect_result.state.exit_code,
+                    container_id: Some(container_id.clone()),
                     ..Default::default()
                 })
                 .await?;
-
-            return Ok(());
         }
 
-        Ok(())
-    }
-
-    async fn list_jobs(&self, params: ListJobsRequest) -> errors::Result<ListJobsResponse> {
-        let query = &params.request_query;
-
-        // 페이지네이션 계산
-        let page_number = query.page_number.unwrap_or(1);
-        let page_size = query.page_size.unwrap_or(10);
-        let offset = (page_number - 1) * page_size;
-
-        // 상태 필터링
-        let mut statuses = Vec::new();
-        if let Some(status_str) = &query.status {
-            match status_str.as_str() {
-                "Pending" => statuses.push(JobStatus::Pending),
-                "Starting" => statuses.push(JobStatus::Starting),
-                "Running" => statuses.push(JobStatus::Running),
-                "Finished" => statuses.push(JobStatus::Finished),
-                "Failed" => statuses.push(JobStatus::Failed),
-                _ => {}
+        match (&job.status, container_id) {
+            (JobStatus::Pulling | JobStatus::Starting, Some(container_id)) => {
+                if job.status == JobStatus::Pulling {
+                    job_repository
+                        .transition_job(TransitionJobParams {
+                            to_status: JobStatus::Starting,
+                            reason: Some("Recovered after restart".to_string()),
+                            patch: PatchJobParams {
+                                job_id: job.id,
+                                ..Default::default()
+                            },
+                        })
+                        .await?;
+                }
+
+                job_repository
+                    .transition_job(TransitionJobParams {
+                        to_status: JobStatus::Running,
+                        reason: Some(format!(
+                            "Re-attached to container {} after restart",
+                            container_id
+                        )),
+                        patch: PatchJobParams {
+                            job_id: job.id,
+                            ..Default::default()
+                        },
+                    })
+                    .await?;
             }
-        }
-
-        // job_id 필터링
-        let mut job_ids = Vec::new();
-        if let Some(job_id) = query.job_id {
-            job_ids.push(job_id);
-        }
+            (JobStatus::Pulling | JobStatus::Starting | JobStatus::Running, None) => {
+                let error = errors::Error::JobInterrupted;
+
+                job_repository
+                    .transition_job(TransitionJobParams {
+                        to_status: JobStatus::Failed,
+                        reason: Some(error.to_string()),
+                        patch: PatchJobParams {
+                            job_id: job.id,
+                            exit_reason: Some(ExitReason::RuntimeError),
+                            finished_at: Some(chrono::Utc::now()),
+                            error_code: Some(error.error_code()),
+                            error_message: Some(error.to_string()),
+                            ..Default::default()
+                        },
+                    })
+                    .await?;
+            }
+            (JobStatus::Cancelling, Some(container_id)) => {
+                unit_of_work.commit().await?;
 
-        let list_params = ListJobsParams {
-            job_ids: job_ids.clone(),
-            statuses: statuses.clone(),
-            limit: Some(page_size),
-            offset: Some(offset),
-            contains_name: query.contains_name.clone(),
-        };
+                // 재시작 전에 요청한 종료가 끝나지 않았을 수 있으므로 다시 요청
+                let timeout_seconds = self.stop_grace_period_seconds(job).await?;
+                self.spawn_stop_container(job.id, container_id, timeout_seconds);
 
-        let count_params = ListJobsParams {
-            job_ids,
-            statuses,
-            limit: None,
-            offset: None,
-            contains_name: query.contains_name.clone(),
-        };
+                return Ok(());
+            }
+            (JobStatus::Cancelling, None) => {
+                unit_of_work.commit().await?;
 
-        // 목록과 전체 카운트를 각각 조회
-        let total_count = self.job_repository.count_jobs(count_params).await?;
-        if total_count == 0 {
-            return Ok(ListJobsResponse {
-                jobs: Vec::new(),
-                total_count,
-            });
+                return self.finalize_cancelled_job(job.id).await;
+            }
+            _ => {}
         }
 
-        let jobs = self.job_repository.list_jobs(list_params).await?;
+        unit_of_work.commit().await?;
 
-        let task_definition_ids: Vec<i64> = jobs.iter().map(|job| job.task_definition_id).collect();
+        Ok(())
+    }
 
-        let task_definitions = self
+    /*
+    원격 node가 job을 실행하는 데 필요한 task definition, 복호화한 secret, 레지스트리 인증 정보를 모읍니다.
+    */
+    async fn build_node_job_assignment(
+        &self,
+        job: &entities::job::Model,
+    ) -> errors::Result<NodeJobAssignment> {
+        let task_definition = self
             .task_definition_repository
             .list_task_definitions(ListTaskDefinitionsParams {
-                task_definition_ids,
+                task_definition_ids: vec![job.task_definition_id],
                 ..Default::default()
             })
+            .await?
+            .pop()
+            .ok_or(errors::Error::TaskDefinitionNotFound)?;
+
+        let secret_env = match &task_definition.secrets {
+            Some(secrets) => self.secret_service.resolve_secrets(&secrets.0).await?,
+            None => Default::default(),
+        };
+
+        let registry_auth = self
+            .registry_credential_service
+            .resolve_registry_auth(&task_definition.image)
             .await?;
 
-        // Model을 JobDto로 변환
-        let mut job_dtos: Vec<JobDto> = jobs.into_iter().map(|job| job.into()).collect();
+        Ok(NodeJobAssignment {
+            job_id: job.id,
+            task_definition,
+            secret_env,
+            registry_auth,
+        })
+    }
 
-        for job_dto in &mut job_dtos {
-            if let Some(task_definition) = task_definitions
-                .iter()
-                .find(|td| td.id == job_dto.task_definition_id)
-            {
-                job_dto.task_definition_name = Some(task_definition.name.clone());
-            }
+    /*
+    job이 요청한 node에 배정되어 있는지 확인합니다.
+    */
+    async fn get_node_job(
+        &self,
+        node_id: i64,
+        job_id: i64,
+    ) -> errors::Result<entities::job::Model> {
+        let job = self.get_job(job_id).await?;
+
+        if job.node_id != Some(node_id) {
+            return Err(errors::Error::JobNotAssignedToNode);
         }
 
-        Ok(ListJobsResponse {
-            jobs: job_dtos,
-            total_count,
-        })
+        Ok(job)
     }
 
-    async fn list_job_logs(
+    /*
+    주어진 node 라벨로는 placement constraint를 만족하지 못하는 task definition id 목록을 반환합니다.
+    constraint가 없는 task definition의 job은 어느 worker에서든 실행할 수 있습니다.
+    */
+    async fn placement_excluded_task_definition_ids(
         &self,
-        request: ListJobLogsRequest,
-    ) -> errors::Result<ListJobLogsResponse> {
-        let job_id = request.job_id;
+        labels: &BTreeMap<String, String>,
+    ) -> errors::Result<Vec<i64>> {
+        let constrained_task_definitions = self
+            .task_definition_repository
+            .list_task_definitions(ListTaskDefinitionsParams {
+                has_placement_constraints: Some(true),
+                ..Default::default()
+            })
+            .await?;
 
-        // job_id로 job 조회
-        let mut jobs = self
-            .job_repository
+        Ok(constrained_task_definitions
+            .into_iter()
+            .filter(|task_definition| {
+                task_definition
+                    .placement_constraints
+                    .as_ref()
+                    .is_some_and(|constraints| !constraints.matches(labels))
+            })
+            .map(|task_definition| task_definition.id)
+            .collect())
+    }
+
+    async fn get_job(&self, job_id: i64) -> errors::Result<entities::job::Model> {
+        self.job_repository
             .list_jobs(ListJobsParams {
                 job_ids: vec![job_id],
                 ..Default::default()
             })
-            .await?;
+            .await?
+            .pop()
+            .ok_or(errors::Error::JobNotFound)
+    }
 
-        let Some(job) = jobs.pop() else {
-            return Err(errors::Error::JobNotFound);
-        };
+    /*
+    조건에 맞는 job을 id 순서로 batch_size개씩 job 테이블에서 빼냅니다. batch마다 하나의 트랜잭션으로 처리하므로
+    중간에 실패해도 이미 처리한 batch는 유지됩니다. 내보내기는 파일을 먼저 쓴 뒤 삭제하므로,
+    삭제 전에 실패하면 다음 실행에서 같은 job이 다시 내보내질 수 있습니다.
+    */
+    async fn remove_jobs_in_batches(
+        &self,
+        params: ListJobsParams,
+        batch_size: u64,
+        destination: RemovedJobDestination<'_>,
+    ) -> errors::Result<u64> {
+        let mut removed_count = 0;
+        let mut after_id = None;
+
+        loop {
+            let jobs = self
+                .job_repository
+                .list_jobs(ListJobsParams {
+                    limit: Some(batch_size),
+                    after_id,
+                    ascending: true,
+                    ..params.clone()
+                })
+                .await?;
 
-        // 로그 만료 확인
-        if job.log_expired {
-            return Err(errors::Error::JobLogExpired);
-        }
+            let Some(last_job) = jobs.last() else {
+                break;
+            };
 
-        // 로그 만료일 확인
-        if let Some(expire_after) = job.log_expire_after {
-            if chrono::Utc::now() > expire_after {
-                return Err(errors::Error::JobLogExpired);
+            after_id = Some(last_job.id);
+
+            if let RemovedJobDestination::Export(export_dir) = destination {
+                let records = self.job_repository.list_job_records(&jobs).await?;
+                let path = export_jobs(export_dir, &records).await?;
+                log::info!("Exported {} jobs to {}", jobs.len(), path.display());
+            }
+
+            removed_count += self
+                .job_repository
+                .remove_jobs(RemoveJobsParams {
+                    job_ids: jobs.iter().map(|job| job.id).collect(),
+                    archive: matches!(destination, RemovedJobDestination::Archive),
+                })
+                .await?;
+
+            if (jobs.len() as u64) < batch_size {
+                break;
             }
         }
 
-        // 컨테이너 ID가 없으면 에러
-        let Some(container_id) = &job.container_id else {
-            return Err(errors::Error::ContainerIDNotFound);
-        };
+        Ok(removed_count)
+    }
+}
 
-        // 컨테이너 정보 조회
-        // TODO: inspect 시점을 컨테이너 생성 후로 조정할 필요 있음
-        let container_info = self
-            .container_repository
-            .inspect_container(InspectContainerParams {
-                container_id: container_id.clone(),
-            })
-            .await?;
+/*
+job 기록을 gzip으로 압축한 NDJSON 파일(한 줄에 JobRecordDto 하나)로 저장하고 파일 경로를 반환합니다.
+쓰는 도중 실패해도 불완전한 파일이 남지 않도록 임시 파일에 쓴 뒤 이름을 바꿉니다.
+*/
+async fn export_jobs(export_dir: &Path, records: &[JobRecordDto]) -> errors::Result<PathBuf> {
+    let (Some(first_record), Some(last_record)) = (records.first(), records.last()) else {
+        return Err(std::io::Error::other("no jobs to export").into());
+    };
+
+    let file_name = format!(
+        "jobs-{}-{}-{}.ndjson.gz",
+        chrono::Utc::now().format("%Y%m%dT%H%M%SZ"),
+        first_record.job.id,
+        last_record.job.id
+    );
+
+    let mut lines = Vec::new();
+    for record in records {
+        serde_json::to_writer(&mut lines, record)?;
+        lines.push(b'\n');
+    }
 
-        let lines = crate::utils::read_lines_range(
-            &container_info.log_path,
-            request.query.offset,
-            request.query.limit,
-        )?;
+    let export_dir = export_dir.to_path_buf();
 
-        let mut logs = vec![];
+    tokio::task::spawn_blocking(move || -> errors::Result<PathBuf> {
+        std::fs::create_dir_all(&export_dir)?;
 
-        for (i, line) in lines.into_iter().enumerate() {
-            #[derive(serde::Deserialize)]
-            struct LogLine {
-                log: String,
-                time: chrono::DateTime<chrono::Utc>,
-            }
+        let path = export_dir.join(&file_name);
+        let temp_path = export_dir.join(format!("{}.tmp", file_name));
 
-            let line = serde_json::from_str::<LogLine>(&line)?;
-            logs.push(JobLogDto {
-                index: request.query.offset + i,
-                time: line.time,
-                message: line.log,
-            });
-        }
+        let mut encoder = flate2::write::GzEncoder::new(
+            std::fs::File::create(&temp_path)?,
+            flate2::Compression::default(),
+        );
+        encoder.write_all(&lines)?;
+        encoder.finish()?.sync_all()?;
 
-        Ok(ListJobLogsResponse { logs })
-    }
+        std::fs::rename(&temp_path, &path)?;
 
-    async fn count_job_logs(
-        &self,
-        job_id: CountJobLogsRequest,
-    ) -> errors::Result<CountJobLogsResponse> {
-        let mut jobs = self
-            .job_repository
-            .list_jobs(ListJobsParams {
-                job_ids: vec![job_id.job_id],
+        Ok(path)
+    })
+    .await
+    .map_err(std::io::Error::other)?
+}
+
+/*
+실행 준비 중인 job의 lease를 주기적으로 연장합니다.
+이미지 pull이나 컨테이너 실행이 lease보다 오래 걸려도 다른 worker가 job을 다시 가져가지 않도록 하며,
+drop되면 연장을 멈춥니다.
+*/
+struct LeaseRenewal {
+    handle: tokio::task::JoinHandle<()>,
+}
+
+impl LeaseRenewal {
+    fn spawn(
+        job_repository: Arc<dyn JobRepository + Send + Sync>,
+        job_id: i64,
+        worker_id: String,
+        lease_duration: chrono::Duration,
+    ) -> Self {
+        // lease가 만료되기 전에 여러 번 연장을 시도할 수 있도록 lease의 1/3마다 연장
+        let interval = (lease_duration / 3)
+            .to_std()
+            .unwrap_or_default()
+            .max(std::time::Duration::from_millis(10));
+
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+
+                let result = job_repository
+                    .renew_job_lease(RenewJobLeaseParams {
+                        job_id,
+                        worker_id: worker_id.clone(),
+                        lease_expires_at: chrono::Utc::now() + lease_duration,
+                    })
+                    .await;
+
+                match result {
+                    Ok(true) => {}
+                    // 다른 worker가 가져간 경우, run_pending_job이 lease를 확인하고 실행을 멈춤
+                    Ok(false) => break,
+                    Err(error) => {
+                        log::error!("Failed to renew lease of job {}: {}", job_id, error)
+                    }
+                }
+            }
+        });
+
+        Self { handle }
+    }
+}
+
+impl Drop for LeaseRenewal {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/*
+이미 종료된 job은 중지할 수 없으므로 상태에 맞는 에러를 반환합니다.
+*/
+fn ensure_stoppable(status: &JobStatus) -> errors::Result<()> {
+    match status {
+        JobStatus::Succeeded => Err(errors::Error::JobAlreadyFinished),
+        JobStatus::Failed => Err(errors::Error::JobAlreadyFailed),
+        JobStatus::Cancelled => Err(errors::Error::JobAlreadyCancelled),
+        _ => Ok(()),
+    }
+}
+
+/*
+추적하는 worker가 없는 로컬 job인지 확인합니다.
+worker_id가 없거나(worker를 기록하기 전에 실행된 job), lease가 없거나 만료되었다면(worker가 죽었거나 worker id가 바뀜) 다른 worker가 가져갈 수 있습니다.
+*/
+fn is_orphaned(job: &entities::job::Model, now: chrono::DateTime<chrono::Utc>) -> bool {
+    job.node_id.is_none()
+        && (job.worker_id.is_none() || job.lease_expires_at.is_none_or(|lease| lease < now))
+}
+
+/*
+콤마로 구분된 상태 목록을 변환합니다. (예: Running,Failed)
+알 수 없는 상태가 있다면 InvalidJobFilter 에러를 반환합니다.
+*/
+fn parse_job_statuses(statuses: Option<&str>) -> errors::Result<Vec<JobStatus>> {
+    let Some(statuses) = statuses else {
+        return Ok(Vec::new());
+    };
+
+    statuses
+        .split(',')
+        .map(str::trim)
+        .filter(|status| !status.is_empty())
+        .map(|status| {
+            JobStatus::iter()
+                .find(|job_status| job_status.to_value() == status)
+                .ok_or_else(|| {
+                    errors::Error::InvalidJobFilter(format!("unknown status: {}", status))
+                })
+        })
+        .collect()
+}
+
+/*
+{name}_after ~ {name}_before 범위를 검증합니다.
+*/
+fn time_range(
+    name: &str,
+    after: Option<chrono::DateTime<chrono::Utc>>,
+    before: Option<chrono::DateTime<chrono::Utc>>,
+) -> errors::Result<TimeRange> {
+    if let (Some(after), Some(before)) = (after, before)
+        && after > before
+    {
+        return Err(errors::Error::InvalidJobFilter(format!(
+            "{}_after must not be later than {}_before",
+            name, name
+        )));
+    }
+
+    Ok(TimeRange {
+        from: after,
+        to: before,
+    })
+}
+
+/*
+purge 요청을 job 조회 조건으로 변환합니다. 종료되지 않은 job은 삭제할 수 없고,
+실수로 모든 job을 지우지 않도록 task definition, schedule, 종료 시각 중 하나 이상의 조건이 필요합니다.
+*/
+fn purge_jobs_params(request: &PurgeJobsRequest) -> errors::Result<ListJobsParams> {
+    let body = &request.request_body;
+
+    let statuses: Vec<JobStatus> = match &body.statuses {
+        Some(statuses) => statuses.iter().cloned().map(Into::into).collect(),
+        None => TERMINAL_JOB_STATUSES.to_vec(),
+    };
+
+    if statuses.is_empty() {
+        return Err(errors::Error::InvalidJobFilter(
+            "statuses must not be empty".to_string(),
+        ));
+    }
+
+    if let Some(status) = statuses.iter().find(|status| !status.is_terminal()) {
+        return Err(errors::Error::InvalidJobFilter(format!(
+            "only finished jobs can be purged (got {})",
+            status.to_value()
+        )));
+    }
+
+    if body.task_definition_id.is_none()
+        && body.schedule_id.is_none()
+        && body.finished_before.is_none()
+    {
+        return Err(errors::Error::InvalidJobFilter(
+            "at least one of task_definition_id, schedule_id or finished_before is required"
+                .to_string(),
+        ));
+    }
+
+    Ok(ListJobsParams {
+        statuses,
+        task_definition_ids: body.task_definition_id.into_iter().collect(),
+        schedule_ids: body.schedule_id.into_iter().collect(),
+        finished_at: TimeRange {
+            from: None,
+            to: body.finished_before,
+        },
+        ..Default::default()
+    })
+}
+
+#[async_trait::async_trait]
+impl JobService for JobServiceImpl {
+    async fn submit_job(&self, params: SubmitJobRequest) -> errors::Result<SubmitJobResponse> {
+        // task definition 확인과 job 생성을 하나의 트랜잭션으로 처리
+        let unit_of_work = self.unit_of_work_factory.begin().await?;
+
+        let task_definitions = unit_of_work
+            .task_definition_repository()
+            .list_task_definitions(ListTaskDefinitionsParams {
+                task_definition_ids: vec![params.request_body.task_definition_id],
                 ..Default::default()
             })
             .await?;
 
-        let Some(job) = jobs.pop() else {
+        if task_definitions.is_empty() {
+            return Err(errors::Error::TaskDefinitionNotFound);
+        };
+
+        let new_job_id = unit_of_work
+            .job_repository()
+            .create_job(CreateJobParams {
+                name: params.request_body.job_name.clone(),
+                task_definition_id: params.request_body.task_definition_id,
+                status: entities::job::JobStatus::Pending,
+                submited_at: Some(chrono::Utc::now()),
+                log_expire_after: params.request_body.log_expire_after,
+                schedule_id: params.schedule_id,
+                ..Default::default()
+            })
+            .await?;
+
+        unit_of_work.commit().await?;
+
+        // runner가 대기 중이라면 바로 깨워서 실행하도록 함
+        self.job_submitted_notify.notify_one();
+
+        Ok(SubmitJobResponse { job_id: new_job_id })
+    }
+
+    async fn stop_job(&self, params: StopJobRequest) -> errors::Result<()> {
+        let request_body = params.request_body;
+        let job_id = request_body.job_id;
+
+        let job = self.get_job(job_id).await?;
+
+        ensure_stoppable(&job.status)?;
+
+        // 이미 취소가 진행 중인 경우
+        if job.status == JobStatus::Cancelling {
+            return Ok(());
+        }
+
+        let now = chrono::Utc::now();
+
+        let cancel_event_reason = format!(
+            "Cancel requested by {}: {}",
+            request_body.requested_by.as_deref().unwrap_or("unknown"),
+            request_body.reason.as_deref().unwrap_or("no reason given")
+        );
+
+        // 1. 아직 실행되지 않은 job은 즉시 취소
+        if job.status == JobStatus::Pending {
+            let cancelled = self
+                .try_transition_job(TransitionJobParams {
+                    to_status: JobStatus::Cancelled,
+                    reason: Some(cancel_event_reason.clone()),
+                    patch: PatchJobParams {
+                        job_id,
+                        exit_reason: Some(ExitReason::KilledByUser),
+                        stop_requested_at: Some(now),
+                        finished_at: Some(now),
+                        cancel_reason: request_body.reason.clone(),
+                        cancelled_by: request_body.requested_by.clone(),
+                        ..Default::default()
+                    },
+                })
+                .await?;
+
+            if cancelled {
+                return Ok(());
+            }
+
+            // 그 사이 runner가 job을 가져간 경우, 실행 중인 job과 동일하게 처리
+        }
+
+        // 2. 실행 중인 job은 Cancelling으로 변경하고, 컨테이너 종료 후 tracker가 Cancelled로 마무리
+        let cancelling = self
+            .try_transition_job(TransitionJobParams {
+                to_status: JobStatus::Cancelling,
+                reason: Some(cancel_event_reason),
+                patch: PatchJobParams {
+                    job_id,
+                    stop_requested_at: Some(now),
+                    cancel_reason: request_body.reason,
+                    cancelled_by: request_body.requested_by,
+                    ..Default::default()
+                },
+            })
+            .await?;
+
+        if !cancelling {
+            // 그 사이 job이 종료되었거나 다른 요청으로 취소가 시작된 경우
+            let job = self.get_job(job_id).await?;
+            return ensure_stoppable(&job.status);
+        }
+
+        // 3. 컨테이너가 아직 없다면 runner가 컨테이너 실행 전에 취소를 마무리
+        let job = self.get_job(job_id).await?;
+
+        // 원격 node에서 실행 중인 job은 agent가 heartbeat 응답으로 취소 요청을 받아 컨테이너를 종료
+        if job.node_id.is_some() {
+            return Ok(());
+        }
+
+        let Some(container_id) = job.container_id.clone() else {
+            return Ok(());
+        };
+
+        let timeout_seconds = match request_body.grace_period_seconds {
+            Some(grace_period_seconds) => grace_period_seconds,
+            None => self.stop_grace_period_seconds(&job).await?,
+        };
+
+        self.spawn_stop_container(job_id, container_id, timeout_seconds);
+
+        Ok(())
+    }
+
+    async fn run_pending_job(
+        &self,
+        pending_job: &entities::job::Model,
+        lease_duration: chrono::Duration,
+    ) -> errors::Result<()> {
+        // TODO: 리소스 제한이나 실행 제한 등에 걸리지 않는지 확인 (차후 개발)
+
+        // pending_job은 claim_pending_jobs로 이 worker가 가져온 Starting 상태의 job
+
+        // 컨테이너가 Running이 될 때까지 lease를 연장 (함수가 끝나면 연장을 멈춤)
+        let _lease_renewal = pending_job.worker_id.clone().map(|worker_id| {
+            LeaseRenewal::spawn(
+                self.job_repository.clone(),
+                pending_job.id,
+                worker_id,
+                lease_duration,
+            )
+        });
+
+        // 1. 컨테이너 실행을 위해 task definition을 가져옴
+        let mut task_definitions = self
+            .task_definition_repository
+            .list_task_definitions(ListTaskDefinitionsParams {
+                task_definition_ids: vec![pending_job.task_definition_id],
+                ..Default::default()
+            })
+            .await?;
+
+        let Some(task_definition) = task_definitions.pop() else {
             return Err(errors::Error::JobNotFound);
         };
 
-        // 로그 만료 확인
-        if job.log_expired {
-            return Err(errors::Error::JobLogExpired);
+        // 2. pull 정책에 따라 이미지 준비 (필요 시 Pulling 상태로 변경)
+        let pulled = self
+            .prepare_image(
+                pending_job.id,
+                &task_definition.image,
+                task_definition.image_pull_policy.unwrap_or_default(),
+            )
+            .await?;
+
+        // 3. pull 이후 job 상태를 다시 Starting으로 변경 (그 사이 취소되었다면 컨테이너를 실행하지 않고 종료)
+        if pulled {
+            let starting = self
+                .try_transition_job(TransitionJobParams {
+                    to_status: JobStatus::Starting,
+                    reason: Some("Image pulled".to_string()),
+                    patch: PatchJobParams {
+                        job_id: pending_job.id,
+                        ..Default::default()
+                    },
+                })
+                .await?;
+
+            if !starting {
+                return self.finalize_cancelled_job(pending_job.id).await;
+            }
         }
 
-        // 로그 만료일 확인
-        if let Some(expire_after) = job.log_expire_after {
-            if chrono::Utc::now() > expire_after {
-                return Err(errors::Error::JobLogExpired);
+        // pull 도중 lease가 만료되어 다른 worker가 가져갔다면 중복 실행하지 않음
+        if let Some(worker_id) = &pending_job.worker_id {
+            let renewed = self
+                .job_repository
+                .renew_job_lease(RenewJobLeaseParams {
+                    job_id: pending_job.id,
+                    worker_id: worker_id.clone(),
+                    lease_expires_at: chrono::Utc::now() + lease_duration,
+                })
+                .await?;
+
+            if !renewed {
+                log::warn!(
+                    "Lease of job {} was taken over by another worker",
+                    pending_job.id
+                );
+                return Ok(());
+            }
+        }
+
+        // 4. 참조된 secret 값을 복호화 (DB에는 평문으로 남기지 않고, 실행 시점에만 주입)
+        let secret_env = match &task_definition.secrets {
+            Some(secrets) => self.secret_service.resolve_secrets(&secrets.0).await?,
+            None => Default::default(),
+        };
+
+        // 5. 컨테이너 실행
+        // lease가 만료된 job을 다시 가져온 경우, 이전 worker가 이미 실행한 컨테이너가 있다면 그것을 사용
+        let existing_container = self
+            .container_repository
+            .list_containers(ListContainersParams {
+                label: format!("{}={}", JOB_ID_LABEL, pending_job.id),
+            })
+            .await?
+            .into_iter()
+            .next();
+
+        let (container_id, running_reason) = match existing_container {
+            Some(container) => {
+                let reason = format!("Re-attached to container {}", container.container_id);
+                (container.container_id, reason)
             }
+            None => {
+                // pull 없이 Starting 상태에서 취소된 경우, 컨테이너를 실행하지 않고 종료
+                if self.get_job(pending_job.id).await?.status == JobStatus::Cancelling {
+                    return self.finalize_cancelled_job(pending_job.id).await;
+                }
+
+                let container_id = self
+                    .container_repository
+                    .run_container(RunContainerParams {
+                        job_id: pending_job.id,
+                        task_definition: task_definition.clone(),
+                        secret_env,
+                    })
+                    .await?
+                    .container_id;
+                let reason = format!("Container {} started", container_id);
+                (container_id, reason)
+            }
+        };
+
+        // 6. 컨테이너 정보를 job에 업데이트, job 상태를 RUNNING으로 변경
+        self.job_repository
+            .patch_job(PatchJobParams {
+                job_id: pending_job.id,
+                container_id: Some(container_id.clone()),
+                ..Default::default()
+            })
+            .await?;
+
+        let running = self
+            .try_transition_job(TransitionJobParams {
+                to_status: JobStatus::Running,
+                reason: Some(running_reason),
+                patch: PatchJobParams {
+                    job_id: pending_job.id,
+                    ..Default::default()
+                },
+            })
+            .await?;
+
+        // 컨테이너 실행 중에 취소된 경우, 컨테이너를 종료하고 tracker가 Cancelled로 마무리
+        if !running {
+            self.container_repository
+                .stop_container(StopContainerParams {
+                    container_id,
+                    timeout_seconds: task_definition
+                        .stop_grace_period_seconds
+                        .map(|seconds| seconds as u32)
+                        .unwrap_or(DEFAULT_STOP_GRACE_PERIOD_SECONDS),
+                })
+                .await?;
         }
 
+        Ok(())
+    }
+
+    async fn track_running_job(&self, job: &entities::job::Model) -> errors::Result<()> {
         let Some(container_id) = &job.container_id else {
+            // 컨테이너 실행 전에 취소된 job은 runner가 마무리
+            if job.status == JobStatus::Cancelling {
+                return Ok(());
+            }
+
             return Err(errors::Error::ContainerIDNotFound);
         };
 
-        let container_info = self
+        let inspect_result = self
             .container_repository
             .inspect_container(InspectContainerParams {
                 container_id: container_id.clone(),
             })
             .await?;
 
-        let log_count = crate::utils::count_lines(&container_info.log_path)?;
+        // 1. 컨테이너가 여전히 실행 중인 경우, 아무 작업도 하지 않음
+        if inspect_result.state.running
+            || inspect_result.state.restarting
+            || inspect_result.state.paused
+        {
+            return Ok(());
+        }
 
-        Ok(CountJobLogsResponse { count: log_count })
+        // 2. 컨테이너가 종료되었을 경우 종료 사유를 분류하여 종료 처리
+        let outcome = classify_exit(&inspect_result.state, job.stop_requested_at.is_some());
+
+        let metrics_summary = self.job_repository.summarize_job_metrics(job.id).await?;
+
+        self.job_repository
+            .transition_job(TransitionJobParams {
+                to_status: outcome.status,
+                reason: Some(format!(
+                    "Container exited with code {} ({:?})",
+                    inspect_result.state.exit_code.unwrap_or(-1),
+                    outcome.exit_reason
+                )),
+                patch: PatchJobParams {
+                    job_id: job.id,
+                    exit_reason: Some(outcome.exit_reason),
+                    // 종료 시각을 알 수 없는 경우 docker는 0001-01-01T00:00:00Z를 반환함
+                    finished_at: Some(
+                        inspect_result
+                            .state
+                            .finished_at
+                            .filter(|finished_at| finished_at.timestamp() > 0)
+                            .unwrap_or_else(chrono::Utc::now),
+                    ),
+                    exit_code: inspect_result.state.exit_code,
+                    error_message: outcome.error_message,
+                    peak_memory_bytes: metrics_summary.peak_memory_bytes,
+                    cpu_seconds: metrics_summary.cpu_seconds,
+                    ..Default::default()
+                },
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn sample_job_metrics(&self, jobs: &[entities::job::Model]) -> errors::Result<()> {
+        let container_ids = jobs
+            .iter()
+            .filter_map(|job| job.container_id.clone())
+            .collect::<Vec<_>>();
+
+        if container_ids.is_empty() {
+            return Ok(());
+        }
+
+        let stats = self
+            .container_repository
+            .stats(ContainerStatsParams { container_ids })
+            .await?;
+
+        let sampled_at = chrono::Utc::now();
+
+        for stats in stats {
+            let Some(job) = jobs
+                .iter()
+                .find(|job| job.container_id.as_deref() == Some(stats.container_id.as_str()))
+            else {
+                continue;
+            };
+
+            let cpu_seconds = self.cumulative_cpu_seconds(job, &stats, sampled_at).await?;
+
+            self.job_repository
+                .create_job_metric(CreateJobMetricParams {
+                    job_id: job.id,
+                    sampled_at,
+                    cpu_percent: stats.cpu_percent,
+                    cpu_seconds,
+                    memory_usage_bytes: stats.memory_usage_bytes,
+                    memory_limit_bytes: stats.memory_limit_bytes,
+                    pids: stats.pids,
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn list_jobs(&self, params: ListJobsRequest) -> errors::Result<ListJobsResponse> {
+        let query = &params.request_query;
+
+        // 페이지네이션 계산
+        let page_number = query.page_number.unwrap_or(1);
+        let page_size = query.page_size.unwrap_or(10);
+        // cursor(after_id)가 있다면 offset 대신 cursor 이후부터 조회
+        let offset = match query.after_id {
+            Some(_) => 0,
+            None => (page_number - 1) * page_size,
+        };
+
+        if let (Some(_), Some(sort_by)) = (query.after_id, query.sort_by)
+            && sort_by != JobSortBy::Id
+        {
+            return Err(errors::Error::InvalidJobFilter(
+                "after_id can only be used when sorting by id".to_string(),
+            ));
+        }
+
+        let empty_response = ListJobsResponse {
+            jobs: Vec::new(),
+            total_count: 0,
+            next_cursor: None,
+        };
+
+        // 이름으로 찾은 task definition의 모든 버전의 job을 조회
+        let mut task_definition_ids: Vec<i64> = query.task_definition_id.into_iter().collect();
+
+        if let Some(task_definition_name) = &query.task_definition_name {
+            let mut named_ids = self
+                .task_definition_repository
+                .list_task_definitions(ListTaskDefinitionsParams {
+                    name: Some(task_definition_name.clone()),
+                    ..Default::default()
+                })
+                .await?
+                .into_iter()
+                .map(|task_definition| task_definition.id)
+                .collect::<Vec<_>>();
+
+            // task_definition_id도 주어졌다면 두 조건을 모두 만족해야 함
+            if !task_definition_ids.is_empty() {
+                named_ids.retain(|id| task_definition_ids.contains(id));
+            }
+
+            if named_ids.is_empty() {
+                return Ok(empty_response);
+            }
+
+            task_definition_ids = named_ids;
+        }
+
+        let count_params = ListJobsParams {
+            job_ids: query.job_id.into_iter().collect(),
+            statuses: parse_job_statuses(query.status.as_deref())?,
+            task_definition_ids,
+            schedule_ids: query.schedule_id.into_iter().collect(),
+            contains_name: query.contains_name.clone(),
+            container_id_prefix: query.container_id.clone(),
+            exit_code: query.exit_code,
+            submitted_at: time_range("submitted", query.submitted_after, query.submitted_before)?,
+            started_at: time_range("started", query.started_after, query.started_before)?,
+            finished_at: time_range("finished", query.finished_after, query.finished_before)?,
+            ..Default::default()
+        };
+
+        let list_params = ListJobsParams {
+            // 다음 페이지가 있는지 확인하기 위해 하나 더 조회
+            limit: Some(page_size + 1),
+            offset: Some(offset),
+            after_id: query.after_id,
+            sort_by: query.sort_by.map(Into::into),
+            ascending: query.order == Some(SortOrder::Asc),
+            ..count_params.clone()
+        };
+
+        // 목록과 전체 카운트를 각각 조회
+        let total_count = self.job_repository.count_jobs(count_params).await?;
+        if total_count == 0 {
+            return Ok(empty_response);
+        }
+
+        let mut jobs = self.job_repository.list_jobs(list_params).await?;
+        let next_cursor = utils::take_page(&mut jobs, page_size, |job| job.id);
+
+        // 다른 컬럼으로 정렬하면 id 순서가 아니므로 마지막 id로 다음 페이지를 찾을 수 없음 (page_number 사용)
+        let sorted_by_id = query.sort_by.is_none_or(|sort_by| sort_by == JobSortBy::Id);
+        let next_cursor = next_cursor.filter(|_| sorted_by_id);
+
+        let task_definition_ids: Vec<i64> = jobs.iter().map(|job| job.task_definition_id).collect();
+
+        let task_definitions = self
+            .task_definition_repository
+            .list_task_definitions(ListTaskDefinitionsParams {
+                task_definition_ids,
+                ..Default::default()
+            })
+            .await?;
+
+        // Model을 JobDto로 변환
+        let mut job_dtos: Vec<JobDto> = jobs.into_iter().map(|job| job.into()).collect();
+
+        for job_dto in &mut job_dtos {
+            if let Some(task_definition) = task_definitions
+                .iter()
+                .find(|td| td.id == job_dto.task_definition_id)
+            {
+                job_dto.task_definition_name = Some(task_definition.name.clone());
+            }
+        }
+
+        Ok(ListJobsResponse {
+            jobs: job_dtos,
+            total_count,
+            next_cursor,
+        })
+    }
+
+    async fn list_job_logs(
+        &self,
+        request: ListJobLogsRequest,
+    ) -> errors::Result<ListJobLogsResponse> {
+        let job_id = request.job_id;
+
+        // job_id로 job 조회
+        let mut jobs = self
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: vec![job_id],
+                ..Default::default()
+            })
+            .await?;
+
+        let Some(job) = jobs.pop() else {
+            return Err(errors::Error::JobNotFound);
+        };
+
+        // 로그 만료 확인
+        if job.log_expired {
+            return Err(errors::Error::JobLogExpired);
+        }
+
+        // 로그 만료일 확인
+        if let Some(expire_after) = job.log_expire_after
+            && chrono::Utc::now() > expire_after
+        {
+            return Err(errors::Error::JobLogExpired);
+        }
+
+        // 원격 node에서 실행된 job은 agent가 보낸 로그를 DB에서 읽음
+        if job.node_id.is_some() {
+            let lines = self
+                .job_repository
+                .list_job_log_lines(ListJobLogLinesParams {
+                    job_id,
+                    offset: request.query.offset as u64,
+                    limit: request.query.limit as u64,
+                })
+                .await?;
+
+            return Ok(ListJobLogsResponse {
+                logs: lines.into_iter().map(JobLogDto::from).collect(),
+            });
+        }
+
+        // 컨테이너 ID가 없으면 에러
+        let Some(container_id) = &job.container_id else {
+            return Err(errors::Error::ContainerIDNotFound);
+        };
+
+        // 컨테이너 정보 조회
+        // TODO: inspect 시점을 컨테이너 생성 후로 조정할 필요 있음
+        let container_info = self
+            .container_repository
+            .inspect_container(InspectContainerParams {
+                container_id: container_id.clone(),
+            })
+            .await?;
+
+        let lines = crate::utils::read_lines_range(
+            &container_info.log_path,
+            request.query.offset,
+            request.query.limit,
+        )?;
+
+        let mut logs = vec![];
+
+        for (i, line) in lines.into_iter().enumerate() {
+            let line = serde_json::from_str::<ContainerLogLine>(&line)?;
+            logs.push(JobLogDto {
+                index: request.query.offset + i,
+                time: line.time,
+                message: line.log,
+            });
+        }
+
+        Ok(ListJobLogsResponse { logs })
+    }
+
+    async fn count_job_logs(
+        &self,
+        job_id: CountJobLogsRequest,
+    ) -> errors::Result<CountJobLogsResponse> {
+        let mut jobs = self
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: vec![job_id.job_id],
+                ..Default::default()
+            })
+            .await?;
+
+        let Some(job) = jobs.pop() else {
+            return Err(errors::Error::JobNotFound);
+        };
+
+        // 로그 만료 확인
+        if job.log_expired {
+            return Err(errors::Error::JobLogExpired);
+        }
+
+        // 로그 만료일 확인
+        if let Some(expire_after) = job.log_expire_after
+            && chrono::Utc::now() > expire_after
+        {
+            return Err(errors::Error::JobLogExpired);
+        }
+
+        if job.node_id.is_some() {
+            let count = self.job_repository.count_job_log_lines(job.id).await?;
+
+            return Ok(CountJobLogsResponse {
+                count: count as usize,
+            });
+        }
+
+        let Some(container_id) = &job.container_id else {
+            return Err(errors::Error::ContainerIDNotFound);
+        };
+
+        let container_info = self
+            .container_repository
+            .inspect_container(InspectContainerParams {
+                container_id: container_id.clone(),
+            })
+            .await?;
+
+        let log_count = crate::utils::count_lines(&container_info.log_path)?;
+
+        Ok(CountJobLogsResponse { count: log_count })
+    }
+
+    async fn list_job_metrics(
+        &self,
+        request: ListJobMetricsRequest,
+    ) -> errors::Result<ListJobMetricsResponse> {
+        let mut jobs = self
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: vec![request.job_id],
+                ..Default::default()
+            })
+            .await?;
+
+        let Some(job) = jobs.pop() else {
+            return Err(errors::Error::JobNotFound);
+        };
+
+        // since가 없으면 최신 샘플을, 있으면 그 이후의 샘플을 오래된 순서로 limit개 가져옴
+        let latest_first = request.query.since.is_none();
+
+        let mut metrics = self
+            .job_repository
+            .list_job_metrics(ListJobMetricsParams {
+                job_id: job.id,
+                since: request.query.since,
+                limit: Some(request.query.limit.unwrap_or(DEFAULT_JOB_METRICS_LIMIT)),
+                latest_first,
+            })
+            .await?;
+
+        if latest_first {
+            metrics.reverse();
+        }
+
+        // 종료된 job은 종료 시점에 기록된 값을, 실행 중인 job은 지금까지의 샘플로 계산한 값을 반환
+        let (peak_memory_bytes, cpu_seconds) =
+            if job.peak_memory_bytes.is_some() || job.cpu_seconds.is_some() {
+                (job.peak_memory_bytes, job.cpu_seconds)
+            } else {
+                let summary = self.job_repository.summarize_job_metrics(job.id).await?;
+                (summary.peak_memory_bytes, summary.cpu_seconds)
+            };
+
+        Ok(ListJobMetricsResponse {
+            metrics: metrics.into_iter().map(JobMetricDto::from).collect(),
+            peak_memory_bytes,
+            cpu_seconds,
+        })
+    }
+
+    async fn claim_running_jobs(
+        &self,
+        worker_id: &str,
+        lease_duration: chrono::Duration,
+    ) -> errors::Result<Vec<entities::job::Model>> {
+        let jobs = self
+            .job_repository
+            .list_jobs(ListJobsParams {
+                statuses: vec![JobStatus::Running, JobStatus::Cancelling],
+                ..Default::default()
+            })
+            .await?;
+
+        let now = chrono::Utc::now();
+        let lease_expires_at = now + lease_duration;
+
+        let mut claimed_jobs = Vec::new();
+
+        for mut job in jobs {
+            // 원격 node에서 실행 중인 job은 해당 agent가 컨테이너 상태를 보고
+            if job.node_id.is_some() {
+                continue;
+            }
+
+            let claimed = if job.worker_id.as_deref() == Some(worker_id) {
+                self.job_repository
+                    .renew_job_lease(RenewJobLeaseParams {
+                        job_id: job.id,
+                        worker_id: worker_id.to_string(),
+                        lease_expires_at,
+                    })
+                    .await?
+            } else if is_orphaned(&job, now) {
+                let adopted = self
+                    .job_repository
+                    .adopt_job(AdoptJobParams {
+                        job_id: job.id,
+                        worker_id: worker_id.to_string(),
+                        lease_expires_at,
+                    })
+                    .await?;
+
+                if adopted {
+                    log::info!(
+                        "Took over job {} from worker {}",
+                        job.id,
+                        job.worker_id.as_deref().unwrap_or("unknown")
+                    );
+                }
+
+                adopted
+            } else {
+                // 다른 worker가 lease를 연장하며 추적 중인 job
+                false
+            };
+
+            if claimed {
+                job.worker_id = Some(worker_id.to_string());
+                job.lease_expires_at = Some(lease_expires_at);
+                claimed_jobs.push(job);
+            }
+        }
+
+        Ok(claimed_jobs)
+    }
+
+    async fn reconcile_jobs(
+        &self,
+        worker_id: &str,
+        lease_duration: chrono::Duration,
+    ) -> errors::Result<()> {
+        let containers = self
+            .container_repository
+            .list_containers(ListContainersParams {
+                label: JOB_ID_LABEL.to_string(),
+            })
+            .await?;
+
+        let mut containers_by_job_id: BTreeMap<i64, Vec<ContainerSummary>> = BTreeMap::new();
+
+        for container in containers {
+            let job_id = container
+                .labels
+                .get(JOB_ID_LABEL)
+                .and_then(|job_id| job_id.parse::<i64>().ok());
+
+            match job_id {
+                Some(job_id) => containers_by_job_id
+                    .entry(job_id)
+                    .or_default()
+                    .push(container),
+                None => self.remove_orphan_container(&container.container_id).await,
+            }
+        }
+
+        // 1. 진행 중이던 job을 컨테이너 상태와 맞춤
+        let active_jobs = self
+            .job_repository
+            .list_jobs(ListJobsParams {
+                statuses: vec![
+                    JobStatus::Pulling,
+                    JobStatus::Starting,
+                    JobStatus::Running,
+                    JobStatus::Cancelling,
+                ],
+                ..Default::default()
+            })
+            .await?;
+
+        let now = chrono::Utc::now();
+
+        for job in active_jobs {
+            let containers = containers_by_job_id.remove(&job.id).unwrap_or_default();
+
+            // 원격 node에서 실행 중인 job은 해당 agent가 관리
+            if job.node_id.is_some() {
+                continue;
+            }
+
+            let owned_by_this_worker = match job.status {
+                // 다른 worker가 lease를 가지고 실행 중인 job은 건드리지 않음
+                JobStatus::Pulling | JobStatus::Starting => {
+                    job.worker_id.as_deref().is_none_or(|id| id == worker_id)
+                        || job.lease_expires_at.is_none_or(|lease| lease <= now)
+                }
+                // 다른 worker가 추적 중인 job은 건드리지 않고, 추적하는 worker가 없는 job은 가져와서 이어서 추적
+                _ if job.worker_id.as_deref() == Some(worker_id) => true,
+                _ if is_orphaned(&job, now) => {
+                    self.job_repository
+                        .adopt_job(AdoptJobParams {
+                            job_id: job.id,
+                            worker_id: worker_id.to_string(),
+                            lease_expires_at: now + lease_duration,
+                        })
+                        .await?
+                }
+                _ => false,
+            };
+
+            if !owned_by_this_worker {
+                continue;
+            }
+
+            if let Err(error) = self.reconcile_job(&job, containers).await {
+                log::error!("Failed to reconcile job {}: {}", job.id, error);
+            }
+        }
+
+        // 2. 남은 컨테이너 중 job에 기록되지 않은 컨테이너를 정리
+        // (종료된 job에 기록된 컨테이너는 로그 조회를 위해 유지)
+        if containers_by_job_id.is_empty() {
+            return Ok(());
+        }
+
+        let jobs = self
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: containers_by_job_id.keys().copied().collect(),
+                ..Default::default()
+            })
+            .await?;
+
+        for (job_id, containers) in containers_by_job_id {
+            let recorded_container_id = jobs
+                .iter()
+                .find(|job| job.id == job_id)
+                .and_then(|job| job.container_id.as_deref());
+
+            for container in containers {
+                if recorded_container_id != Some(container.container_id.as_str()) {
+                    self.remove_orphan_container(&container.container_id).await;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn list_job_events(
+        &self,
+        request: ListJobEventsRequest,
+    ) -> errors::Result<ListJobEventsResponse> {
+        let job = self.get_job(request.job_id).await?;
+
+        let events = self.job_repository.list_job_events(job.id).await?;
+
+        Ok(ListJobEventsResponse {
+            events: events.into_iter().map(JobEventDto::from).collect(),
+        })
+    }
+
+    async fn purge_jobs(&self, request: PurgeJobsRequest) -> errors::Result<PurgeJobsResponse> {
+        let params = purge_jobs_params(&request)?;
+
+        let matched_count = self.job_repository.count_jobs(params.clone()).await?;
+
+        if request.request_body.dry_run || matched_count == 0 {
+            return Ok(PurgeJobsResponse {
+                matched_count,
+                purged_count: 0,
+            });
+        }
+
+        let destination = if request.request_body.archive {
+            RemovedJobDestination::Archive
+        } else {
+            RemovedJobDestination::Delete
+        };
+
+        let purged_count = self
+            .remove_jobs_in_batches(params, PURGE_BATCH_SIZE, destination)
+            .await?;
+
+        log::info!("Purged {} jobs", purged_count);
+
+        Ok(PurgeJobsResponse {
+            matched_count,
+            purged_count,
+        })
+    }
+
+    async fn get_job_stats(&self, request: JobStatsRequest) -> errors::Result<JobStatsResponse> {
+        let counts = self
+            .job_repository
+            .count_jobs_by_status(
+                request
+                    .request_query
+                    .task_definition_id
+                    .into_iter()
+                    .collect(),
+            )
+            .await?;
+
+        let mut stats = BTreeMap::<i64, TaskDefinitionJobStatsDto>::new();
+
+        for count in counts {
+            let task_definition_stats =
+                stats.entry(count.task_definition_id).or_insert_with(|| {
+                    TaskDefinitionJobStatsDto {
+                        task_definition_id: count.task_definition_id,
+                        ..Default::default()
+                    }
+                });
+
+            let job_count = count.job_count + count.removed_count;
+
+            task_definition_stats.total_count += job_count;
+            task_definition_stats.removed_count += count.removed_count;
+            *task_definition_stats
+                .status_counts
+                .entry(count.status.to_value())
+                .or_default() += job_count;
+        }
+
+        let task_definitions = self
+            .task_definition_repository
+            .list_task_definitions(ListTaskDefinitionsParams {
+                task_definition_ids: stats.keys().copied().collect(),
+                ..Default::default()
+            })
+            .await?;
+
+        for task_definition in task_definitions {
+            if let Some(task_definition_stats) = stats.get_mut(&task_definition.id) {
+                task_definition_stats.task_definition_name = Some(task_definition.name);
+            }
+        }
+
+        Ok(JobStatsResponse {
+            task_definitions: stats.into_values().collect(),
+        })
+    }
+
+    async fn remove_expired_jobs(&self, retention: &RetentionConfig) -> errors::Result<u64> {
+        let params = ListJobsParams {
+            statuses: TERMINAL_JOB_STATUSES.to_vec(),
+            finished_at: TimeRange {
+                from: None,
+                to: Some(chrono::Utc::now() - retention.retention_period()),
+            },
+            ..Default::default()
+        };
+
+        let destination = match retention.mode {
+            RetentionMode::Archive => RemovedJobDestination::Archive,
+            RetentionMode::Export => {
+                RemovedJobDestination::Export(Path::new(&retention.export_dir))
+            }
+        };
+
+        self.remove_jobs_in_batches(params, retention.batch_size, destination)
+            .await
+    }
+
+    async fn claim_local_jobs(
+        &self,
+        worker_id: &str,
+        limit: u64,
+        lease_duration: chrono::Duration,
+    ) -> errors::Result<Vec<entities::job::Model>> {
+        // 이 서버는 라벨이 없는 worker로 취급
+        let excluded_task_definition_ids = self
+            .placement_excluded_task_definition_ids(&BTreeMap::new())
+            .await?;
+
+        self.job_repository
+            .claim_pending_jobs(ClaimPendingJobsParams {
+                worker_id: worker_id.to_string(),
+                node_id: None,
+                limit,
+                excluded_task_definition_ids,
+                lease_duration,
+            })
+            .await
+    }
+
+    async fn claim_node_jobs(
+        &self,
+        request: ClaimNodeJobsRequest,
+    ) -> errors::Result<ClaimNodeJobsResponse> {
+        let node = self
+            .node_repository
+            .list_nodes(ListNodesParams {
+                node_ids: vec![request.node_id],
+                ..Default::default()
+            })
+            .await?
+            .pop()
+            .ok_or(errors::Error::NodeNotFound)?;
+
+        // 1. node가 이미 실행 중인 job을 제외한 남은 capacity만큼만 배정
+        let active_job_count = self
+            .job_repository
+            .count_jobs(ListJobsParams {
+                node_ids: vec![node.id],
+                statuses: vec![
+                    JobStatus::Starting,
+                    JobStatus::Pulling,
+                    JobStatus::Running,
+                    JobStatus::Cancelling,
+                ],
+                ..Default::default()
+            })
+            .await?;
+
+        let free_slots = (node.capacity.max(0) as u64).saturating_sub(active_job_count);
+        let limit = request.request_body.limit.min(free_slots);
+
+        // drain된 node에는 새 job을 배정하지 않음
+        if limit == 0 || node.status != NodeStatus::Active {
+            return Ok(ClaimNodeJobsResponse { jobs: vec![] });
+        }
+
+        // 2. node 라벨이 placement constraint를 만족하는 job만 배정
+        let excluded_task_definition_ids = self
+            .placement_excluded_task_definition_ids(&node.labels.0)
+            .await?;
+
+        let claimed_jobs = self
+            .job_repository
+            .claim_pending_jobs(ClaimPendingJobsParams {
+                worker_id: node.name.clone(),
+                node_id: Some(node.id),
+                limit,
+                excluded_task_definition_ids,
+                lease_duration: request.lease_duration,
+            })
+            .await?;
+
+        // 3. 실행 정보를 준비하지 못한 job은 node로 보내지 않고 실패 처리
+        let mut jobs = Vec::new();
+
+        for job in claimed_jobs {
+            match self.build_node_job_assignment(&job).await {
+                Ok(assignment) => jobs.push(assignment),
+                Err(error) => {
+                    log::error!(
+                        "Failed to prepare job {} for node {}: {}",
+                        job.id,
+                        node.name,
+                        error
+                    );
+
+                    self.try_transition_job(TransitionJobParams {
+                        to_status: JobStatus::Failed,
+                        reason: Some(error.to_string()),
+                        patch: PatchJobParams {
+                            job_id: job.id,
+                            exit_reason: Some(ExitReason::RuntimeError),
+                            finished_at: Some(chrono::Utc::now()),
+                            error_code: Some(error.error_code()),
+                            error_message: Some(error.to_string()),
+                            ..Default::default()
+                        },
+                    })
+                    .await?;
+                }
+            }
+        }
+
+        Ok(ClaimNodeJobsResponse { jobs })
+    }
+
+    async fn report_node_job(&self, request: ReportNodeJobRequest) -> errors::Result<()> {
+        let job = self.get_node_job(request.node_id, request.job_id).await?;
+
+        let body = request.request_body;
+        let to_status = JobStatus::from(body.status);
+
+        // 컨테이너 id 기록과 상태 전이가 함께 반영되도록 하나의 트랜잭션으로 처리
+        let unit_of_work = self.unit_of_work_factory.begin().await?;
+        let job_repository = unit_of_work.job_repository();
+
+        if let Some(container_id) = &body.container_id
+            && job.container_id.as_ref() != Some(container_id)
+        {
+            job_repository
+                .patch_job(PatchJobParams {
+                    job_id: job.id,
+                    container_id: Some(container_id.clone()),
+                    ..Default::default()
+                })
+                .await?;
+        }
+
+        let finished_at = to_status
+            .is_terminal()
+            .then(|| body.finished_at.unwrap_or_else(chrono::Utc::now));
+
+        job_repository
+            .transition_job(TransitionJobParams {
+                to_status,
+                reason: body.reason,
+                patch: PatchJobParams {
+                    job_id: job.id,
+                    finished_at,
+                    exit_code: body.exit_code,
+                    exit_reason: body.exit_reason,
+                    error_code: body.error_code,
+                    error_message: body.error_message,
+                    ..Default::default()
+                },
+            })
+            .await?;
+
+        unit_of_work.commit().await
+    }
+
+    async fn append_node_job_logs(
+        &self,
+        request: AppendNodeJobLogsRequest,
+    ) -> errors::Result<AppendNodeJobLogsResponse> {
+        let job = self.get_node_job(request.node_id, request.job_id).await?;
+
+        let next_offset = self
+            .job_repository
+            .append_job_logs(AppendJobLogsParams {
+                job_id: job.id,
+                offset: request.request_body.offset,
+                lines: request
+                    .request_body
+                    .logs
+                    .into_iter()
+                    .map(|line| (line.time, line.message))
+                    .collect(),
+            })
+            .await?;
+
+        Ok(AppendNodeJobLogsResponse { next_offset })
+    }
+}
+
+pub struct JobOutcome {
+    pub status: JobStatus,
+    pub exit_reason: ExitReason,
+    pub error_message: Option<String>,
+}
+
+/*
+종료된 컨테이너의 상태로 job의 최종 상태와 종료 사유를 분류합니다.
+우선순위: 런타임 오류 > OOM > 정상 종료 > 사용자 취소 > exit code
+*/
+pub fn classify_exit(state: &ContainerState, stop_requested: bool) -> JobOutcome {
+    let runtime_error = state.error.as_deref().filter(|error| !error.is_empty());
+
+    if state.dead || runtime_error.is_some() {
+        return JobOutcome {
+            status: JobStatus::Failed,
+            exit_reason: ExitReason::RuntimeError,
+            error_message: Some(format!(
+                "Container runtime error: {}",
+                runtime_error.unwrap_or("container is dead")
+            )),
+        };
+    }
+
+    if state.oom_killed {
+        return JobOutcome {
+            status: JobStatus::Failed,
+            exit_reason: ExitReason::OomKilled,
+            error_message: Some(
+                "Container was killed because it exceeded the memory limit".to_string(),
+            ),
+        };
+    }
+
+    match state.exit_code {
+        Some(0) => JobOutcome {
+            status: JobStatus::Succeeded,
+            exit_reason: ExitReason::Succeeded,
+            error_message: None,
+        },
+        _ if stop_requested => JobOutcome {
+            status: JobStatus::Cancelled,
+            exit_reason: ExitReason::KilledByUser,
+            error_message: None,
+        },
+        exit_code => JobOutcome {
+            status: JobStatus::Failed,
+            exit_reason: ExitReason::NonZeroExit,
+            error_message: Some(format!(
+                "Container exited with code {}",
+                exit_code.unwrap_or(-1)
+            )),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Config,
+        context::{Context, SharedContext},
+        domain::{
+            container::repository::fake::FakeContainerRepository,
+            job::dto::{
+                ClaimNodeJobsBody, JobStatusDto, ListJobMetricsQuery, PurgeJobsBody, StopJobBody,
+            },
+            node::dto::{RegisterNodeBody, RegisterNodeRequest},
+            task_definition::dto::CreateDefinitionRequest,
+        },
+    };
+
+    fn exited(exit_code: i32) -> ContainerState {
+        ContainerState {
+            status: "exited".to_string(),
+            running: false,
+            paused: false,
+            restarting: false,
+            oom_killed: false,
+            dead: false,
+            exit_code: Some(exit_code),
+            started_at: None,
+            finished_at: None,
+            error: Some(String::new()),
+        }
+    }
+
+    #[test]
+    fn test_classify_exit() {
+        let outcome = classify_exit(&exited(0), false);
+        assert_eq!(outcome.status, JobStatus::Succeeded);
+        assert_eq!(outcome.exit_reason, ExitReason::Succeeded);
+
+        let outcome = classify_exit(&exited(1), false);
+        assert_eq!(outcome.status, JobStatus::Failed);
+        assert_eq!(outcome.exit_reason, ExitReason::NonZeroExit);
+
+        let outcome = classify_exit(&exited(137), true);
+        assert_eq!(outcome.status, JobStatus::Cancelled);
+        assert_eq!(outcome.exit_reason, ExitReason::KilledByUser);
+
+        let outcome = classify_exit(&exited(0), true);
+        assert_eq!(outcome.status, JobStatus::Succeeded);
+
+        let outcome = classify_exit(
+            &ContainerState {
+                oom_killed: true,
+                ..exited(137)
+            },
+            true,
+        );
+        assert_eq!(outcome.exit_reason, ExitReason::OomKilled);
+
+        let outcome = classify_exit(
+            &ContainerState {
+                error: Some("mount failed".to_string()),
+                ..exited(127)
+            },
+            false,
+        );
+        assert_eq!(outcome.exit_reason, ExitReason::RuntimeError);
+    }
+
+    #[test]
+    fn test_parse_job_filters() {
+        assert_eq!(
+            parse_job_statuses(Some("Running, Failed,")).unwrap(),
+            vec![JobStatus::Running, JobStatus::Failed]
+        );
+        assert!(parse_job_statuses(None).unwrap().is_empty());
+        assert!(matches!(
+            parse_job_statuses(Some("Running,running")),
+            Err(errors::Error::InvalidJobFilter(_))
+        ));
+
+        let now = chrono::Utc::now();
+        assert!(time_range("started", Some(now), Some(now)).is_ok());
+        assert!(matches!(
+            time_range(
+                "started",
+                Some(now),
+                Some(now - chrono::Duration::seconds(1))
+            ),
+            Err(errors::Error::InvalidJobFilter(_))
+        ));
+    }
+
+    #[test]
+    fn test_purge_jobs_params() {
+        let purge =
+            |body: PurgeJobsBody| purge_jobs_params(&PurgeJobsRequest { request_body: body });
+
+        let params = purge(PurgeJobsBody {
+            task_definition_id: Some(1),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(params.statuses, TERMINAL_JOB_STATUSES.to_vec());
+        assert_eq!(params.task_definition_ids, vec![1]);
+
+        // 조건이 없으면 모든 job이 삭제되므로 거부
+        assert!(matches!(
+            purge(PurgeJobsBody::default()),
+            Err(errors::Error::InvalidJobFilter(_))
+        ));
+        assert!(matches!(
+            purge(PurgeJobsBody {
+                statuses: Some(vec![JobStatusDto::Failed, JobStatusDto::Running]),
+                finished_before: Some(chrono::Utc::now()),
+                ..Default::default()
+            }),
+            Err(errors::Error::InvalidJobFilter(_))
+        ));
+    }
+
+    async fn create_task_definition(context: &SharedContext) -> i64 {
+        context
+            .task_definition_service
+            .create_task_definition(CreateDefinitionRequest {
+                request_body: serde_json::from_value(serde_json::json!({
+                    "name": "task",
+                    "description": "task",
+                    "image": "alpine",
+                }))
+                .unwrap(),
+            })
+            .await
+            .unwrap()
+            .task_definition_id
+    }
+
+    async fn create_running_jobs(
+        context: &SharedContext,
+        count: usize,
+    ) -> Vec<entities::job::Model> {
+        let task_definition_id = create_task_definition(context).await;
+
+        let mut job_ids = Vec::new();
+        for index in 0..count {
+            let job_id = context
+                .job_repository
+                .create_job(CreateJobParams {
+                    name: format!("job-{}", index),
+                    task_definition_id,
+                    status: JobStatus::Running,
+                    started_at: Some(chrono::Utc::now() - chrono::Duration::seconds(10)),
+                    container_id: Some(format!("container-{}", index)),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            job_ids.push(job_id);
+        }
+
+        context
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids,
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sample_job_metrics() {
+        let containers = Arc::new(FakeContainerRepository::new());
+        let context = Context::for_test(Config::default(), containers.clone()).await;
+        let jobs = create_running_jobs(&context, 2).await;
+
+        {
+            let mut state = containers.state();
+            state.running.insert("container-0".to_string(), true);
+            state.running.insert("container-1".to_string(), true);
+        }
+
+        let list_metrics = |job_id: i64, limit: Option<u64>| {
+            let context = context.clone();
+            async move {
+                context
+                    .job_service
+                    .list_job_metrics(ListJobMetricsRequest {
+                        job_id,
+                        query: ListJobMetricsQuery { since: None, limit },
+                    })
+                    .await
+                    .unwrap()
+                    .metrics
+            }
+        };
+
+        // cgroup을 읽을 수 없으면 첫 샘플은 job 시작(10초 전) 이후의 사용률(50%)로 추정
+        context
+            .job_service
+            .sample_job_metrics(&jobs[..1])
+            .await
+            .unwrap();
+        let estimated = list_metrics(jobs[0].id, None).await;
+        assert!(
+            (4.9..5.5).contains(&estimated[0].cpu_seconds),
+            "{}",
+            estimated[0].cpu_seconds
+        );
+
+        // 모든 컨테이너를 한 번의 stats 호출로 샘플링하고, cgroup 값을 그대로 기록
+        containers.state().cpu_usage_seconds = Some(2.5);
+        context.job_service.sample_job_metrics(&jobs).await.unwrap();
+        assert_eq!(containers.state().stats_calls, 2);
+
+        let metrics = list_metrics(jobs[1].id, None).await;
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].cpu_seconds, 2.5);
+
+        // since가 없으면 최신 샘플을 오래된 순서로 반환
+        context
+            .job_service
+            .sample_job_metrics(&jobs[..1])
+            .await
+            .unwrap();
+        let all = list_metrics(jobs[0].id, None).await;
+        assert_eq!(all.len(), 3);
+        let latest = list_metrics(jobs[0].id, Some(2)).await;
+        let sampled_at = |metrics: &[JobMetricDto]| {
+            metrics
+                .iter()
+                .map(|metric| metric.sampled_at)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sampled_at(&latest), sampled_at(&all[1..]));
+    }
+
+    async fn wait_until<F, Fut>(condition: F)
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = bool>,
+    {
+        tokio::time::timeout(tokio::time::Duration::from_secs(10), async {
+            while !condition().await {
+                tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("condition was not met in time");
+    }
+
+    /*
+    취소 요청 후 컨테이너 종료가 실패해도 재시도/kill로 종료하고, 끝내 실패하면 job을 RuntimeError로 실패 처리하는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_stop_job_retries_and_escalates() {
+        let containers = Arc::new(FakeContainerRepository::new());
+        let context = Context::for_test(Config::default(), containers.clone()).await;
+        let jobs = create_running_jobs(&context, 4).await;
+
+        for job in &jobs {
+            let container_id = job.container_id.clone().unwrap();
+            containers.state().running.insert(container_id, true);
+        }
+
+        let get_job = async |job_id: i64| {
+            context
+                .job_repository
+                .list_jobs(ListJobsParams {
+                    job_ids: vec![job_id],
+                    ..Default::default()
+                })
+                .await
+                .unwrap()
+                .pop()
+                .unwrap()
+        };
+
+        // 컨테이너가 종료되면 tracker가 Cancelled로 마무리
+        let stop_and_track = async |job: &entities::job::Model| {
+            context
+                .job_service
+                .stop_job(StopJobRequest {
+                    request_body: StopJobBody {
+                        job_id: job.id,
+                        reason: None,
+                        requested_by: None,
+                        grace_period_seconds: Some(0),
+                    },
+                })
+                .await
+                .unwrap();
+
+            let container_id = job.container_id.clone().unwrap();
+            wait_until(|| async { !containers.state().running[&container_id] }).await;
+
+            let cancelling = get_job(job.id).await;
+            assert_eq!(cancelling.status, JobStatus::Cancelling);
+            context
+                .job_service
+                .track_running_job(&cancelling)
+                .await
+                .unwrap();
+            assert_eq!(get_job(job.id).await.status, JobStatus::Cancelled);
+        };
+
+        // stop이 일시적으로 실패하면 재시도
+        containers.state().stop_failures = 2;
+        stop_and_track(&jobs[0]).await;
+        assert_eq!(containers.state().stop_calls, 3);
+        assert_eq!(containers.state().kill_calls, 0);
+
+        // stop이 계속 실패하면 kill로 종료
+        containers.state().stop_failures = 3;
+        stop_and_track(&jobs[1]).await;
+        assert_eq!(containers.state().kill_calls, 1);
+
+        // grace period 후에도 컨테이너가 남아 있으면 kill로 종료
+        containers.state().stop_ignored = true;
+        stop_and_track(&jobs[2]).await;
+        assert_eq!(containers.state().kill_calls, 2);
+
+        // 종료하지 못하면 Cancelling에 머무르지 않고 RuntimeError로 실패
+        {
+            let mut state = containers.state();
+            state.stop_ignored = false;
+            state.stop_failures = 3;
+            state.kill_failures = 3;
+        }
+        context
+            .job_service
+            .stop_job(StopJobRequest {
+                request_body: StopJobBody {
+                    job_id: jobs[3].id,
+                    reason: None,
+                    requested_by: None,
+                    grace_period_seconds: Some(0),
+                },
+            })
+            .await
+            .unwrap();
+
+        wait_until(|| async { get_job(jobs[3].id).await.status == JobStatus::Failed }).await;
+        let failed = get_job(jobs[3].id).await;
+        assert_eq!(failed.exit_reason, Some(ExitReason::RuntimeError));
+        assert_eq!(
+            failed.error_code.as_deref(),
+            Some("FAILED_TO_KILL_CONTAINER")
+        );
+        assert!(containers.state().running[jobs[3].container_id.as_ref().unwrap()]);
+    }
+
+    async fn create_pending_job(context: &SharedContext, task_definition_id: i64) -> i64 {
+        context
+            .job_repository
+            .create_job(CreateJobParams {
+                name: "job".to_string(),
+                task_definition_id,
+                status: JobStatus::Pending,
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn get_job_model(context: &SharedContext, job_id: i64) -> entities::job::Model {
+        context
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: vec![job_id],
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .pop()
+            .unwrap()
+    }
+
+    // lease를 만료시킴 (worker가 죽어 연장하지 못한 상황)
+    async fn expire_lease(context: &SharedContext, job_id: i64, worker_id: &str) {
+        let renewed = context
+            .job_repository
+            .renew_job_lease(RenewJobLeaseParams {
+                job_id,
+                worker_id: worker_id.to_string(),
+                lease_expires_at: chrono::Utc::now() - chrono::Duration::seconds(1),
+            })
+            .await
+            .unwrap();
+        assert!(renewed);
+    }
+
+    /*
+    lease가 만료된 Starting/Pulling job만 다른 worker가 다시 가져가는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_claim_reclaims_expired_leases() {
+        let containers = Arc::new(FakeContainerRepository::new());
+        let context = Context::for_test(Config::default(), containers.clone()).await;
+        let task_definition_id = create_task_definition(&context).await;
+        let starting_job_id = create_pending_job(&context, task_definition_id).await;
+        let pulling_job_id = create_pending_job(&context, task_definition_id).await;
+        let lease_duration = chrono::Duration::seconds(60);
+
+        let claim = |worker_id: &'static str| {
+            let context = context.clone();
+            async move {
+                context
+                    .job_service
+                    .claim_local_jobs(worker_id, 10, lease_duration)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|job| job.id)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(
+            claim("worker-a").await,
+            vec![starting_job_id, pulling_job_id]
+        );
+        context
+            .job_repository
+            .transition_job(TransitionJobParams {
+                to_status: JobStatus::Pulling,
+                reason: None,
+                patch: PatchJobParams {
+                    job_id: pulling_job_id,
+                    ..Default::default()
+                },
+            })
+            .await
+            .unwrap();
+
+        // lease가 남아 있는 동안은 다른 worker가 가져가지 않음
+        assert!(claim("worker-b").await.is_empty());
+
+        // Running job은 lease가 만료되어도 컨테이너가 실행 중이므로 가져가지 않음
+        let running_job = create_running_jobs(&context, 1).await.pop().unwrap();
+        expire_lease(&context, starting_job_id, "worker-a").await;
+        expire_lease(&context, pulling_job_id, "worker-a").await;
+
+        assert_eq!(
+            claim("worker-b").await,
+            vec![starting_job_id, pulling_job_id]
+        );
+        assert_eq!(
+            get_job_model(&context, running_job.id).await.status,
+            JobStatus::Running
+        );
+
+        for job_id in [starting_job_id, pulling_job_id] {
+            let job = get_job_model(&context, job_id).await;
+            assert_eq!(job.status, JobStatus::Starting);
+            assert_eq!(job.worker_id.as_deref(), Some("worker-b"));
+
+            let event = context
+                .job_repository
+                .list_job_events(job_id)
+                .await
+                .unwrap()
+                .pop()
+                .unwrap();
+            assert_eq!(event.to_status, JobStatus::Starting);
+            assert_eq!(
+                event.reason.as_deref(),
+                Some("Lease of worker worker-a expired, reclaimed by worker worker-b")
+            );
+        }
+
+        // 이전 worker는 더 이상 lease를 연장할 수 없음
+        let renewed = context
+            .job_repository
+            .renew_job_lease(RenewJobLeaseParams {
+                job_id: starting_job_id,
+                worker_id: "worker-a".to_string(),
+                lease_expires_at: chrono::Utc::now() + lease_duration,
+            })
+            .await
+            .unwrap();
+        assert!(!renewed);
+    }
+
+    /*
+    node는 라벨이 placement constraint를 만족하는 job만, 실행 중인 job을 제외한 capacity만큼만 가져가는지 확인합니다.
+    라벨이 없는 로컬 worker는 constraint가 있는 job을 가져가지 않습니다.
+    */
+    #[tokio::test]
+    async fn test_claim_node_jobs_respects_placement_and_capacity() {
+        let containers = Arc::new(FakeContainerRepository::new());
+        let context = Context::for_test(Config::default(), containers).await;
+        let lease_duration = chrono::Duration::seconds(60);
+
+        let register_node = async |name: &str, labels: &[(&str, &str)], capacity: i32| {
+            context
+                .node_service
+                .register_node(RegisterNodeRequest {
+                    request_body: RegisterNodeBody {
+                        name: name.to_string(),
+                        labels: labels
+                            .iter()
+                            .map(|(key, value)| (key.to_string(), value.to_string()))
+                            .collect(),
+                        capacity,
+                    },
+                    lease_duration: chrono::Duration::seconds(60),
+                    heartbeat_timeout: chrono::Duration::seconds(30),
+                })
+                .await
+                .unwrap()
+                .node_id
+        };
+
+        let claim_node_jobs = async |node_id: i64, limit: u64| {
+            context
+                .job_service
+                .claim_node_jobs(ClaimNodeJobsRequest {
+                    node_id,
+                    request_body: ClaimNodeJobsBody { limit },
+                    lease_duration,
+                })
+                .await
+                .unwrap()
+                .jobs
+                .iter()
+                .map(|assignment| assignment.job_id)
+                .collect::<Vec<_>>()
+        };
+
+        let gpu_node_id = register_node("gpu-node", &[("gpu", "true")], 2).await;
+        let cpu_node_id = register_node("cpu-node", &[("gpu", "false")], 1).await;
+
+        let gpu_task_definition_id = context
+            .task_definition_service
+            .create_task_definition(CreateDefinitionRequest {
+                request_body: serde_json::from_value(serde_json::json!({
+                    "name": "gpu-task",
+                    "description": "gpu-task",
+                    "image": "alpine",
+                    "placement_constraints": [
+                        {"key": "gpu", "operator": "In", "values": ["true"]}
+                    ],
+                }))
+                .unwrap(),
+            })
+            .await
+            .unwrap()
+            .task_definition_id;
+        let task_definition_id = create_task_definition(&context).await;
+
+        let gpu_job_ids = [
+            create_pending_job(&context, gpu_task_definition_id).await,
+            create_pending_job(&context, gpu_task_definition_id).await,
+            create_pending_job(&context, gpu_task_definition_id).await,
+        ];
+        let job_ids = [
+            create_pending_job(&context, task_definition_id).await,
+            create_pending_job(&context, task_definition_id).await,
+        ];
+
+        // 1. constraint를 만족하지 않는 node는 constraint가 없는 job만 capacity만큼 가져감
+        assert_eq!(claim_node_jobs(cpu_node_id, 10).await, vec![job_ids[0]]);
+        assert!(claim_node_jobs(cpu_node_id, 10).await.is_empty());
+
+        // 2. 라벨이 없는 로컬 worker도 constraint가 있는 job은 가져가지 않음
+        let local_job_ids = context
+            .job_service
+            .claim_local_jobs("worker-a", 10, lease_duration)
+            .await
+            .unwrap()
+            .iter()
+            .map(|job| job.id)
+            .collect::<Vec<_>>();
+        assert_eq!(local_job_ids, vec![job_ids[1]]);
+
+        // 3. constraint를 만족하는 node는 요청한 개수와 남은 capacity 중 작은 만큼 가져감
+        assert_eq!(claim_node_jobs(gpu_node_id, 1).await, vec![gpu_job_ids[0]]);
+        assert_eq!(claim_node_jobs(gpu_node_id, 10).await, vec![gpu_job_ids[1]]);
+        assert!(claim_node_jobs(gpu_node_id, 10).await.is_empty());
+
+        let job = get_job_model(&context, gpu_job_ids[0]).await;
+        assert_eq!(job.status, JobStatus::Starting);
+        assert_eq!(job.node_id, Some(gpu_node_id));
+        assert_eq!(job.worker_id.as_deref(), Some("gpu-node"));
+
+        // 4. node의 job이 끝나면 빈 슬롯만큼 다시 가져감
+        context
+            .job_repository
+            .transition_job(TransitionJobParams {
+                to_status: JobStatus::Failed,
+                reason: None,
+                patch: PatchJobParams {
+                    job_id: gpu_job_ids[0],
+                    ..Default::default()
+                },
+            })
+            .await
+            .unwrap();
+        assert_eq!(claim_node_jobs(gpu_node_id, 10).await, vec![gpu_job_ids[2]]);
+    }
+
+    /*
+    재시작 시 worker_id가 없거나(worker를 기록하기 전의 job) worker id가 바뀌어 lease가 만료된 Running/Cancelling job은 가져와서 맞추고,
+    다른 worker가 lease를 연장하며 추적 중인 job은 건드리지 않는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_reconcile_adopts_jobs_without_live_worker() {
+        let containers = Arc::new(FakeContainerRepository::new());
+        let context = Context::for_test(Config::default(), containers.clone()).await;
+        let task_definition_id = create_task_definition(&context).await;
+        let lease_duration = chrono::Duration::seconds(60);
+
+        let create_job =
+            async |status: JobStatus,
+                   worker_id: Option<&str>,
+                   lease_expires_at: Option<chrono::DateTime<chrono::Utc>>| {
+                let job_id = context
+                    .job_repository
+                    .create_job(CreateJobParams {
+                        name: "job".to_string(),
+                        task_definition_id,
+                        status,
+                        ..Default::default()
+                    })
+                    .await
+                    .unwrap();
+
+                context
+                    .job_repository
+                    .patch_job(PatchJobParams {
+                        job_id,
+                        worker_id: worker_id.map(str::to_string),
+                        lease_expires_at,
+                        ..Default::default()
+                    })
+                    .await
+                    .unwrap();
+
+                job_id
+            };
+
+        let live_lease = chrono::Utc::now() + lease_duration;
+        let expired_lease = chrono::Utc::now() - chrono::Duration::seconds(1);
+
+        // 업그레이드 전부터 실행 중이던 job (worker_id 없음)
+        let legacy_running_job_id = create_job(JobStatus::Running, None, None).await;
+        let legacy_cancelling_job_id = create_job(JobStatus::Cancelling, None, None).await;
+        // 재배포로 worker id가 바뀐 이전 worker의 job
+        let previous_worker_job_id =
+            create_job(JobStatus::Running, Some("worker-old"), Some(expired_lease)).await;
+        // 살아있는 다른 worker의 job
+        let other_worker_job_id =
+            create_job(JobStatus::Running, Some("worker-b"), Some(live_lease)).await;
+
+        context
+            .job_service
+            .reconcile_jobs("worker-a", lease_duration)
+            .await
+            .unwrap();
+
+        // 컨테이너가 없으므로 가져온 job은 실패/취소로 마무리
+        for (job_id, status) in [
+            (legacy_running_job_id, JobStatus::Failed),
+            (legacy_cancelling_job_id, JobStatus::Cancelled),
+            (previous_worker_job_id, JobStatus::Failed),
+        ] {
+            let job = get_job_model(&context, job_id).await;
+            assert_eq!(job.status, status, "job {}", job_id);
+            assert_eq!(job.worker_id.as_deref(), Some("worker-a"));
+        }
+
+        let job = get_job_model(&context, other_worker_job_id).await;
+        assert_eq!(job.status, JobStatus::Running);
+        assert_eq!(job.worker_id.as_deref(), Some("worker-b"));
+
+        // 가져온 Running job은 tracker가 lease를 연장하며 이어서 추적
+        let legacy_job_id = create_job(JobStatus::Running, None, None).await;
+        let claimed = context
+            .job_service
+            .claim_running_jobs("worker-a", lease_duration)
+            .await
+            .unwrap();
+        assert_eq!(
+            claimed.iter().map(|job| job.id).collect::<Vec<_>>(),
+            vec![legacy_job_id]
+        );
+        assert!(
+            context
+                .job_service
+                .claim_running_jobs("worker-b", lease_duration)
+                .await
+                .unwrap()
+                .iter()
+                .all(|job| job.id != legacy_job_id)
+        );
+    }
+
+    /*
+    이미지 pull이 lease보다 오래 걸려도 lease를 연장하여 다른 worker가 job을 가져가지 않는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_run_pending_job_renews_lease() {
+        let containers = Arc::new(FakeContainerRepository::with_blocking_pulls());
+        let context = Context::for_test(Config::default(), containers.clone()).await;
+        let task_definition_id = create_task_definition(&context).await;
+        let job_id = create_pending_job(&context, task_definition_id).await;
+        let lease_duration = chrono::Duration::milliseconds(300);
+
+        let mut claimed = context
+            .job_service
+            .claim_local_jobs("worker-a", 1, lease_duration)
+            .await
+            .unwrap();
+        let job = claimed.pop().unwrap();
+
+        let run = tokio::spawn({
+            let context = context.clone();
+            async move {
+                context
+                    .job_service
+                    .run_pending_job(&job, lease_duration)
+                    .await
+            }
+        });
+
+        wait_until(|| async { containers.state().pulling == 1 }).await;
+        tokio::time::sleep(tokio::time::Duration::from_millis(900)).await;
+
+        // 처음 받은 lease는 만료되었지만 연장되었으므로 다른 worker가 가져가지 않음
+        let reclaimed = context
+            .job_service
+            .claim_local_jobs("worker-b", 1, lease_duration)
+            .await
+            .unwrap();
+        assert!(reclaimed.is_empty());
+        let job = get_job_model(&context, job_id).await;
+        assert_eq!(job.status, JobStatus::Pulling);
+        assert!(job.lease_expires_at.unwrap() > chrono::Utc::now());
+
+        containers.release_pulls(1);
+        run.await.unwrap().unwrap();
+
+        let job = get_job_model(&context, job_id).await;
+        assert_eq!(job.status, JobStatus::Running);
+        assert_eq!(job.worker_id.as_deref(), Some("worker-a"));
+        assert_eq!(containers.state().runs.len(), 1);
+    }
+
+    /*
+    이미지 pull 없이 Starting 상태에서 취소된 job은 컨테이너를 실행하지 않고 Cancelled로 끝나는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_run_pending_job_cancelled_while_starting() {
+        let containers = Arc::new(FakeContainerRepository::new());
+        containers.state().pulled_images.push("alpine".to_string());
+        let context = Context::for_test(Config::default(), containers.clone()).await;
+        let task_definition_id = create_task_definition(&context).await;
+        let job_id = create_pending_job(&context, task_definition_id).await;
+        let lease_duration = chrono::Duration::seconds(60);
+
+        let job = context
+            .job_service
+            .claim_local_jobs("worker-a", 1, lease_duration)
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        context
+            .job_service
+            .stop_job(StopJobRequest {
+                request_body: StopJobBody {
+                    job_id,
+                    reason: None,
+                    requested_by: None,
+                    grace_period_seconds: None,
+                },
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            get_job_model(&context, job_id).await.status,
+            JobStatus::Cancelling
+        );
+
+        context
+            .job_service
+            .run_pending_job(&job, lease_duration)
+            .await
+            .unwrap();
+
+        let job = get_job_model(&context, job_id).await;
+        assert_eq!(job.status, JobStatus::Cancelled);
+        assert_eq!(job.exit_reason, Some(ExitReason::KilledByUser));
+        assert!(job.finished_at.is_some());
+        assert!(containers.state().runs.is_empty());
+        assert_eq!(containers.state().stop_calls, 0);
+    }
+
+    /*
+    종료 상태로 전이할 때 finished_at을 주지 않으면 전이 시각이, 주면 그 값이 기록되는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_transition_to_terminal_status_sets_finished_at() {
+        let containers = Arc::new(FakeContainerRepository::new());
+        let context = Context::for_test(Config::default(), containers.clone()).await;
+        let jobs = create_running_jobs(&context, 2).await;
+
+        let before = chrono::Utc::now();
+        context
+            .job_repository
+            .transition_job(TransitionJobParams {
+                to_status: JobStatus::Failed,
+                reason: None,
+                patch: PatchJobParams {
+                    job_id: jobs[0].id,
+                    exit_reason: Some(ExitReason::RuntimeError),
+                    ..Default::default()
+                },
+            })
+            .await
+            .unwrap();
+        let finished_at = get_job_model(&context, jobs[0].id).await.finished_at;
+        assert!(finished_at.is_some_and(|finished_at| finished_at >= before));
+
+        // 주어진 종료 시각은 그대로 기록
+        let container_finished_at = chrono::Utc::now() - chrono::Duration::minutes(5);
+        context
+            .job_repository
+            .transition_job(TransitionJobParams {
+                to_status: JobStatus::Succeeded,
+                reason: None,
+                patch: PatchJobParams {
+                    job_id: jobs[1].id,
+                    finished_at: Some(container_finished_at),
+                    ..Default::default()
+                },
+            })
+            .await
+            .unwrap();
+        let finished_at = get_job_model(&context, jobs[1].id)
+            .await
+            .finished_at
+            .unwrap();
+        assert_eq!(finished_at.timestamp(), container_finished_at.timestamp());
+    }
+
+    /*
+    id 순서로 정렬할 때만 next_cursor를 반환하는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_list_jobs_next_cursor_only_when_sorted_by_id() {
+        let context =
+            Context::for_test(Config::default(), Arc::new(FakeContainerRepository::new())).await;
+        let jobs = create_running_jobs(&context, 3).await;
+
+        let list = |query: serde_json::Value| {
+            let context = context.clone();
+            async move {
+                context
+                    .job_service
+                    .list_jobs(ListJobsRequest {
+                        request_query: serde_json::from_value(query).unwrap(),
+                    })
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let first_page = list(serde_json::json!({ "page_size": 2 })).await;
+        assert_eq!(first_page.jobs.len(), 2);
+        assert_eq!(first_page.next_cursor, Some(jobs[1].id));
+
+        let second_page = list(serde_json::json!({
+            "page_size": 2,
+            "after_id": first_page.next_cursor,
+        }))
+        .await;
+        assert_eq!(second_page.jobs.len(), 1);
+        assert_eq!(second_page.jobs[0].id, jobs[2].id);
+        assert_eq!(second_page.next_cursor, None);
+
+        // 이름 순서의 페이지는 page_number로 넘김
+        let by_name = list(serde_json::json!({ "page_size": 2, "sort_by": "name" })).await;
+        assert_eq!(by_name.jobs.len(), 2);
+        assert_eq!(by_name.next_cursor, None);
+    }
+
+    /*
+    next_cursor를 after_id로 넘기며 끝까지 조회하면 모든 job이 한 번씩만 나오고, 마지막 페이지의 next_cursor는 None인지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_list_jobs_pages_through_every_job_with_after_id() {
+        let context =
+            Context::for_test(Config::default(), Arc::new(FakeContainerRepository::new())).await;
+        let jobs = create_running_jobs(&context, 7).await;
+
+        let mut listed_job_ids = Vec::new();
+        let mut after_id = None;
+        let mut page_count = 0;
+
+        loop {
+            let page = context
+                .job_service
+                .list_jobs(ListJobsRequest {
+                    request_query: serde_json::from_value(serde_json::json!({
+                        "page_size": 3,
+                        "after_id": after_id,
+                    }))
+                    .unwrap(),
+                })
+                .await
+                .unwrap();
+            page_count += 1;
+            listed_job_ids.extend(page.jobs.iter().map(|job| job.id));
+
+            match page.next_cursor {
+                Some(next_cursor) => after_id = Some(next_cursor),
+                None => break,
+            }
+        }
+
+        let mut job_ids = jobs.iter().map(|job| job.id).collect::<Vec<_>>();
+        job_ids.sort_by(|a, b| b.cmp(a));
+
+        assert_eq!(page_count, 3);
+        assert_eq!(listed_job_ids, job_ids);
+    }
+
+    // 40일 전에 끝난 job을 이벤트, 리소스 샘플, agent 로그와 함께 만듦
+    async fn create_finished_job(context: &SharedContext, task_definition_id: i64) -> i64 {
+        let finished_at = chrono::Utc::now() - chrono::Duration::days(40);
+        let job_id = context
+            .job_repository
+            .create_job(CreateJobParams {
+                name: "job".to_string(),
+                task_definition_id,
+                status: JobStatus::Running,
+                started_at: Some(finished_at - chrono::Duration::minutes(1)),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        context
+            .job_repository
+            .create_job_metric(CreateJobMetricParams {
+                job_id,
+                sampled_at: finished_at,
+                cpu_percent: 50.0,
+                cpu_seconds: 30.0,
+                memory_usage_bytes: 1024,
+                memory_limit_bytes: 4096,
+                pids: 1,
+            })
+            .await
+            .unwrap();
+        context
+            .job_repository
+            .append_job_logs(AppendJobLogsParams {
+                job_id,
+                offset: 0,
+                lines: vec![(finished_at, "hello\n".to_string())],
+            })
+            .await
+            .unwrap();
+        context
+            .job_repository
+            .transition_job(TransitionJobParams {
+                to_status: JobStatus::Succeeded,
+                reason: Some("Container exited".to_string()),
+                patch: PatchJobParams {
+                    job_id,
+                    exit_code: Some(0),
+                    finished_at: Some(finished_at),
+                    ..Default::default()
+                },
+            })
+            .await
+            .unwrap();
+
+        job_id
+    }
+
+    // job 기록에 job과 함께 지워지는 이벤트, 리소스 샘플, agent 로그가 들어있는지 확인
+    fn assert_job_record(record: &serde_json::Value, job_id: i64) {
+        assert_eq!(record["id"], job_id);
+        let events = record["events"].as_array().unwrap();
+        assert_eq!(
+            events.last().unwrap()["to_status"],
+            serde_json::to_value(JobStatusDto::Succeeded).unwrap()
+        );
+        assert_eq!(record["metrics"][0]["cpu_seconds"], 30.0);
+        assert_eq!(record["logs"][0]["message"], "hello\n");
+    }
+
+    async fn assert_job_removed(context: &SharedContext, job_id: i64) {
+        let jobs = context
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: vec![job_id],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(jobs.is_empty());
+        assert!(
+            context
+                .job_repository
+                .list_job_events(job_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            context
+                .job_repository
+                .count_job_log_lines(job_id)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    /*
+    purge(삭제/archive)와 보관 기간 export가 job을 지우면서, archive/export에는 이벤트와 리소스 샘플, 로그를 남기는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_remove_jobs_keeps_job_records() {
+        use sea_orm::EntityTrait;
+
+        let context =
+            Context::for_test(Config::default(), Arc::new(FakeContainerRepository::new())).await;
+
+        let purge = |task_definition_id: i64, archive: bool| {
+            let context = context.clone();
+            async move {
+                context
+                    .job_service
+                    .purge_jobs(PurgeJobsRequest {
+                        request_body: PurgeJobsBody {
+                            task_definition_id: Some(task_definition_id),
+                            archive,
+                            ..Default::default()
+                        },
+                    })
+                    .await
+                    .unwrap()
+                    .purged_count
+            }
+        };
+
+        // 1. archive: job_archive의 record에 기록을 남김
+        let archived_task_definition_id = create_task_definition(&context).await;
+        let archived_job_id = create_finished_job(&context, archived_task_definition_id).await;
+        assert_eq!(purge(archived_task_definition_id, true).await, 1);
+        assert_job_removed(&context, archived_job_id).await;
+
+        let archived = entities::job_archive::Entity::find()
+            .all(&context.connection)
+            .await
+            .unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_job_record(
+            &serde_json::from_str(&archived[0].record).unwrap(),
+            archived_job_id,
+        );
+
+        // 2. 삭제: 아무것도 남기지 않음
+        let deleted_task_definition_id = create_task_definition(&context).await;
+        let deleted_job_id = create_finished_job(&context, deleted_task_definition_id).await;
+        assert_eq!(purge(deleted_task_definition_id, false).await, 1);
+        assert_job_removed(&context, deleted_job_id).await;
+        assert_eq!(
+            entities::job_archive::Entity::find()
+                .all(&context.connection)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // 3. export: batch마다 NDJSON(gzip) 파일 하나
+        let task_definition_id = create_task_definition(&context).await;
+        let mut exported_job_ids = Vec::new();
+        for _ in 0..3 {
+            exported_job_ids.push(create_finished_job(&context, task_definition_id).await);
+        }
+
+        let export_dir = std::env::temp_dir().join(format!(
+            "batchman-export-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let retention = RetentionConfig {
+            mode: RetentionMode::Export,
+            export_dir: export_dir.to_string_lossy().to_string(),
+            batch_size: 2,
+            ..Default::default()
+        };
+        let removed_count = context
+            .job_service
+            .remove_expired_jobs(&retention)
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 3);
+
+        let mut files = std::fs::read_dir(&export_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files.len(), 2);
+
+        let mut records = Vec::new();
+        for file in files {
+            let mut ndjson = String::new();
+            std::io::Read::read_to_string(
+                &mut flate2::read::GzDecoder::new(std::fs::File::open(file).unwrap()),
+                &mut ndjson,
+            )
+            .unwrap();
+            records.extend(
+                ndjson
+                    .lines()
+                    .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()),
+            );
+        }
+        std::fs::remove_dir_all(&export_dir).unwrap();
+
+        assert_eq!(records.len(), 3);
+        for job_id in exported_job_ids {
+            let record = records
+                .iter()
+                .find(|record| record["id"] == job_id)
+                .unwrap();
+            assert_job_record(record, job_id);
+            assert_job_removed(&context, job_id).await;
+        }
     }
 }
@@ -1,4 +1,8 @@
 pub mod container;
 pub mod job;
+pub mod node;
+pub mod registry_credential;
 pub mod schedule;
+pub mod secret;
 pub mod task_definition;
+pub mod unit_of_work;
@@ -0,0 +1,53 @@
+pub mod dao;
+pub mod dto;
+pub mod entities;
+pub mod repository;
+pub mod routes;
+pub mod service;
+
+use dao::*;
+use dto::{
+    ListNodesRequest, ListNodesResponse, NodeHeartbeatRequest, NodeHeartbeatResponse,
+    RegisterNodeRequest, RegisterNodeResponse,
+};
+
+use crate::errors;
+
+#[async_trait::async_trait]
+pub trait NodeRepository {
+    async fn list_nodes(
+        &self,
+        params: ListNodesParams,
+    ) -> errors::Result<Vec<entities::node::Model>>;
+
+    /// 같은 이름의 node가 있다면 labels/capacity를 갱신하고, 없다면 새로 등록합니다.
+    async fn register_node(&self, params: RegisterNodeParams) -> errors::Result<i64>;
+
+    /// node의 마지막 heartbeat 시각을 갱신합니다. 등록되지 않은 node라면 NodeNotFound를 반환합니다.
+    async fn record_node_heartbeat(&self, params: RecordNodeHeartbeatParams) -> errors::Result<()>;
+
+    /// Active node를 Draining으로 바꿉니다. 그 사이에 heartbeat를 보냈다면 false를 반환합니다.
+    async fn drain_node(&self, params: DrainNodeParams) -> errors::Result<bool>;
+}
+
+#[async_trait::async_trait]
+pub trait NodeService {
+    async fn register_node(
+        &self,
+        request: RegisterNodeRequest,
+    ) -> errors::Result<RegisterNodeResponse>;
+
+    /// heartbeat를 기록하고 node가 실행 중인 job의 lease를 연장합니다. 취소해야 할 job 목록을 반환합니다.
+    async fn heartbeat_node(
+        &self,
+        request: NodeHeartbeatRequest,
+    ) -> errors::Result<NodeHeartbeatResponse>;
+
+    async fn list_nodes(&self, request: ListNodesRequest) -> errors::Result<ListNodesResponse>;
+
+    /// heartbeat가 끊긴 node를 drain하고, 그 node에 배정된 job을 다시 대기시키거나 실패 처리합니다.
+    async fn drain_unresponsive_nodes(
+        &self,
+        heartbeat_timeout: chrono::Duration,
+    ) -> errors::Result<()>;
+}
@@ -0,0 +1,30 @@
+use chrono::Utc;
+
+use super::entities::node::{NodeLabels, NodeStatus};
+
+#[derive(Debug, Default, Clone)]
+pub struct ListNodesParams {
+    pub node_ids: Vec<i64>,
+    pub names: Vec<String>,
+    pub statuses: Vec<NodeStatus>,
+    pub last_heartbeat_before: Option<chrono::DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RegisterNodeParams {
+    pub name: String,       // agent name (unique)
+    pub labels: NodeLabels, // labels advertised by the agent
+    pub capacity: i32,      // max number of concurrent jobs
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordNodeHeartbeatParams {
+    pub node_id: i64,
+    pub running_jobs: i32, // number of jobs the agent is running now
+}
+
+#[derive(Debug, Clone)]
+pub struct DrainNodeParams {
+    pub node_id: i64,
+    pub last_heartbeat_before: chrono::DateTime<Utc>, // skip the node if it sent a heartbeat since
+}
@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::entities::{self, node::NodeStatus};
+use crate::{
+    openapi::schema::{schema_enum, schema_object},
+    validation::{MAX_NAME_LENGTH, Validate, Validator},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RegisterNodeBody {
+    pub name: String,                     // agent name (unique, reused after restart)
+    pub labels: BTreeMap<String, String>, // labels advertised by the agent
+    pub capacity: i32,                    // max number of concurrent jobs
+}
+
+#[derive(Debug, Clone)]
+pub struct RegisterNodeRequest {
+    pub request_body: RegisterNodeBody,
+    pub lease_duration: chrono::Duration, // how long a claim is valid without a heartbeat
+    pub heartbeat_timeout: chrono::Duration, // the node is drained after this long without a heartbeat
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RegisterNodeResponse {
+    pub node_id: i64,                   // primary key of the node
+    pub lease_duration_seconds: u64,    // runner.lease_duration_seconds of the server
+    pub heartbeat_timeout_seconds: u64, // node.heartbeat_timeout_seconds of the server
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NodeHeartbeatBody {
+    pub running_jobs: i32, // number of jobs the agent is running now
+    #[serde(default)]
+    pub job_ids: Vec<i64>, // jobs the agent is running now
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeHeartbeatRequest {
+    pub node_id: i64,
+    pub lease_duration: chrono::Duration, // how long to extend the leases of the node's jobs
+    pub request_body: NodeHeartbeatBody,
+}
+
+/// agent가 종료해야 하는 job입니다. 컨테이너가 아직 없다면 container_id는 None입니다.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CancelJobDto {
+    pub job_id: i64,
+    pub container_id: Option<String>,
+    pub grace_period_seconds: u32, // SIGTERM -> SIGKILL grace period
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NodeHeartbeatResponse {
+    pub cancel_jobs: Vec<CancelJobDto>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ListNodesQuery {
+    pub node_id: Option<i64>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListNodesRequest {
+    pub query: ListNodesQuery,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct NodeDto {
+    pub id: i64,
+    pub name: String,
+    pub labels: BTreeMap<String, String>,
+    pub capacity: i32,
+    pub running_jobs: i32,
+    pub status: NodeStatus,
+    pub drained_at: Option<chrono::DateTime<Utc>>,
+    pub registered_at: chrono::DateTime<Utc>,
+    pub last_heartbeat_at: chrono::DateTime<Utc>,
+}
+
+impl From<entities::node::Model> for NodeDto {
+    fn from(model: entities::node::Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            labels: model.labels.0,
+            capacity: model.capacity,
+            running_jobs: model.running_jobs,
+            status: model.status,
+            drained_at: model.drained_at,
+            registered_at: model.registered_at,
+            last_heartbeat_at: model.last_heartbeat_at,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ListNodesResponse {
+    pub nodes: Vec<NodeDto>,
+}
+
+impl Validate for RegisterNodeBody {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .not_blank("name", &self.name)
+            .max_length("name", &self.name, MAX_NAME_LENGTH)
+            .min("capacity", self.capacity, 1);
+    }
+}
+
+impl Validate for NodeHeartbeatBody {
+    fn validate(&self, validator: &mut Validator) {
+        validator.min("running_jobs", self.running_jobs, 0);
+    }
+}
+
+impl Validate for ListNodesQuery {
+    fn validate(&self, _validator: &mut Validator) {}
+}
+
+schema_object!(RegisterNodeBody {
+    name,
+    labels,
+    capacity,
+});
+
+schema_object!(RegisterNodeResponse {
+    node_id,
+    lease_duration_seconds,
+    heartbeat_timeout_seconds,
+});
+
+schema_object!(NodeHeartbeatBody {
+    running_jobs,
+    #[optional]
+    job_ids,
+});
+
+schema_object!(CancelJobDto {
+    job_id,
+    container_id,
+    grace_period_seconds,
+});
+
+schema_object!(NodeHeartbeatResponse { cancel_jobs });
+
+schema_object!(ListNodesQuery { node_id, name });
+
+schema_object!(NodeDto {
+    id,
+    name,
+    labels,
+    capacity,
+    running_jobs,
+    status,
+    drained_at,
+    registered_at,
+    last_heartbeat_at,
+});
+
+schema_object!(ListNodesResponse { nodes });
+
+schema_enum!(NodeStatus { Active, Draining });
@@ -0,0 +1,52 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use sea_orm::{FromJsonQueryResult, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+
+/*
+원격 agent가 등록한 실행 노드입니다. agent는 주기적으로 heartbeat를 보내며, capacity만큼 job을 가져가 실행합니다.
+*/
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "node")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64, // primary key
+    #[sea_orm(unique)]
+    pub name: String, // agent name (worker id of the agent, unique)
+    pub labels: NodeLabels, // labels advertised by the agent (example: gpu=true)
+    pub capacity: i32,      // max number of jobs the agent runs at the same time
+    pub running_jobs: i32,  // number of jobs the agent reported in the last heartbeat
+    pub status: NodeStatus, // whether the node accepts new jobs
+    pub drained_at: Option<chrono::DateTime<Utc>>, // when the node was drained after missing heartbeats
+
+    pub registered_at: chrono::DateTime<Utc>,
+    pub last_heartbeat_at: chrono::DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/*
+node 상태입니다.
+- Active: heartbeat를 보내고 있으며 새 job을 배정받음
+- Draining: heartbeat가 끊겨 새 job을 배정받지 않음. 다시 heartbeat를 보내면 Active로 돌아옴
+*/
+#[derive(EnumIter, DeriveActiveEnum, Clone, Copy, Debug, PartialEq, Eq, Default, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+pub enum NodeStatus {
+    #[sea_orm(string_value = "Active")]
+    #[default]
+    Active,
+    #[sea_orm(string_value = "Draining")]
+    Draining,
+}
+
+/*
+node 라벨 목록입니다. DB에는 JSON 객체로 저장됩니다.
+*/
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct NodeLabels(pub BTreeMap<String, String>);
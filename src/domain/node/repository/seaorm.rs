@@ -0,0 +1,139 @@
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+};
+
+use crate::{
+    domain::node::{
+        NodeRepository,
+        dao::{DrainNodeParams, ListNodesParams, RecordNodeHeartbeatParams, RegisterNodeParams},
+        entities::{self, node::NodeStatus},
+    },
+    errors,
+};
+
+pub struct NodeSeaOrmRepository {
+    pub connection: sea_orm::DatabaseConnection,
+}
+
+impl NodeSeaOrmRepository {
+    pub fn new(connection: sea_orm::DatabaseConnection) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait::async_trait]
+impl NodeRepository for NodeSeaOrmRepository {
+    async fn list_nodes(
+        &self,
+        params: ListNodesParams,
+    ) -> errors::Result<Vec<entities::node::Model>> {
+        let mut query = entities::node::Entity::find();
+
+        if !params.node_ids.is_empty() {
+            query = query.filter(entities::node::Column::Id.is_in(params.node_ids));
+        }
+
+        if !params.names.is_empty() {
+            query = query.filter(entities::node::Column::Name.is_in(params.names));
+        }
+
+        if !params.statuses.is_empty() {
+            query = query.filter(entities::node::Column::Status.is_in(params.statuses));
+        }
+
+        if let Some(last_heartbeat_before) = params.last_heartbeat_before {
+            query = query.filter(entities::node::Column::LastHeartbeatAt.lt(last_heartbeat_before));
+        }
+
+        let nodes = query
+            .order_by_asc(entities::node::Column::Name)
+            .all(&self.connection)
+            .await?;
+
+        Ok(nodes)
+    }
+
+    async fn register_node(&self, params: RegisterNodeParams) -> errors::Result<i64> {
+        let now = chrono::Utc::now();
+
+        let existing = entities::node::Entity::find()
+            .filter(entities::node::Column::Name.eq(params.name.clone()))
+            .one(&self.connection)
+            .await?;
+
+        // agent가 재시작한 경우 같은 node를 재사용
+        if let Some(node) = existing {
+            let node_id = node.id;
+            let mut model = node.into_active_model();
+
+            model.labels = Set(params.labels);
+            model.capacity = Set(params.capacity);
+            model.last_heartbeat_at = Set(now);
+            model.status = Set(NodeStatus::Active);
+            model.drained_at = Set(None);
+
+            model.update(&self.connection).await?;
+
+            return Ok(node_id);
+        }
+
+        let new_node = entities::node::ActiveModel {
+            id: NotSet,
+            name: Set(params.name),
+            labels: Set(params.labels),
+            capacity: Set(params.capacity),
+            running_jobs: Set(0),
+            status: Set(NodeStatus::Active),
+            drained_at: Set(None),
+            registered_at: Set(now),
+            last_heartbeat_at: Set(now),
+        };
+
+        let saved = new_node.insert(&self.connection).await?;
+
+        Ok(saved.id)
+    }
+
+    async fn record_node_heartbeat(&self, params: RecordNodeHeartbeatParams) -> errors::Result<()> {
+        let model = entities::node::ActiveModel {
+            running_jobs: Set(params.running_jobs),
+            last_heartbeat_at: Set(chrono::Utc::now()),
+            status: Set(NodeStatus::Active),
+            drained_at: Set(None),
+            ..Default::default()
+        };
+
+        let result = entities::node::Entity::update_many()
+            .set(model)
+            .filter(entities::node::Column::Id.eq(params.node_id))
+            .exec(&self.connection)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(errors::Error::NodeNotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn drain_node(&self, params: DrainNodeParams) -> errors::Result<bool> {
+        let model = entities::node::ActiveModel {
+            status: Set(NodeStatus::Draining),
+            drained_at: Set(Some(chrono::Utc::now())),
+            ..Default::default()
+        };
+
+        // 조회 이후 heartbeat가 도착했다면 drain하지 않음
+        let result = entities::node::Entity::update_many()
+            .set(model)
+            .filter(entities::node::Column::Id.eq(params.node_id))
+            .filter(entities::node::Column::Status.eq(NodeStatus::Active))
+            .filter(entities::node::Column::LastHeartbeatAt.lt(params.last_heartbeat_before))
+            .exec(&self.connection)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -0,0 +1,174 @@
+use axum::{
+    Extension, Json,
+    extract::FromRequestParts,
+    http::{header, request::Parts},
+};
+
+use crate::{
+    context::SharedContext,
+    domain::{
+        job::dto::{
+            AppendNodeJobLogsBody, AppendNodeJobLogsRequest, AppendNodeJobLogsResponse,
+            ClaimNodeJobsBody, ClaimNodeJobsRequest, ClaimNodeJobsResponse, ReportNodeJobBody,
+            ReportNodeJobRequest,
+        },
+        node::dto::{
+            ListNodesQuery, ListNodesRequest, ListNodesResponse, NodeHeartbeatBody,
+            NodeHeartbeatRequest, NodeHeartbeatResponse, RegisterNodeBody, RegisterNodeRequest,
+            RegisterNodeResponse,
+        },
+    },
+    errors,
+    openapi::{Operation, router::OperationInput},
+    validation::{ValidJson, ValidPath, ValidQuery},
+};
+
+/*
+agent 요청의 Bearer 토큰을 확인하는 extractor입니다.
+claim 응답에는 복호화한 secret과 registry 비밀번호가 들어가므로, node.agent_token이 설정되지 않았다면 agent API를 모두 거부합니다.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct AgentAuth;
+
+impl<S: Send + Sync> FromRequestParts<S> for AgentAuth {
+    type Rejection = errors::Error;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let context = parts
+            .extensions
+            .get::<SharedContext>()
+            .ok_or(errors::Error::AgentTokenNotConfigured)?;
+
+        let Some(agent_token) = &context.config.node.agent_token else {
+            return Err(errors::Error::AgentTokenNotConfigured);
+        };
+
+        let bearer_token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        match bearer_token {
+            Some(bearer_token) if constant_time_eq(bearer_token, agent_token) => Ok(AgentAuth),
+            _ => Err(errors::Error::AgentUnauthorized),
+        }
+    }
+}
+
+impl OperationInput for AgentAuth {
+    fn document(operation: &mut Operation) {
+        operation.agent_auth = true;
+    }
+}
+
+// 비교 시간으로 토큰을 추측할 수 없도록 길이가 같으면 모든 바이트를 비교
+fn constant_time_eq(left: &str, right: &str) -> bool {
+    left.len() == right.len()
+        && left
+            .bytes()
+            .zip(right.bytes())
+            .fold(0, |difference, (left, right)| difference | (left ^ right))
+            == 0
+}
+
+pub async fn list_nodes(
+    ValidQuery(query): ValidQuery<ListNodesQuery>,
+    Extension(context): Extension<SharedContext>,
+) -> errors::Result<Json<ListNodesResponse>> {
+    let response = context
+        .node_service
+        .list_nodes(ListNodesRequest { query })
+        .await?;
+
+    Ok(Json(response))
+}
+
+pub async fn register_node(
+    _: AgentAuth,
+    Extension(context): Extension<SharedContext>,
+    ValidJson(body): ValidJson<RegisterNodeBody>,
+) -> errors::Result<Json<RegisterNodeResponse>> {
+    let response = context
+        .node_service
+        .register_node(RegisterNodeRequest {
+            request_body: body,
+            lease_duration: context.config.runner.lease_duration(),
+            heartbeat_timeout: context.config.node.heartbeat_timeout(),
+        })
+        .await?;
+
+    Ok(Json(response))
+}
+
+pub async fn heartbeat_node(
+    ValidPath(node_id): ValidPath<i64>,
+    _: AgentAuth,
+    Extension(context): Extension<SharedContext>,
+    ValidJson(body): ValidJson<NodeHeartbeatBody>,
+) -> errors::Result<Json<NodeHeartbeatResponse>> {
+    let response = context
+        .node_service
+        .heartbeat_node(NodeHeartbeatRequest {
+            node_id,
+            lease_duration: context.config.runner.lease_duration(),
+            request_body: body,
+        })
+        .await?;
+
+    Ok(Json(response))
+}
+
+pub async fn claim_node_jobs(
+    ValidPath(node_id): ValidPath<i64>,
+    _: AgentAuth,
+    Extension(context): Extension<SharedContext>,
+    ValidJson(body): ValidJson<ClaimNodeJobsBody>,
+) -> errors::Result<Json<ClaimNodeJobsResponse>> {
+    let response = context
+        .job_service
+        .claim_node_jobs(ClaimNodeJobsRequest {
+            node_id,
+            lease_duration: context.config.runner.lease_duration(),
+            request_body: body,
+        })
+        .await?;
+
+    Ok(Json(response))
+}
+
+pub async fn report_node_job(
+    ValidPath((node_id, job_id)): ValidPath<(i64, i64)>,
+    _: AgentAuth,
+    Extension(context): Extension<SharedContext>,
+    ValidJson(body): ValidJson<ReportNodeJobBody>,
+) -> errors::Result<Json<()>> {
+    context
+        .job_service
+        .report_node_job(ReportNodeJobRequest {
+            node_id,
+            job_id,
+            request_body: body,
+        })
+        .await?;
+
+    Ok(Json(()))
+}
+
+pub async fn append_node_job_logs(
+    ValidPath((node_id, job_id)): ValidPath<(i64, i64)>,
+    _: AgentAuth,
+    Extension(context): Extension<SharedContext>,
+    ValidJson(body): ValidJson<AppendNodeJobLogsBody>,
+) -> errors::Result<Json<AppendNodeJobLogsResponse>> {
+    let response = context
+        .job_service
+        .append_node_job_logs(AppendNodeJobLogsRequest {
+            node_id,
+            job_id,
+            request_body: body,
+        })
+        .await?;
+
+    Ok(Json(response))
+}
@@ -0,0 +1,29 @@
+use crate::openapi::router::ApiRouter;
+
+use super::http;
+
+pub fn router() -> ApiRouter {
+    ApiRouter::new("nodes")
+        .get("/nodes", "List agent nodes", http::list_nodes)
+        .post("/nodes/register", "Register an agent", http::register_node)
+        .post(
+            "/nodes/{node_id}/heartbeat",
+            "Agent heartbeat",
+            http::heartbeat_node,
+        )
+        .post(
+            "/nodes/{node_id}/claim",
+            "Claim pending jobs for an agent",
+            http::claim_node_jobs,
+        )
+        .post(
+            "/nodes/{node_id}/jobs/{job_id}/report",
+            "Report the status of a job running on an agent",
+            http::report_node_job,
+        )
+        .post(
+            "/nodes/{node_id}/jobs/{job_id}/logs",
+            "Upload log lines of a job running on an agent",
+            http::append_node_job_logs,
+        )
+}
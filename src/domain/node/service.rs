@@ -0,0 +1,641 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        job::{
+            JobRepository,
+            dao::{ListJobsParams, PatchJobParams, RenewJobLeaseParams, TransitionJobParams},
+            entities::job::{self, ExitReason, JobStatus},
+            service::DEFAULT_STOP_GRACE_PERIOD_SECONDS,
+        },
+        task_definition::{TaskDefinitionRepository, dao::ListTaskDefinitionsParams},
+    },
+    errors,
+};
+
+use super::{
+    NodeRepository, NodeService,
+    dao::{DrainNodeParams, ListNodesParams, RecordNodeHeartbeatParams, RegisterNodeParams},
+    dto::{
+        CancelJobDto, ListNodesRequest, ListNodesResponse, NodeDto, NodeHeartbeatRequest,
+        NodeHeartbeatResponse, RegisterNodeRequest, RegisterNodeResponse,
+    },
+    entities::node::{self, NodeLabels, NodeStatus},
+};
+
+pub struct NodeServiceImpl {
+    pub node_repository: Arc<dyn NodeRepository + Send + Sync>,
+    pub job_repository: Arc<dyn JobRepository + Send + Sync>,
+    pub task_definition_repository: Arc<dyn TaskDefinitionRepository + Send + Sync>,
+}
+
+impl NodeServiceImpl {
+    pub fn new(
+        node_repository: Arc<dyn NodeRepository + Send + Sync>,
+        job_repository: Arc<dyn JobRepository + Send + Sync>,
+        task_definition_repository: Arc<dyn TaskDefinitionRepository + Send + Sync>,
+    ) -> Self {
+        Self {
+            node_repository,
+            job_repository,
+            task_definition_repository,
+        }
+    }
+
+    /*
+    heartbeat가 끊긴 node에 배정된 job을 정리합니다.
+    - Starting/Pulling: 컨테이너가 아직 실행되지 않았으므로 lease를 만료시켜 다른 worker가 다시 가져가도록 함
+    - Running: 컨테이너 상태를 알 수 없으므로 Failed 처리
+    - Cancelling: 취소 요청을 마무리할 수 없으므로 Cancelled 처리
+    */
+    async fn release_node_jobs(&self, node: &node::Model) -> errors::Result<()> {
+        let now = chrono::Utc::now();
+        let error = errors::Error::NodeLost(node.name.clone());
+
+        let jobs = self
+            .job_repository
+            .list_jobs(ListJobsParams {
+                node_ids: vec![node.id],
+                statuses: vec![
+                    JobStatus::Starting,
+                    JobStatus::Pulling,
+                    JobStatus::Running,
+                    JobStatus::Cancelling,
+                ],
+                ..Default::default()
+            })
+            .await?;
+
+        for job in jobs {
+            let transition = match job.status {
+                JobStatus::Starting | JobStatus::Pulling => {
+                    self.job_repository
+                        .renew_job_lease(RenewJobLeaseParams {
+                            job_id: job.id,
+                            worker_id: node.name.clone(),
+                            lease_expires_at: now,
+                        })
+                        .await?;
+
+                    log::warn!("Re-queued job {} of lost node {}", job.id, node.name);
+                    continue;
+                }
+                JobStatus::Running => TransitionJobParams {
+                    to_status: JobStatus::Failed,
+                    reason: Some(error.to_string()),
+                    patch: PatchJobParams {
+                        job_id: job.id,
+                        exit_reason: Some(ExitReason::RuntimeError),
+                        finished_at: Some(now),
+                        error_code: Some(error.error_code()),
+                        error_message: Some(error.to_string()),
+                        ..Default::default()
+                    },
+                },
+                JobStatus::Cancelling => TransitionJobParams {
+                    to_status: JobStatus::Cancelled,
+                    reason: Some(error.to_string()),
+                    patch: PatchJobParams {
+                        job_id: job.id,
+                        exit_reason: Some(ExitReason::KilledByUser),
+                        finished_at: Some(now),
+                        ..Default::default()
+                    },
+                },
+                _ => continue,
+            };
+
+            // 그 사이에 agent가 종료를 보고했다면 건너뜀
+            match self.job_repository.transition_job(transition).await {
+                Ok(()) => log::warn!("Released job {} of lost node {}", job.id, node.name),
+                Err(errors::Error::InvalidJobStatusTransition(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(())
+    }
+
+    /*
+    agent가 실행 중이라고 보고한 job 중 더 이상 이 node에 배정되어 있지 않은 job을 반환합니다.
+    node가 drain된 동안 다른 worker가 가져갔거나 종료 처리된 job의 컨테이너는 agent가 정리해야 합니다.
+    */
+    async fn list_unassigned_jobs(
+        &self,
+        node_id: i64,
+        job_ids: Vec<i64>,
+    ) -> errors::Result<Vec<job::Model>> {
+        if job_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let jobs = self
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids,
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(jobs
+            .into_iter()
+            .filter(|job| job.node_id != Some(node_id) || job.status.is_terminal())
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl NodeService for NodeServiceImpl {
+    async fn register_node(
+        &self,
+        request: RegisterNodeRequest,
+    ) -> errors::Result<RegisterNodeResponse> {
+        let body = request.request_body;
+
+        let name = body.name.trim().to_string();
+
+        if name.is_empty() {
+            return Err(errors::Error::InvalidNode(
+                "name must not be empty".to_string(),
+            ));
+        }
+
+        if body.capacity < 1 {
+            return Err(errors::Error::InvalidNode(format!(
+                "capacity must be at least 1 (got {})",
+                body.capacity
+            )));
+        }
+
+        let node_id = self
+            .node_repository
+            .register_node(RegisterNodeParams {
+                name,
+                labels: NodeLabels(body.labels),
+                capacity: body.capacity,
+            })
+            .await?;
+
+        // agent는 heartbeat 주기가 이 값들보다 짧은지 확인함
+        Ok(RegisterNodeResponse {
+            node_id,
+            lease_duration_seconds: request.lease_duration.num_seconds() as u64,
+            heartbeat_timeout_seconds: request.heartbeat_timeout.num_seconds() as u64,
+        })
+    }
+
+    async fn heartbeat_node(
+        &self,
+        request: NodeHeartbeatRequest,
+    ) -> errors::Result<NodeHeartbeatResponse> {
+        let node_id = request.node_id;
+        let body = request.request_body;
+
+        let node = self
+            .node_repository
+            .list_nodes(ListNodesParams {
+                node_ids: vec![node_id],
+                ..Default::default()
+            })
+            .await?
+            .pop()
+            .ok_or(errors::Error::NodeNotFound)?;
+
+        // drain된 node도 heartbeat를 다시 보내면 Active로 돌아옴
+        self.node_repository
+            .record_node_heartbeat(RecordNodeHeartbeatParams {
+                node_id,
+                running_jobs: body.running_jobs,
+            })
+            .await?;
+
+        if node.status == NodeStatus::Draining {
+            log::info!(
+                "Node {} is sending heartbeats again, reactivating",
+                node.name
+            );
+        }
+
+        let jobs = self
+            .job_repository
+            .list_jobs(ListJobsParams {
+                node_ids: vec![node_id],
+                statuses: vec![
+                    JobStatus::Starting,
+                    JobStatus::Pulling,
+                    JobStatus::Cancelling,
+                ],
+                ..Default::default()
+            })
+            .await?;
+
+        // 1. agent가 살아있는 동안은 실행 준비 중인 job을 다른 worker가 가져가지 않도록 lease 연장
+        let lease_expires_at = chrono::Utc::now() + request.lease_duration;
+
+        for job in jobs.iter() {
+            self.job_repository
+                .renew_job_lease(RenewJobLeaseParams {
+                    job_id: job.id,
+                    worker_id: node.name.clone(),
+                    lease_expires_at,
+                })
+                .await?;
+        }
+
+        // 2. 취소 요청된 job과 더 이상 이 node에 배정되지 않은 job은 agent가 컨테이너를 종료하도록 전달
+        let mut cancelling_jobs = jobs
+            .into_iter()
+            .filter(|job| job.status == JobStatus::Cancelling)
+            .collect::<Vec<_>>();
+
+        cancelling_jobs.extend(self.list_unassigned_jobs(node_id, body.job_ids).await?);
+
+        if cancelling_jobs.is_empty() {
+            return Ok(NodeHeartbeatResponse {
+                cancel_jobs: vec![],
+            });
+        }
+
+        let task_definitions = self
+            .task_definition_repository
+            .list_task_definitions(ListTaskDefinitionsParams {
+                task_definition_ids: cancelling_jobs
+                    .iter()
+                    .map(|job| job.task_definition_id)
+                    .collect(),
+                ..Default::default()
+            })
+            .await?;
+
+        let cancel_jobs = cancelling_jobs
+            .into_iter()
+            .map(|job| {
+                let grace_period_seconds = task_definitions
+                    .iter()
+                    .find(|task_definition| task_definition.id == job.task_definition_id)
+                    .and_then(|task_definition| task_definition.stop_grace_period_seconds)
+                    .map(|seconds| seconds as u32)
+                    .unwrap_or(DEFAULT_STOP_GRACE_PERIOD_SECONDS);
+
+                CancelJobDto {
+                    job_id: job.id,
+                    container_id: job.container_id,
+                    grace_period_seconds,
+                }
+            })
+            .collect();
+
+        Ok(NodeHeartbeatResponse { cancel_jobs })
+    }
+
+    async fn list_nodes(&self, request: ListNodesRequest) -> errors::Result<ListNodesResponse> {
+        let nodes = self
+            .node_repository
+            .list_nodes(ListNodesParams {
+                node_ids: request.query.node_id.into_iter().collect(),
+                names: request.query.name.into_iter().collect(),
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(ListNodesResponse {
+            nodes: nodes.into_iter().map(NodeDto::from).collect(),
+        })
+    }
+
+    async fn drain_unresponsive_nodes(
+        &self,
+        heartbeat_timeout: chrono::Duration,
+    ) -> errors::Result<()> {
+        let last_heartbeat_before = chrono::Utc::now() - heartbeat_timeout;
+
+        let nodes = self
+            .node_repository
+            .list_nodes(ListNodesParams {
+                statuses: vec![NodeStatus::Active],
+                last_heartbeat_before: Some(last_heartbeat_before),
+                ..Default::default()
+            })
+            .await?;
+
+        for node in nodes {
+            let drained = self
+                .node_repository
+                .drain_node(DrainNodeParams {
+                    node_id: node.id,
+                    last_heartbeat_before,
+                })
+                .await?;
+
+            if !drained {
+                continue;
+            }
+
+            log::warn!(
+                "Node {} missed heartbeats since {}, draining",
+                node.name,
+                node.last_heartbeat_at
+            );
+
+            self.release_node_jobs(&node).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{
+        config::Config,
+        context::{Context, SharedContext},
+        domain::{
+            container::repository::fake::FakeContainerRepository,
+            job::{
+                dao::CreateJobParams,
+                dto::{ClaimNodeJobsBody, ClaimNodeJobsRequest},
+            },
+            node::dto::{NodeHeartbeatBody, RegisterNodeBody},
+            task_definition::dto::CreateDefinitionRequest,
+        },
+    };
+
+    async fn register_node(
+        context: &SharedContext,
+        name: &str,
+        labels: &[(&str, &str)],
+        capacity: i32,
+    ) -> i64 {
+        context
+            .node_service
+            .register_node(RegisterNodeRequest {
+                request_body: RegisterNodeBody {
+                    name: name.to_string(),
+                    labels: labels
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect(),
+                    capacity,
+                },
+                lease_duration: chrono::Duration::seconds(60),
+                heartbeat_timeout: chrono::Duration::seconds(30),
+            })
+            .await
+            .unwrap()
+            .node_id
+    }
+
+    async fn get_node(context: &SharedContext, node_id: i64) -> node::Model {
+        context
+            .node_repository
+            .list_nodes(ListNodesParams {
+                node_ids: vec![node_id],
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .pop()
+            .unwrap()
+    }
+
+    async fn get_job(context: &SharedContext, job_id: i64) -> job::Model {
+        context
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: vec![job_id],
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .pop()
+            .unwrap()
+    }
+
+    // Pending job을 만들고 node가 가져가도록 함
+    async fn create_node_jobs(context: &SharedContext, node_id: i64, count: usize) -> Vec<i64> {
+        let task_definition_id = context
+            .task_definition_service
+            .create_task_definition(CreateDefinitionRequest {
+                request_body: serde_json::from_value(serde_json::json!({
+                    "name": "task",
+                    "description": "task",
+                    "image": "alpine",
+                }))
+                .unwrap(),
+            })
+            .await
+            .unwrap()
+            .task_definition_id;
+
+        for _ in 0..count {
+            context
+                .job_repository
+                .create_job(CreateJobParams {
+                    name: "job".to_string(),
+                    task_definition_id,
+                    status: JobStatus::Pending,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+
+        context
+            .job_service
+            .claim_node_jobs(ClaimNodeJobsRequest {
+                node_id,
+                lease_duration: chrono::Duration::seconds(60),
+                request_body: ClaimNodeJobsBody {
+                    limit: count as u64,
+                },
+            })
+            .await
+            .unwrap()
+            .jobs
+            .iter()
+            .map(|assignment| assignment.job_id)
+            .collect()
+    }
+
+    async fn transition(context: &SharedContext, job_id: i64, to_status: JobStatus) {
+        context
+            .job_repository
+            .transition_job(TransitionJobParams {
+                to_status,
+                reason: None,
+                patch: PatchJobParams {
+                    job_id,
+                    ..Default::default()
+                },
+            })
+            .await
+            .unwrap();
+    }
+
+    /*
+    heartbeat가 끊긴 node만 drain하고, 그 node의 job을 상태에 따라 다시 대기시키거나 실패/취소 처리하는지 확인합니다.
+    다시 대기시킨 job은 다른 worker가 가져갈 수 있고, drain된 node에는 새 job을 배정하지 않습니다.
+    */
+    #[tokio::test]
+    async fn test_drain_unresponsive_nodes_releases_jobs() {
+        let context =
+            Context::for_test(Config::default(), Arc::new(FakeContainerRepository::new())).await;
+        let heartbeat_timeout = chrono::Duration::milliseconds(200);
+
+        let lost_node_id = register_node(&context, "node-a", &[], 5).await;
+        let live_node_id = register_node(&context, "node-b", &[], 5).await;
+
+        let job_ids = create_node_jobs(&context, lost_node_id, 3).await;
+        let (starting_job_id, running_job_id, cancelling_job_id) =
+            (job_ids[0], job_ids[1], job_ids[2]);
+        transition(&context, running_job_id, JobStatus::Running).await;
+        transition(&context, cancelling_job_id, JobStatus::Running).await;
+        transition(&context, cancelling_job_id, JobStatus::Cancelling).await;
+
+        // node-b만 timeout 안에 heartbeat를 보냄
+        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+        context
+            .node_service
+            .heartbeat_node(NodeHeartbeatRequest {
+                node_id: live_node_id,
+                lease_duration: chrono::Duration::seconds(60),
+                request_body: NodeHeartbeatBody {
+                    running_jobs: 0,
+                    job_ids: vec![],
+                },
+            })
+            .await
+            .unwrap();
+
+        context
+            .node_service
+            .drain_unresponsive_nodes(heartbeat_timeout)
+            .await
+            .unwrap();
+
+        let lost_node = get_node(&context, lost_node_id).await;
+        assert_eq!(lost_node.status, NodeStatus::Draining);
+        assert!(lost_node.drained_at.is_some());
+        assert_eq!(
+            get_node(&context, live_node_id).await.status,
+            NodeStatus::Active
+        );
+
+        // 1. Running job은 컨테이너 상태를 알 수 없으므로 실패, Cancelling job은 취소로 마무리
+        let running_job = get_job(&context, running_job_id).await;
+        assert_eq!(running_job.status, JobStatus::Failed);
+        assert_eq!(running_job.error_code.as_deref(), Some("NODE_LOST"));
+        assert!(running_job.finished_at.is_some());
+
+        let cancelling_job = get_job(&context, cancelling_job_id).await;
+        assert_eq!(cancelling_job.status, JobStatus::Cancelled);
+        assert_eq!(cancelling_job.exit_reason, Some(ExitReason::KilledByUser));
+
+        // 2. drain된 node에는 capacity가 남아도 배정하지 않음
+        let claimed = context
+            .job_service
+            .claim_node_jobs(ClaimNodeJobsRequest {
+                node_id: lost_node_id,
+                lease_duration: chrono::Duration::seconds(60),
+                request_body: ClaimNodeJobsBody { limit: 5 },
+            })
+            .await
+            .unwrap();
+        assert!(claimed.jobs.is_empty());
+
+        // 3. 아직 실행되지 않은 job은 다른 node가 다시 가져감
+        let claimed = context
+            .job_service
+            .claim_node_jobs(ClaimNodeJobsRequest {
+                node_id: live_node_id,
+                lease_duration: chrono::Duration::seconds(60),
+                request_body: ClaimNodeJobsBody { limit: 5 },
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            claimed
+                .jobs
+                .iter()
+                .map(|assignment| assignment.job_id)
+                .collect::<Vec<_>>(),
+            vec![starting_job_id]
+        );
+
+        let starting_job = get_job(&context, starting_job_id).await;
+        assert_eq!(starting_job.status, JobStatus::Starting);
+        assert_eq!(starting_job.node_id, Some(live_node_id));
+        assert_eq!(starting_job.worker_id.as_deref(), Some("node-b"));
+    }
+
+    /*
+    재시작한 agent가 같은 이름으로 다시 등록하면 drain된 node를 재사용하여 Active로 되돌리고 라벨과 capacity를 갱신하는지 확인합니다.
+    drain된 동안 다른 worker가 가져간 job은 heartbeat 응답으로 컨테이너를 종료하도록 알려줍니다.
+    */
+    #[tokio::test]
+    async fn test_register_node_after_agent_restart() {
+        let context =
+            Context::for_test(Config::default(), Arc::new(FakeContainerRepository::new())).await;
+
+        let node_id = register_node(&context, "node-a", &[("zone", "a")], 1).await;
+        let job_id = create_node_jobs(&context, node_id, 1).await[0];
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        context
+            .node_service
+            .drain_unresponsive_nodes(chrono::Duration::milliseconds(10))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_node(&context, node_id).await.status,
+            NodeStatus::Draining
+        );
+
+        // drain된 동안 로컬 worker가 job을 가져감
+        let claimed = context
+            .job_service
+            .claim_local_jobs("worker-a", 10, chrono::Duration::seconds(60))
+            .await
+            .unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, job_id);
+        assert_eq!(claimed[0].node_id, None);
+
+        let registered_node_id = register_node(&context, "node-a", &[("zone", "b")], 3).await;
+        assert_eq!(registered_node_id, node_id);
+
+        let node = get_node(&context, node_id).await;
+        assert_eq!(node.status, NodeStatus::Active);
+        assert!(node.drained_at.is_none());
+        assert_eq!(node.capacity, 3);
+        assert_eq!(
+            node.labels.0,
+            BTreeMap::from([("zone".to_string(), "b".to_string())])
+        );
+
+        // 재시작 전에 실행하던 컨테이너를 보고하면 종료 대상으로 돌려줌
+        let response = context
+            .node_service
+            .heartbeat_node(NodeHeartbeatRequest {
+                node_id,
+                lease_duration: chrono::Duration::seconds(60),
+                request_body: NodeHeartbeatBody {
+                    running_jobs: 1,
+                    job_ids: vec![job_id],
+                },
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            response
+                .cancel_jobs
+                .iter()
+                .map(|cancel_job| cancel_job.job_id)
+                .collect::<Vec<_>>(),
+            vec![job_id]
+        );
+    }
+}
@@ -0,0 +1,63 @@
+pub mod dao;
+pub mod dto;
+pub mod entities;
+pub mod repository;
+pub mod routes;
+pub mod service;
+
+use dao::*;
+use dto::{
+    CreateRegistryCredentialRequest, CreateRegistryCredentialResponse,
+    ListRegistryCredentialsRequest, ListRegistryCredentialsResponse,
+    PatchRegistryCredentialRequest,
+};
+
+use crate::{domain::container::dao::RegistryAuth, errors};
+
+#[async_trait::async_trait]
+pub trait RegistryCredentialRepository {
+    async fn list_registry_credentials(
+        &self,
+        params: ListRegistryCredentialsParams,
+    ) -> errors::Result<Vec<entities::registry_credential::Model>>;
+
+    async fn count_registry_credentials(
+        &self,
+        params: ListRegistryCredentialsParams,
+    ) -> errors::Result<u64>;
+
+    async fn create_registry_credential(
+        &self,
+        params: CreateRegistryCredentialParams,
+    ) -> errors::Result<i64>;
+
+    async fn patch_registry_credential(
+        &self,
+        params: PatchRegistryCredentialParams,
+    ) -> errors::Result<()>;
+
+    async fn delete_registry_credential(&self, registry_credential_id: i64) -> errors::Result<()>;
+}
+
+#[async_trait::async_trait]
+pub trait RegistryCredentialService {
+    async fn create_registry_credential(
+        &self,
+        request: CreateRegistryCredentialRequest,
+    ) -> errors::Result<CreateRegistryCredentialResponse>;
+
+    async fn patch_registry_credential(
+        &self,
+        request: PatchRegistryCredentialRequest,
+    ) -> errors::Result<()>;
+
+    async fn delete_registry_credential(&self, registry_credential_id: i64) -> errors::Result<()>;
+
+    async fn list_registry_credentials(
+        &self,
+        request: ListRegistryCredentialsRequest,
+    ) -> errors::Result<ListRegistryCredentialsResponse>;
+
+    /// 이미지가 속한 레지스트리의 인증 정보를 찾아 복호화합니다. 등록된 인증 정보가 없다면 None을 반환합니다.
+    async fn resolve_registry_auth(&self, image: &str) -> errors::Result<Option<RegistryAuth>>;
+}
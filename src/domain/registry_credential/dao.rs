@@ -0,0 +1,21 @@
+#[derive(Debug, Default, Clone)]
+pub struct ListRegistryCredentialsParams {
+    pub registry_credential_ids: Vec<i64>,
+    pub registries: Vec<String>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct CreateRegistryCredentialParams {
+    pub registry: String,           // registry host (example: ghcr.io, docker.io)
+    pub username: String,           // registry username
+    pub encrypted_password: String, // base64(nonce || ciphertext)
+}
+
+#[derive(Debug, Default)]
+pub struct PatchRegistryCredentialParams {
+    pub registry_credential_id: i64,
+    pub username: Option<String>,
+    pub encrypted_password: Option<String>,
+}
@@ -0,0 +1,134 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::entities;
+use crate::{
+    openapi::schema::schema_object,
+    validation::{MAX_NAME_LENGTH, Validate, Validator},
+};
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CreateRegistryCredentialBody {
+    pub registry: String, // registry host (example: ghcr.io, docker.io)
+    pub username: String, // registry username
+    pub password: String, // password or access token (never returned)
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateRegistryCredentialRequest {
+    pub request_body: CreateRegistryCredentialBody,
+}
+
+#[derive(Serialize)]
+pub struct CreateRegistryCredentialResponse {
+    pub registry_credential_id: i64, // primary key of the registry credential
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PatchRegistryCredentialBody {
+    pub username: Option<String>, // registry username
+    pub password: Option<String>, // new password or access token (never returned)
+}
+
+#[derive(Debug, Clone)]
+pub struct PatchRegistryCredentialRequest {
+    pub registry_credential_id: i64,
+    pub request_body: PatchRegistryCredentialBody,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ListRegistryCredentialsQuery {
+    pub registry_credential_id: Option<i64>,
+    pub registry: Option<String>,
+    pub page_number: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListRegistryCredentialsRequest {
+    pub query: ListRegistryCredentialsQuery,
+}
+
+/// 레지스트리 인증 정보 목록 항목입니다. 비밀번호는 절대 포함하지 않습니다.
+#[derive(Serialize)]
+pub struct ListRegistryCredentialsItem {
+    pub id: i64,
+    pub registry: String,
+    pub username: String,
+    pub created_at: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+impl From<entities::registry_credential::Model> for ListRegistryCredentialsItem {
+    fn from(model: entities::registry_credential::Model) -> Self {
+        ListRegistryCredentialsItem {
+            id: model.id,
+            registry: model.registry,
+            username: model.username,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ListRegistryCredentialsResponse {
+    pub registry_credentials: Vec<ListRegistryCredentialsItem>,
+    pub total_count: u64,
+}
+
+impl Validate for CreateRegistryCredentialBody {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .not_blank("registry", &self.registry)
+            .max_length("registry", &self.registry, MAX_NAME_LENGTH)
+            .not_blank("username", &self.username)
+            .not_blank("password", &self.password);
+    }
+}
+
+impl Validate for PatchRegistryCredentialBody {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .optional_not_blank("username", self.username.as_deref())
+            .optional_not_blank("password", self.password.as_deref());
+    }
+}
+
+impl Validate for ListRegistryCredentialsQuery {
+    fn validate(&self, validator: &mut Validator) {
+        validator.pagination(self.page_number, self.page_size);
+    }
+}
+
+schema_object!(CreateRegistryCredentialBody {
+    registry,
+    username,
+    password,
+});
+
+schema_object!(CreateRegistryCredentialResponse {
+    registry_credential_id,
+});
+
+schema_object!(PatchRegistryCredentialBody { username, password });
+
+schema_object!(ListRegistryCredentialsQuery {
+    registry_credential_id,
+    registry,
+    page_number,
+    page_size,
+});
+
+schema_object!(ListRegistryCredentialsItem {
+    id,
+    registry,
+    username,
+    created_at,
+    updated_at,
+});
+
+schema_object!(ListRegistryCredentialsResponse {
+    registry_credentials,
+    total_count,
+});
@@ -0,0 +1,35 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "registry_credential")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64, // primary key
+    #[sea_orm(unique)]
+    pub registry: String, // registry host (example: ghcr.io, docker.io)
+    pub username: String, // registry username
+    #[sea_orm(column_type = "Text")]
+    pub encrypted_password: String, // base64(nonce || ciphertext)
+
+    pub created_at: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+// 암호문이라도 로그에 남지 않도록 encrypted_password는 출력하지 않습니다.
+impl std::fmt::Debug for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Model")
+            .field("id", &self.id)
+            .field("registry", &self.registry)
+            .field("username", &self.username)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .finish()
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
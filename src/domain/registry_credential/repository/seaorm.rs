@@ -0,0 +1,142 @@
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select,
+};
+
+use crate::{
+    domain::registry_credential::{
+        RegistryCredentialRepository,
+        dao::{
+            CreateRegistryCredentialParams, ListRegistryCredentialsParams,
+            PatchRegistryCredentialParams,
+        },
+        entities,
+    },
+    errors,
+};
+
+pub struct RegistryCredentialSeaOrmRepository {
+    pub connection: sea_orm::DatabaseConnection,
+}
+
+impl RegistryCredentialSeaOrmRepository {
+    pub fn new(connection: sea_orm::DatabaseConnection) -> Self {
+        Self { connection }
+    }
+
+    fn build_filter_query(
+        params: &ListRegistryCredentialsParams,
+    ) -> Select<entities::registry_credential::Entity> {
+        let mut query = entities::registry_credential::Entity::find();
+
+        if !params.registry_credential_ids.is_empty() {
+            query = query.filter(
+                entities::registry_credential::Column::Id
+                    .is_in(params.registry_credential_ids.clone()),
+            );
+        }
+
+        if !params.registries.is_empty() {
+            query = query.filter(
+                entities::registry_credential::Column::Registry.is_in(params.registries.clone()),
+            );
+        }
+
+        query
+    }
+}
+
+#[async_trait::async_trait]
+impl RegistryCredentialRepository for RegistryCredentialSeaOrmRepository {
+    async fn list_registry_credentials(
+        &self,
+        params: ListRegistryCredentialsParams,
+    ) -> errors::Result<Vec<entities::registry_credential::Model>> {
+        let mut query = Self::build_filter_query(&params)
+            .order_by_asc(entities::registry_credential::Column::Registry);
+
+        if let Some(limit) = params.limit {
+            query = query.limit(limit);
+        }
+
+        if let Some(offset) = params.offset {
+            query = query.offset(offset);
+        }
+
+        let registry_credentials = query.all(&self.connection).await?;
+
+        Ok(registry_credentials)
+    }
+
+    async fn count_registry_credentials(
+        &self,
+        params: ListRegistryCredentialsParams,
+    ) -> errors::Result<u64> {
+        let count = Self::build_filter_query(&params)
+            .count(&self.connection)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn create_registry_credential(
+        &self,
+        params: CreateRegistryCredentialParams,
+    ) -> errors::Result<i64> {
+        let now = chrono::Utc::now();
+
+        let new_registry_credential = entities::registry_credential::ActiveModel {
+            id: NotSet,
+            registry: Set(params.registry),
+            username: Set(params.username),
+            encrypted_password: Set(params.encrypted_password),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+
+        let saved = new_registry_credential.insert(&self.connection).await?;
+
+        Ok(saved.id)
+    }
+
+    async fn patch_registry_credential(
+        &self,
+        params: PatchRegistryCredentialParams,
+    ) -> errors::Result<()> {
+        let registry_credential =
+            entities::registry_credential::Entity::find_by_id(params.registry_credential_id)
+                .one(&self.connection)
+                .await?
+                .ok_or_else(|| errors::Error::RegistryCredentialNotFound)?;
+
+        let mut model = registry_credential.into_active_model();
+
+        if let Some(username) = params.username {
+            model.username = Set(username);
+        }
+
+        if let Some(encrypted_password) = params.encrypted_password {
+            model.encrypted_password = Set(encrypted_password);
+        }
+
+        model.updated_at = Set(chrono::Utc::now());
+
+        model.update(&self.connection).await?;
+
+        Ok(())
+    }
+
+    async fn delete_registry_credential(&self, registry_credential_id: i64) -> errors::Result<()> {
+        let result = entities::registry_credential::Entity::delete_by_id(registry_credential_id)
+            .exec(&self.connection)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(errors::Error::RegistryCredentialNotFound);
+        }
+
+        Ok(())
+    }
+}
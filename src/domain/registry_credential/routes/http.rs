@@ -0,0 +1,65 @@
+use axum::{Extension, Json};
+
+use crate::{
+    context::SharedContext,
+    domain::registry_credential::dto::{
+        CreateRegistryCredentialBody, CreateRegistryCredentialRequest,
+        CreateRegistryCredentialResponse, ListRegistryCredentialsQuery,
+        ListRegistryCredentialsRequest, ListRegistryCredentialsResponse,
+        PatchRegistryCredentialBody, PatchRegistryCredentialRequest,
+    },
+    errors,
+    validation::{ValidJson, ValidPath, ValidQuery},
+};
+
+pub async fn list_registry_credentials(
+    ValidQuery(query): ValidQuery<ListRegistryCredentialsQuery>,
+    Extension(context): Extension<SharedContext>,
+) -> errors::Result<Json<ListRegistryCredentialsResponse>> {
+    let response = context
+        .registry_credential_service
+        .list_registry_credentials(ListRegistryCredentialsRequest { query })
+        .await?;
+
+    Ok(Json(response))
+}
+
+pub async fn create_registry_credential(
+    Extension(context): Extension<SharedContext>,
+    ValidJson(body): ValidJson<CreateRegistryCredentialBody>,
+) -> errors::Result<Json<CreateRegistryCredentialResponse>> {
+    let response = context
+        .registry_credential_service
+        .create_registry_credential(CreateRegistryCredentialRequest { request_body: body })
+        .await?;
+
+    Ok(Json(response))
+}
+
+pub async fn patch_registry_credential(
+    ValidPath(registry_credential_id): ValidPath<i64>,
+    Extension(context): Extension<SharedContext>,
+    ValidJson(body): ValidJson<PatchRegistryCredentialBody>,
+) -> errors::Result<Json<()>> {
+    context
+        .registry_credential_service
+        .patch_registry_credential(PatchRegistryCredentialRequest {
+            registry_credential_id,
+            request_body: body,
+        })
+        .await?;
+
+    Ok(Json(()))
+}
+
+pub async fn delete_registry_credential(
+    ValidPath(registry_credential_id): ValidPath<i64>,
+    Extension(context): Extension<SharedContext>,
+) -> errors::Result<Json<()>> {
+    context
+        .registry_credential_service
+        .delete_registry_credential(registry_credential_id)
+        .await?;
+
+    Ok(Json(()))
+}
@@ -0,0 +1,27 @@
+use crate::openapi::router::ApiRouter;
+
+use super::http;
+
+pub fn router() -> ApiRouter {
+    ApiRouter::new("registry-credentials")
+        .get(
+            "/registry-credentials",
+            "List registry credentials (passwords are never returned)",
+            http::list_registry_credentials,
+        )
+        .post(
+            "/registry-credentials",
+            "Create a registry credential",
+            http::create_registry_credential,
+        )
+        .patch(
+            "/registry-credentials/{registry_credential_id}",
+            "Update a registry credential",
+            http::patch_registry_credential,
+        )
+        .delete(
+            "/registry-credentials/{registry_credential_id}",
+            "Delete a registry credential",
+            http::delete_registry_credential,
+        )
+}
@@ -0,0 +1,262 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{container::dao::RegistryAuth, secret::cipher::SecretCipher},
+    errors,
+};
+
+use super::{
+    RegistryCredentialRepository, RegistryCredentialService,
+    dao::{
+        CreateRegistryCredentialParams, ListRegistryCredentialsParams,
+        PatchRegistryCredentialParams,
+    },
+    dto::{
+        CreateRegistryCredentialRequest, CreateRegistryCredentialResponse,
+        ListRegistryCredentialsItem, ListRegistryCredentialsRequest,
+        ListRegistryCredentialsResponse, PatchRegistryCredentialRequest,
+    },
+};
+
+/// 레지스트리가 생략된 이미지(예: alpine, library/ubuntu)가 사용하는 기본 레지스트리
+pub const DEFAULT_REGISTRY: &str = "docker.io";
+
+pub struct RegistryCredentialServiceImpl {
+    pub registry_credential_repository: Arc<dyn RegistryCredentialRepository + Send + Sync>,
+    pub secret_cipher: Option<SecretCipher>,
+}
+
+impl RegistryCredentialServiceImpl {
+    pub fn new(
+        registry_credential_repository: Arc<dyn RegistryCredentialRepository + Send + Sync>,
+        secret_cipher: Option<SecretCipher>,
+    ) -> Self {
+        Self {
+            registry_credential_repository,
+            secret_cipher,
+        }
+    }
+
+    fn cipher(&self) -> errors::Result<&SecretCipher> {
+        self.secret_cipher
+            .as_ref()
+            .ok_or(errors::Error::SecretKeyNotConfigured)
+    }
+}
+
+#[async_trait::async_trait]
+impl RegistryCredentialService for RegistryCredentialServiceImpl {
+    async fn create_registry_credential(
+        &self,
+        request: CreateRegistryCredentialRequest,
+    ) -> errors::Result<CreateRegistryCredentialResponse> {
+        let body = request.request_body;
+
+        let registry = normalize_registry(&body.registry);
+
+        if registry.is_empty() || registry.contains('/') {
+            return Err(errors::Error::InvalidRegistryCredential(format!(
+                "invalid registry: {:?}",
+                body.registry
+            )));
+        }
+
+        if body.username.trim().is_empty() {
+            return Err(errors::Error::InvalidRegistryCredential(
+                "username must not be empty".to_string(),
+            ));
+        }
+
+        let cipher = self.cipher()?;
+
+        let existing = self
+            .registry_credential_repository
+            .count_registry_credentials(ListRegistryCredentialsParams {
+                registries: vec![registry.clone()],
+                ..Default::default()
+            })
+            .await?;
+
+        if existing > 0 {
+            return Err(errors::Error::RegistryCredentialAlreadyExists);
+        }
+
+        let registry_credential_id = self
+            .registry_credential_repository
+            .create_registry_credential(CreateRegistryCredentialParams {
+                registry,
+                username: body.username,
+                encrypted_password: cipher.encrypt(&body.password)?,
+            })
+            .await?;
+
+        Ok(CreateRegistryCredentialResponse {
+            registry_credential_id,
+        })
+    }
+
+    async fn patch_registry_credential(
+        &self,
+        request: PatchRegistryCredentialRequest,
+    ) -> errors::Result<()> {
+        if let Some(username) = &request.request_body.username
+            && username.trim().is_empty()
+        {
+            return Err(errors::Error::InvalidRegistryCredential(
+                "username must not be empty".to_string(),
+            ));
+        }
+
+        let encrypted_password = match &request.request_body.password {
+            Some(password) => Some(self.cipher()?.encrypt(password)?),
+            None => None,
+        };
+
+        self.registry_credential_repository
+            .patch_registry_credential(PatchRegistryCredentialParams {
+                registry_credential_id: request.registry_credential_id,
+                username: request.request_body.username,
+                encrypted_password,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_registry_credential(&self, registry_credential_id: i64) -> errors::Result<()> {
+        self.registry_credential_repository
+            .delete_registry_credential(registry_credential_id)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_registry_credentials(
+        &self,
+        request: ListRegistryCredentialsRequest,
+    ) -> errors::Result<ListRegistryCredentialsResponse> {
+        let page_number = request.query.page_number.unwrap_or(1);
+        let page_size = request.query.page_size.unwrap_or(10);
+        let offset = (page_number - 1) * page_size;
+
+        let params = ListRegistryCredentialsParams {
+            registry_credential_ids: match request.query.registry_credential_id {
+                Some(registry_credential_id) => vec![registry_credential_id],
+                None => vec![],
+            },
+            registries: match &request.query.registry {
+                Some(registry) => vec![normalize_registry(registry)],
+                None => vec![],
+            },
+            ..Default::default()
+        };
+
+        let total_count = self
+            .registry_credential_repository
+            .count_registry_credentials(params.clone())
+            .await?;
+
+        let registry_credentials = self
+            .registry_credential_repository
+            .list_registry_credentials(ListRegistryCredentialsParams {
+                limit: Some(page_size),
+                offset: Some(offset),
+                ..params
+            })
+            .await?;
+
+        Ok(ListRegistryCredentialsResponse {
+            registry_credentials: registry_credentials
+                .into_iter()
+                .map(ListRegistryCredentialsItem::from)
+                .collect(),
+            total_count,
+        })
+    }
+
+    async fn resolve_registry_auth(&self, image: &str) -> errors::Result<Option<RegistryAuth>> {
+        let registry = registry_of_image(image);
+
+        let mut registry_credentials = self
+            .registry_credential_repository
+            .list_registry_credentials(ListRegistryCredentialsParams {
+                registries: vec![registry.clone()],
+                limit: Some(1),
+                ..Default::default()
+            })
+            .await?;
+
+        let Some(registry_credential) = registry_credentials.pop() else {
+            return Ok(None);
+        };
+
+        let password = self
+            .cipher()?
+            .decrypt(&registry_credential.encrypted_password)?;
+
+        Ok(Some(RegistryAuth {
+            registry,
+            username: registry_credential.username,
+            password,
+        }))
+    }
+}
+
+/*
+레지스트리 이름을 비교 가능한 형태로 정규화합니다.
+- 스킴(https://)과 경로(/v1/)를 제거하고 소문자로 변환합니다.
+- Docker Hub의 여러 별칭(index.docker.io, registry-1.docker.io)은 docker.io로 통일합니다.
+*/
+pub fn normalize_registry(registry: &str) -> String {
+    let registry = registry.trim().to_lowercase();
+    let registry = registry
+        .strip_prefix("https://")
+        .or_else(|| registry.strip_prefix("http://"))
+        .unwrap_or(&registry);
+    let registry = registry.split('/').next().unwrap_or_default();
+
+    match registry {
+        "index.docker.io" | "registry-1.docker.io" => DEFAULT_REGISTRY.to_string(),
+        registry => registry.to_string(),
+    }
+}
+
+/*
+이미지 참조에서 레지스트리를 추출합니다.
+docker와 동일하게 첫 번째 경로 요소가 '.' 또는 ':'를 포함하거나 localhost인 경우에만 레지스트리로 취급합니다.
+예: ghcr.io/org/app:1.0 -> ghcr.io, localhost:5000/app -> localhost:5000, alpine:3 -> docker.io
+*/
+pub fn registry_of_image(image: &str) -> String {
+    match image.split_once('/') {
+        Some((host, _)) if host.contains(['.', ':']) || host == "localhost" => {
+            normalize_registry(host)
+        }
+        _ => DEFAULT_REGISTRY.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_of_image() {
+        assert_eq!(registry_of_image("alpine"), "docker.io");
+        assert_eq!(registry_of_image("library/ubuntu:24.04"), "docker.io");
+        assert_eq!(registry_of_image("ghcr.io/org/app:1.0"), "ghcr.io");
+        assert_eq!(registry_of_image("localhost:5000/app"), "localhost:5000");
+        assert_eq!(
+            registry_of_image("index.docker.io/library/alpine"),
+            "docker.io"
+        );
+    }
+
+    #[test]
+    fn test_normalize_registry() {
+        assert_eq!(
+            normalize_registry("https://index.docker.io/v1/"),
+            "docker.io"
+        );
+        assert_eq!(normalize_registry(" GHCR.io "), "ghcr.io");
+    }
+}
@@ -6,6 +6,7 @@ pub struct ListSchedulesParams {
     pub contains_name: Option<String>,
     pub limit: Option<u64>,
     pub offset: Option<u64>,
+    pub after_id: Option<i64>, // only schedules listed after this id (ids are listed in descending order)
 }
 
 #[derive(Debug, Default)]
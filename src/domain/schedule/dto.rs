@@ -1,7 +1,11 @@
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
-use crate::domain::schedule::entities;
+use crate::{
+    domain::schedule::entities,
+    openapi::schema::schema_object,
+    validation::{MAX_NAME_LENGTH, Validate, Validator},
+};
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct CreateScheduleBody {
@@ -46,6 +50,7 @@ pub struct ListSchedulesQuery {
     pub enabled: Option<bool>,         // enabled status
     pub page_number: Option<i64>,      // page number (1-based)
     pub page_size: Option<i64>,        // page size
+    pub after_id: Option<i64>, // cursor: next_cursor of the previous page (page_number is ignored)
 }
 
 #[derive(Debug, Clone)]
@@ -88,4 +93,86 @@ impl From<entities::schedule::Model> for ListSchedulesItem {
 pub struct ListSchedulesResponse {
     pub schedules: Vec<ListSchedulesItem>,
     pub total_count: i64,
+    pub next_cursor: Option<i64>, // after_id of the next page (None: last page)
 }
+
+impl Validate for CreateScheduleBody {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .not_blank("name", &self.name)
+            .max_length("name", &self.name, MAX_NAME_LENGTH)
+            .not_blank("job_name", &self.job_name)
+            .max_length("job_name", &self.job_name, MAX_NAME_LENGTH)
+            .not_blank("cron_expression", &self.cron_expression)
+            .min("task_definition_id", self.task_definition_id, 1);
+    }
+}
+
+impl Validate for PatchScheduleBody {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .optional_not_blank("name", self.name.as_deref())
+            .optional_max_length("name", self.name.as_deref(), MAX_NAME_LENGTH)
+            .optional_not_blank("job_name", self.job_name.as_deref())
+            .optional_max_length("job_name", self.job_name.as_deref(), MAX_NAME_LENGTH)
+            .optional_not_blank("cron_expression", self.cron_expression.as_deref())
+            .optional_min("task_definition_id", self.task_definition_id, 1);
+    }
+}
+
+impl Validate for ListSchedulesQuery {
+    fn validate(&self, validator: &mut Validator) {
+        validator.pagination(self.page_number, self.page_size);
+    }
+}
+
+schema_object!(CreateScheduleBody {
+    name,
+    job_name,
+    cron_expression,
+    task_definition_id,
+    command,
+    timezone,
+    timezone_offset,
+    enabled,
+});
+
+schema_object!(PatchScheduleBody {
+    name,
+    job_name,
+    cron_expression,
+    task_definition_id,
+    command,
+    timezone,
+    timezone_offset,
+    enabled,
+});
+
+schema_object!(ListSchedulesQuery {
+    schedule_id,
+    contains_name,
+    name,
+    enabled,
+    page_number,
+    page_size,
+    after_id,
+});
+
+schema_object!(ListSchedulesItem {
+    id,
+    name,
+    job_name,
+    cron_expression,
+    task_definition_id,
+    command,
+    timezone,
+    timezone_offset,
+    enabled,
+    created_at,
+});
+
+schema_object!(ListSchedulesResponse {
+    schedules,
+    total_count,
+    next_cursor,
+});
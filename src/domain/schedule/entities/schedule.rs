@@ -11,12 +11,13 @@ use crate::{
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i64, // primary key
-    pub name: String,                                     // schedule name
-    pub job_name: String,                                 // job name
-    pub cron_expression: String,                          // cron expression
-    pub task_definition_id: i64,                          // task definition id
-    pub command: Option<String>,                          // docker run command
-    pub timezone: Option<String>,                         // timezone text (example: "Asia/Seoul")
+    pub name: String,            // schedule name
+    pub job_name: String,        // job name
+    pub cron_expression: String, // cron expression
+    pub task_definition_id: i64, // task definition id
+    #[sea_orm(column_type = "Text", nullable)]
+    pub command: Option<String>, // docker run command
+    pub timezone: Option<String>, // timezone text (example: "Asia/Seoul")
     pub timezone_offset: Option<i32>, // timezone offset (in minutes) (example: 540=9:00 for "Asia/Seoul")
     pub enabled: bool,                // schedule enabled status
     pub created_at: chrono::DateTime<Utc>, // job submited time
@@ -187,17 +188,16 @@ impl ScheduleWithStates {
 
         // 8. 동일 시간에 2번 이상 트리거되지 않도록 last_triggered_at 기반으로 검증
         match self.model.last_triggered_at {
-            Some(last_triggered_at) => {
-                // 현재 시간과 last_triggered_at이 동일한 경우, 트리거하지 않음 (분 단위로 체크)
+            // 현재 시간과 last_triggered_at이 동일한 경우, 트리거하지 않음 (분 단위로 체크)
+            Some(last_triggered_at)
                 if now.year() == last_triggered_at.year()
                     && now.month() == last_triggered_at.month()
                     && now.day() == last_triggered_at.day()
                     && now.hour() == last_triggered_at.hour()
-                    && now.minute() == last_triggered_at.minute()
-                {
-                    // NO
-                    return false;
-                }
+                    && now.minute() == last_triggered_at.minute() =>
+            {
+                // NO
+                return false;
             }
             _ => {
                 // OK
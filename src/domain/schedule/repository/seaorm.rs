@@ -1,6 +1,6 @@
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, ModelTrait, PaginatorTrait,
-    QueryFilter, QuerySelect, Set,
+    QueryFilter, QueryOrder, QuerySelect, Set,
 };
 
 use crate::{
@@ -40,6 +40,12 @@ impl ScheduleRepository for ScheduleSeaOrmRepository {
             query = query.filter(entities::schedule::Column::Name.contains(contains_name));
         }
 
+        if let Some(after_id) = params.after_id {
+            query = query.filter(entities::schedule::Column::Id.lt(after_id));
+        }
+
+        query = query.order_by_desc(entities::schedule::Column::Id);
+
         if let Some(limit) = params.limit {
             query = query.limit(limit);
         }
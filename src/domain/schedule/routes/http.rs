@@ -1,116 +1,60 @@
-use axum::{
-    Extension, Json,
-    body::Body,
-    extract::{Path, Query},
-    http::StatusCode,
-    response::{IntoResponse, Response},
-};
+use axum::{Extension, Json};
 
 use crate::{
     context::SharedContext,
     domain::schedule::dto::{
         CreateSchduleRequest, CreateScheduleBody, ListSchedulesQuery, ListSchedulesRequest,
-        PatchScheduleBody, PatchScheduleRequest,
+        ListSchedulesResponse, PatchScheduleBody, PatchScheduleRequest,
     },
     errors,
+    validation::{ValidJson, ValidPath, ValidQuery},
 };
 
 pub async fn create_schedule(
     Extension(state): Extension<SharedContext>,
-    Json(body): Json<CreateScheduleBody>,
-) -> impl IntoResponse {
-    let result = state
+    ValidJson(body): ValidJson<CreateScheduleBody>,
+) -> errors::Result<Json<()>> {
+    state
         .schedule_service
         .create_schdule(CreateSchduleRequest { request_body: body })
-        .await;
+        .await?;
 
-    match result {
-        Ok(_) => Json(()).into_response(),
-        Err(errors::Error::CronExpressionIsInvalid(message)) => Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .body(Body::new(
-                errors::Error::CronExpressionIsInvalid(message).into_json_response(),
-            ))
-            .unwrap(),
-        Err(error) => Response::builder()
-            .status(StatusCode::INTERNAL_SERVER_ERROR)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+    Ok(Json(()))
 }
 
 pub async fn patch_schedule(
-    Path(schedule_id): Path<i64>,
+    ValidPath(schedule_id): ValidPath<i64>,
     Extension(context): Extension<SharedContext>,
-    Json(body): Json<PatchScheduleBody>,
-) -> impl IntoResponse {
-    let result = context
+    ValidJson(body): ValidJson<PatchScheduleBody>,
+) -> errors::Result<Json<()>> {
+    context
         .schedule_service
         .patch_schedule(PatchScheduleRequest { schedule_id, body })
-        .await;
+        .await?;
 
-    match result {
-        Ok(_) => Response::builder()
-            .status(StatusCode::OK)
-            .body(Body::empty())
-            .unwrap(),
-        Err(errors::Error::ScheduleNotFound) => Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .body(Body::new(
-                errors::Error::ScheduleNotFound.into_json_response(),
-            ))
-            .unwrap(),
-        Err(errors::Error::CronExpressionIsInvalid(message)) => Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .body(Body::new(
-                errors::Error::CronExpressionIsInvalid(message).into_json_response(),
-            ))
-            .unwrap(),
-        Err(error) => Response::builder()
-            .status(StatusCode::INTERNAL_SERVER_ERROR)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+    Ok(Json(()))
 }
 
 pub async fn delete_schedule(
-    Path(schedule_id): Path<i64>,
+    ValidPath(schedule_id): ValidPath<i64>,
     Extension(context): Extension<SharedContext>,
-) -> impl IntoResponse {
-    let result = context.schedule_service.delete_schedule(schedule_id).await;
+) -> errors::Result<Json<()>> {
+    context
+        .schedule_service
+        .delete_schedule(schedule_id)
+        .await?;
 
-    match result {
-        Ok(_) => Response::builder()
-            .status(StatusCode::OK)
-            .body(Body::empty())
-            .unwrap(),
-        Err(errors::Error::ScheduleNotFound) => Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .body(Body::new(
-                errors::Error::ScheduleNotFound.into_json_response(),
-            ))
-            .unwrap(),
-        Err(error) => Response::builder()
-            .status(StatusCode::INTERNAL_SERVER_ERROR)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+    Ok(Json(()))
 }
 
 pub async fn list_schedules(
-    Query(query): Query<ListSchedulesQuery>,
+    ValidQuery(query): ValidQuery<ListSchedulesQuery>,
     Extension(context): Extension<SharedContext>,
-) -> impl IntoResponse {
-    let result = context
+) -> errors::Result<Json<ListSchedulesResponse>> {
+    let response = context
         .schedule_service
         .list_schedules(ListSchedulesRequest { query })
-        .await;
+        .await?;
 
-    match result {
-        Ok(response) => Json(response).into_response(),
-        Err(error) => Response::builder()
-            .status(500)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+    Ok(Json(response))
 }
@@ -0,0 +1,19 @@
+use crate::openapi::router::ApiRouter;
+
+use super::http;
+
+pub fn router() -> ApiRouter {
+    ApiRouter::new("schedules")
+        .get("/schedules", "List schedules", http::list_schedules)
+        .post("/schedules", "Create a schedule", http::create_schedule)
+        .patch(
+            "/schedules/{schedule_id}",
+            "Update a schedule",
+            http::patch_schedule,
+        )
+        .delete(
+            "/schedules/{schedule_id}",
+            "Delete a schedule",
+            http::delete_schedule,
+        )
+}
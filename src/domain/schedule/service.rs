@@ -7,6 +7,7 @@ use crate::{
     },
     errors,
     types::cron::CronExpression,
+    utils,
 };
 
 use super::{
@@ -122,7 +123,11 @@ impl ScheduleService for ScheduleServiceImpl {
     ) -> errors::Result<ListSchedulesResponse> {
         let page_number = request.query.page_number.unwrap_or(1);
         let page_size = request.query.page_size.unwrap_or(10);
-        let offset = ((page_number - 1) * page_size) as u64;
+        // cursor(after_id)가 있다면 offset 대신 cursor 이후부터 조회
+        let offset = match request.query.after_id {
+            Some(_) => 0,
+            None => ((page_number - 1) * page_size) as u64,
+        };
         let limit = page_size as u64;
 
         let params = ListSchedulesParams {
@@ -133,15 +138,17 @@ impl ScheduleService for ScheduleServiceImpl {
                 Some(schedule_id) => vec![schedule_id],
                 _ => vec![],
             },
-            limit: Some(limit),
+            // 다음 페이지가 있는지 확인하기 위해 하나 더 조회
+            limit: Some(limit + 1),
             offset: Some(offset),
-            ..Default::default()
+            after_id: request.query.after_id,
         };
 
-        let schedules = self
+        let mut schedules = self
             .schedule_repository
             .list_schedules(params.clone())
             .await?;
+        let next_cursor = utils::take_page(&mut schedules, limit, |schedule| schedule.id);
 
         let count_params = ListSchedulesParams {
             enabled: request.query.enabled,
@@ -162,8 +169,98 @@ impl ScheduleService for ScheduleServiceImpl {
         let response = ListSchedulesResponse {
             schedules: schedules.into_iter().map(ListSchedulesItem::from).collect(),
             total_count,
+            next_cursor,
         };
 
         Ok(response)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        config::Config,
+        context::Context,
+        domain::{
+            container::repository::fake::FakeContainerRepository,
+            schedule::dto::{CreateSchduleRequest, CreateScheduleBody, ListSchedulesRequest},
+            task_definition::dto::CreateDefinitionRequest,
+        },
+    };
+
+    /*
+    next_cursor를 after_id로 넘기며 끝까지 조회하면 모든 schedule이 한 번씩만 나오고,
+    마지막 페이지의 next_cursor는 None인지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_list_schedules_pages_through_with_after_id() {
+        let context =
+            Context::for_test(Config::default(), Arc::new(FakeContainerRepository::new())).await;
+
+        let task_definition_id = context
+            .task_definition_service
+            .create_task_definition(CreateDefinitionRequest {
+                request_body: serde_json::from_value(serde_json::json!({
+                    "name": "task",
+                    "description": "task",
+                    "image": "alpine",
+                }))
+                .unwrap(),
+            })
+            .await
+            .unwrap()
+            .task_definition_id;
+
+        let mut schedule_ids = Vec::new();
+        for index in 0..5 {
+            let schedule_id = context
+                .schedule_service
+                .create_schdule(CreateSchduleRequest {
+                    request_body: CreateScheduleBody {
+                        name: format!("schedule-{}", index),
+                        job_name: "job".to_string(),
+                        cron_expression: "0 * * * ? *".to_string(),
+                        task_definition_id,
+                        command: None,
+                        timezone: None,
+                        timezone_offset: None,
+                        enabled: true,
+                    },
+                })
+                .await
+                .unwrap();
+            schedule_ids.push(schedule_id);
+        }
+        schedule_ids.reverse();
+
+        let mut listed_ids = Vec::new();
+        let mut after_id = None;
+        let mut page_count = 0;
+
+        loop {
+            let page = context
+                .schedule_service
+                .list_schedules(ListSchedulesRequest {
+                    query: serde_json::from_value(serde_json::json!({
+                        "page_size": 2,
+                        "after_id": after_id,
+                    }))
+                    .unwrap(),
+                })
+                .await
+                .unwrap();
+            page_count += 1;
+            listed_ids.extend(page.schedules.iter().map(|schedule| schedule.id));
+
+            match page.next_cursor {
+                Some(next_cursor) => after_id = Some(next_cursor),
+                None => break,
+            }
+        }
+
+        assert_eq!(page_count, 3);
+        assert_eq!(listed_ids, schedule_ids);
+    }
+}
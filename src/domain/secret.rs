@@ -0,0 +1,56 @@
+pub mod cipher;
+pub mod dao;
+pub mod dto;
+pub mod entities;
+pub mod repository;
+pub mod routes;
+pub mod service;
+
+use std::collections::BTreeMap;
+
+use dao::*;
+use dto::{
+    CreateSecretRequest, CreateSecretResponse, ListSecretsRequest, ListSecretsResponse,
+    PatchSecretRequest,
+};
+
+use crate::errors;
+
+#[async_trait::async_trait]
+pub trait SecretRepository {
+    async fn list_secrets(
+        &self,
+        params: ListSecretsParams,
+    ) -> errors::Result<Vec<entities::secret::Model>>;
+
+    async fn count_secrets(&self, params: ListSecretsParams) -> errors::Result<u64>;
+
+    async fn create_secret(&self, params: CreateSecretParams) -> errors::Result<i64>;
+
+    async fn patch_secret(&self, params: PatchSecretParams) -> errors::Result<()>;
+
+    async fn delete_secret(&self, secret_id: i64) -> errors::Result<()>;
+}
+
+#[async_trait::async_trait]
+pub trait SecretService {
+    async fn create_secret(
+        &self,
+        request: CreateSecretRequest,
+    ) -> errors::Result<CreateSecretResponse>;
+
+    async fn patch_secret(&self, request: PatchSecretRequest) -> errors::Result<()>;
+
+    async fn delete_secret(&self, secret_id: i64) -> errors::Result<()>;
+
+    async fn list_secrets(
+        &self,
+        request: ListSecretsRequest,
+    ) -> errors::Result<ListSecretsResponse>;
+
+    /// 환경 변수 이름 -> secret 이름 매핑을 받아서, 환경 변수 이름 -> 복호화된 값으로 변환합니다.
+    async fn resolve_secrets(
+        &self,
+        references: &BTreeMap<String, String>,
+    ) -> errors::Result<BTreeMap<String, String>>;
+}
@@ -0,0 +1,94 @@
+use aes_gcm::{
+    Aes256Gcm, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use crate::{config::SecretConfig, errors};
+
+const NONCE_SIZE: usize = 12;
+
+/*
+secret 값을 AES-256-GCM으로 암호화/복호화합니다.
+암호문은 base64(nonce || ciphertext) 형식으로 저장됩니다.
+*/
+#[derive(Clone)]
+pub struct SecretCipher {
+    cipher: Aes256Gcm,
+}
+
+impl SecretCipher {
+    pub fn new(key: &[u8]) -> errors::Result<Self> {
+        let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| {
+            errors::Error::SecretKeyInvalid(format!(
+                "master key must be 32 bytes, got {} bytes",
+                key.len()
+            ))
+        })?;
+
+        Ok(Self { cipher })
+    }
+
+    /// 설정(secret.key)의 마스터 키로 만듭니다. 설정되지 않았다면 None을 반환합니다.
+    pub fn from_config(config: &SecretConfig) -> errors::Result<Option<Self>> {
+        config.master_key()?.map(|key| Self::new(&key)).transpose()
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> errors::Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|error| errors::Error::SecretFailedToEncrypt(error.to_string()))?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&ciphertext);
+
+        Ok(STANDARD.encode(payload))
+    }
+
+    pub fn decrypt(&self, encrypted: &str) -> errors::Result<String> {
+        let payload = STANDARD
+            .decode(encrypted)
+            .map_err(|error| errors::Error::SecretFailedToDecrypt(error.to_string()))?;
+
+        if payload.len() < NONCE_SIZE {
+            return Err(errors::Error::SecretFailedToDecrypt(
+                "payload is too short".to_string(),
+            ));
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_SIZE);
+
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|error| errors::Error::SecretFailedToDecrypt(error.to_string()))?;
+
+        String::from_utf8(plaintext)
+            .map_err(|error| errors::Error::SecretFailedToDecrypt(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let cipher = SecretCipher::new(&[7u8; 32]).unwrap();
+
+        let encrypted = cipher.encrypt("p@ss,word=1").unwrap();
+        assert_ne!(encrypted, "p@ss,word=1");
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "p@ss,word=1");
+
+        let other_cipher = SecretCipher::new(&[8u8; 32]).unwrap();
+        assert!(other_cipher.decrypt(&encrypted).is_err());
+    }
+
+    #[test]
+    fn test_invalid_key_length() {
+        assert!(SecretCipher::new(&[0u8; 16]).is_err());
+    }
+}
@@ -0,0 +1,35 @@
+#[derive(Debug, Default, Clone)]
+pub struct ListSecretsParams {
+    pub secret_ids: Vec<i64>,
+    pub names: Vec<String>,
+    pub contains_name: Option<String>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+    pub lock: Option<SecretLock>, // lock the listed rows until the unit of work commits
+}
+
+/*
+secret 삭제와 그 secret을 참조하는 task definition 저장이 서로를 기다리도록 잡는 행 잠금입니다.
+SQLite는 행 잠금이 없고 쓰기 트랜잭션이 하나씩만 실행되므로 무시됩니다.
+- Share: 참조를 검증하는 동안 secret이 삭제되지 않도록 함 (task definition 저장)
+- Update: 참조를 확인하는 동안 secret을 참조하는 task definition이 저장되지 않도록 함 (secret 삭제)
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretLock {
+    Share,
+    Update,
+}
+
+#[derive(Debug)]
+pub struct CreateSecretParams {
+    pub name: String,            // secret name
+    pub description: String,     // secret description
+    pub encrypted_value: String, // base64(nonce || ciphertext)
+}
+
+#[derive(Debug, Default)]
+pub struct PatchSecretParams {
+    pub secret_id: i64,
+    pub description: Option<String>,
+    pub encrypted_value: Option<String>,
+}
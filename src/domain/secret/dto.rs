@@ -0,0 +1,126 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::entities;
+use crate::{
+    openapi::schema::schema_object,
+    validation::{MAX_NAME_LENGTH, Validate, Validator},
+};
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CreateSecretBody {
+    pub name: String,                // secret name
+    pub description: Option<String>, // secret description
+    pub value: String,               // plaintext value (never returned)
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateSecretRequest {
+    pub request_body: CreateSecretBody,
+}
+
+#[derive(Serialize)]
+pub struct CreateSecretResponse {
+    pub secret_id: i64, // primary key of the secret
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PatchSecretBody {
+    pub description: Option<String>, // secret description
+    pub value: Option<String>,       // new plaintext value (never returned)
+}
+
+#[derive(Debug, Clone)]
+pub struct PatchSecretRequest {
+    pub secret_id: i64,
+    pub request_body: PatchSecretBody,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ListSecretsQuery {
+    pub secret_id: Option<i64>,
+    pub contains_name: Option<String>,
+    pub page_number: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListSecretsRequest {
+    pub query: ListSecretsQuery,
+}
+
+/// secret 목록 항목입니다. 값은 절대 포함하지 않습니다.
+#[derive(Serialize)]
+pub struct ListSecretsItem {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub created_at: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+impl From<entities::secret::Model> for ListSecretsItem {
+    fn from(model: entities::secret::Model) -> Self {
+        ListSecretsItem {
+            id: model.id,
+            name: model.name,
+            description: model.description,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ListSecretsResponse {
+    pub secrets: Vec<ListSecretsItem>,
+    pub total_count: u64,
+}
+
+impl Validate for CreateSecretBody {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .not_blank("name", &self.name)
+            .max_length("name", &self.name, MAX_NAME_LENGTH);
+    }
+}
+
+impl Validate for PatchSecretBody {
+    fn validate(&self, _validator: &mut Validator) {}
+}
+
+impl Validate for ListSecretsQuery {
+    fn validate(&self, validator: &mut Validator) {
+        validator.pagination(self.page_number, self.page_size);
+    }
+}
+
+schema_object!(CreateSecretBody {
+    name,
+    description,
+    value,
+});
+
+schema_object!(CreateSecretResponse { secret_id });
+
+schema_object!(PatchSecretBody { description, value });
+
+schema_object!(ListSecretsQuery {
+    secret_id,
+    contains_name,
+    page_number,
+    page_size,
+});
+
+schema_object!(ListSecretsItem {
+    id,
+    name,
+    description,
+    created_at,
+    updated_at,
+});
+
+schema_object!(ListSecretsResponse {
+    secrets,
+    total_count,
+});
@@ -0,0 +1,2 @@
+pub mod seaorm;
+pub use seaorm::SecretSeaOrmRepository;
@@ -0,0 +1,135 @@
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select,
+};
+
+use crate::{
+    db::unit_of_work::DatabaseExecutor,
+    domain::secret::{
+        SecretRepository,
+        dao::{CreateSecretParams, ListSecretsParams, PatchSecretParams, SecretLock},
+        entities,
+    },
+    errors,
+};
+
+pub struct SecretSeaOrmRepository {
+    pub connection: DatabaseExecutor,
+}
+
+impl SecretSeaOrmRepository {
+    pub fn new(connection: sea_orm::DatabaseConnection) -> Self {
+        Self {
+            connection: connection.into(),
+        }
+    }
+
+    fn build_filter_query(params: &ListSecretsParams) -> Select<entities::secret::Entity> {
+        let mut query = entities::secret::Entity::find();
+
+        if !params.secret_ids.is_empty() {
+            query = query.filter(entities::secret::Column::Id.is_in(params.secret_ids.clone()));
+        }
+
+        if !params.names.is_empty() {
+            query = query.filter(entities::secret::Column::Name.is_in(params.names.clone()));
+        }
+
+        if let Some(contains_name) = &params.contains_name {
+            query = query.filter(entities::secret::Column::Name.contains(contains_name));
+        }
+
+        query
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretRepository for SecretSeaOrmRepository {
+    async fn list_secrets(
+        &self,
+        params: ListSecretsParams,
+    ) -> errors::Result<Vec<entities::secret::Model>> {
+        let mut query =
+            Self::build_filter_query(&params).order_by_asc(entities::secret::Column::Name);
+
+        if let Some(limit) = params.limit {
+            query = query.limit(limit);
+        }
+
+        if let Some(offset) = params.offset {
+            query = query.offset(offset);
+        }
+
+        query = match params.lock {
+            Some(SecretLock::Share) => query.lock_shared(),
+            Some(SecretLock::Update) => query.lock_exclusive(),
+            None => query,
+        };
+
+        let secrets = query.all(&self.connection).await?;
+
+        Ok(secrets)
+    }
+
+    async fn count_secrets(&self, params: ListSecretsParams) -> errors::Result<u64> {
+        let count = Self::build_filter_query(&params)
+            .count(&self.connection)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn create_secret(&self, params: CreateSecretParams) -> errors::Result<i64> {
+        let now = chrono::Utc::now();
+
+        let new_secret = entities::secret::ActiveModel {
+            id: NotSet,
+            name: Set(params.name),
+            description: Set(params.description),
+            encrypted_value: Set(params.encrypted_value),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+
+        let saved = new_secret.insert(&self.connection).await?;
+
+        Ok(saved.id)
+    }
+
+    async fn patch_secret(&self, params: PatchSecretParams) -> errors::Result<()> {
+        let secret = entities::secret::Entity::find_by_id(params.secret_id)
+            .one(&self.connection)
+            .await?
+            .ok_or_else(|| errors::Error::SecretNotFound)?;
+
+        let mut model = secret.into_active_model();
+
+        if let Some(description) = params.description {
+            model.description = Set(description);
+        }
+
+        if let Some(encrypted_value) = params.encrypted_value {
+            model.encrypted_value = Set(encrypted_value);
+        }
+
+        model.updated_at = Set(chrono::Utc::now());
+
+        model.update(&self.connection).await?;
+
+        Ok(())
+    }
+
+    async fn delete_secret(&self, secret_id: i64) -> errors::Result<()> {
+        let result = entities::secret::Entity::delete_by_id(secret_id)
+            .exec(&self.connection)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(errors::Error::SecretNotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use axum::{Extension, Json};
+
+use crate::{
+    context::SharedContext,
+    domain::secret::dto::{
+        CreateSecretBody, CreateSecretRequest, CreateSecretResponse, ListSecretsQuery,
+        ListSecretsRequest, ListSecretsResponse, PatchSecretBody, PatchSecretRequest,
+    },
+    errors,
+    validation::{ValidJson, ValidPath, ValidQuery},
+};
+
+pub async fn list_secrets(
+    ValidQuery(query): ValidQuery<ListSecretsQuery>,
+    Extension(context): Extension<SharedContext>,
+) -> errors::Result<Json<ListSecretsResponse>> {
+    let response = context
+        .secret_service
+        .list_secrets(ListSecretsRequest { query })
+        .await?;
+
+    Ok(Json(response))
+}
+
+pub async fn create_secret(
+    Extension(context): Extension<SharedContext>,
+    ValidJson(body): ValidJson<CreateSecretBody>,
+) -> errors::Result<Json<CreateSecretResponse>> {
+    let response = context
+        .secret_service
+        .create_secret(CreateSecretRequest { request_body: body })
+        .await?;
+
+    Ok(Json(response))
+}
+
+pub async fn patch_secret(
+    ValidPath(secret_id): ValidPath<i64>,
+    Extension(context): Extension<SharedContext>,
+    ValidJson(body): ValidJson<PatchSecretBody>,
+) -> errors::Result<Json<()>> {
+    context
+        .secret_service
+        .patch_secret(PatchSecretRequest {
+            secret_id,
+            request_body: body,
+        })
+        .await?;
+
+    Ok(Json(()))
+}
+
+pub async fn delete_secret(
+    ValidPath(secret_id): ValidPath<i64>,
+    Extension(context): Extension<SharedContext>,
+) -> errors::Result<Json<()>> {
+    context.secret_service.delete_secret(secret_id).await?;
+
+    Ok(Json(()))
+}
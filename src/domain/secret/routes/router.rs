@@ -0,0 +1,23 @@
+use crate::openapi::router::ApiRouter;
+
+use super::http;
+
+pub fn router() -> ApiRouter {
+    ApiRouter::new("secrets")
+        .get(
+            "/secrets",
+            "List secrets (values are never returned)",
+            http::list_secrets,
+        )
+        .post("/secrets", "Create a secret", http::create_secret)
+        .patch(
+            "/secrets/{secret_id}",
+            "Update a secret",
+            http::patch_secret,
+        )
+        .delete(
+            "/secrets/{secret_id}",
+            "Delete a secret",
+            http::delete_secret,
+        )
+}
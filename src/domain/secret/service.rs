@@ -0,0 +1,396 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::{
+    domain::{task_definition::dao::ListSecretReferrersParams, unit_of_work::UnitOfWorkFactory},
+    errors,
+};
+
+use super::{
+    SecretRepository, SecretService,
+    cipher::SecretCipher,
+    dao::{CreateSecretParams, ListSecretsParams, PatchSecretParams, SecretLock},
+    dto::{
+        CreateSecretRequest, CreateSecretResponse, ListSecretsItem, ListSecretsRequest,
+        ListSecretsResponse, PatchSecretRequest,
+    },
+};
+
+// secret을 삭제할 수 없을 때 에러 메시지에 보여주는 task definition 수
+const SECRET_IN_USE_REPORT_LIMIT: u64 = 10;
+
+// secret 값은 docker --env-file로 전달되므로 한 줄이어야 함
+fn is_single_line(value: &str) -> bool {
+    !value.contains(['\n', '\r', '\0'])
+}
+
+pub struct SecretServiceImpl {
+    pub secret_repository: Arc<dyn SecretRepository + Send + Sync>,
+    pub unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+    pub secret_cipher: Option<SecretCipher>,
+}
+
+impl SecretServiceImpl {
+    pub fn new(
+        secret_repository: Arc<dyn SecretRepository + Send + Sync>,
+        unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+        secret_cipher: Option<SecretCipher>,
+    ) -> Self {
+        Self {
+            secret_repository,
+            unit_of_work_factory,
+            secret_cipher,
+        }
+    }
+
+    fn cipher(&self) -> errors::Result<&SecretCipher> {
+        self.secret_cipher
+            .as_ref()
+            .ok_or(errors::Error::SecretKeyNotConfigured)
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretService for SecretServiceImpl {
+    async fn create_secret(
+        &self,
+        request: CreateSecretRequest,
+    ) -> errors::Result<CreateSecretResponse> {
+        let body = request.request_body;
+
+        if body.name.trim().is_empty() {
+            return Err(errors::Error::InvalidSecret(
+                "name must not be empty".to_string(),
+            ));
+        }
+
+        if !is_single_line(&body.value) {
+            return Err(errors::Error::InvalidSecret(
+                "value must not contain line breaks".to_string(),
+            ));
+        }
+
+        let cipher = self.cipher()?;
+
+        let existing = self
+            .secret_repository
+            .count_secrets(ListSecretsParams {
+                names: vec![body.name.clone()],
+                ..Default::default()
+            })
+            .await?;
+
+        if existing > 0 {
+            return Err(errors::Error::SecretAlreadyExists);
+        }
+
+        let secret_id = self
+            .secret_repository
+            .create_secret(CreateSecretParams {
+                name: body.name,
+                description: body.description.unwrap_or_default(),
+                encrypted_value: cipher.encrypt(&body.value)?,
+            })
+            .await?;
+
+        Ok(CreateSecretResponse { secret_id })
+    }
+
+    async fn patch_secret(&self, request: PatchSecretRequest) -> errors::Result<()> {
+        if !request
+            .request_body
+            .value
+            .as_deref()
+            .is_none_or(is_single_line)
+        {
+            return Err(errors::Error::InvalidSecret(
+                "value must not contain line breaks".to_string(),
+            ));
+        }
+
+        let encrypted_value = match &request.request_body.value {
+            Some(value) => Some(self.cipher()?.encrypt(value)?),
+            None => None,
+        };
+
+        self.secret_repository
+            .patch_secret(PatchSecretParams {
+                secret_id: request.secret_id,
+                description: request.request_body.description,
+                encrypted_value,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_secret(&self, secret_id: i64) -> errors::Result<()> {
+        // 참조 확인과 삭제 사이에 secret을 참조하는 task definition이 저장되지 않도록 secret 행을 잠근 채로 처리
+        let unit_of_work = self.unit_of_work_factory.begin().await?;
+        let secret_repository = unit_of_work.secret_repository();
+
+        let Some(secret) = secret_repository
+            .list_secrets(ListSecretsParams {
+                secret_ids: vec![secret_id],
+                lock: Some(SecretLock::Update),
+                ..Default::default()
+            })
+            .await?
+            .pop()
+        else {
+            return Err(errors::Error::SecretNotFound);
+        };
+
+        // 이전 버전을 포함해 secret을 참조하는 task definition이 있으면 삭제하지 않음 (실행 시 MISSING_SECRET_REFERENCE로 실패하므로)
+        let referrers = unit_of_work
+            .task_definition_repository()
+            .list_secret_referrers(ListSecretReferrersParams {
+                secret_name: secret.name,
+                limit: SECRET_IN_USE_REPORT_LIMIT,
+            })
+            .await?;
+
+        if !referrers.is_empty() {
+            return Err(errors::Error::SecretInUse(
+                referrers
+                    .iter()
+                    .map(|referrer| {
+                        format!("{} (id {})", referrer.name, referrer.task_definition_id)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+
+        secret_repository.delete_secret(secret_id).await?;
+
+        unit_of_work.commit().await
+    }
+
+    async fn list_secrets(
+        &self,
+        request: ListSecretsRequest,
+    ) -> errors::Result<ListSecretsResponse> {
+        let page_number = request.query.page_number.unwrap_or(1);
+        let page_size = request.query.page_size.unwrap_or(10);
+        let offset = (page_number - 1) * page_size;
+
+        let params = ListSecretsParams {
+            secret_ids: match request.query.secret_id {
+                Some(secret_id) => vec![secret_id],
+                None => vec![],
+            },
+            contains_name: request.query.contains_name,
+            ..Default::default()
+        };
+
+        let total_count = self.secret_repository.count_secrets(params.clone()).await?;
+
+        let secrets = self
+            .secret_repository
+            .list_secrets(ListSecretsParams {
+                limit: Some(page_size),
+                offset: Some(offset),
+                ..params
+            })
+            .await?;
+
+        Ok(ListSecretsResponse {
+            secrets: secrets.into_iter().map(ListSecretsItem::from).collect(),
+            total_count,
+        })
+    }
+
+    async fn resolve_secrets(
+        &self,
+        references: &BTreeMap<String, String>,
+    ) -> errors::Result<BTreeMap<String, String>> {
+        if references.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let cipher = self.cipher()?;
+
+        let secrets = self
+            .secret_repository
+            .list_secrets(ListSecretsParams {
+                names: references.values().cloned().collect(),
+                ..Default::default()
+            })
+            .await?;
+
+        let mut resolved = BTreeMap::new();
+
+        for (env_name, secret_name) in references {
+            // 데이터베이스를 직접 수정하는 등으로 참조가 남을 수 있으므로 실행 시점에 다시 확인
+            let Some(secret) = secrets.iter().find(|secret| &secret.name == secret_name) else {
+                return Err(errors::Error::MissingSecretReference(format!(
+                    "secret {} for environment variable {}",
+                    secret_name, env_name
+                )));
+            };
+
+            resolved.insert(env_name.clone(), cipher.decrypt(&secret.encrypted_value)?);
+        }
+
+        Ok(resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Config,
+        context::{Context, SharedContext},
+        domain::{
+            container::repository::fake::FakeContainerRepository,
+            secret::dto::CreateSecretBody,
+            task_definition::dto::{CreateDefinitionRequest, DeleteDefinitionRequest},
+        },
+    };
+
+    async fn create_task_definition(
+        context: &SharedContext,
+        secrets: serde_json::Value,
+    ) -> errors::Result<i64> {
+        context
+            .task_definition_service
+            .create_task_definition(CreateDefinitionRequest {
+                request_body: serde_json::from_value(serde_json::json!({
+                    "name": "task",
+                    "description": "task",
+                    "image": "alpine",
+                    "secrets": secrets,
+                }))
+                .unwrap(),
+            })
+            .await
+            .map(|response| response.task_definition_id)
+    }
+
+    #[tokio::test]
+    async fn test_secret_env_names_and_delete_conflict() {
+        let context =
+            Context::for_test(Config::default(), Arc::new(FakeContainerRepository::new())).await;
+
+        let secret_id = context
+            .secret_service
+            .create_secret(CreateSecretRequest {
+                request_body: CreateSecretBody {
+                    name: "db-password".to_string(),
+                    description: None,
+                    value: "p4ss".to_string(),
+                },
+            })
+            .await
+            .unwrap()
+            .secret_id;
+
+        // docker CLI나 컨테이너 기본 환경을 바꾸는 이름은 secret으로 설정할 수 없음
+        for env_name in [
+            "DOCKER_HOST",
+            "docker_config",
+            "PATH",
+            "HOME",
+            "1TOKEN",
+            "A B",
+        ] {
+            let result =
+                create_task_definition(&context, serde_json::json!({ env_name: "db-password" }))
+                    .await;
+            assert!(
+                matches!(result, Err(errors::Error::InvalidTaskDefinition(_))),
+                "{}: {:?}",
+                env_name,
+                result
+            );
+        }
+
+        let task_definition_id = create_task_definition(
+            &context,
+            serde_json::json!({ "DB_PASSWORD": "db-password" }),
+        )
+        .await
+        .unwrap();
+
+        let result = context.secret_service.delete_secret(secret_id).await;
+        assert!(
+            matches!(&result, Err(errors::Error::SecretInUse(names)) if names.contains("task")),
+            "{:?}",
+            result
+        );
+
+        context
+            .task_definition_service
+            .delete_task_definition(DeleteDefinitionRequest { task_definition_id })
+            .await
+            .unwrap();
+
+        context
+            .secret_service
+            .delete_secret(secret_id)
+            .await
+            .unwrap();
+        assert!(matches!(
+            context.secret_service.delete_secret(secret_id).await,
+            Err(errors::Error::SecretNotFound)
+        ));
+    }
+
+    /*
+    이전 버전이 많이 쌓여도 secret을 참조하는 task definition을 찾아 삭제를 거부하고, 에러 메시지에는 일부만 보여주는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_delete_secret_checks_all_task_definition_versions() {
+        let context =
+            Context::for_test(Config::default(), Arc::new(FakeContainerRepository::new())).await;
+
+        let create_secret = async |name: &str| {
+            context
+                .secret_service
+                .create_secret(CreateSecretRequest {
+                    request_body: CreateSecretBody {
+                        name: name.to_string(),
+                        description: None,
+                        value: "value".to_string(),
+                    },
+                })
+                .await
+                .unwrap()
+                .secret_id
+        };
+
+        let api_key_id = create_secret("api-key").await;
+        let token_id = create_secret("token").await;
+
+        // 한 번에 읽는 수보다 많은 이전 버전 뒤에 api-key를 참조하는 최신 버전이 있음
+        for _ in 0..510 {
+            create_task_definition(&context, serde_json::json!({ "TOKEN": "token" }))
+                .await
+                .unwrap();
+        }
+        let task_definition_id =
+            create_task_definition(&context, serde_json::json!({ "API_KEY": "api-key" }))
+                .await
+                .unwrap();
+
+        let result = context.secret_service.delete_secret(api_key_id).await;
+        let expected = format!("task (id {})", task_definition_id);
+        assert!(
+            matches!(&result, Err(errors::Error::SecretInUse(names)) if *names == expected),
+            "{:?}",
+            result
+        );
+
+        let result = context.secret_service.delete_secret(token_id).await;
+        assert!(
+            matches!(
+                &result,
+                Err(errors::Error::SecretInUse(names))
+                    if names.split(", ").count() == SECRET_IN_USE_REPORT_LIMIT as usize
+            ),
+            "{:?}",
+            result
+        );
+    }
+}
@@ -28,6 +28,12 @@ pub trait TaskDefinitionRepository {
         params: CountTaskDefinitionsParams,
     ) -> errors::Result<u64>;
 
+    /// 이전 버전을 포함해 secret을 참조하는 task definition을 id 순서로 limit개까지 조회합니다.
+    async fn list_secret_referrers(
+        &self,
+        params: ListSecretReferrersParams,
+    ) -> errors::Result<Vec<SecretReferrer>>;
+
     async fn create_task_definition(
         &self,
         params: CreateTaskDefinitionParams,
@@ -1,4 +1,9 @@
-use super::entities;
+use std::collections::BTreeMap;
+
+use super::entities::{
+    self,
+    task_definition::{ImagePullPolicy, Mount, PlacementConstraint, Ulimit},
+};
 
 #[derive(Debug, Default)]
 pub struct ListTaskDefinitionsParams {
@@ -6,8 +11,10 @@ pub struct ListTaskDefinitionsParams {
     pub name: Option<String>,
     pub contains_name: Option<String>,
     pub is_latest: Option<bool>,
+    pub has_placement_constraints: Option<bool>,
     pub limit: Option<u64>,
     pub offset: Option<u64>,
+    pub after_id: Option<i64>, // only task definitions listed after this id (ids are listed in descending order, only valid when sorted by id)
     pub order_by_desc: Option<entities::task_definition::Column>,
 }
 
@@ -15,6 +22,19 @@ pub struct ListTaskDefinitionsParams {
 pub struct CountTaskDefinitionsParams {
     pub name: Option<String>,
     pub contains_name: Option<String>,
+    pub is_latest: Option<bool>,
+}
+
+#[derive(Debug)]
+pub struct ListSecretReferrersParams {
+    pub secret_name: String,
+    pub limit: u64, // max number of task definitions to return
+}
+
+#[derive(Debug)]
+pub struct SecretReferrer {
+    pub task_definition_id: i64,
+    pub name: String, // task definition name
 }
 
 #[derive(Debug)]
@@ -23,15 +43,35 @@ pub struct CreateTaskDefinitionParams {
     pub version: i64,        // task version
     pub description: String, // task description
 
-    pub image: String,           // docker image
-    pub command: Option<String>, // docker run command
-    pub args: Option<String>,    // docker run arguments
-    pub env: Option<String>,     // environment variables
+    pub image: String,                              // docker image
+    pub image_pull_policy: Option<ImagePullPolicy>, // image pull policy (default IfNotPresent)
+    pub command: Option<Vec<String>>,               // docker run command (argv)
+    pub args: Option<Vec<String>>,                  // docker run arguments (argv)
+    pub env: Option<BTreeMap<String, String>>,      // environment variables
+    pub secrets: Option<BTreeMap<String, String>>,  // environment variable name -> secret name
+
+    pub memory_limit: Option<u32>,       // hard memory limit in MB
+    pub memory_reservation: Option<u32>, // soft memory limit in MB
+    pub memory_swap: Option<i64>,        // memory + swap limit in MB (-1 = unlimited swap)
+    pub nano_cpus: Option<i64>,          // hard cpu quota in 10^-9 CPUs
+    pub cpu_shares: Option<u32>,         // relative cpu weight (docker default 1024)
+    pub cpuset_cpus: Option<String>,     // cpus to pin (example: "0-3,5")
 
-    pub memory_limit: Option<u32>, // memory limit in MB
-    pub cpu_limit: Option<u32>,    // cpu limit (default 1024)
+    pub entrypoint: Option<Vec<String>>, // entrypoint override (argv)
+    pub working_dir: Option<String>,     // working directory inside the container
+    pub user: Option<String>,            // user[:group] to run as
+    pub network: Option<String>,         // network to connect to
+    pub mounts: Option<Vec<Mount>>,      // bind mounts, named volumes, tmpfs
+    pub labels: Option<BTreeMap<String, String>>, // container labels
+    pub shm_size: Option<u32>,           // /dev/shm size in MB
+    pub ulimits: Option<Vec<Ulimit>>,    // ulimits
+    pub stop_grace_period_seconds: Option<u32>, // SIGTERM -> SIGKILL grace period when stopping
+    pub placement_constraints: Option<Vec<PlacementConstraint>>, // node label selectors
 }
 
+/*
+지울 수 있는 필드는 None이면 유지하고, Some(None)이면 지웁니다.
+*/
 #[derive(Debug, Default)]
 pub struct PatchTaskDefinitionParams {
     pub task_definition_id: i64,
@@ -39,11 +79,27 @@ pub struct PatchTaskDefinitionParams {
     pub name: Option<String>,
     pub version: Option<i64>,
     pub image: Option<String>,
-    pub command: Option<String>,
-    pub args: Option<String>,
-    pub env: Option<String>,
-    pub memory_limit: Option<u32>,
-    pub cpu_limit: Option<u32>,
+    pub image_pull_policy: Option<Option<ImagePullPolicy>>,
+    pub command: Option<Option<Vec<String>>>,
+    pub args: Option<Option<Vec<String>>>,
+    pub env: Option<Option<BTreeMap<String, String>>>,
+    pub secrets: Option<Option<BTreeMap<String, String>>>,
+    pub memory_limit: Option<Option<u32>>,
+    pub memory_reservation: Option<Option<u32>>,
+    pub memory_swap: Option<Option<i64>>,
+    pub nano_cpus: Option<Option<i64>>,
+    pub cpu_shares: Option<Option<u32>>,
+    pub cpuset_cpus: Option<Option<String>>,
+    pub entrypoint: Option<Option<Vec<String>>>,
+    pub working_dir: Option<Option<String>>,
+    pub user: Option<Option<String>>,
+    pub network: Option<Option<String>>,
+    pub mounts: Option<Option<Vec<Mount>>>,
+    pub labels: Option<Option<BTreeMap<String, String>>>,
+    pub shm_size: Option<Option<u32>>,
+    pub ulimits: Option<Option<Vec<Ulimit>>>,
+    pub stop_grace_period_seconds: Option<Option<u32>>,
+    pub placement_constraints: Option<Option<Vec<PlacementConstraint>>>,
     pub enabled: Option<bool>,
     pub is_latest: Option<bool>,
 }
@@ -1,16 +1,46 @@
+use std::collections::BTreeMap;
+
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
+use super::entities::task_definition::{
+    ArgumentList, EnvironmentVariables, ImagePullPolicy, Labels, Model, Mount, MountList,
+    MountType, PlacementConstraint, PlacementConstraintList, PlacementOperator, SecretReferences,
+    Ulimit, UlimitList,
+};
+use crate::{
+    openapi::schema::{schema_enum, schema_object, schema_transparent},
+    types::patch::Patch,
+    validation::{MAX_NAME_LENGTH, Validate, Validator},
+};
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct CreateTaskDefinitionBody {
-    pub name: String,              // task name
-    pub description: String,       // task description
-    pub image: String,             // docker image
-    pub command: Option<String>,   // docker run command
-    pub args: Option<String>,      // docker run arguments
-    pub env: Option<String>,       // environment variables
-    pub memory_limit: Option<u32>, // memory limit in MB
-    pub cpu_limit: Option<u32>,    // cpu limit (default 1024)
+    pub name: String,                               // task name
+    pub description: String,                        // task description
+    pub image: String,                              // docker image
+    pub image_pull_policy: Option<ImagePullPolicy>, // image pull policy (default IfNotPresent)
+    pub command: Option<Vec<String>>,               // docker run command (argv)
+    pub args: Option<Vec<String>>,                  // docker run arguments (argv)
+    pub env: Option<BTreeMap<String, String>>,      // environment variables
+    pub secrets: Option<BTreeMap<String, String>>,  // environment variable name -> secret name
+    pub memory_limit: Option<u32>,                  // hard memory limit in MB
+    pub memory_reservation: Option<u32>,            // soft memory limit in MB
+    pub memory_swap: Option<i64>, // memory + swap limit in MB (-1 = unlimited swap)
+    pub cpus: Option<f64>,        // hard cpu quota (fractional CPUs, example: 1.5)
+    #[serde(alias = "cpu_limit")]
+    pub cpu_shares: Option<u32>, // relative cpu weight (docker default 1024)
+    pub cpuset_cpus: Option<String>, // cpus to pin (example: "0-3,5")
+    pub entrypoint: Option<Vec<String>>, // entrypoint override (argv)
+    pub working_dir: Option<String>, // working directory inside the container
+    pub user: Option<String>,     // user[:group] to run as
+    pub network: Option<String>,  // network to connect to
+    pub mounts: Option<Vec<Mount>>, // bind mounts, named volumes, tmpfs
+    pub labels: Option<BTreeMap<String, String>>, // container labels
+    pub shm_size: Option<u32>,    // /dev/shm size in MB
+    pub ulimits: Option<Vec<Ulimit>>, // ulimits
+    pub stop_grace_period_seconds: Option<u32>, // SIGTERM -> SIGKILL grace period when stopping
+    pub placement_constraints: Option<Vec<PlacementConstraint>>, // node label selectors
 }
 
 #[derive(Debug, Clone)]
@@ -23,15 +53,36 @@ pub struct CreateDefinitionResponse {
     pub task_definition_id: i64, // primary key of the task definition
 }
 
-#[derive(Deserialize, Debug, Clone)]
+/*
+생략한 필드는 유지하고, null을 보낸 필드는 지웁니다.
+*/
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
 pub struct PatchTaskDefinitionBody {
-    pub description: Option<String>, // task description
-    pub image: Option<String>,       // docker image
-    pub command: Option<String>,     // docker run command
-    pub args: Option<String>,        // docker run arguments
-    pub env: Option<String>,         // environment variables
-    pub memory_limit: Option<u32>,   // memory limit in MB
-    pub cpu_limit: Option<u32>,      // cpu limit (default 1024)
+    pub description: Option<String>,               // task description
+    pub image: Option<String>,                     // docker image
+    pub image_pull_policy: Patch<ImagePullPolicy>, // image pull policy
+    pub command: Patch<Vec<String>>,               // docker run command (argv)
+    pub args: Patch<Vec<String>>,                  // docker run arguments (argv)
+    pub env: Patch<BTreeMap<String, String>>,      // environment variables
+    pub secrets: Patch<BTreeMap<String, String>>,  // environment variable name -> secret name
+    pub memory_limit: Patch<u32>,                  // hard memory limit in MB
+    pub memory_reservation: Patch<u32>,            // soft memory limit in MB
+    pub memory_swap: Patch<i64>, // memory + swap limit in MB (-1 = unlimited swap)
+    pub cpus: Patch<f64>,        // hard cpu quota (fractional CPUs, example: 1.5)
+    #[serde(alias = "cpu_limit")]
+    pub cpu_shares: Patch<u32>, // relative cpu weight (docker default 1024)
+    pub cpuset_cpus: Patch<String>, // cpus to pin (example: "0-3,5")
+    pub entrypoint: Patch<Vec<String>>, // entrypoint override (argv)
+    pub working_dir: Patch<String>, // working directory inside the container
+    pub user: Patch<String>,     // user[:group] to run as
+    pub network: Patch<String>,  // network to connect to
+    pub mounts: Patch<Vec<Mount>>, // bind mounts, named volumes, tmpfs
+    pub labels: Patch<BTreeMap<String, String>>, // container labels
+    pub shm_size: Patch<u32>,    // /dev/shm size in MB
+    pub ulimits: Patch<Vec<Ulimit>>, // ulimits
+    pub stop_grace_period_seconds: Patch<u32>, // SIGTERM -> SIGKILL grace period when stopping
+    pub placement_constraints: Patch<Vec<PlacementConstraint>>, // node label selectors
     pub enabled: Option<bool>,
 }
 
@@ -47,8 +98,9 @@ pub struct ListTaskDefinitionsQuery {
     pub contains_name: Option<String>,
     pub name: Option<String>,
     pub is_latest_only: Option<bool>,
-    pub page_number: u64,
-    pub page_size: u64,
+    pub page_number: Option<u64>, // page number (1-based, default 1)
+    pub page_size: Option<u64>,   // page size (default 10)
+    pub after_id: Option<i64>, // cursor: next_cursor of the previous page (page_number is ignored)
 }
 
 #[derive(Debug, Clone)]
@@ -63,13 +115,30 @@ pub struct ListTaskDefinitionsItem {
     pub version: i64,        // task version
     pub description: String, // task description
 
-    pub image: String,           // docker image
-    pub command: Option<String>, // docker run command
-    pub args: Option<String>,    // docker run arguments
-    pub env: Option<String>,     // environment variables
+    pub image: String,                              // docker image
+    pub image_pull_policy: Option<ImagePullPolicy>, // image pull policy (default IfNotPresent)
+    pub command: Option<Vec<String>>,               // docker run command (argv)
+    pub args: Option<Vec<String>>,                  // docker run arguments (argv)
+    pub env: Option<BTreeMap<String, String>>,      // environment variables
+    pub secrets: Option<BTreeMap<String, String>>,  // environment variable name -> secret name
+
+    pub memory_limit: Option<u32>,       // hard memory limit in MB
+    pub memory_reservation: Option<u32>, // soft memory limit in MB
+    pub memory_swap: Option<i64>,        // memory + swap limit in MB (-1 = unlimited swap)
+    pub cpus: Option<f64>,               // hard cpu quota (fractional CPUs)
+    pub cpu_shares: Option<u32>,         // relative cpu weight (docker default 1024)
+    pub cpuset_cpus: Option<String>,     // cpus to pin (example: "0-3,5")
 
-    pub memory_limit: Option<u32>, // memory limit in MB
-    pub cpu_limit: Option<u32>,    // cpu limit (default 1024)
+    pub entrypoint: Option<Vec<String>>, // entrypoint override (argv)
+    pub working_dir: Option<String>,     // working directory inside the container
+    pub user: Option<String>,            // user[:group] to run as
+    pub network: Option<String>,         // network to connect to
+    pub mounts: Option<Vec<Mount>>,      // bind mounts, named volumes, tmpfs
+    pub labels: Option<BTreeMap<String, String>>, // container labels
+    pub shm_size: Option<u32>,           // /dev/shm size in MB
+    pub ulimits: Option<Vec<Ulimit>>,    // ulimits
+    pub stop_grace_period_seconds: Option<u32>, // SIGTERM -> SIGKILL grace period when stopping
+    pub placement_constraints: Option<Vec<PlacementConstraint>>, // node label selectors
 
     pub created_at: chrono::DateTime<Utc>,
     pub enabled: bool,
@@ -80,9 +149,226 @@ pub struct ListTaskDefinitionsItem {
 pub struct ListTaskDefinitionsResponse {
     pub task_definitions: Vec<ListTaskDefinitionsItem>,
     pub total_count: u64,
+    pub next_cursor: Option<i64>, // after_id of the next page (None: last page)
 }
 
 #[derive(Debug, Clone)]
 pub struct DeleteDefinitionRequest {
     pub task_definition_id: i64,
 }
+
+impl Validate for CreateTaskDefinitionBody {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .not_blank("name", &self.name)
+            .max_length("name", &self.name, MAX_NAME_LENGTH)
+            .not_blank("image", &self.image)
+            .max_length("image", &self.image, MAX_NAME_LENGTH);
+    }
+}
+
+impl Validate for PatchTaskDefinitionBody {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .optional_not_blank("image", self.image.as_deref())
+            .optional_max_length("image", self.image.as_deref(), MAX_NAME_LENGTH);
+    }
+}
+
+impl Validate for ListTaskDefinitionsQuery {
+    fn validate(&self, validator: &mut Validator) {
+        validator.pagination(self.page_number, self.page_size);
+    }
+}
+
+schema_object!(CreateTaskDefinitionBody {
+    name,
+    description,
+    image,
+    image_pull_policy,
+    command,
+    args,
+    env,
+    secrets,
+    memory_limit,
+    memory_reservation,
+    memory_swap,
+    cpus,
+    #[alias = "cpu_limit"]
+    cpu_shares,
+    cpuset_cpus,
+    entrypoint,
+    working_dir,
+    user,
+    network,
+    mounts,
+    labels,
+    shm_size,
+    ulimits,
+    stop_grace_period_seconds,
+    placement_constraints,
+});
+
+schema_object!(CreateDefinitionResponse { task_definition_id });
+
+schema_object!(
+    #[optional]
+    PatchTaskDefinitionBody {
+        description,
+        image,
+        image_pull_policy,
+        command,
+        args,
+        env,
+        secrets,
+        memory_limit,
+        memory_reservation,
+        memory_swap,
+        cpus,
+        #[alias = "cpu_limit"]
+        cpu_shares,
+        cpuset_cpus,
+        entrypoint,
+        working_dir,
+        user,
+        network,
+        mounts,
+        labels,
+        shm_size,
+        ulimits,
+        stop_grace_period_seconds,
+        placement_constraints,
+        enabled,
+    }
+);
+
+schema_object!(ListTaskDefinitionsQuery {
+    task_definition_id,
+    contains_name,
+    name,
+    is_latest_only,
+    page_number,
+    page_size,
+    after_id,
+});
+
+schema_object!(ListTaskDefinitionsItem {
+    id,
+    name,
+    version,
+    description,
+    image,
+    image_pull_policy,
+    command,
+    args,
+    env,
+    secrets,
+    memory_limit,
+    memory_reservation,
+    memory_swap,
+    cpus,
+    cpu_shares,
+    cpuset_cpus,
+    entrypoint,
+    working_dir,
+    user,
+    network,
+    mounts,
+    labels,
+    shm_size,
+    ulimits,
+    stop_grace_period_seconds,
+    placement_constraints,
+    created_at,
+    enabled,
+    is_latest,
+});
+
+schema_object!(ListTaskDefinitionsResponse {
+    task_definitions,
+    total_count,
+    next_cursor,
+});
+
+schema_object!(Model as "TaskDefinition" {
+    id,
+    name,
+    description,
+    version,
+    image,
+    image_pull_policy,
+    command,
+    args,
+    env,
+    secrets,
+    memory_limit,
+    memory_reservation,
+    memory_swap,
+    nano_cpus,
+    cpu_shares,
+    cpuset_cpus,
+    entrypoint,
+    working_dir,
+    user,
+    network,
+    mounts,
+    labels,
+    shm_size,
+    ulimits,
+    stop_grace_period_seconds,
+    placement_constraints,
+    created_at,
+    enabled,
+    is_latest,
+});
+
+schema_object!(Mount {
+    #[rename = "type"]
+    mount_type,
+    source,
+    target,
+    #[optional]
+    read_only,
+});
+
+schema_object!(Ulimit { name, soft, hard });
+
+schema_object!(PlacementConstraint {
+    key,
+    operator,
+    #[optional]
+    values,
+});
+
+schema_enum!(ImagePullPolicy {
+    Always,
+    IfNotPresent,
+    Never
+});
+
+schema_enum!(MountType {
+    Bind,
+    Volume,
+    Tmpfs
+});
+
+schema_enum!(PlacementOperator {
+    In,
+    NotIn,
+    Exists,
+    DoesNotExist
+});
+
+schema_transparent!(ArgumentList);
+
+schema_transparent!(EnvironmentVariables);
+
+schema_transparent!(SecretReferences);
+
+schema_transparent!(MountList);
+
+schema_transparent!(Labels);
+
+schema_transparent!(UlimitList);
+
+schema_transparent!(PlacementConstraintList);
@@ -1,23 +1,47 @@
+use std::collections::BTreeMap;
+
 use chrono::Utc;
-use sea_orm::entity::prelude::*;
-use serde::Serialize;
+use sea_orm::{FromJsonQueryResult, entity::prelude::*};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "task_definition")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = true)]
     pub id: i64, // primary key
-    pub name: String,        // task name
+    pub name: String, // task name
+    #[sea_orm(column_type = "Text")]
     pub description: String, // task description
-    pub version: i64,        // task version
+    pub version: i64, // task version
+
+    pub image: String,                              // docker image
+    pub image_pull_policy: Option<ImagePullPolicy>, // image pull policy (default IfNotPresent)
+    pub command: Option<ArgumentList>,              // docker run command (argv)
+    pub args: Option<ArgumentList>,                 // docker run arguments (argv)
+    pub env: Option<EnvironmentVariables>,          // environment variables (key/value)
+    pub secrets: Option<SecretReferences>,          // environment variable name -> secret name
 
-    pub image: String,           // docker image
-    pub command: Option<String>, // docker run command
-    pub args: Option<String>,    // docker run arguments
-    pub env: Option<String>,     // environment variables
+    // memory_limit, memory_reservation, cpu_shares, shm_size, stop_grace_period_seconds는 u32 범위로 검증하지만,
+    // Postgres에서는 u32 컬럼을 읽을 수 없으므로 i64로 저장합니다.
+    pub memory_limit: Option<i64>,       // hard memory limit in MB
+    pub memory_reservation: Option<i64>, // soft memory limit in MB
+    pub memory_swap: Option<i64>,        // memory + swap limit in MB (-1 = unlimited swap)
 
-    pub memory_limit: Option<u32>, // memory limit in MB
-    pub cpu_limit: Option<u32>,    // cpu limit (default 1024)
+    pub nano_cpus: Option<i64>, // hard cpu quota in 10^-9 CPUs (1.5 CPUs = 1_500_000_000)
+    #[sea_orm(column_name = "cpu_limit")]
+    pub cpu_shares: Option<i64>, // relative cpu weight (docker default 1024)
+    pub cpuset_cpus: Option<String>, // cpus to pin (example: "0-3,5")
+
+    pub entrypoint: Option<ArgumentList>, // entrypoint override (argv)
+    pub working_dir: Option<String>,      // working directory inside the container
+    pub user: Option<String>,             // user[:group] to run as
+    pub network: Option<String>,          // network to connect to
+    pub mounts: Option<MountList>,        // bind mounts, named volumes, tmpfs
+    pub labels: Option<Labels>,           // container labels
+    pub shm_size: Option<i64>,            // /dev/shm size in MB
+    pub ulimits: Option<UlimitList>,      // ulimits
+    pub stop_grace_period_seconds: Option<i64>, // SIGTERM -> SIGKILL grace period when stopping
+    pub placement_constraints: Option<PlacementConstraintList>, // node label selectors the job must be placed on
 
     pub created_at: chrono::DateTime<Utc>,
 
@@ -29,3 +53,285 @@ pub struct Model {
 pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
+
+/*
+컨테이너 실행 전에 이미지를 받아오는 정책입니다.
+- Always: 항상 레지스트리에서 pull
+- IfNotPresent: 로컬에 이미지가 없을 때만 pull
+- Never: pull 하지 않음 (로컬에 없으면 실패)
+*/
+#[derive(
+    EnumIter, DeriveActiveEnum, Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+pub enum ImagePullPolicy {
+    #[sea_orm(string_value = "Always")]
+    Always,
+    #[sea_orm(string_value = "IfNotPresent")]
+    #[default]
+    IfNotPresent,
+    #[sea_orm(string_value = "Never")]
+    Never,
+}
+
+pub const NANO_CPUS_PER_CPU: f64 = 1_000_000_000.0;
+
+/// 소수 단위 CPU 개수(예: 1.5)를 docker NanoCPUs 값으로 변환합니다.
+pub fn cpus_to_nano_cpus(cpus: f64) -> i64 {
+    (cpus * NANO_CPUS_PER_CPU).round() as i64
+}
+
+/// docker NanoCPUs 값을 소수 단위 CPU 개수로 변환합니다.
+pub fn nano_cpus_to_cpus(nano_cpus: i64) -> f64 {
+    nano_cpus as f64 / NANO_CPUS_PER_CPU
+}
+
+/*
+컨테이너에 그대로 전달되는 argv 목록입니다. DB에는 JSON 문자열 배열로 저장됩니다.
+예: ["/bin/bash", "-c", "echo hello, world"]
+*/
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct ArgumentList(pub Vec<String>);
+
+impl ArgumentList {
+    /// 공백으로 구분된 기존 command 문자열을 변환합니다.
+    pub fn from_legacy_command(command: &str) -> Self {
+        Self(command.split_whitespace().map(str::to_string).collect())
+    }
+
+    /// 콤마로 구분된 기존 args 문자열을 변환합니다.
+    pub fn from_legacy_args(args: &str) -> Self {
+        Self(
+            args.split(',')
+                .map(str::trim)
+                .filter(|arg| !arg.is_empty())
+                .map(str::to_string)
+                .collect(),
+        )
+    }
+}
+
+/*
+컨테이너 환경 변수 목록입니다. DB에는 JSON 객체로 저장됩니다.
+예: {"DATABASE_URL": "postgres://a,b@host/db", "DEBUG": "1"}
+*/
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct EnvironmentVariables(pub BTreeMap<String, String>);
+
+impl EnvironmentVariables {
+    /// 기존 env 문자열을 변환합니다.
+    /// 웹 UI가 저장하던 `[{"key": ..., "value": ...}]` 형식과 `KEY=VALUE,KEY2=VALUE2` 형식을 모두 지원합니다.
+    pub fn from_legacy_env(env: &str) -> Self {
+        #[derive(Deserialize)]
+        struct KeyValue {
+            key: String,
+            value: String,
+        }
+
+        if let Ok(items) = serde_json::from_str::<Vec<KeyValue>>(env) {
+            return Self(
+                items
+                    .into_iter()
+                    .map(|item| (item.key, item.value))
+                    .collect(),
+            );
+        }
+
+        Self(
+            env.split(',')
+                .map(str::trim)
+                .filter(|env_var| !env_var.is_empty())
+                .map(|env_var| match env_var.split_once('=') {
+                    Some((key, value)) => (key.trim().to_string(), value.to_string()),
+                    None => (env_var.to_string(), String::new()),
+                })
+                .collect(),
+        )
+    }
+}
+
+/*
+컨테이너 실행 시점에 secret 값으로 채워지는 환경 변수 목록입니다. (환경 변수 이름 -> secret 이름)
+값 자체는 secret 테이블에 암호화되어 저장되며, task definition에는 이름만 저장됩니다.
+예: {"DATABASE_PASSWORD": "prod-db-password"}
+*/
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct SecretReferences(pub BTreeMap<String, String>);
+
+/*
+컨테이너에 연결할 마운트 목록입니다. DB에는 JSON 배열로 저장됩니다.
+예: [{"type": "bind", "source": "/data", "target": "/data", "read_only": true}]
+*/
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct MountList(pub Vec<Mount>);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mount {
+    #[serde(rename = "type")]
+    pub mount_type: MountType,
+    pub source: Option<String>, // host path (bind) or volume name (volume). tmpfs는 사용하지 않음
+    pub target: String,         // absolute path inside the container
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MountType {
+    Bind,
+    Volume,
+    Tmpfs,
+}
+
+impl MountType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MountType::Bind => "bind",
+            MountType::Volume => "volume",
+            MountType::Tmpfs => "tmpfs",
+        }
+    }
+}
+
+/*
+컨테이너 라벨 목록입니다. DB에는 JSON 객체로 저장됩니다.
+*/
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct Labels(pub BTreeMap<String, String>);
+
+/*
+컨테이너 ulimit 목록입니다. DB에는 JSON 배열로 저장됩니다.
+예: [{"name": "nofile", "soft": 1024, "hard": 4096}]
+*/
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct UlimitList(pub Vec<Ulimit>);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ulimit {
+    pub name: String, // e.g. nofile, nproc, memlock
+    pub soft: i64,
+    pub hard: i64,
+}
+
+/*
+job을 실행할 node를 고르는 라벨 셀렉터 목록입니다. DB에는 JSON 배열로 저장됩니다.
+모든 조건을 만족하는 node에서만 job이 실행됩니다.
+예: [{"key": "gpu", "operator": "In", "values": ["true"]}, {"key": "spot", "operator": "DoesNotExist"}]
+*/
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct PlacementConstraintList(pub Vec<PlacementConstraint>);
+
+impl PlacementConstraintList {
+    /// node 라벨이 모든 조건을 만족하는지 확인합니다.
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.0.iter().all(|constraint| constraint.matches(labels))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlacementConstraint {
+    pub key: String, // node label name
+    pub operator: PlacementOperator,
+    #[serde(default)]
+    pub values: Vec<String>, // label values (In/NotIn only)
+}
+
+impl PlacementConstraint {
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        let value = labels.get(&self.key);
+
+        match self.operator {
+            PlacementOperator::In => value.is_some_and(|value| self.values.contains(value)),
+            PlacementOperator::NotIn => !value.is_some_and(|value| self.values.contains(value)),
+            PlacementOperator::Exists => value.is_some(),
+            PlacementOperator::DoesNotExist => value.is_none(),
+        }
+    }
+}
+
+/*
+라벨 셀렉터 연산자입니다.
+- In: 라벨 값이 values 중 하나
+- NotIn: 라벨이 없거나 값이 values에 없음
+- Exists: 라벨이 있음 (값은 상관없음)
+- DoesNotExist: 라벨이 없음
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlacementOperator {
+    In,
+    NotIn,
+    Exists,
+    DoesNotExist,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_legacy_command() {
+        assert_eq!(
+            ArgumentList::from_legacy_command("  /bin/echo  hello world "),
+            ArgumentList(vec![
+                "/bin/echo".to_string(),
+                "hello".to_string(),
+                "world".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_from_legacy_env() {
+        let expected = EnvironmentVariables(BTreeMap::from([
+            ("A".to_string(), "1".to_string()),
+            ("B".to_string(), "x=y".to_string()),
+        ]));
+
+        assert_eq!(
+            EnvironmentVariables::from_legacy_env("A=1, B=x=y"),
+            expected
+        );
+        assert_eq!(
+            EnvironmentVariables::from_legacy_env(
+                r#"[{"key":"A","value":"1"},{"key":"B","value":"x=y"}]"#
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_placement_constraints_matches() {
+        let constraints: PlacementConstraintList = serde_json::from_str(
+            r#"[
+                {"key": "gpu", "operator": "In", "values": ["true"]},
+                {"key": "zone", "operator": "NotIn", "values": ["a"]},
+                {"key": "spot", "operator": "DoesNotExist"}
+            ]"#,
+        )
+        .unwrap();
+
+        let labels = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(key, value)| (String::from(*key), String::from(*value)))
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        assert!(constraints.matches(&labels(&[("gpu", "true")])));
+        assert!(constraints.matches(&labels(&[("gpu", "true"), ("zone", "b")])));
+        assert!(!constraints.matches(&labels(&[])));
+        assert!(!constraints.matches(&labels(&[("gpu", "false")])));
+        assert!(!constraints.matches(&labels(&[("gpu", "true"), ("zone", "a")])));
+        assert!(!constraints.matches(&labels(&[("gpu", "true"), ("spot", "")])));
+
+        // 조건이 없다면 모든 node에서 실행 가능
+        assert!(PlacementConstraintList::default().matches(&labels(&[])));
+    }
+}
@@ -6,19 +6,24 @@ use sea_orm::{
 };
 
 use crate::{
+    db::unit_of_work::DatabaseExecutor,
     domain::task_definition::{
         TaskDefinitionRepository,
         dao::{
             CountTaskDefinitionsParams, CreateTaskDefinitionParams, DeleteTaskDefinitionParams,
-            ListTaskDefinitionsParams, PatchTaskDefinitionParams,
+            ListSecretReferrersParams, ListTaskDefinitionsParams, PatchTaskDefinitionParams,
+            SecretReferrer,
         },
         entities,
     },
     errors,
 };
 
+// secret 참조를 찾을 때 한 번에 읽는 task definition 수
+const SECRET_REFERRER_SCAN_BATCH_SIZE: u64 = 500;
+
 pub struct TaskDefinitionSeaOrmRepository {
-    pub connection: sea_orm::DatabaseConnection,
+    pub connection: DatabaseExecutor,
 }
 
 #[async_trait::async_trait]
@@ -48,10 +53,37 @@ impl TaskDefinitionRepository for TaskDefinitionSeaOrmRepository {
                 find_query.filter(entities::task_definition::Column::IsLatest.eq(is_latest));
         }
 
+        if let Some(has_placement_constraints) = params.has_placement_constraints {
+            let column = entities::task_definition::Column::PlacementConstraints;
+
+            find_query = if has_placement_constraints {
+                find_query.filter(column.is_not_null())
+            } else {
+                find_query.filter(column.is_null())
+            };
+        }
+
+        if let Some(after_id) = params.after_id {
+            // id가 아닌 컬럼으로 정렬하면 id cursor가 정렬 순서와 맞지 않아 페이지가 겹치거나 빠짐
+            if params
+                .order_by_desc
+                .is_some_and(|column| !matches!(column, entities::task_definition::Column::Id))
+            {
+                return Err(crate::errors::Error::InvalidTaskDefinition(
+                    "after_id can only be used when sorting by id".to_string(),
+                ));
+            }
+
+            find_query = find_query.filter(entities::task_definition::Column::Id.lt(after_id));
+        }
+
         if let Some(order_by_desc) = params.order_by_desc {
             find_query = find_query.order_by_desc(order_by_desc);
         }
 
+        // 같은 값끼리는 id 순서로 정렬하여 페이지가 겹치거나 빠지지 않도록 함
+        find_query = find_query.order_by_desc(entities::task_definition::Column::Id);
+
         if let Some(limit) = params.limit {
             find_query = find_query.limit(limit);
         }
@@ -80,11 +112,73 @@ impl TaskDefinitionRepository for TaskDefinitionSeaOrmRepository {
                 find_query.filter(entities::task_definition::Column::Name.contains(contains_name));
         }
 
+        if let Some(is_latest) = params.is_latest {
+            find_query =
+                find_query.filter(entities::task_definition::Column::IsLatest.eq(is_latest));
+        }
+
         let count = find_query.count(&self.connection).await?;
 
         Ok(count)
     }
 
+    /*
+    secrets는 JSON 컬럼이라 데이터베이스마다 조회 방법이 다르므로, id/name/secrets만 나눠서 읽고 여기서 걸러냅니다.
+    */
+    async fn list_secret_referrers(
+        &self,
+        params: ListSecretReferrersParams,
+    ) -> errors::Result<Vec<SecretReferrer>> {
+        use entities::task_definition::{Column, SecretReferences};
+
+        let mut referrers = Vec::new();
+        let mut after_id = 0;
+
+        loop {
+            let rows: Vec<(i64, String, Option<SecretReferences>)> =
+                entities::task_definition::Entity::find()
+                    .select_only()
+                    .columns([Column::Id, Column::Name, Column::Secrets])
+                    .filter(Column::Secrets.is_not_null())
+                    .filter(Column::Id.gt(after_id))
+                    .order_by_asc(Column::Id)
+                    .limit(SECRET_REFERRER_SCAN_BATCH_SIZE)
+                    .into_tuple()
+                    .all(&self.connection)
+                    .await?;
+
+            let row_count = rows.len() as u64;
+
+            for (task_definition_id, name, secrets) in rows {
+                after_id = task_definition_id;
+
+                let references_secret = secrets.is_some_and(|secrets| {
+                    secrets
+                        .0
+                        .values()
+                        .any(|secret_name| secret_name == &params.secret_name)
+                });
+
+                if !references_secret {
+                    continue;
+                }
+
+                referrers.push(SecretReferrer {
+                    task_definition_id,
+                    name,
+                });
+
+                if referrers.len() as u64 >= params.limit {
+                    return Ok(referrers);
+                }
+            }
+
+            if row_count < SECRET_REFERRER_SCAN_BATCH_SIZE {
+                return Ok(referrers);
+            }
+        }
+    }
+
     async fn create_task_definition(
         &self,
         params: CreateTaskDefinitionParams,
@@ -94,11 +188,35 @@ impl TaskDefinitionRepository for TaskDefinitionSeaOrmRepository {
             name: Set(params.name),
             version: Set(params.version),
             image: Set(params.image),
-            command: Set(params.command),
-            args: Set(params.args),
-            env: Set(params.env),
-            memory_limit: Set(params.memory_limit),
-            cpu_limit: Set(params.cpu_limit),
+            image_pull_policy: Set(params.image_pull_policy),
+            command: Set(params.command.map(entities::task_definition::ArgumentList)),
+            args: Set(params.args.map(entities::task_definition::ArgumentList)),
+            env: Set(params
+                .env
+                .map(entities::task_definition::EnvironmentVariables)),
+            secrets: Set(params
+                .secrets
+                .map(entities::task_definition::SecretReferences)),
+            memory_limit: Set(params.memory_limit.map(i64::from)),
+            memory_reservation: Set(params.memory_reservation.map(i64::from)),
+            memory_swap: Set(params.memory_swap),
+            nano_cpus: Set(params.nano_cpus),
+            cpu_shares: Set(params.cpu_shares.map(i64::from)),
+            cpuset_cpus: Set(params.cpuset_cpus),
+            entrypoint: Set(params
+                .entrypoint
+                .map(entities::task_definition::ArgumentList)),
+            working_dir: Set(params.working_dir),
+            user: Set(params.user),
+            network: Set(params.network),
+            mounts: Set(params.mounts.map(entities::task_definition::MountList)),
+            labels: Set(params.labels.map(entities::task_definition::Labels)),
+            shm_size: Set(params.shm_size.map(i64::from)),
+            ulimits: Set(params.ulimits.map(entities::task_definition::UlimitList)),
+            stop_grace_period_seconds: Set(params.stop_grace_period_seconds.map(i64::from)),
+            placement_constraints: Set(params
+                .placement_constraints
+                .map(entities::task_definition::PlacementConstraintList)),
             description: Set(params.description),
             created_at: Set(chrono::Utc::now()),
             enabled: Set(true),
@@ -138,24 +256,89 @@ impl TaskDefinitionRepository for TaskDefinitionSeaOrmRepository {
             model.image = Set(image);
         }
 
+        if let Some(image_pull_policy) = params.image_pull_policy {
+            model.image_pull_policy = Set(image_pull_policy);
+        }
+
         if let Some(command) = params.command {
-            model.command = Set(Some(command));
+            model.command = Set(command.map(entities::task_definition::ArgumentList));
         }
 
         if let Some(args) = params.args {
-            model.args = Set(Some(args));
+            model.args = Set(args.map(entities::task_definition::ArgumentList));
         }
 
         if let Some(env) = params.env {
-            model.env = Set(Some(env));
+            model.env = Set(env.map(entities::task_definition::EnvironmentVariables));
+        }
+
+        if let Some(secrets) = params.secrets {
+            model.secrets = Set(secrets.map(entities::task_definition::SecretReferences));
         }
 
         if let Some(memory_limit) = params.memory_limit {
-            model.memory_limit = Set(Some(memory_limit));
+            model.memory_limit = Set(memory_limit.map(Into::into));
+        }
+
+        if let Some(memory_reservation) = params.memory_reservation {
+            model.memory_reservation = Set(memory_reservation.map(Into::into));
         }
 
-        if let Some(cpu_limit) = params.cpu_limit {
-            model.cpu_limit = Set(Some(cpu_limit));
+        if let Some(memory_swap) = params.memory_swap {
+            model.memory_swap = Set(memory_swap);
+        }
+
+        if let Some(nano_cpus) = params.nano_cpus {
+            model.nano_cpus = Set(nano_cpus);
+        }
+
+        if let Some(cpu_shares) = params.cpu_shares {
+            model.cpu_shares = Set(cpu_shares.map(Into::into));
+        }
+
+        if let Some(cpuset_cpus) = params.cpuset_cpus {
+            model.cpuset_cpus = Set(cpuset_cpus);
+        }
+
+        if let Some(entrypoint) = params.entrypoint {
+            model.entrypoint = Set(entrypoint.map(entities::task_definition::ArgumentList));
+        }
+
+        if let Some(working_dir) = params.working_dir {
+            model.working_dir = Set(working_dir);
+        }
+
+        if let Some(user) = params.user {
+            model.user = Set(user);
+        }
+
+        if let Some(network) = params.network {
+            model.network = Set(network);
+        }
+
+        if let Some(mounts) = params.mounts {
+            model.mounts = Set(mounts.map(entities::task_definition::MountList));
+        }
+
+        if let Some(labels) = params.labels {
+            model.labels = Set(labels.map(entities::task_definition::Labels));
+        }
+
+        if let Some(shm_size) = params.shm_size {
+            model.shm_size = Set(shm_size.map(Into::into));
+        }
+
+        if let Some(ulimits) = params.ulimits {
+            model.ulimits = Set(ulimits.map(entities::task_definition::UlimitList));
+        }
+
+        if let Some(stop_grace_period_seconds) = params.stop_grace_period_seconds {
+            model.stop_grace_period_seconds = Set(stop_grace_period_seconds.map(Into::into));
+        }
+
+        if let Some(placement_constraints) = params.placement_constraints {
+            model.placement_constraints =
+                Set(placement_constraints.map(entities::task_definition::PlacementConstraintList));
         }
 
         if let Some(enabled) = params.enabled {
@@ -185,6 +368,8 @@ impl TaskDefinitionRepository for TaskDefinitionSeaOrmRepository {
 
 impl TaskDefinitionSeaOrmRepository {
     pub fn new(connection: sea_orm::DatabaseConnection) -> Self {
-        Self { connection }
+        Self {
+            connection: connection.into(),
+        }
     }
 }
@@ -1,22 +1,18 @@
-use axum::{
-    Extension, Json,
-    body::Body,
-    extract::{Path, Query},
-    http::Response,
-    response::{self, IntoResponse},
-};
+use axum::{Extension, Json};
 
 use crate::{
     context::SharedContext,
     domain::task_definition::{
         dto::{
-            CreateDefinitionRequest, CreateTaskDefinitionBody, DeleteDefinitionRequest,
-            ListTaskDefinitionsItem, ListTaskDefinitionsQuery, ListTaskDefinitionsRequest,
-            PatchDefinitionRequest, PatchTaskDefinitionBody,
+            CreateDefinitionRequest, CreateDefinitionResponse, CreateTaskDefinitionBody,
+            DeleteDefinitionRequest, ListTaskDefinitionsItem, ListTaskDefinitionsQuery,
+            ListTaskDefinitionsRequest, ListTaskDefinitionsResponse, PatchDefinitionRequest,
+            PatchTaskDefinitionBody,
         },
         entities,
     },
     errors,
+    validation::{ValidJson, ValidPath, ValidQuery},
 };
 
 impl From<entities::task_definition::Model> for ListTaskDefinitionsItem {
@@ -27,11 +23,31 @@ impl From<entities::task_definition::Model> for ListTaskDefinitionsItem {
             version: model.version,
             description: model.description,
             image: model.image,
-            command: model.command,
-            args: model.args,
-            env: model.env,
-            memory_limit: model.memory_limit,
-            cpu_limit: model.cpu_limit,
+            image_pull_policy: model.image_pull_policy,
+            command: model.command.map(|command| command.0),
+            args: model.args.map(|args| args.0),
+            env: model.env.map(|env| env.0),
+            secrets: model.secrets.map(|secrets| secrets.0),
+            memory_limit: model.memory_limit.map(|value| value as u32),
+            memory_reservation: model.memory_reservation.map(|value| value as u32),
+            memory_swap: model.memory_swap,
+            cpus: model
+                .nano_cpus
+                .map(entities::task_definition::nano_cpus_to_cpus),
+            cpu_shares: model.cpu_shares.map(|value| value as u32),
+            cpuset_cpus: model.cpuset_cpus,
+            entrypoint: model.entrypoint.map(|entrypoint| entrypoint.0),
+            working_dir: model.working_dir,
+            user: model.user,
+            network: model.network,
+            mounts: model.mounts.map(|mounts| mounts.0),
+            labels: model.labels.map(|labels| labels.0),
+            shm_size: model.shm_size.map(|value| value as u32),
+            ulimits: model.ulimits.map(|ulimits| ulimits.0),
+            stop_grace_period_seconds: model.stop_grace_period_seconds.map(|value| value as u32),
+            placement_constraints: model
+                .placement_constraints
+                .map(|placement_constraints| placement_constraints.0),
             created_at: model.created_at,
             enabled: model.enabled,
             is_latest: model.is_latest,
@@ -40,83 +56,53 @@ impl From<entities::task_definition::Model> for ListTaskDefinitionsItem {
 }
 
 pub async fn list_task_definitions(
-    Query(query): Query<ListTaskDefinitionsQuery>,
+    ValidQuery(query): ValidQuery<ListTaskDefinitionsQuery>,
     Extension(context): Extension<SharedContext>,
-) -> response::Response {
+) -> errors::Result<Json<ListTaskDefinitionsResponse>> {
     let response = context
         .task_definition_service
         .list_task_definitions(ListTaskDefinitionsRequest { query })
-        .await;
+        .await?;
 
-    match response {
-        Ok(response) => Json(response).into_response(),
-        Err(error) => Response::builder()
-            .status(500)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+    Ok(Json(response))
 }
 
 pub async fn create_task_definition(
     Extension(context): Extension<SharedContext>,
-    Json(body): Json<CreateTaskDefinitionBody>,
-) -> response::Response {
-    let task_definition_id = context
+    ValidJson(body): ValidJson<CreateTaskDefinitionBody>,
+) -> errors::Result<Json<CreateDefinitionResponse>> {
+    let response = context
         .task_definition_service
         .create_task_definition(CreateDefinitionRequest { request_body: body })
-        .await;
+        .await?;
 
-    match task_definition_id {
-        Ok(task_definition_id) => Json(task_definition_id).into_response(),
-        Err(error) => Response::builder()
-            .status(500)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+    Ok(Json(response))
 }
 
 pub async fn patch_task_definition(
-    Path(task_definition_id): Path<i64>,
+    ValidPath(task_definition_id): ValidPath<i64>,
     Extension(context): Extension<SharedContext>,
-    Json(query): Json<PatchTaskDefinitionBody>,
-) -> response::Response {
-    let result = context
+    ValidJson(query): ValidJson<PatchTaskDefinitionBody>,
+) -> errors::Result<Json<()>> {
+    context
         .task_definition_service
         .patch_task_definition(PatchDefinitionRequest {
             task_definition_id,
             request: query,
         })
-        .await;
+        .await?;
 
-    match result {
-        Ok(_) => Response::builder().status(200).body(Body::empty()).unwrap(),
-        Err(errors::Error::TaskDefinitionNotFound) => Response::builder()
-            .status(404)
-            .body(Body::new(
-                errors::Error::TaskDefinitionNotFound.into_json_response(),
-            ))
-            .unwrap(),
-        Err(error) => Response::builder()
-            .status(500)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+    Ok(Json(()))
 }
 
 pub async fn delete_task_definition(
-    Path(task_definition_id): Path<i64>,
+    ValidPath(task_definition_id): ValidPath<i64>,
     Extension(context): Extension<SharedContext>,
-) -> response::Response {
-    let result = context
+) -> errors::Result<Json<()>> {
+    context
         .task_definition_service
         .delete_task_definition(DeleteDefinitionRequest { task_definition_id })
-        .await;
+        .await?;
 
-    match result {
-        Ok(_) => Response::builder().status(200).body(Body::empty()).unwrap(),
-        Err(error) => Response::builder()
-            .status(500)
-            .body(Body::new(error.into_json_response()))
-            .unwrap(),
-    }
+    Ok(Json(()))
 }
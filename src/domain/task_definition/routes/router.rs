@@ -0,0 +1,27 @@
+use crate::openapi::router::ApiRouter;
+
+use super::http;
+
+pub fn router() -> ApiRouter {
+    ApiRouter::new("task-definitions")
+        .get(
+            "/task-definitions",
+            "List task definitions",
+            http::list_task_definitions,
+        )
+        .post(
+            "/task-definitions",
+            "Create a task definition (a new version if the name exists)",
+            http::create_task_definition,
+        )
+        .patch(
+            "/task-definitions/{task_definition_id}",
+            "Update a task definition",
+            http::patch_task_definition,
+        )
+        .delete(
+            "/task-definitions/{task_definition_id}",
+            "Delete a task definition",
+            http::delete_task_definition,
+        )
+}
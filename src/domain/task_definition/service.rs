@@ -1,11 +1,18 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use crate::{
-    domain::task_definition::{
-        dao::CountTaskDefinitionsParams,
-        dto::{CreateDefinitionResponse, ListTaskDefinitionsItem, ListTaskDefinitionsResponse},
+    domain::{
+        secret::{
+            SecretRepository,
+            dao::{ListSecretsParams, SecretLock},
+        },
+        task_definition::{
+            dao::CountTaskDefinitionsParams,
+            dto::{CreateDefinitionResponse, ListTaskDefinitionsItem, ListTaskDefinitionsResponse},
+        },
+        unit_of_work::UnitOfWorkFactory,
     },
-    errors,
+    errors, utils,
 };
 
 use super::{
@@ -18,20 +25,94 @@ use super::{
         CreateDefinitionRequest, DeleteDefinitionRequest, ListTaskDefinitionsRequest,
         PatchDefinitionRequest,
     },
-    entities,
+    entities::{
+        self,
+        task_definition::{self, Mount, MountType, PlacementConstraint, PlacementOperator, Ulimit},
+    },
 };
 
 pub struct TaskDefinitionServiceImpl {
     pub task_definition_repository: Arc<dyn TaskDefinitionRepository + Send + Sync>,
+    pub unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
 }
 
 impl TaskDefinitionServiceImpl {
     pub fn new(
         task_definition_repository: Arc<dyn TaskDefinitionRepository + Send + Sync>,
+        unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
     ) -> Self {
         Self {
             task_definition_repository,
+            unit_of_work_factory,
+        }
+    }
+
+    /*
+    secret 참조를 검증합니다.
+    - 환경 변수 이름은 [A-Za-z_][A-Za-z0-9_]* 이어야 하고, env와 같은 이름을 사용할 수 없습니다.
+    - PATH, HOME 등 예약된 이름과 DOCKER_로 시작하는 이름은 사용할 수 없습니다.
+    - 참조하는 secret이 모두 존재해야 합니다. 저장이 끝날 때까지 삭제되지 않도록 unit of work 안에서 secret 행을 잠급니다.
+    */
+    async fn validate_secret_references(
+        secret_repository: &(dyn SecretRepository + Send + Sync),
+        secrets: Option<&BTreeMap<String, String>>,
+        env: Option<&BTreeMap<String, String>>,
+    ) -> errors::Result<()> {
+        let Some(secrets) = secrets else {
+            return Ok(());
+        };
+
+        for (env_name, secret_name) in secrets {
+            if !is_portable_env_name(env_name) {
+                return Err(errors::Error::InvalidTaskDefinition(format!(
+                    "invalid environment variable name: {:?}",
+                    env_name
+                )));
+            }
+
+            if is_reserved_secret_env_name(env_name) {
+                return Err(errors::Error::InvalidTaskDefinition(format!(
+                    "environment variable {} is reserved and cannot be set from a secret",
+                    env_name
+                )));
+            }
+
+            if env.is_some_and(|env| env.contains_key(env_name)) {
+                return Err(errors::Error::InvalidTaskDefinition(format!(
+                    "environment variable {} is defined in both env and secrets",
+                    env_name
+                )));
+            }
+
+            if secret_name.trim().is_empty() {
+                return Err(errors::Error::InvalidTaskDefinition(format!(
+                    "secret name for environment variable {} must not be empty",
+                    env_name
+                )));
+            }
+        }
+
+        let names: Vec<String> = secrets.values().cloned().collect();
+
+        let found = secret_repository
+            .list_secrets(ListSecretsParams {
+                names: names.clone(),
+                lock: Some(SecretLock::Share),
+                ..Default::default()
+            })
+            .await?;
+
+        if let Some(missing) = names
+            .iter()
+            .find(|name| !found.iter().any(|secret| &secret.name == *name))
+        {
+            return Err(errors::Error::InvalidTaskDefinition(format!(
+                "secret {} does not exist",
+                missing
+            )));
         }
+
+        Ok(())
     }
 }
 
@@ -41,13 +122,51 @@ impl super::TaskDefinitionService for TaskDefinitionServiceImpl {
         &self,
         request: CreateDefinitionRequest,
     ) -> errors::Result<CreateDefinitionResponse> {
+        validate_container_spec(
+            request.request_body.entrypoint.as_ref(),
+            request.request_body.command.as_ref(),
+            request.request_body.args.as_ref(),
+            request.request_body.env.as_ref(),
+        )?;
+
+        validate_runtime_options(
+            request.request_body.working_dir.as_deref(),
+            request.request_body.user.as_deref(),
+            request.request_body.network.as_deref(),
+            request.request_body.mounts.as_ref(),
+            request.request_body.labels.as_ref(),
+            request.request_body.shm_size,
+            request.request_body.ulimits.as_ref(),
+        )?;
+
+        validate_placement_constraints(request.request_body.placement_constraints.as_ref())?;
+
+        validate_resource_limits(&ResourceLimits {
+            memory_limit: request.request_body.memory_limit,
+            memory_reservation: request.request_body.memory_reservation,
+            memory_swap: request.request_body.memory_swap,
+            cpus: request.request_body.cpus,
+            cpu_shares: request.request_body.cpu_shares,
+            cpuset_cpus: request.request_body.cpuset_cpus.as_deref(),
+        })?;
+
+        // 이전 버전의 is_latest 변경과 새 버전 추가가 함께 반영되도록 하나의 트랜잭션으로 처리
+        let unit_of_work = self.unit_of_work_factory.begin().await?;
+        let task_definition_repository = unit_of_work.task_definition_repository();
+
+        Self::validate_secret_references(
+            unit_of_work.secret_repository(),
+            request.request_body.secrets.as_ref(),
+            request.request_body.env.as_ref(),
+        )
+        .await?;
+
         // version이 없다면 동일한 이름의 task definition이 있는지 확인
 
         let mut version = 1;
 
         {
-            let task_definitions = self
-                .task_definition_repository
+            let task_definitions = task_definition_repository
                 .list_task_definitions(ListTaskDefinitionsParams {
                     name: Some(request.request_body.name.clone()),
                     limit: Some(1),
@@ -59,8 +178,8 @@ impl super::TaskDefinitionService for TaskDefinitionServiceImpl {
             if !task_definitions.is_empty() {
                 version = task_definitions[0].version + 1;
 
-                // 기존 작업정의의 is_latest 를 false 로 변경 (TODO: Transaction 처리 필요)
-                self.task_definition_repository
+                // 기존 작업정의의 is_latest 를 false 로 변경
+                task_definition_repository
                     .patch_task_definition(PatchTaskDefinitionParams {
                         task_definition_id: task_definitions[0].id,
                         is_latest: Some(false),
@@ -70,29 +189,73 @@ impl super::TaskDefinitionService for TaskDefinitionServiceImpl {
             }
         }
 
-        let task_definition_id = self
-            .task_definition_repository
+        let task_definition_id = task_definition_repository
             .create_task_definition(CreateTaskDefinitionParams {
                 name: request.request_body.name,
                 description: request.request_body.description,
                 version,
                 image: request.request_body.image,
+                image_pull_policy: request.request_body.image_pull_policy,
                 command: request.request_body.command,
                 args: request.request_body.args,
                 env: request.request_body.env,
+                secrets: request.request_body.secrets,
                 memory_limit: request.request_body.memory_limit,
-                cpu_limit: request.request_body.cpu_limit,
+                memory_reservation: request.request_body.memory_reservation,
+                memory_swap: request.request_body.memory_swap,
+                nano_cpus: request
+                    .request_body
+                    .cpus
+                    .map(task_definition::cpus_to_nano_cpus),
+                cpu_shares: request.request_body.cpu_shares,
+                cpuset_cpus: request.request_body.cpuset_cpus,
+                entrypoint: request.request_body.entrypoint,
+                working_dir: request.request_body.working_dir,
+                user: request.request_body.user,
+                network: request.request_body.network,
+                mounts: request.request_body.mounts,
+                labels: request.request_body.labels,
+                shm_size: request.request_body.shm_size,
+                ulimits: request.request_body.ulimits,
+                stop_grace_period_seconds: request.request_body.stop_grace_period_seconds,
+                placement_constraints: request.request_body.placement_constraints,
             })
             .await?;
 
+        unit_of_work.commit().await?;
+
         Ok(CreateDefinitionResponse { task_definition_id })
     }
 
     async fn patch_task_definition(&self, request: PatchDefinitionRequest) -> errors::Result<()> {
+        let body = request.request;
+
+        validate_container_spec(
+            body.entrypoint.value(),
+            body.command.value(),
+            body.args.value(),
+            body.env.value(),
+        )?;
+
+        validate_runtime_options(
+            body.working_dir.value().map(String::as_str),
+            body.user.value().map(String::as_str),
+            body.network.value().map(String::as_str),
+            body.mounts.value(),
+            body.labels.value(),
+            body.shm_size.value().copied(),
+            body.ulimits.value(),
+        )?;
+
+        validate_placement_constraints(body.placement_constraints.value())?;
+
+        // secret 참조 검증과 변경이 함께 반영되도록 하나의 트랜잭션으로 처리
+        let unit_of_work = self.unit_of_work_factory.begin().await?;
+        let task_definition_repository = unit_of_work.task_definition_repository();
+
         // version이 없다면 동일한 이름의 task definition이 있는지 확인
 
-        let task_definitions = self
-            .task_definition_repository
+        let task_definitions = task_definition_repository
             .list_task_definitions(ListTaskDefinitionsParams {
                 task_definition_ids: vec![request.task_definition_id],
                 limit: Some(1),
@@ -104,23 +267,76 @@ impl super::TaskDefinitionService for TaskDefinitionServiceImpl {
             return Err(errors::Error::TaskDefinitionNotFound);
         }
 
-        let _ = self
-            .task_definition_repository
+        // 변경 후의 값(생략한 필드는 현재 값, null은 삭제) 기준으로 리소스 제한을 검증
+        let current = &task_definitions[0];
+
+        validate_resource_limits(&ResourceLimits {
+            memory_limit: body
+                .memory_limit
+                .apply(current.memory_limit.map(|value| value as u32)),
+            memory_reservation: body
+                .memory_reservation
+                .apply(current.memory_reservation.map(|value| value as u32)),
+            memory_swap: body.memory_swap.apply(current.memory_swap),
+            cpus: body
+                .cpus
+                .apply(current.nano_cpus.map(task_definition::nano_cpus_to_cpus)),
+            cpu_shares: body
+                .cpu_shares
+                .apply(current.cpu_shares.map(|value| value as u32)),
+            cpuset_cpus: body
+                .cpuset_cpus
+                .as_ref()
+                .map(String::as_str)
+                .apply(current.cpuset_cpus.as_deref()),
+        })?;
+
+        // 변경 후의 env/secrets 기준으로 secret 참조를 검증
+        let current_env = current.env.as_ref().map(|env| &env.0);
+        let current_secrets = current.secrets.as_ref().map(|secrets| &secrets.0);
+
+        Self::validate_secret_references(
+            unit_of_work.secret_repository(),
+            body.secrets.as_ref().apply(current_secrets),
+            body.env.as_ref().apply(current_env),
+        )
+        .await?;
+
+        task_definition_repository
             .patch_task_definition(PatchTaskDefinitionParams {
                 task_definition_id: request.task_definition_id,
-                description: request.request.description,
-                image: request.request.image,
-                command: request.request.command,
-                args: request.request.args,
-                env: request.request.env,
-                memory_limit: request.request.memory_limit,
-                cpu_limit: request.request.cpu_limit,
-                enabled: request.request.enabled,
+                description: body.description,
+                image: body.image,
+                image_pull_policy: body.image_pull_policy.into_update(),
+                command: body.command.into_update(),
+                args: body.args.into_update(),
+                env: body.env.into_update(),
+                secrets: body.secrets.into_update(),
+                memory_limit: body.memory_limit.into_update(),
+                memory_reservation: body.memory_reservation.into_update(),
+                memory_swap: body.memory_swap.into_update(),
+                nano_cpus: body
+                    .cpus
+                    .map(task_definition::cpus_to_nano_cpus)
+                    .into_update(),
+                cpu_shares: body.cpu_shares.into_update(),
+                cpuset_cpus: body.cpuset_cpus.into_update(),
+                entrypoint: body.entrypoint.into_update(),
+                working_dir: body.working_dir.into_update(),
+                user: body.user.into_update(),
+                network: body.network.into_update(),
+                mounts: body.mounts.into_update(),
+                labels: body.labels.into_update(),
+                shm_size: body.shm_size.into_update(),
+                ulimits: body.ulimits.into_update(),
+                stop_grace_period_seconds: body.stop_grace_period_seconds.into_update(),
+                placement_constraints: body.placement_constraints.into_update(),
+                enabled: body.enabled,
                 ..Default::default()
             })
-            .await;
+            .await?;
 
-        Ok(())
+        unit_of_work.commit().await
     }
 
     async fn delete_task_definition(&self, params: DeleteDefinitionRequest) -> errors::Result<()> {
@@ -137,10 +353,16 @@ impl super::TaskDefinitionService for TaskDefinitionServiceImpl {
         &self,
         params: ListTaskDefinitionsRequest,
     ) -> errors::Result<ListTaskDefinitionsResponse> {
-        let limit = params.query.page_size;
-        let offset = (params.query.page_number - 1) * params.query.page_size;
+        let limit = params.query.page_size.unwrap_or(10);
+        // cursor(after_id)가 있다면 offset 대신 cursor 이후부터 조회
+        let offset = match params.query.after_id {
+            Some(_) => 0,
+            None => (params.query.page_number.unwrap_or(1) - 1) * limit,
+        };
+        // is_latest_only=false는 모든 버전을 조회
+        let is_latest = params.query.is_latest_only.filter(|is_latest| *is_latest);
 
-        let task_definitions = self
+        let mut task_definitions = self
             .task_definition_repository
             .list_task_definitions(ListTaskDefinitionsParams {
                 task_definition_ids: match params.query.task_definition_id {
@@ -149,22 +371,24 @@ impl super::TaskDefinitionService for TaskDefinitionServiceImpl {
                 },
                 name: params.query.name.clone(),
                 contains_name: params.query.contains_name.clone(),
-                is_latest: if let Some(true) = params.query.is_latest_only {
-                    Some(true)
-                } else {
-                    None
-                },
-                limit: Some(limit),
+                is_latest,
+                // 다음 페이지가 있는지 확인하기 위해 하나 더 조회
+                limit: Some(limit + 1),
                 offset: Some(offset),
+                after_id: params.query.after_id,
                 ..Default::default()
             })
             .await?;
+        let next_cursor = utils::take_page(&mut task_definitions, limit, |task_definition| {
+            task_definition.id
+        });
 
         let total_count = self
             .task_definition_repository
             .count_task_definitions(CountTaskDefinitionsParams {
                 name: params.query.name,
                 contains_name: params.query.contains_name,
+                is_latest,
             })
             .await?;
 
@@ -174,8 +398,708 @@ impl super::TaskDefinitionService for TaskDefinitionServiceImpl {
                 .map(ListTaskDefinitionsItem::from)
                 .collect(),
             total_count,
+            next_cursor,
         };
 
         Ok(response)
     }
 }
+
+/*
+컨테이너 런타임에 그대로 전달될 entrypoint/command/args/env 값을 검증합니다.
+- command는 비어있지 않은 실행 파일 경로로 시작해야 합니다.
+- 환경 변수 키는 비어있을 수 없고 '='를 포함할 수 없습니다.
+- 모든 값에 NUL 문자는 허용되지 않습니다.
+*/
+fn validate_container_spec(
+    entrypoint: Option<&Vec<String>>,
+    command: Option<&Vec<String>>,
+    args: Option<&Vec<String>>,
+    env: Option<&BTreeMap<String, String>>,
+) -> errors::Result<()> {
+    if let Some(command) = command {
+        if let Some(executable) = command.first()
+            && executable.trim().is_empty()
+        {
+            return Err(errors::Error::InvalidTaskDefinition(
+                "command must start with a non-empty executable".to_string(),
+            ));
+        }
+
+        if command.iter().any(|arg| arg.contains('\0')) {
+            return Err(errors::Error::InvalidTaskDefinition(
+                "command must not contain NUL characters".to_string(),
+            ));
+        }
+    }
+
+    if let Some(entrypoint) = entrypoint
+        && entrypoint.iter().any(|arg| arg.contains('\0'))
+    {
+        return Err(errors::Error::InvalidTaskDefinition(
+            "entrypoint must not contain NUL characters".to_string(),
+        ));
+    }
+
+    if let Some(args) = args
+        && args.iter().any(|arg| arg.contains('\0'))
+    {
+        return Err(errors::Error::InvalidTaskDefinition(
+            "args must not contain NUL characters".to_string(),
+        ));
+    }
+
+    if let Some(env) = env {
+        for (key, value) in env {
+            if key.is_empty() || key.contains('=') || key.contains('\0') {
+                return Err(errors::Error::InvalidTaskDefinition(format!(
+                    "invalid environment variable name: {:?}",
+                    key
+                )));
+            }
+
+            if value.contains('\0') {
+                return Err(errors::Error::InvalidTaskDefinition(format!(
+                    "environment variable {} must not contain NUL characters",
+                    key
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/*
+마운트, 라벨, ulimit 등 컨테이너 실행 옵션을 검증합니다.
+*/
+fn validate_runtime_options(
+    working_dir: Option<&str>,
+    user: Option<&str>,
+    network: Option<&str>,
+    mounts: Option<&Vec<Mount>>,
+    labels: Option<&BTreeMap<String, String>>,
+    shm_size: Option<u32>,
+    ulimits: Option<&Vec<Ulimit>>,
+) -> errors::Result<()> {
+    let invalid = |reason: String| Err(errors::Error::InvalidTaskDefinition(reason));
+
+    if let Some(working_dir) = working_dir
+        && !working_dir.starts_with('/')
+    {
+        return invalid("working_dir must be an absolute path".to_string());
+    }
+
+    if user.is_some_and(|user| user.trim().is_empty()) {
+        return invalid("user must not be empty".to_string());
+    }
+
+    if network.is_some_and(|network| network.trim().is_empty()) {
+        return invalid("network must not be empty".to_string());
+    }
+
+    for mount in mounts.into_iter().flatten() {
+        if !mount.target.starts_with('/') {
+            return invalid(format!(
+                "mount target must be an absolute path: {:?}",
+                mount.target
+            ));
+        }
+
+        match (&mount.mount_type, mount.source.as_deref()) {
+            (MountType::Bind, Some(source)) if !source.starts_with('/') => {
+                return invalid(format!(
+                    "bind mount source must be an absolute path: {:?}",
+                    source
+                ));
+            }
+            (MountType::Volume, Some(source)) if source.trim().is_empty() => {
+                return invalid("volume mount source must not be empty".to_string());
+            }
+            (MountType::Bind | MountType::Volume, None) => {
+                return invalid(format!(
+                    "{} mount requires a source",
+                    mount.mount_type.as_str()
+                ));
+            }
+            (MountType::Tmpfs, Some(_)) => {
+                return invalid("tmpfs mount must not have a source".to_string());
+            }
+            _ => {}
+        }
+
+        // --mount 옵션은 콤마로 구분된 key=value 형식이므로 콤마를 허용하지 않음
+        if mount.target.contains(',') || mount.source.as_deref().is_some_and(|s| s.contains(',')) {
+            return invalid("mount source/target must not contain ','".to_string());
+        }
+    }
+
+    for key in labels.into_iter().flat_map(|labels| labels.keys()) {
+        if key.trim().is_empty() || key.contains('=') {
+            return invalid(format!("invalid label name: {:?}", key));
+        }
+
+        // batchman이 컨테이너 관리에 사용하는 라벨은 덮어쓸 수 없음
+        if key.starts_with("batchman.") {
+            return invalid(format!("label name {:?} is reserved", key));
+        }
+    }
+
+    if shm_size == Some(0) {
+        return invalid("shm_size must be greater than 0".to_string());
+    }
+
+    for ulimit in ulimits.into_iter().flatten() {
+        if ulimit.name.trim().is_empty() || ulimit.name.contains('=') {
+            return invalid(format!("invalid ulimit name: {:?}", ulimit.name));
+        }
+
+        // -1은 unlimited
+        if ulimit.soft < -1 || ulimit.hard < -1 {
+            return invalid(format!("ulimit {} must be -1 or greater", ulimit.name));
+        }
+
+        if ulimit.hard != -1 && (ulimit.soft == -1 || ulimit.soft > ulimit.hard) {
+            return invalid(format!(
+                "ulimit {} soft limit must not exceed hard limit",
+                ulimit.name
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/*
+placement constraint(node 라벨 셀렉터)를 검증합니다.
+In/NotIn은 values가 필요하고, Exists/DoesNotExist는 values를 가질 수 없습니다.
+*/
+fn validate_placement_constraints(
+    placement_constraints: Option<&Vec<PlacementConstraint>>,
+) -> errors::Result<()> {
+    let invalid = |reason: String| Err(errors::Error::InvalidTaskDefinition(reason));
+
+    for constraint in placement_constraints.into_iter().flatten() {
+        if constraint.key.trim().is_empty() || constraint.key.contains('=') {
+            return invalid(format!(
+                "invalid placement constraint key: {:?}",
+                constraint.key
+            ));
+        }
+
+        match constraint.operator {
+            PlacementOperator::In | PlacementOperator::NotIn if constraint.values.is_empty() => {
+                return invalid(format!(
+                    "placement constraint {} {:?} requires values",
+                    constraint.key, constraint.operator
+                ));
+            }
+            PlacementOperator::Exists | PlacementOperator::DoesNotExist
+                if !constraint.values.is_empty() =>
+            {
+                return invalid(format!(
+                    "placement constraint {} {:?} must not have values",
+                    constraint.key, constraint.operator
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+struct ResourceLimits<'a> {
+    memory_limit: Option<u32>,
+    memory_reservation: Option<u32>,
+    memory_swap: Option<i64>,
+    cpus: Option<f64>,
+    cpu_shares: Option<u32>,
+    cpuset_cpus: Option<&'a str>,
+}
+
+/*
+CPU/메모리 제한 값을 검증합니다.
+- cpus는 hard limit(CFS quota)이며, cpu_shares는 경합 시에만 적용되는 상대 가중치입니다.
+- memory_reservation은 memory_limit보다 클 수 없습니다.
+- memory_swap은 memory_limit과 함께 설정해야 하며, memory_limit 이상이거나 -1(무제한)이어야 합니다.
+*/
+fn validate_resource_limits(limits: &ResourceLimits) -> errors::Result<()> {
+    let invalid = |reason: String| Err(errors::Error::InvalidTaskDefinition(reason));
+
+    if let Some(cpus) = limits.cpus
+        && (!cpus.is_finite() || cpus < 0.01)
+    {
+        return invalid("cpus must be 0.01 or greater".to_string());
+    }
+
+    // docker(cgroup v1) cpu.shares 허용 범위
+    if let Some(cpu_shares) = limits.cpu_shares
+        && !(2..=262144).contains(&cpu_shares)
+    {
+        return invalid("cpu_shares must be between 2 and 262144".to_string());
+    }
+
+    if let Some(cpuset_cpus) = limits.cpuset_cpus
+        && !is_valid_cpuset(cpuset_cpus)
+    {
+        return invalid(format!(
+            "cpuset_cpus must be a list of cpus or ranges (example: \"0-3,5\"): {:?}",
+            cpuset_cpus
+        ));
+    }
+
+    if limits.memory_limit == Some(0) {
+        return invalid("memory_limit must be greater than 0".to_string());
+    }
+
+    if limits.memory_reservation == Some(0) {
+        return invalid("memory_reservation must be greater than 0".to_string());
+    }
+
+    if let (Some(memory_limit), Some(memory_reservation)) =
+        (limits.memory_limit, limits.memory_reservation)
+        && memory_reservation > memory_limit
+    {
+        return invalid("memory_reservation must not exceed memory_limit".to_string());
+    }
+
+    if let Some(memory_swap) = limits.memory_swap {
+        let Some(memory_limit) = limits.memory_limit else {
+            return invalid("memory_swap requires memory_limit".to_string());
+        };
+
+        if memory_swap != -1 && memory_swap < memory_limit as i64 {
+            return invalid(
+                "memory_swap must be -1 or greater than or equal to memory_limit".to_string(),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// "0-3,5" 형식의 cpuset 문자열인지 확인합니다.
+fn is_valid_cpuset(cpuset: &str) -> bool {
+    let is_cpu = |cpu: &str| !cpu.is_empty() && cpu.bytes().all(|b| b.is_ascii_digit());
+
+    cpuset.split(',').all(|part| match part.split_once('-') {
+        Some((start, end)) => {
+            is_cpu(start) && is_cpu(end) && start.parse::<u32>().ok() <= end.parse::<u32>().ok()
+        }
+        None => is_cpu(part),
+    })
+}
+
+// secret으로 설정할 수 없는 환경 변수 이름 (DOCKER_로 시작하는 이름도 예약됨)
+const RESERVED_SECRET_ENV_NAMES: &[&str] = &["PATH", "HOME", "HOSTNAME"];
+
+/// 셸과 docker --env-file에서 그대로 쓸 수 있는 [A-Za-z_][A-Za-z0-9_]* 형식의 이름인지 확인합니다.
+fn is_portable_env_name(name: &str) -> bool {
+    let mut bytes = name.bytes();
+
+    bytes
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == b'_')
+        && bytes.all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+}
+
+fn is_reserved_secret_env_name(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+
+    RESERVED_SECRET_ENV_NAMES.contains(&upper.as_str()) || upper.starts_with("DOCKER_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Config,
+        context::{Context, SharedContext},
+        domain::container::repository::fake::FakeContainerRepository,
+    };
+
+    fn mount(mount_type: MountType, source: Option<&str>, target: &str) -> Mount {
+        Mount {
+            mount_type,
+            source: source.map(str::to_string),
+            target: target.to_string(),
+            read_only: false,
+        }
+    }
+
+    fn ulimit(soft: i64, hard: i64) -> Ulimit {
+        Ulimit {
+            name: "nofile".to_string(),
+            soft,
+            hard,
+        }
+    }
+
+    #[test]
+    fn test_validate_runtime_options() {
+        let validate_mounts = |mounts: Vec<Mount>| {
+            validate_runtime_options(None, None, None, Some(&mounts), None, None, None)
+        };
+        let validate_ulimits = |ulimits: Vec<Ulimit>| {
+            validate_runtime_options(None, None, None, None, None, None, Some(&ulimits))
+        };
+
+        assert!(
+            validate_mounts(vec![
+                mount(MountType::Bind, Some("/data"), "/data"),
+                mount(MountType::Volume, Some("cache"), "/cache"),
+                mount(MountType::Tmpfs, None, "/tmp"),
+            ])
+            .is_ok()
+        );
+
+        for invalid_mount in [
+            mount(MountType::Bind, Some("/data"), "data"),
+            mount(MountType::Bind, Some("./data"), "/data"),
+            mount(MountType::Bind, None, "/data"),
+            mount(MountType::Volume, Some(" "), "/cache"),
+            mount(MountType::Tmpfs, Some("/tmp"), "/tmp"),
+            mount(MountType::Bind, Some("/data,readonly"), "/data"),
+        ] {
+            assert!(
+                matches!(
+                    validate_mounts(vec![invalid_mount.clone()]),
+                    Err(errors::Error::InvalidTaskDefinition(_))
+                ),
+                "{:?}",
+                invalid_mount
+            );
+        }
+
+        assert!(validate_ulimits(vec![ulimit(1024, 4096), ulimit(-1, -1), ulimit(10, -1)]).is_ok());
+
+        // soft > hard, -1보다 작은 값, hard만 제한하고 soft는 무제한
+        for invalid_ulimit in [ulimit(4096, 1024), ulimit(-2, 10), ulimit(-1, 10)] {
+            assert!(
+                validate_ulimits(vec![invalid_ulimit.clone()]).is_err(),
+                "{:?}",
+                invalid_ulimit
+            );
+        }
+        assert!(
+            validate_ulimits(vec![Ulimit {
+                name: "no=file".to_string(),
+                soft: 1,
+                hard: 1,
+            }])
+            .is_err()
+        );
+
+        let labels = BTreeMap::from([("batchman.job_id".to_string(), "1".to_string())]);
+        assert!(
+            validate_runtime_options(None, None, None, None, Some(&labels), None, None).is_err()
+        );
+        assert!(
+            validate_runtime_options(Some("work"), None, None, None, None, None, None).is_err()
+        );
+        assert!(validate_runtime_options(None, Some(" "), None, None, None, None, None).is_err());
+        assert!(validate_runtime_options(None, None, None, None, None, Some(0), None).is_err());
+    }
+
+    #[test]
+    fn test_is_valid_cpuset() {
+        for cpuset in ["0", "0-3", "0-3,5", "1,3,5-7", "2-2"] {
+            assert!(is_valid_cpuset(cpuset), "{}", cpuset);
+        }
+
+        for cpuset in [
+            "", "a", "0-", "-3", "3-1", "0,,1", "0-3,", " 1", "1-2-3", "0x1",
+        ] {
+            assert!(!is_valid_cpuset(cpuset), "{:?}", cpuset);
+        }
+    }
+
+    #[test]
+    fn test_validate_resource_limits() {
+        let limits = || ResourceLimits {
+            memory_limit: Some(512),
+            memory_reservation: Some(256),
+            memory_swap: Some(1024),
+            cpus: Some(1.5),
+            cpu_shares: Some(1024),
+            cpuset_cpus: Some("0-3"),
+        };
+        let is_invalid = |limits: ResourceLimits| {
+            matches!(
+                validate_resource_limits(&limits),
+                Err(errors::Error::InvalidTaskDefinition(_))
+            )
+        };
+
+        assert!(validate_resource_limits(&limits()).is_ok());
+        assert!(
+            validate_resource_limits(&ResourceLimits {
+                memory_swap: Some(-1),
+                ..limits()
+            })
+            .is_ok()
+        );
+
+        // memory_reservation은 memory_limit보다 클 수 없음
+        assert!(is_invalid(ResourceLimits {
+            memory_reservation: Some(1024),
+            ..limits()
+        }));
+        // memory_swap은 memory_limit 이상이거나 -1이어야 하고, memory_limit 없이 설정할 수 없음
+        assert!(is_invalid(ResourceLimits {
+            memory_swap: Some(256),
+            ..limits()
+        }));
+        assert!(is_invalid(ResourceLimits {
+            memory_limit: None,
+            memory_reservation: None,
+            ..limits()
+        }));
+        assert!(is_invalid(ResourceLimits {
+            memory_limit: Some(0),
+            ..limits()
+        }));
+        assert!(is_invalid(ResourceLimits {
+            cpus: Some(0.001),
+            ..limits()
+        }));
+        assert!(is_invalid(ResourceLimits {
+            cpus: Some(f64::NAN),
+            ..limits()
+        }));
+        assert!(is_invalid(ResourceLimits {
+            cpu_shares: Some(1),
+            ..limits()
+        }));
+        assert!(is_invalid(ResourceLimits {
+            cpuset_cpus: Some("0-3,a"),
+            ..limits()
+        }));
+    }
+
+    async fn create_task_definition(context: &SharedContext, body: serde_json::Value) -> i64 {
+        let mut request_body = serde_json::json!({
+            "name": "task",
+            "description": "task",
+            "image": "alpine",
+        });
+        request_body
+            .as_object_mut()
+            .unwrap()
+            .extend(body.as_object().unwrap().clone());
+
+        context
+            .task_definition_service
+            .create_task_definition(CreateDefinitionRequest {
+                request_body: serde_json::from_value(request_body).unwrap(),
+            })
+            .await
+            .unwrap()
+            .task_definition_id
+    }
+
+    async fn patch(
+        context: &SharedContext,
+        task_definition_id: i64,
+        body: serde_json::Value,
+    ) -> errors::Result<entities::task_definition::Model> {
+        context
+            .task_definition_service
+            .patch_task_definition(PatchDefinitionRequest {
+                task_definition_id,
+                request: serde_json::from_value(body).unwrap(),
+            })
+            .await?;
+
+        Ok(context
+            .task_definition_repository
+            .list_task_definitions(ListTaskDefinitionsParams {
+                task_definition_ids: vec![task_definition_id],
+                ..Default::default()
+            })
+            .await?
+            .pop()
+            .unwrap())
+    }
+
+    /*
+    PATCH에서 생략한 필드는 유지하고, null을 보낸 필드는 지우는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_patch_task_definition_clears_null_fields() {
+        let context =
+            Context::for_test(Config::default(), Arc::new(FakeContainerRepository::new())).await;
+        let task_definition_id = create_task_definition(
+            &context,
+            serde_json::json!({
+                "user": "batch",
+                "working_dir": "/work",
+                "labels": { "team": "data" },
+                "mounts": [{ "type": "tmpfs", "target": "/tmp" }],
+            }),
+        )
+        .await;
+
+        let task_definition = patch(
+            &context,
+            task_definition_id,
+            serde_json::json!({ "user": null, "labels": null, "network": "host" }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(task_definition.user, None);
+        assert_eq!(task_definition.labels, None);
+        assert_eq!(task_definition.network.as_deref(), Some("host"));
+        assert_eq!(task_definition.working_dir.as_deref(), Some("/work"));
+        assert!(task_definition.mounts.is_some());
+
+        // 생략한 필드는 현재 값, null은 삭제된 값으로 리소스 제한을 검증
+        let task_definition_id = create_task_definition(
+            &context,
+            serde_json::json!({ "memory_limit": 512, "memory_swap": 1024, "cpus": 2.0 }),
+        )
+        .await;
+
+        assert!(matches!(
+            patch(
+                &context,
+                task_definition_id,
+                serde_json::json!({ "memory_reservation": 1024 })
+            )
+            .await,
+            Err(errors::Error::InvalidTaskDefinition(_))
+        ));
+        assert!(matches!(
+            patch(
+                &context,
+                task_definition_id,
+                serde_json::json!({ "memory_limit": null })
+            )
+            .await,
+            Err(errors::Error::InvalidTaskDefinition(_))
+        ));
+
+        let task_definition = patch(
+            &context,
+            task_definition_id,
+            serde_json::json!({ "memory_limit": null, "memory_swap": null, "cpus": null }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(task_definition.memory_limit, None);
+        assert_eq!(task_definition.memory_swap, None);
+        assert_eq!(task_definition.nano_cpus, None);
+
+        // 지우지 않고 잘못된 값을 보내면 거부
+        assert!(matches!(
+            patch(
+                &context,
+                task_definition_id,
+                serde_json::json!({ "working_dir": "work" })
+            )
+            .await,
+            Err(errors::Error::InvalidTaskDefinition(_))
+        ));
+    }
+
+    /*
+    is_latest_only로 조회하면 total_count도 최신 버전만 세는지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_list_task_definitions_counts_latest_only() {
+        let context =
+            Context::for_test(Config::default(), Arc::new(FakeContainerRepository::new())).await;
+        create_task_definition(&context, serde_json::json!({})).await;
+        create_task_definition(&context, serde_json::json!({})).await;
+        create_task_definition(&context, serde_json::json!({ "name": "other" })).await;
+
+        let list = |is_latest_only: Option<bool>| {
+            let context = context.clone();
+            async move {
+                context
+                    .task_definition_service
+                    .list_task_definitions(ListTaskDefinitionsRequest {
+                        query: serde_json::from_value(serde_json::json!({
+                            "is_latest_only": is_latest_only,
+                        }))
+                        .unwrap(),
+                    })
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let latest = list(Some(true)).await;
+        assert_eq!(latest.task_definitions.len(), 2);
+        assert_eq!(latest.total_count, 2);
+
+        let all = list(None).await;
+        assert_eq!(all.task_definitions.len(), 3);
+        assert_eq!(all.total_count, 3);
+    }
+
+    /*
+    next_cursor를 after_id로 넘기며 끝까지 조회하면 모든 task definition이 한 번씩만 나오고,
+    마지막 페이지의 next_cursor는 None인지 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_list_task_definitions_pages_through_with_after_id() {
+        let context =
+            Context::for_test(Config::default(), Arc::new(FakeContainerRepository::new())).await;
+
+        let mut task_definition_ids = Vec::new();
+        for index in 0..5 {
+            task_definition_ids.push(
+                create_task_definition(
+                    &context,
+                    serde_json::json!({ "name": format!("task-{}", index) }),
+                )
+                .await,
+            );
+        }
+        task_definition_ids.reverse();
+
+        let mut listed_ids = Vec::new();
+        let mut after_id = None;
+        let mut page_count = 0;
+
+        loop {
+            let page = context
+                .task_definition_service
+                .list_task_definitions(ListTaskDefinitionsRequest {
+                    query: serde_json::from_value(serde_json::json!({
+                        "page_size": 2,
+                        "after_id": after_id,
+                    }))
+                    .unwrap(),
+                })
+                .await
+                .unwrap();
+            page_count += 1;
+            listed_ids.extend(page.task_definitions.iter().map(|item| item.id));
+
+            match page.next_cursor {
+                Some(next_cursor) => after_id = Some(next_cursor),
+                None => break,
+            }
+        }
+
+        assert_eq!(page_count, 3);
+        assert_eq!(listed_ids, task_definition_ids);
+
+        // id cursor는 다른 컬럼의 정렬 순서와 맞지 않으므로 거부
+        assert!(matches!(
+            context
+                .task_definition_repository
+                .list_task_definitions(ListTaskDefinitionsParams {
+                    after_id: Some(task_definition_ids[0]),
+                    order_by_desc: Some(entities::task_definition::Column::Version),
+                    ..Default::default()
+                })
+                .await,
+            Err(errors::Error::InvalidTaskDefinition(_))
+        ));
+    }
+}
@@ -0,0 +1,22 @@
+use crate::{
+    domain::{
+        job::JobRepository, secret::SecretRepository, task_definition::TaskDefinitionRepository,
+    },
+    errors,
+};
+
+/// 여러 repository의 쓰기를 하나의 트랜잭션으로 묶습니다.
+/// commit하지 않고 drop하면 그때까지의 변경은 모두 rollback됩니다.
+#[async_trait::async_trait]
+pub trait UnitOfWork {
+    fn task_definition_repository(&self) -> &(dyn TaskDefinitionRepository + Send + Sync);
+    fn job_repository(&self) -> &(dyn JobRepository + Send + Sync);
+    fn secret_repository(&self) -> &(dyn SecretRepository + Send + Sync);
+    async fn commit(self: Box<Self>) -> errors::Result<()>;
+}
+
+#[async_trait::async_trait]
+pub trait UnitOfWorkFactory {
+    /// 트랜잭션을 시작합니다. 끝날 때까지 커넥션 하나를 점유하므로 트랜잭션 밖의 repository와 섞어 쓰지 않아야 합니다.
+    async fn begin(&self) -> errors::Result<Box<dyn UnitOfWork + Send + Sync>>;
+}
@@ -1,21 +1,62 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
 use serde::{Deserialize, Serialize};
 
+use crate::{openapi::schema::schema_object, request_id, validation::FieldError};
+
 #[derive(Debug)]
 pub enum Error {
     TaskDefinitionNotFound,
+    InvalidTaskDefinition(String),
     JobNotFound,
     JobAlreadyFinished,
     JobAlreadyFailed,
+    JobAlreadyCancelled,
+    InvalidJobStatusTransition(String),
+    InvalidJobFilter(String),
+    JobInterrupted,
     JobHasNoContainerID,
     ContainerIDNotFound,
     ScheduleNotFound,
+    SecretNotFound,
+    SecretAlreadyExists,
+    SecretInUse(String),
+    MissingSecretReference(String),
+    InvalidSecret(String),
+    SecretKeyNotConfigured,
+    SecretKeyInvalid(String),
+    SecretFailedToEncrypt(String),
+    SecretFailedToDecrypt(String),
+    RegistryCredentialNotFound,
+    RegistryCredentialAlreadyExists,
+    InvalidRegistryCredential(String),
     CronExpressionIsInvalid(String),
     ContainerNotFound,
     ContainerFailedToKill(String),
     ContainerFailedToStart(String),
     ContainerFailedToInspect(String),
+    ContainerFailedToGetStats(String),
     ContainerFailedToRemove(String),
+    ContainerFailedToList(String),
+    ContainerFailedToPullImage(String),
+    ContainerImageNotPresent(String),
     JobLogExpired,
+    NodeNotFound,
+    RouteNotFound(String),
+    InvalidNode(String),
+    JobNotAssignedToNode,
+    AgentUnauthorized,
+    AgentTokenNotConfigured,
+    AgentRequestFailed(String),
+    NodeLost(String),
+    ShuttingDown,
+    Validation(Vec<FieldError>),
+    InvalidConfig(String),
+    UnknownSchemaVersion(i64),
+    PendingMigrations(String),
     IO(std::io::Error),
     Seaorm(sea_orm::DbErr),
     SerdeJson(serde_json::Error),
@@ -25,28 +66,119 @@ impl Error {
     pub fn error_code(&self) -> String {
         match self {
             Error::TaskDefinitionNotFound => "TASK_DEFINITION_NOT_FOUND".to_string(),
+            Error::InvalidTaskDefinition(_) => "INVALID_TASK_DEFINITION".to_string(),
             Error::JobNotFound => "JOB_NOT_FOUND".to_string(),
             Error::JobAlreadyFinished => "JOB_ALREADY_FINISHED".to_string(),
             Error::JobAlreadyFailed => "JOB_ALREADY_FAILED".to_string(),
+            Error::JobAlreadyCancelled => "JOB_ALREADY_CANCELLED".to_string(),
+            Error::InvalidJobStatusTransition(_) => "INVALID_JOB_STATUS_TRANSITION".to_string(),
+            Error::InvalidJobFilter(_) => "INVALID_JOB_FILTER".to_string(),
+            Error::JobInterrupted => "JOB_INTERRUPTED".to_string(),
             Error::JobHasNoContainerID => "JOB_HAS_NO_CONTAINER_ID".to_string(),
             Error::ContainerIDNotFound => "CONTAINER_ID_NOT_FOUND".to_string(),
             Error::ScheduleNotFound => "SCHEDULE_NOT_FOUND".to_string(),
+            Error::SecretNotFound => "SECRET_NOT_FOUND".to_string(),
+            Error::SecretAlreadyExists => "SECRET_ALREADY_EXISTS".to_string(),
+            Error::SecretInUse(_) => "SECRET_IN_USE".to_string(),
+            Error::MissingSecretReference(_) => "MISSING_SECRET_REFERENCE".to_string(),
+            Error::InvalidSecret(_) => "INVALID_SECRET".to_string(),
+            Error::SecretKeyNotConfigured => "SECRET_KEY_NOT_CONFIGURED".to_string(),
+            Error::SecretKeyInvalid(_) => "INVALID_SECRET_KEY".to_string(),
+            Error::SecretFailedToEncrypt(_) => "FAILED_TO_ENCRYPT_SECRET".to_string(),
+            Error::SecretFailedToDecrypt(_) => "FAILED_TO_DECRYPT_SECRET".to_string(),
+            Error::RegistryCredentialNotFound => "REGISTRY_CREDENTIAL_NOT_FOUND".to_string(),
+            Error::RegistryCredentialAlreadyExists => {
+                "REGISTRY_CREDENTIAL_ALREADY_EXISTS".to_string()
+            }
+            Error::InvalidRegistryCredential(_) => "INVALID_REGISTRY_CREDENTIAL".to_string(),
             Error::CronExpressionIsInvalid(_) => "INVALID_CRON_EXPRESSION".to_string(),
             Error::ContainerNotFound => "CONTAINER_NOT_FOUND".to_string(),
             Error::ContainerFailedToKill(_) => "FAILED_TO_KILL_CONTAINER".to_string(),
             Error::ContainerFailedToStart(_) => "FAILED_TO_START_CONTAINER".to_string(),
             Error::ContainerFailedToInspect(_) => "FAILED_TO_INSPECT_CONTAINER".to_string(),
+            Error::ContainerFailedToGetStats(_) => "FAILED_TO_GET_CONTAINER_STATS".to_string(),
             Error::ContainerFailedToRemove(_) => "FAILED_TO_REMOVE_CONTAINER".to_string(),
+            Error::ContainerFailedToList(_) => "FAILED_TO_LIST_CONTAINERS".to_string(),
+            Error::ContainerFailedToPullImage(_) => "FAILED_TO_PULL_IMAGE".to_string(),
+            Error::ContainerImageNotPresent(_) => "IMAGE_NOT_PRESENT".to_string(),
             Error::JobLogExpired => "JOB_LOG_EXPIRED".to_string(),
+            Error::NodeNotFound => "NODE_NOT_FOUND".to_string(),
+            Error::RouteNotFound(_) => "ROUTE_NOT_FOUND".to_string(),
+            Error::InvalidNode(_) => "INVALID_NODE".to_string(),
+            Error::JobNotAssignedToNode => "JOB_NOT_ASSIGNED_TO_NODE".to_string(),
+            Error::AgentUnauthorized => "AGENT_UNAUTHORIZED".to_string(),
+            Error::AgentTokenNotConfigured => "AGENT_TOKEN_NOT_CONFIGURED".to_string(),
+            Error::AgentRequestFailed(_) => "AGENT_REQUEST_FAILED".to_string(),
+            Error::NodeLost(_) => "NODE_LOST".to_string(),
+            Error::ShuttingDown => "SHUTTING_DOWN".to_string(),
+            Error::Validation(_) => "VALIDATION_FAILED".to_string(),
+            Error::InvalidConfig(_) => "INVALID_CONFIG".to_string(),
+            Error::UnknownSchemaVersion(_) => "UNKNOWN_SCHEMA_VERSION".to_string(),
+            Error::PendingMigrations(_) => "PENDING_MIGRATIONS".to_string(),
             Error::IO(_) => "IO_ERROR".to_string(),
             Error::Seaorm(_) => "DATABASE_ERROR".to_string(),
             Error::SerdeJson(_) => "JSON_SERIALIZATION_ERROR".to_string(),
         }
     }
 
-    pub fn into_json_response(self) -> String {
-        let error_response: ErrorResponse = self.into();
-        serde_json::to_string(&error_response).unwrap_or("{}".to_string())
+    /*
+    HTTP 응답의 상태 코드입니다. 모든 handler가 이 매핑을 사용합니다.
+    */
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Error::InvalidTaskDefinition(_)
+            | Error::InvalidJobFilter(_)
+            | Error::InvalidSecret(_)
+            | Error::InvalidRegistryCredential(_)
+            | Error::CronExpressionIsInvalid(_)
+            | Error::InvalidNode(_)
+            | Error::Validation(_) => StatusCode::BAD_REQUEST,
+            Error::AgentUnauthorized => StatusCode::UNAUTHORIZED,
+            Error::TaskDefinitionNotFound
+            | Error::JobNotFound
+            | Error::ScheduleNotFound
+            | Error::SecretNotFound
+            | Error::RegistryCredentialNotFound
+            | Error::NodeNotFound
+            | Error::RouteNotFound(_)
+            | Error::ContainerNotFound => StatusCode::NOT_FOUND,
+            Error::JobAlreadyFinished
+            | Error::JobAlreadyFailed
+            | Error::JobAlreadyCancelled
+            | Error::InvalidJobStatusTransition(_)
+            | Error::SecretAlreadyExists
+            | Error::SecretInUse(_)
+            | Error::MissingSecretReference(_)
+            | Error::RegistryCredentialAlreadyExists
+            | Error::JobNotAssignedToNode
+            | Error::JobHasNoContainerID
+            | Error::ContainerIDNotFound
+            /* pull policy가 Never인데 이미지가 없는 경우로, 서버가 아니라 설정의 문제 */
+            | Error::ContainerImageNotPresent(_) => StatusCode::CONFLICT,
+            Error::JobLogExpired => StatusCode::GONE,
+            Error::AgentRequestFailed(_) => StatusCode::BAD_GATEWAY,
+            Error::ShuttingDown
+            | Error::SecretKeyNotConfigured
+            | Error::AgentTokenNotConfigured
+            | Error::PendingMigrations(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Error::JobInterrupted
+            | Error::SecretKeyInvalid(_)
+            | Error::SecretFailedToEncrypt(_)
+            | Error::SecretFailedToDecrypt(_)
+            | Error::ContainerFailedToKill(_)
+            | Error::ContainerFailedToStart(_)
+            | Error::ContainerFailedToInspect(_)
+            | Error::ContainerFailedToGetStats(_)
+            | Error::ContainerFailedToRemove(_)
+            | Error::ContainerFailedToList(_)
+            | Error::ContainerFailedToPullImage(_)
+            | Error::NodeLost(_)
+            | Error::InvalidConfig(_)
+            | Error::UnknownSchemaVersion(_)
+            | Error::IO(_)
+            | Error::Seaorm(_)
+            | Error::SerdeJson(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
     }
 }
 
@@ -60,19 +192,92 @@ impl From<&Error> for String {
     fn from(error: &Error) -> String {
         match error {
             Error::TaskDefinitionNotFound => "Task definition not found".to_string(),
+            Error::InvalidTaskDefinition(reason) => format!("Invalid task definition: {}", reason),
             Error::JobNotFound => "Job not found".to_string(),
             Error::JobAlreadyFinished => "Job is already finished".to_string(),
             Error::JobAlreadyFailed => "Job is already failed".to_string(),
+            Error::JobAlreadyCancelled => "Job is already cancelled".to_string(),
+            Error::InvalidJobStatusTransition(transition) => {
+                format!("Invalid job status transition: {}", transition)
+            }
+            Error::InvalidJobFilter(reason) => format!("Invalid job filter: {}", reason),
+            Error::JobInterrupted => {
+                "Job was interrupted because batchman restarted before its container was recorded"
+                    .to_string()
+            }
             Error::JobHasNoContainerID => "Job has no container ID".to_string(),
             Error::ContainerIDNotFound => "Container ID not found".to_string(),
             Error::ScheduleNotFound => "Schedule not found".to_string(),
+            Error::SecretNotFound => "Secret not found".to_string(),
+            Error::SecretAlreadyExists => "Secret with the same name already exists".to_string(),
+            Error::SecretInUse(task_definitions) => format!(
+                "Secret is referenced by task definitions: {}",
+                task_definitions
+            ),
+            Error::MissingSecretReference(reference) => {
+                format!("Referenced secret does not exist: {}", reference)
+            }
+            Error::InvalidSecret(reason) => format!("Invalid secret: {}", reason),
+            Error::SecretKeyNotConfigured => {
+                "Secret master key is not configured (secret.key)".to_string()
+            }
+            Error::SecretKeyInvalid(reason) => format!("Invalid secret master key: {}", reason),
+            Error::SecretFailedToEncrypt(err) => format!("Failed to encrypt secret: {}", err),
+            Error::SecretFailedToDecrypt(err) => format!("Failed to decrypt secret: {}", err),
+            Error::RegistryCredentialNotFound => "Registry credential not found".to_string(),
+            Error::RegistryCredentialAlreadyExists => {
+                "Credential for the same registry already exists".to_string()
+            }
+            Error::InvalidRegistryCredential(reason) => {
+                format!("Invalid registry credential: {}", reason)
+            }
             Error::CronExpressionIsInvalid(expr) => format!("Invalid Cron Expression: {}", expr),
             Error::ContainerNotFound => "Container not found".to_string(),
             Error::ContainerFailedToKill(err) => format!("Failed to kill container: {}", err),
             Error::ContainerFailedToStart(err) => format!("Failed to start container: {}", err),
             Error::ContainerFailedToInspect(err) => format!("Failed to inspect container: {}", err),
+            Error::ContainerFailedToGetStats(err) => {
+                format!("Failed to get container stats: {}", err)
+            }
             Error::ContainerFailedToRemove(err) => format!("Failed to remove container: {}", err),
+            Error::ContainerFailedToList(err) => format!("Failed to list containers: {}", err),
+            Error::ContainerFailedToPullImage(err) => format!("Failed to pull image: {}", err),
+            Error::ContainerImageNotPresent(image) => format!(
+                "Image {} is not present locally and pull policy is Never",
+                image
+            ),
             Error::JobLogExpired => "Job log has expired and is no longer available".to_string(),
+            Error::NodeNotFound => "Node not found".to_string(),
+            Error::RouteNotFound(route) => format!("No API route for {}", route),
+            Error::InvalidNode(reason) => format!("Invalid node: {}", reason),
+            Error::JobNotAssignedToNode => "Job is not assigned to this node".to_string(),
+            Error::AgentUnauthorized => "Agent token is missing or invalid".to_string(),
+            Error::AgentTokenNotConfigured => {
+                "Agent API is disabled until node.agent_token (BATCHMAN_AGENT_TOKEN) is configured"
+                    .to_string()
+            }
+            Error::AgentRequestFailed(err) => format!("Request to batchman server failed: {}", err),
+            Error::NodeLost(name) => format!("Node {} stopped sending heartbeats", name),
+            Error::ShuttingDown => {
+                "Server is shutting down and does not accept new jobs".to_string()
+            }
+            Error::Validation(errors) => format!(
+                "Request validation failed: {}",
+                errors
+                    .iter()
+                    .map(|error| format!("{}: {}", error.field, error.message))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Error::InvalidConfig(reason) => format!("Invalid configuration: {}", reason),
+            Error::UnknownSchemaVersion(version) => format!(
+                "Database schema version {} is newer than this batchman supports, upgrade batchman",
+                version
+            ),
+            Error::PendingMigrations(versions) => format!(
+                "Database schema is outdated, run `api migrate` to apply migrations {}",
+                versions
+            ),
             Error::IO(err) => format!("I/O error: {}", err),
             Error::Seaorm(err) => format!("Database error: {}", err),
             Error::SerdeJson(err) => {
@@ -108,17 +313,96 @@ impl From<serde_json::Error> for Error {
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/*
+HTTP 에러 응답 본문입니다. 클라이언트는 error_code로 에러를 구분하고, request_id로 서버 로그를 찾습니다.
+*/
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ErrorResponse {
     pub error_code: String,
     pub message: String,
+    #[serde(default)]
+    pub request_id: Option<String>, // same as the x-request-id response header
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>, // field errors of VALIDATION_FAILED
 }
 
+schema_object!(ErrorResponse {
+    error_code,
+    message,
+    #[optional]
+    request_id,
+    #[optional]
+    #[skip_serializing]
+    details,
+});
+
 impl From<Error> for ErrorResponse {
     fn from(error: Error) -> Self {
+        let details = match &error {
+            Error::Validation(errors) => errors.clone(),
+            _ => Vec::new(),
+        };
+
         ErrorResponse {
             error_code: error.error_code(),
             message: error.to_string(),
+            request_id: request_id::current(),
+            details,
         }
     }
 }
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status_code = self.status_code();
+        let error_response = ErrorResponse::from(self);
+
+        /* 서버 쪽 문제는 응답의 request_id로 찾을 수 있도록 기록 */
+        if status_code.is_server_error() {
+            log::error!(
+                "Request {} failed: {}",
+                error_response.request_id.as_deref().unwrap_or("-"),
+                error_response.message
+            );
+        }
+
+        (status_code, Json(error_response)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_error_into_response() {
+        let response = Error::JobNotFound.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers()[axum::http::header::CONTENT_TYPE],
+            "application/json"
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error_response: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error_response.error_code, "JOB_NOT_FOUND");
+        assert_eq!(error_response.request_id, None);
+
+        assert_eq!(
+            Error::CronExpressionIsInvalid("* *".to_string()).status_code(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(Error::JobLogExpired.status_code(), StatusCode::GONE);
+        assert_eq!(
+            Error::ShuttingDown.status_code(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        /* pull policy가 Never인데 이미지가 없는 것은 설정의 문제이므로 500이 아님 */
+        assert_eq!(
+            Error::ContainerImageNotPresent("alpine".to_string()).status_code(),
+            StatusCode::CONFLICT
+        );
+    }
+}
@@ -0,0 +1,14 @@
+pub mod agent;
+pub mod background;
+pub mod config;
+pub mod context;
+pub mod db;
+pub mod domain;
+pub mod errors;
+pub mod openapi;
+pub mod request_id;
+pub mod routes;
+pub mod types;
+pub mod utils;
+pub mod validation;
+pub mod web;
@@ -0,0 +1,1132 @@
+use std::sync::OnceLock;
+
+use axum::{Json, http::Method, response::Html};
+use serde_json::{Map, Value, json};
+
+use crate::{errors::ErrorResponse, routes};
+
+pub mod router;
+pub mod schema;
+pub mod typescript;
+
+use schema::{SchemaRegistry, ToSchema};
+
+pub(crate) type SchemaFn = fn(&mut SchemaRegistry) -> Value;
+
+// 요청 DTO를 JSON에서 읽어 Debug 문자열로 돌려줍니다. (schema와 serde 속성이 맞는지 테스트에서 확인)
+pub(crate) type DecodeFn = fn(Value) -> Result<String, String>;
+
+/*
+HTTP API 하나의 문서입니다. ApiRouter가 handler의 extractor와 반환 타입에서 채웁니다.
+*/
+#[derive(Debug, Clone)]
+pub struct Operation {
+    pub method: Method,
+    pub path: &'static str, // path under /api, axum route syntax (example: /jobs/{job_id})
+    pub operation_id: String, // handler function name in camelCase
+    pub summary: &'static str,
+    pub tag: &'static str,
+    pub(crate) query: Option<SchemaFn>,        // ValidQuery<T>
+    pub(crate) request_body: Option<SchemaFn>, // ValidJson<T>
+    pub(crate) query_decoder: Option<DecodeFn>,
+    pub(crate) request_body_decoder: Option<DecodeFn>,
+    pub(crate) response: ResponseBody,
+    pub(crate) agent_auth: bool, // requires Authorization: Bearer <node.agent_token>
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum ResponseBody {
+    Json(SchemaFn),
+    Content(&'static str), // media type of a non-JSON response
+}
+
+impl Operation {
+    pub fn new(
+        method: Method,
+        path: &'static str,
+        operation_id: String,
+        summary: &'static str,
+    ) -> Self {
+        Operation {
+            method,
+            path,
+            operation_id,
+            summary,
+            tag: "",
+            query: None,
+            request_body: None,
+            query_decoder: None,
+            request_body_decoder: None,
+            response: ResponseBody::Json(<() as ToSchema>::schema),
+            agent_auth: false,
+        }
+    }
+
+    fn to_json(&self, registry: &mut SchemaRegistry) -> Value {
+        let mut parameters = path_parameters(self.path);
+
+        if let Some(query) = self.query {
+            parameters.extend(query_parameters(registry, query));
+        }
+
+        let error_response = ErrorResponse::schema(registry);
+        let success_response = match &self.response {
+            ResponseBody::Json(schema) => json!({
+                "description": "OK",
+                "content": { "application/json": { "schema": schema(registry) } },
+            }),
+            ResponseBody::Content(media_type) => json!({
+                "description": "OK",
+                "content": { *media_type: { "schema": { "type": "string" } } },
+            }),
+        };
+
+        let mut operation = json!({
+            "operationId": self.operation_id,
+            "summary": self.summary,
+            "responses": {
+                "200": success_response,
+                "default": {
+                    "description": "Error (see error_code)",
+                    "content": { "application/json": { "schema": error_response } },
+                },
+            },
+        });
+
+        if !parameters.is_empty() {
+            operation["parameters"] = Value::Array(parameters);
+        }
+
+        if let Some(request_body) = self.request_body {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": { "schema": registry.request(request_body) } },
+            });
+        }
+
+        if self.agent_auth {
+            operation["security"] = json!([{ "agentToken": [] }]);
+        }
+
+        operation
+    }
+}
+
+/*
+OpenAPI 3.1 문서를 만듭니다.
+*/
+pub fn document() -> Value {
+    let router = routes::api_router();
+    let mut registry = SchemaRegistry::new();
+    let mut paths = Map::new();
+    let mut tags = Vec::<Value>::new();
+
+    register_responses(&mut registry, router.operations());
+
+    for operation in router.operations() {
+        let tag = json!({ "name": operation.tag });
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+
+        let mut operation_json = operation.to_json(&mut registry);
+        operation_json["tags"] = json!([operation.tag]);
+
+        let path_item = paths
+            .entry(operation.path.to_string())
+            .or_insert_with(|| json!({}));
+        path_item[operation.method.as_str().to_lowercase()] = operation_json;
+    }
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "batchman",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "servers": [{ "url": "/api" }],
+        "tags": tags,
+        "paths": paths,
+        "components": {
+            "schemas": registry.into_schemas(),
+            "securitySchemes": {
+                "agentToken": { "type": "http", "scheme": "bearer" },
+            },
+        },
+    })
+}
+
+/*
+오류 응답과 모든 API의 응답 schema를 등록합니다.
+요청 schema보다 먼저 등록해야 같은 DTO의 요청용 schema가 {name}Input으로 구분됩니다.
+*/
+pub(crate) fn register_responses(registry: &mut SchemaRegistry, operations: &[Operation]) {
+    ErrorResponse::schema(registry);
+
+    for operation in operations {
+        if let ResponseBody::Json(response) = operation.response {
+            response(registry);
+        }
+    }
+}
+
+// path의 {name}은 모두 정수 id
+fn path_parameters(path: &str) -> Vec<Value> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(|name| {
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "integer", "format": "int64" },
+            })
+        })
+        .collect()
+}
+
+// query DTO의 필드를 query parameter로 펼침
+fn query_parameters(registry: &mut SchemaRegistry, query: SchemaFn) -> Vec<Value> {
+    let reference = registry.request(query);
+    let Some(object) = registry.resolve(&reference) else {
+        return Vec::new();
+    };
+
+    let required = object["required"].as_array().cloned().unwrap_or_default();
+
+    object["properties"]
+        .as_object()
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, schema)| {
+                    json!({
+                        "name": name,
+                        "in": "query",
+                        "required": required.contains(&Value::from(name.as_str())),
+                        "schema": schema,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub async fn get_openapi_document() -> Json<Value> {
+    static DOCUMENT: OnceLock<Value> = OnceLock::new();
+
+    Json(DOCUMENT.get_or_init(document).clone())
+}
+
+/*
+/api/openapi.json을 보여주는 API 문서 페이지입니다.
+Redoc/Swagger UI가 아닌 자체 뷰어(openapi/docs.html)이며, 외부 스크립트 없이 동작하도록 바이너리에 포함합니다.
+*/
+pub async fn get_api_docs() -> Html<&'static str> {
+    Html(include_str!("openapi/docs.html"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, sync::Arc};
+
+    use super::*;
+    use crate::{
+        config::Config,
+        context::{Context, SharedContext},
+        domain::{
+            container::repository::fake::FakeContainerRepository,
+            job::{
+                dao::{CreateJobMetricParams, ListJobsParams},
+                dto::JobRecordDto,
+            },
+        },
+    };
+
+    const AGENT_TOKEN: &str = "agent-token";
+
+    fn collect_references(value: &Value, references: &mut Vec<String>) {
+        match value {
+            Value::Object(object) => {
+                if let Some(Value::String(reference)) = object.get("$ref") {
+                    references.push(reference.clone());
+                }
+                object
+                    .values()
+                    .for_each(|value| collect_references(value, references));
+            }
+            Value::Array(array) => array
+                .iter()
+                .for_each(|value| collect_references(value, references)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_document_references_resolve() {
+        let document = document();
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+
+        let mut references = Vec::new();
+        collect_references(&document, &mut references);
+        assert!(!references.is_empty());
+
+        for reference in references {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            assert!(schemas.contains_key(name), "{} is not defined", reference);
+        }
+
+        let mut operation_ids = routes::api_router()
+            .operations()
+            .iter()
+            .map(|operation| operation.operation_id.clone())
+            .collect::<Vec<_>>();
+        let count = operation_ids.len();
+        operation_ids.sort();
+        operation_ids.dedup();
+        assert_eq!(operation_ids.len(), count, "operationId must be unique");
+    }
+
+    #[test]
+    fn test_document_schemas() {
+        let document = document();
+        let schemas = &document["components"]["schemas"];
+
+        // serde(rename)과 serde(default). 응답에서는 모든 필드가 항상 직렬화됨
+        let mount = &schemas["Mount"];
+        assert!(mount["properties"]["type"].is_object());
+        assert_eq!(
+            mount["required"],
+            json!(["type", "source", "target", "read_only"])
+        );
+        assert_eq!(schemas["MountInput"]["required"], json!(["type", "target"]));
+
+        // 응답의 Option 필드는 null로 직렬화되므로 필수이고 null을 허용
+        let job = &schemas["JobDto"];
+        assert!(
+            job["required"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("started_at"))
+        );
+        assert_eq!(
+            job["properties"]["started_at"]["type"],
+            json!(["string", "null"])
+        );
+
+        // skip_serializing_if 필드는 응답에서 생략될 수 있음
+        assert_eq!(
+            schemas["ErrorResponse"]["required"],
+            json!(["error_code", "message", "request_id"])
+        );
+
+        // serde(alias)는 요청에서만 deprecated 필드로 받음
+        let create_task_definition = &schemas["CreateTaskDefinitionBody"];
+        assert_eq!(
+            create_task_definition["properties"]["cpu_limit"]["deprecated"],
+            true
+        );
+        assert!(
+            schemas["ListTaskDefinitionsItem"]["properties"]
+                .get("cpu_limit")
+                .is_none()
+        );
+
+        // rename_all = "snake_case"
+        assert!(
+            schemas["JobSortBy"]["enum"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("submitted_at"))
+        );
+
+        let list_jobs = &document["paths"]["/jobs"]["get"];
+        let parameters = list_jobs["parameters"].as_array().unwrap();
+        assert!(
+            parameters
+                .iter()
+                .any(|parameter| parameter["name"] == "page_size" && parameter["in"] == "query")
+        );
+
+        let list_job_logs = &document["paths"]["/jobs/{job_id}/logs"]["get"];
+        let parameters = list_job_logs["parameters"].as_array().unwrap();
+        assert!(
+            parameters
+                .iter()
+                .any(|parameter| parameter["name"] == "job_id"
+                    && parameter["in"] == "path"
+                    && parameter["required"] == true)
+        );
+        assert!(
+            parameters
+                .iter()
+                .any(|parameter| parameter["name"] == "limit" && parameter["required"] == true)
+        );
+    }
+
+    /*
+    JSON 값이 schema를 만족하는지 확인합니다. 필수 필드가 모두 있는지, schema에 없는 필드가 없는지도 확인하며
+    지나간 $ref의 이름을 visited에 모읍니다.
+    */
+    fn check_value(
+        schemas: &Map<String, Value>,
+        schema: &Value,
+        value: &Value,
+        path: &str,
+        visited: &mut BTreeSet<String>,
+    ) -> Result<(), String> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            visited.insert(name.to_string());
+            return check_value(schemas, &schemas[name], value, path, visited);
+        }
+
+        if let Some(any_of) = schema["anyOf"].as_array() {
+            for branch in any_of {
+                let mut branch_visited = visited.clone();
+                if check_value(schemas, branch, value, path, &mut branch_visited).is_ok() {
+                    *visited = branch_visited;
+                    return Ok(());
+                }
+            }
+            return Err(format!("{}: {} matches no schema of anyOf", path, value));
+        }
+
+        if let Some(values) = schema["enum"].as_array() {
+            return match values.contains(value) {
+                true => Ok(()),
+                false => Err(format!("{}: {} is not one of {:?}", path, value, values)),
+            };
+        }
+
+        let types = match &schema["type"] {
+            Value::String(schema_type) => vec![schema_type.as_str()],
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            _ => return Ok(()), // {} (serde_json::Value)
+        };
+        let value_type = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(number) if number.is_f64() => "number",
+            Value::Number(_) => "integer",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        if !types.iter().any(|schema_type| {
+            *schema_type == value_type || (*schema_type == "number" && value_type == "integer")
+        }) {
+            return Err(format!("{}: expected {:?}, got {}", path, types, value));
+        }
+
+        match value {
+            Value::String(string) if schema["format"] == "date-time" => {
+                chrono::DateTime::parse_from_rfc3339(string)
+                    .map_err(|error| format!("{}: {} ({})", path, string, error))?;
+            }
+            Value::Number(number)
+                if schema["minimum"] == 0 && number.as_f64().is_some_and(|number| number < 0.0) =>
+            {
+                return Err(format!("{}: {} is negative", path, number));
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let path = format!("{}[{}]", path, index);
+                    check_value(schemas, &schema["items"], item, &path, visited)?;
+                }
+            }
+            Value::Object(object) => match schema["properties"].as_object() {
+                Some(properties) => {
+                    let required = schema["required"].as_array().into_iter().flatten();
+                    for name in required.filter_map(Value::as_str) {
+                        if !object.contains_key(name) {
+                            return Err(format!("{}.{} is required but missing", path, name));
+                        }
+                    }
+
+                    for (name, value) in object {
+                        let property = properties
+                            .get(name)
+                            .ok_or_else(|| format!("{}.{} is not in the schema", path, name))?;
+                        let path = format!("{}.{}", path, name);
+                        check_value(schemas, property, value, &path, visited)?;
+                    }
+                }
+                None => {
+                    for (name, value) in object {
+                        let path = format!("{}.{}", path, name);
+                        check_value(
+                            schemas,
+                            &schema["additionalProperties"],
+                            value,
+                            &path,
+                            visited,
+                        )?;
+                    }
+                }
+            },
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    // schema를 만족하는 예시 값을 만듭니다. required_only이면 필수 필드만 넣고, deprecated 필드(serde alias)는 넣지 않습니다.
+    fn sample(schemas: &Map<String, Value>, schema: &Value, required_only: bool) -> Value {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            return sample(schemas, &schemas[name], required_only);
+        }
+
+        if let Some(any_of) = schema["anyOf"].as_array() {
+            return sample(schemas, &any_of[0], required_only);
+        }
+
+        if let Some(values) = schema["enum"].as_array() {
+            return values[0].clone();
+        }
+
+        let schema_type = match &schema["type"] {
+            Value::Array(types) => types[0].as_str(),
+            schema_type => schema_type.as_str(),
+        };
+
+        match schema_type {
+            Some("string") if schema["format"] == "date-time" => json!("2025-01-01T00:00:00Z"),
+            Some("string") => json!("sample"),
+            Some("integer") => json!(1),
+            Some("number") => json!(1.5),
+            Some("boolean") => json!(true),
+            Some("array") => json!([sample(schemas, &schema["items"], required_only)]),
+            Some("object") if schema.get("properties").is_some() => {
+                let required = schema["required"].as_array().cloned().unwrap_or_default();
+
+                schema["properties"]
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .filter(|(name, property)| {
+                        property["deprecated"] != true
+                            && (!required_only || required.contains(&Value::from(name.as_str())))
+                    })
+                    .map(|(name, property)| {
+                        (name.clone(), sample(schemas, property, required_only))
+                    })
+                    .collect::<Map<_, _>>()
+                    .into()
+            }
+            Some("object") => {
+                json!({ "key": sample(schemas, &schema["additionalProperties"], required_only) })
+            }
+            _ => json!("sample"),
+        }
+    }
+
+    // 예시 값 안의 object와 그 schema를 JSON pointer와 함께 모읍니다.
+    fn object_nodes(
+        schemas: &Map<String, Value>,
+        schema: &Value,
+        value: &Value,
+        pointer: String,
+        nodes: &mut Vec<(String, Value)>,
+    ) {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            return object_nodes(schemas, &schemas[name], value, pointer, nodes);
+        }
+
+        if let Some(any_of) = schema["anyOf"].as_array() {
+            return object_nodes(schemas, &any_of[0], value, pointer, nodes);
+        }
+
+        match value {
+            Value::Object(object) if schema["properties"].is_object() => {
+                nodes.push((pointer.clone(), schema.clone()));
+                for (name, value) in object {
+                    let pointer = format!("{}/{}", pointer, name);
+                    object_nodes(schemas, &schema["properties"][name], value, pointer, nodes);
+                }
+            }
+            Value::Object(object) => {
+                for (name, value) in object {
+                    let pointer = format!("{}/{}", pointer, name);
+                    object_nodes(
+                        schemas,
+                        &schema["additionalProperties"],
+                        value,
+                        pointer,
+                        nodes,
+                    );
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let pointer = format!("{}/{}", pointer, index);
+                    object_nodes(schemas, &schema["items"], item, pointer, nodes);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /*
+    요청 schema의 필수/선택 필드가 serde와 같은지 확인합니다.
+    필수 필드를 빼면 읽지 못해야 하고, 선택 필드는 빼도 읽을 수 있지만 읽은 값이 달라져야 합니다.
+    deprecated 필드(serde alias)는 원래 필드 대신 보내도 같은 값으로 읽혀야 합니다.
+    */
+    fn check_request(
+        schemas: &Map<String, Value>,
+        reference: &Value,
+        decode: DecodeFn,
+        label: &str,
+        visited: &mut BTreeSet<String>,
+    ) {
+        let minimal = sample(schemas, reference, true);
+        let full = sample(schemas, reference, false);
+
+        for value in [&minimal, &full] {
+            check_value(schemas, reference, value, label, visited).unwrap();
+            decode(value.clone())
+                .unwrap_or_else(|error| panic!("{}: {} is rejected: {}", label, value, error));
+        }
+        let decoded = decode(full.clone()).unwrap();
+
+        let mut nodes = Vec::new();
+        object_nodes(schemas, reference, &full, String::new(), &mut nodes);
+
+        for (pointer, schema) in nodes {
+            let required = schema["required"].as_array().cloned().unwrap_or_default();
+            let properties = schema["properties"].as_object().unwrap();
+
+            for (name, property) in properties {
+                let field = format!("{}{}/{}", label, pointer, name);
+
+                if property["deprecated"] == true {
+                    let is_alias = properties
+                        .keys()
+                        .filter(|other| *other != name)
+                        .any(|other| {
+                            let mut value = full.clone();
+                            let object = value
+                                .pointer_mut(&pointer)
+                                .unwrap()
+                                .as_object_mut()
+                                .unwrap();
+                            let Some(original) = object.remove(other) else {
+                                return false;
+                            };
+                            object.insert(name.clone(), original);
+                            decode(value).as_ref() == Ok(&decoded)
+                        });
+                    assert!(is_alias, "{} is not read as any other field", field);
+                    continue;
+                }
+
+                let mut value = full.clone();
+                value
+                    .pointer_mut(&pointer)
+                    .unwrap()
+                    .as_object_mut()
+                    .unwrap()
+                    .remove(name);
+
+                match required.contains(&Value::from(name.as_str())) {
+                    true => assert!(
+                        decode(value).is_err(),
+                        "{} is required in the schema, but serde accepts it missing",
+                        field
+                    ),
+                    false => {
+                        let without = decode(value).unwrap_or_else(|error| {
+                            panic!(
+                                "{} is optional in the schema, but serde rejects it missing: {}",
+                                field, error
+                            )
+                        });
+                        assert_ne!(without, decoded, "{} is not read by serde", field);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_request_schemas_match_serde() {
+        let router = routes::api_router();
+        let mut registry = SchemaRegistry::new();
+        register_responses(&mut registry, router.operations());
+
+        let mut requests = Vec::new();
+        for operation in router.operations() {
+            let inputs = [
+                (operation.query, operation.query_decoder),
+                (operation.request_body, operation.request_body_decoder),
+            ];
+            for (schema, decode) in inputs {
+                if let (Some(schema), Some(decode)) = (schema, decode) {
+                    let reference = registry.request(schema);
+                    requests.push((operation.operation_id.clone(), reference, decode));
+                }
+            }
+        }
+
+        let schemas = registry.into_schemas().into_iter().collect::<Map<_, _>>();
+        let mut visited = BTreeSet::new();
+        for (label, reference, decode) in &requests {
+            check_request(&schemas, reference, *decode, label, &mut visited);
+        }
+
+        assert!(visited.contains("MountInput"));
+        assert!(visited.contains("PatchTaskDefinitionBody"));
+    }
+
+    /*
+    agent API까지 호출할 수 있는 서버를 띄우고, 호출한 API의 요청과 응답이 문서의 schema와 맞는지 확인합니다.
+    */
+    struct DocumentedClient {
+        base_url: String,
+        http: reqwest::Client,
+        document: Value,
+        visited: BTreeSet<String>,
+    }
+
+    impl DocumentedClient {
+        async fn spawn() -> (SharedContext, Self) {
+            let mut config = Config::default();
+            config.node.agent_token = Some(AGENT_TOKEN.to_string());
+
+            let context = Context::for_test(config, Arc::new(FakeContainerRepository::new())).await;
+
+            let router = axum::Router::new().nest(
+                "/api",
+                routes::api_router()
+                    .into_router()
+                    .layer(axum::Extension(context.clone())),
+            );
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let address = listener.local_addr().unwrap();
+            tokio::spawn(async move { axum::serve(listener, router).await });
+
+            let client = DocumentedClient {
+                base_url: format!("http://{}/api", address),
+                http: reqwest::Client::new(),
+                document: document(),
+                visited: BTreeSet::new(),
+            };
+
+            (context, client)
+        }
+
+        fn schemas(&self) -> Map<String, Value> {
+            self.document["components"]["schemas"]
+                .as_object()
+                .cloned()
+                .unwrap()
+        }
+
+        // path의 {name}은 ids로 순서대로 채웁니다.
+        async fn call(
+            &mut self,
+            method: Method,
+            path: &str,
+            ids: &[i64],
+            query: Value,
+            body: Option<Value>,
+        ) -> (u16, Value) {
+            let operation = self.document["paths"][path][method.as_str().to_lowercase()].clone();
+            assert!(
+                operation.is_object(),
+                "{} {} is not documented",
+                method,
+                path
+            );
+
+            let schemas = self.schemas();
+            let label = format!("{} {}", method, path);
+
+            if let Some(body) = &body {
+                let schema = &operation["requestBody"]["content"]["application/json"]["schema"];
+                check_value(&schemas, schema, body, &label, &mut self.visited).unwrap();
+            }
+
+            let mut ids = ids.iter();
+            let url = path
+                .split('/')
+                .map(|segment| match segment.starts_with('{') {
+                    true => ids.next().unwrap().to_string(),
+                    false => segment.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            let query = query
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(name, value)| match value {
+                    Value::String(value) => (name.clone(), value.clone()),
+                    value => (name.clone(), value.to_string()),
+                })
+                .collect::<Vec<_>>();
+
+            let mut request = self
+                .http
+                .request(method.clone(), format!("{}{}", self.base_url, url))
+                .bearer_auth(AGENT_TOKEN)
+                .query(&query);
+            if let Some(body) = body {
+                request = request.json(&body);
+            }
+
+            let response = request.send().await.unwrap();
+            let status = response.status().as_u16();
+            let body = response.json::<Value>().await.unwrap();
+
+            let schema = match status {
+                200 => &operation["responses"]["200"]["content"]["application/json"]["schema"],
+                _ => &operation["responses"]["default"]["content"]["application/json"]["schema"],
+            };
+            check_value(&schemas, schema, &body, &label, &mut self.visited)
+                .unwrap_or_else(|error| panic!("{} ({}): {}", error, status, body));
+
+            (status, body)
+        }
+    }
+
+    /*
+    문서의 모든 응답 schema가 실제 응답과 맞는지 확인합니다.
+    Option 필드는 null이어도 직렬화되므로 응답에서 필수여야 하고, 모든 응답 schema를 한 번 이상 확인합니다.
+    */
+    #[tokio::test]
+    async fn test_responses_match_document() {
+        let (context, mut client) = DocumentedClient::spawn().await;
+        let no_query = json!({});
+
+        let (_, secret) = client
+            .call(
+                Method::POST,
+                "/secrets",
+                &[],
+                no_query.clone(),
+                Some(
+                    json!({ "name": "db-password", "description": "database", "value": "secret" }),
+                ),
+            )
+            .await;
+        let secret_id = secret["secret_id"].as_i64().unwrap();
+
+        let (_, registry_credential) = client
+            .call(
+                Method::POST,
+                "/registry-credentials",
+                &[],
+                no_query.clone(),
+                Some(json!({ "registry": "registry.example.com", "username": "robot", "password": "password" })),
+            )
+            .await;
+        let registry_credential_id = registry_credential["registry_credential_id"]
+            .as_i64()
+            .unwrap();
+
+        let (_, task_definition) = client
+            .call(
+                Method::POST,
+                "/task-definitions",
+                &[],
+                no_query.clone(),
+                Some(json!({
+                    "name": "task",
+                    "description": "task",
+                    "image": "registry.example.com/app:1",
+                    "image_pull_policy": "IfNotPresent",
+                    "command": ["run"],
+                    "args": ["--verbose"],
+                    "env": { "MODE": "test" },
+                    "secrets": { "DB_PASSWORD": "db-password" },
+                    "memory_limit": 512,
+                    "cpus": 1.5,
+                    "cpu_limit": 512,
+                    "mounts": [
+                        { "type": "bind", "source": "/data", "target": "/data", "read_only": true },
+                        { "type": "tmpfs", "target": "/tmp" },
+                    ],
+                    "labels": { "team": "batch" },
+                    "ulimits": [{ "name": "nofile", "soft": 1024, "hard": 4096 }],
+                    "placement_constraints": [{ "key": "gpu", "operator": "Exists" }],
+                })),
+            )
+            .await;
+        let task_definition_id = task_definition["task_definition_id"].as_i64().unwrap();
+
+        client
+            .call(
+                Method::PATCH,
+                "/task-definitions/{task_definition_id}",
+                &[task_definition_id],
+                no_query.clone(),
+                Some(json!({ "description": "patched" })),
+            )
+            .await;
+
+        client
+            .call(
+                Method::POST,
+                "/schedules",
+                &[],
+                no_query.clone(),
+                Some(json!({
+                    "name": "hourly",
+                    "job_name": "hourly-job",
+                    "cron_expression": "0 * * * ? *",
+                    "task_definition_id": task_definition_id,
+                    "timezone": "Asia/Seoul",
+                    "enabled": true,
+                })),
+            )
+            .await;
+        let (_, schedules) = client
+            .call(Method::GET, "/schedules", &[], no_query.clone(), None)
+            .await;
+        let schedule_id = schedules["schedules"][0]["id"].as_i64().unwrap();
+
+        let mut job_ids = Vec::new();
+        for job_name in ["job-a", "job-b"] {
+            let (_, job) = client
+                .call(
+                    Method::POST,
+                    "/jobs/submit",
+                    &[],
+                    no_query.clone(),
+                    Some(json!({ "task_definition_id": task_definition_id, "job_name": job_name })),
+                )
+                .await;
+            job_ids.push(job["job_id"].as_i64().unwrap());
+        }
+        let (finished_job_id, cancelled_job_id) = (job_ids[0], job_ids[1]);
+
+        let (_, node) = client
+            .call(
+                Method::POST,
+                "/nodes/register",
+                &[],
+                no_query.clone(),
+                Some(json!({ "name": "node-a", "labels": { "gpu": "true" }, "capacity": 2 })),
+            )
+            .await;
+        let node_id = node["node_id"].as_i64().unwrap();
+
+        let (_, claimed) = client
+            .call(
+                Method::POST,
+                "/nodes/{node_id}/claim",
+                &[node_id],
+                no_query.clone(),
+                Some(json!({ "limit": 2 })),
+            )
+            .await;
+        assert_eq!(claimed["jobs"].as_array().unwrap().len(), 2);
+        assert!(claimed["jobs"][0]["registry_auth"].is_object());
+
+        for job_id in [finished_job_id, cancelled_job_id] {
+            client
+                .call(
+                    Method::POST,
+                    "/nodes/{node_id}/jobs/{job_id}/report",
+                    &[node_id, job_id],
+                    no_query.clone(),
+                    Some(json!({ "status": "Running", "container_id": format!("container-{}", job_id) })),
+                )
+                .await;
+        }
+
+        client
+            .call(
+                Method::POST,
+                "/nodes/{node_id}/jobs/{job_id}/logs",
+                &[node_id, finished_job_id],
+                no_query.clone(),
+                Some(json!({ "offset": 0, "logs": [{ "time": "2025-01-01T00:00:00Z", "message": "hello" }] })),
+            )
+            .await;
+        context
+            .job_repository
+            .create_job_metric(CreateJobMetricParams {
+                job_id: finished_job_id,
+                sampled_at: chrono::Utc::now(),
+                cpu_percent: 12.5,
+                cpu_seconds: 0.5,
+                memory_usage_bytes: 1024,
+                memory_limit_bytes: 4096,
+                pids: 3,
+            })
+            .await
+            .unwrap();
+        client
+            .call(
+                Method::POST,
+                "/nodes/{node_id}/jobs/{job_id}/report",
+                &[node_id, finished_job_id],
+                no_query.clone(),
+                Some(json!({ "status": "Succeeded", "exit_code": 0, "exit_reason": "Succeeded" })),
+            )
+            .await;
+
+        // 취소 요청된 job은 heartbeat 응답으로 agent에 전달됨
+        client
+            .call(
+                Method::POST,
+                "/jobs/stop",
+                &[],
+                no_query.clone(),
+                Some(json!({ "job_id": cancelled_job_id, "reason": "test", "requested_by": "tester" })),
+            )
+            .await;
+        let (_, heartbeat) = client
+            .call(
+                Method::POST,
+                "/nodes/{node_id}/heartbeat",
+                &[node_id],
+                no_query.clone(),
+                Some(json!({ "running_jobs": 1, "job_ids": [cancelled_job_id] })),
+            )
+            .await;
+        assert_eq!(heartbeat["cancel_jobs"].as_array().unwrap().len(), 1);
+
+        let lists = [
+            ("/jobs", vec![], no_query.clone()),
+            ("/jobs/stats", vec![], no_query.clone()),
+            (
+                "/jobs/{job_id}/logs",
+                vec![finished_job_id],
+                json!({ "offset": 0, "limit": 10 }),
+            ),
+            (
+                "/jobs/{job_id}/logs/count",
+                vec![finished_job_id],
+                no_query.clone(),
+            ),
+            (
+                "/jobs/{job_id}/metrics",
+                vec![finished_job_id],
+                no_query.clone(),
+            ),
+            (
+                "/jobs/{job_id}/events",
+                vec![finished_job_id],
+                no_query.clone(),
+            ),
+            ("/nodes", vec![], no_query.clone()),
+            ("/schedules", vec![], no_query.clone()),
+            ("/secrets", vec![], no_query.clone()),
+            ("/registry-credentials", vec![], no_query.clone()),
+            ("/task-definitions", vec![], no_query.clone()),
+        ];
+        for (path, ids, query) in lists {
+            let (status, _) = client.call(Method::GET, path, &ids, query, None).await;
+            assert_eq!(status, 200, "GET {}", path);
+        }
+
+        client
+            .call(
+                Method::POST,
+                "/jobs/purge",
+                &[],
+                no_query.clone(),
+                Some(json!({ "task_definition_id": task_definition_id, "dry_run": true })),
+            )
+            .await;
+        client
+            .call(
+                Method::PATCH,
+                "/secrets/{secret_id}",
+                &[secret_id],
+                no_query.clone(),
+                Some(json!({ "description": "patched" })),
+            )
+            .await;
+        client
+            .call(
+                Method::PATCH,
+                "/registry-credentials/{registry_credential_id}",
+                &[registry_credential_id],
+                no_query.clone(),
+                Some(json!({ "username": "robot-2" })),
+            )
+            .await;
+        client
+            .call(
+                Method::PATCH,
+                "/schedules/{schedule_id}",
+                &[schedule_id],
+                no_query.clone(),
+                Some(json!({ "enabled": false })),
+            )
+            .await;
+        client
+            .call(
+                Method::DELETE,
+                "/schedules/{schedule_id}",
+                &[schedule_id],
+                no_query.clone(),
+                None,
+            )
+            .await;
+
+        // 오류 응답
+        let (status, _) = client
+            .call(
+                Method::GET,
+                "/jobs/{job_id}/events",
+                &[i64::MAX],
+                no_query.clone(),
+                None,
+            )
+            .await;
+        assert_eq!(status, 404);
+        let (status, error) = client
+            .call(
+                Method::POST,
+                "/jobs/submit",
+                &[],
+                no_query.clone(),
+                Some(json!({ "task_definition_id": task_definition_id, "job_name": "" })),
+            )
+            .await;
+        assert_eq!(status, 400);
+        assert!(!error["details"].as_array().unwrap().is_empty());
+
+        // 문서에 있는 응답 schema는 모두 확인되어야 함
+        let mut registry = SchemaRegistry::new();
+        register_responses(&mut registry, routes::api_router().operations());
+        let unchecked = registry
+            .into_schemas()
+            .into_keys()
+            .filter(|name| !client.visited.contains(name))
+            .collect::<Vec<_>>();
+        assert!(unchecked.is_empty(), "not checked: {:?}", unchecked);
+
+        // 보관/내보내기에 쓰는 job 기록은 job 필드를 펼쳐서(flatten) 직렬화
+        let jobs = context
+            .job_repository
+            .list_jobs(ListJobsParams {
+                job_ids: vec![finished_job_id],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let records = context
+            .job_repository
+            .list_job_records(&jobs)
+            .await
+            .unwrap();
+
+        let mut registry = SchemaRegistry::new();
+        let reference = JobRecordDto::schema(&mut registry);
+        let schemas = registry.into_schemas().into_iter().collect::<Map<_, _>>();
+        let record = serde_json::to_value(&records[0]).unwrap();
+        let mut visited = BTreeSet::new();
+        check_value(&schemas, &reference, &record, "JobRecordDto", &mut visited).unwrap();
+        assert_eq!(record["status"], "Succeeded");
+        assert_eq!(record["logs"].as_array().unwrap().len(), 1);
+        assert!(visited.contains("JobMetricDto"));
+    }
+}
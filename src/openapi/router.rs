@@ -0,0 +1,296 @@
+use std::{collections::BTreeMap, fmt::Debug, future::Future};
+
+use axum::{
+    Extension, Json, Router,
+    handler::Handler,
+    http::{HeaderMap, Method},
+    response::{Html, sse::Sse},
+    routing::{MethodFilter, MethodRouter},
+};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{
+    errors,
+    validation::{ValidJson, ValidPath, ValidQuery},
+};
+
+use super::{Operation, ResponseBody, schema::ToSchema};
+
+/*
+route와 OpenAPI 문서를 함께 등록하는 router입니다.
+query/body/응답 schema와 operationId는 handler의 extractor와 반환 타입, 함수 이름에서 가져오므로
+route를 추가하면 문서에도 그대로 반영됩니다.
+
+ApiRouter::new("jobs")
+    .get("/jobs", "List jobs", http::list_jobs)
+*/
+pub struct ApiRouter {
+    tag: &'static str,
+    routes: BTreeMap<&'static str, MethodRouter>, // path -> handlers of every method on the path
+    operations: Vec<Operation>,
+}
+
+impl ApiRouter {
+    pub fn new(tag: &'static str) -> Self {
+        Self {
+            tag,
+            routes: BTreeMap::new(),
+            operations: Vec::new(),
+        }
+    }
+
+    pub fn get<H, T, D>(self, path: &'static str, summary: &'static str, handler: H) -> Self
+    where
+        H: Handler<T, ()> + DocumentedHandler<D>,
+        T: 'static,
+    {
+        self.route(Method::GET, path, summary, handler)
+    }
+
+    pub fn post<H, T, D>(self, path: &'static str, summary: &'static str, handler: H) -> Self
+    where
+        H: Handler<T, ()> + DocumentedHandler<D>,
+        T: 'static,
+    {
+        self.route(Method::POST, path, summary, handler)
+    }
+
+    pub fn patch<H, T, D>(self, path: &'static str, summary: &'static str, handler: H) -> Self
+    where
+        H: Handler<T, ()> + DocumentedHandler<D>,
+        T: 'static,
+    {
+        self.route(Method::PATCH, path, summary, handler)
+    }
+
+    pub fn delete<H, T, D>(self, path: &'static str, summary: &'static str, handler: H) -> Self
+    where
+        H: Handler<T, ()> + DocumentedHandler<D>,
+        T: 'static,
+    {
+        self.route(Method::DELETE, path, summary, handler)
+    }
+
+    fn route<H, T, D>(
+        mut self,
+        method: Method,
+        path: &'static str,
+        summary: &'static str,
+        handler: H,
+    ) -> Self
+    where
+        H: Handler<T, ()> + DocumentedHandler<D>,
+        T: 'static,
+    {
+        let mut operation = Operation::new(method.clone(), path, operation_id::<H>(), summary);
+        operation.tag = self.tag;
+        H::document(&mut operation);
+        self.operations.push(operation);
+
+        let filter = MethodFilter::try_from(method).expect("GET/POST/PATCH/DELETE");
+        let method_router = match self.routes.remove(path) {
+            Some(method_router) => method_router.on(filter, handler),
+            None => axum::routing::on(filter, handler),
+        };
+        self.routes.insert(path, method_router);
+
+        self
+    }
+
+    // 다른 domain의 route를 합칩니다. 같은 path와 method가 두 번 등록되면 panic합니다.
+    pub fn merge(mut self, other: ApiRouter) -> Self {
+        for (path, method_router) in other.routes {
+            let method_router = match self.routes.remove(path) {
+                Some(existing) => existing.merge(method_router),
+                None => method_router,
+            };
+            self.routes.insert(path, method_router);
+        }
+
+        self.operations.extend(other.operations);
+        self
+    }
+
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    pub fn into_router(self) -> Router {
+        self.routes
+            .into_iter()
+            .fold(Router::new(), |router, (path, method_router)| {
+                router.route(path, method_router)
+            })
+    }
+}
+
+// handler 함수 이름(list_jobs)을 operationId(listJobs)로 사용
+fn operation_id<H>() -> String {
+    let type_name = std::any::type_name::<H>();
+    let function_name = type_name.rsplit("::").next().unwrap_or(type_name);
+
+    let mut operation_id = String::with_capacity(function_name.len());
+    let mut upper = false;
+    for c in function_name.chars() {
+        match c {
+            '_' => upper = true,
+            c if upper => {
+                operation_id.extend(c.to_uppercase());
+                upper = false;
+            }
+            c => operation_id.push(c),
+        }
+    }
+
+    operation_id
+}
+
+/*
+handler 인자(extractor)가 문서에 더하는 내용입니다. (query, 요청 본문, 인증)
+*/
+pub trait OperationInput {
+    fn document(_operation: &mut Operation) {}
+}
+
+/*
+handler 반환 타입의 응답 schema입니다.
+*/
+pub trait OperationOutput {
+    fn document(operation: &mut Operation);
+}
+
+/*
+인자와 반환 타입이 모두 문서화된 handler입니다. 인자 개수별로 구현되어 있습니다.
+*/
+pub trait DocumentedHandler<T> {
+    fn document(operation: &mut Operation);
+}
+
+macro_rules! documented_handler {
+    ($($input:ident),*) => {
+        impl<F, Fut, R, $($input,)*> DocumentedHandler<($($input,)*)> for F
+        where
+            F: FnOnce($($input),*) -> Fut,
+            Fut: Future<Output = R>,
+            R: OperationOutput,
+            $($input: OperationInput,)*
+        {
+            fn document(operation: &mut Operation) {
+                $(<$input as OperationInput>::document(operation);)*
+                R::document(operation);
+            }
+        }
+    };
+}
+
+documented_handler!();
+documented_handler!(T1);
+documented_handler!(T1, T2);
+documented_handler!(T1, T2, T3);
+documented_handler!(T1, T2, T3, T4);
+documented_handler!(T1, T2, T3, T4, T5);
+
+impl<T: ToSchema + DeserializeOwned + Debug> OperationInput for ValidQuery<T> {
+    fn document(operation: &mut Operation) {
+        operation.query = Some(T::schema);
+        operation.query_decoder = Some(decode_query::<T>);
+    }
+}
+
+impl<T: ToSchema + DeserializeOwned + Debug> OperationInput for ValidJson<T> {
+    fn document(operation: &mut Operation) {
+        operation.request_body = Some(T::schema);
+        operation.request_body_decoder = Some(decode_json::<T>);
+    }
+}
+
+// query string으로 바꿔서 ValidQuery와 같은 방법으로 읽음
+fn decode_query<T: DeserializeOwned + Debug>(value: Value) -> Result<String, String> {
+    let pairs = value
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| match value {
+            Value::String(value) => (name.clone(), value.clone()),
+            value => (name.clone(), value.to_string()),
+        })
+        .collect::<Vec<_>>();
+    let query = serde_urlencoded::to_string(pairs).map_err(|error| error.to_string())?;
+
+    serde_urlencoded::from_str::<T>(&query)
+        .map(|value| format!("{:?}", value))
+        .map_err(|error| error.to_string())
+}
+
+fn decode_json<T: DeserializeOwned + Debug>(value: Value) -> Result<String, String> {
+    serde_json::from_value::<T>(value)
+        .map(|value| format!("{:?}", value))
+        .map_err(|error| error.to_string())
+}
+
+// path parameter는 path의 {name}에서 가져옴
+impl<T> OperationInput for ValidPath<T> {}
+
+impl<T> OperationInput for Extension<T> {}
+
+impl OperationInput for HeaderMap {}
+
+impl<T: OperationOutput> OperationOutput for errors::Result<T> {
+    fn document(operation: &mut Operation) {
+        T::document(operation);
+    }
+}
+
+impl<T: ToSchema> OperationOutput for Json<T> {
+    fn document(operation: &mut Operation) {
+        operation.response = ResponseBody::Json(T::schema);
+    }
+}
+
+impl OperationOutput for &'static str {
+    fn document(operation: &mut Operation) {
+        operation.response = ResponseBody::Content("text/plain");
+    }
+}
+
+impl<T> OperationOutput for Html<T> {
+    fn document(operation: &mut Operation) {
+        operation.response = ResponseBody::Content("text/html");
+    }
+}
+
+impl<S> OperationOutput for Sse<S> {
+    fn document(operation: &mut Operation) {
+        operation.response = ResponseBody::Content("text/event-stream");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{job, node};
+
+    #[test]
+    fn test_operation_from_handler() {
+        let router = ApiRouter::new("jobs")
+            .get("/jobs", "List jobs", job::routes::http::list_jobs)
+            .post(
+                "/nodes/{node_id}/claim",
+                "Claim jobs",
+                node::routes::http::claim_node_jobs,
+            );
+        let operations = router.operations();
+
+        assert_eq!(operations[0].operation_id, "listJobs");
+        assert_eq!(operations[0].tag, "jobs");
+        assert!(operations[0].query.is_some());
+        assert!(operations[0].request_body.is_none());
+        assert!(matches!(operations[0].response, ResponseBody::Json(_)));
+
+        assert_eq!(operations[1].operation_id, "claimNodeJobs");
+        assert!(operations[1].request_body.is_some());
+        assert!(operations[1].agent_auth);
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use serde_json::{Map, Value, json};
+
+/*
+OpenAPI 문서에 들어가는 JSON schema입니다.
+struct/enum은 components.schemas에 한 번만 등록하고 $ref로 참조합니다.
+*/
+pub trait ToSchema {
+    fn schema(registry: &mut SchemaRegistry) -> Value;
+
+    // Option<T> 필드는 생략할 수 있음
+    fn is_required() -> bool {
+        true
+    }
+}
+
+/*
+schema를 요청(query, 요청 본문)에 쓰는지 응답에 쓰는지 나타냅니다.
+요청에서는 생략할 수 있는 필드(Option, #[serde(default)])가 필수가 아니고,
+응답에서는 null이라도 항상 직렬화되므로 모든 필드가 필수입니다.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    #[default]
+    Response,
+    Request,
+}
+
+/*
+이름이 있는 schema(components.schemas)를 모읍니다.
+응답 schema를 먼저 모두 등록해야 합니다. 요청 schema가 같은 이름의 응답 schema와 다르면 {name}Input으로 등록합니다.
+*/
+#[derive(Debug, Default)]
+pub struct SchemaRegistry {
+    schemas: BTreeMap<String, Value>,
+    direction: Direction,
+    request_names: BTreeMap<String, String>, // type name -> name of the request schema
+}
+
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reference(name: &str) -> Value {
+        json!({ "$ref": format!("#/components/schemas/{}", name) })
+    }
+
+    // $ref가 가리키는 schema를 찾습니다. $ref가 아니면 그대로 반환합니다.
+    pub fn resolve<'a>(&'a self, schema: &'a Value) -> Option<&'a Value> {
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => self
+                .schemas
+                .get(reference.trim_start_matches("#/components/schemas/")),
+            None => Some(schema),
+        }
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    // 요청 DTO(query, 요청 본문)의 schema를 만듭니다.
+    pub fn request<R>(&mut self, schema: impl FnOnce(&mut SchemaRegistry) -> R) -> R {
+        let direction = std::mem::replace(&mut self.direction, Direction::Request);
+        let schema = schema(self);
+        self.direction = direction;
+        schema
+    }
+
+    pub fn object(
+        &mut self,
+        name: &str,
+        fields: impl FnOnce(&mut SchemaRegistry) -> Vec<Field>,
+    ) -> Value {
+        match self.direction {
+            Direction::Response => {
+                if !self.schemas.contains_key(name) {
+                    // 자기 자신을 참조하는 타입이 있어도 무한히 돌지 않도록 먼저 자리를 잡아둠
+                    self.schemas.insert(name.to_string(), Value::Null);
+
+                    let fields = fields(self);
+                    let schema = self.object_schema(fields);
+                    self.schemas.insert(name.to_string(), schema);
+                }
+
+                Self::reference(name)
+            }
+            Direction::Request => {
+                if let Some(request_name) = self.request_names.get(name) {
+                    return Self::reference(request_name);
+                }
+                self.request_names
+                    .insert(name.to_string(), name.to_string());
+
+                let fields = fields(self);
+                let schema = self.object_schema(fields);
+
+                // 응답 schema와 같으면 같은 이름을 함께 사용
+                let request_name = match self.schemas.get(name) {
+                    Some(existing) if *existing != schema => format!("{}Input", name),
+                    _ => name.to_string(),
+                };
+                self.schemas.insert(request_name.clone(), schema);
+                self.request_names
+                    .insert(name.to_string(), request_name.clone());
+
+                Self::reference(&request_name)
+            }
+        }
+    }
+
+    fn object_schema(&self, fields: Vec<Field>) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+
+        for field in fields {
+            // #[serde(flatten)] 필드는 안쪽 object의 필드를 그대로 펼침
+            if field.flatten {
+                let inner = self.resolve(&field.schema).cloned().unwrap_or_default();
+                if let Some(inner_properties) = inner["properties"].as_object() {
+                    properties.extend(inner_properties.clone());
+                }
+                if let Some(inner_required) = inner["required"].as_array() {
+                    required.extend(inner_required.iter().cloned());
+                }
+                continue;
+            }
+
+            if field.required {
+                required.push(Value::from(field.name));
+            }
+            for alias in field.aliases {
+                let mut schema = field.schema.clone();
+                schema["deprecated"] = json!(true);
+                properties.insert(alias.to_string(), schema);
+            }
+            properties.insert(field.name.to_string(), field.schema);
+        }
+
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        })
+    }
+
+    pub fn enumeration(&mut self, name: &str, values: Vec<Value>) -> Value {
+        self.schemas
+            .entry(name.to_string())
+            .or_insert_with(|| json!({ "type": "string", "enum": values }));
+
+        Self::reference(name)
+    }
+
+    pub fn into_schemas(self) -> BTreeMap<String, Value> {
+        self.schemas
+    }
+}
+
+/*
+object schema의 필드 하나입니다. 필드 타입은 struct 정의에서 추론하므로 문서와 DTO가 어긋나지 않습니다.
+*/
+#[derive(Debug)]
+pub struct Field {
+    pub name: &'static str,
+    pub schema: Value,
+    pub required: bool,
+    direction: Direction,
+    flatten: bool,
+    aliases: Vec<&'static str>,
+}
+
+impl Field {
+    pub fn of<O, T: ToSchema>(
+        registry: &mut SchemaRegistry,
+        name: &'static str,
+        _field: impl Fn(&O) -> &T,
+    ) -> Self {
+        let direction = registry.direction();
+
+        Field {
+            name,
+            schema: T::schema(registry),
+            required: match direction {
+                Direction::Request => T::is_required(),
+                Direction::Response => true,
+            },
+            direction,
+            flatten: false,
+            aliases: Vec::new(),
+        }
+    }
+
+    // #[serde(default)] 필드 (요청에서 생략할 수 있음)
+    pub fn optional(mut self) -> Self {
+        if self.direction == Direction::Request {
+            self.required = false;
+        }
+        self
+    }
+
+    // #[serde(skip_serializing_if = "...")] 필드 (응답에서 생략될 수 있음)
+    pub fn skip_serializing(mut self) -> Self {
+        if self.direction == Direction::Response {
+            self.required = false;
+        }
+        self
+    }
+
+    // #[serde(rename = "...")] 필드
+    pub fn rename(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    // #[serde(alias = "...")] 필드 (요청에서 예전 이름도 받음)
+    pub fn alias(mut self, name: &'static str) -> Self {
+        if self.direction == Direction::Request {
+            self.aliases.push(name);
+        }
+        self
+    }
+
+    // #[serde(flatten)] 필드
+    pub fn flatten(mut self) -> Self {
+        self.flatten = true;
+        self
+    }
+}
+
+pub fn field_schema<O, T: ToSchema>(
+    registry: &mut SchemaRegistry,
+    _field: impl Fn(&O) -> &T,
+) -> Value {
+    T::schema(registry)
+}
+
+/*
+struct의 object schema를 구현합니다. 모든 필드를 나열해야 하며, 필드가 추가되거나 빠지면 컴파일 오류가 납니다.
+serde 속성은 #[optional](serde default), #[skip_serializing](serde skip_serializing_if),
+#[rename = "type"], #[alias = "cpu_limit"], #[flatten]으로 표시합니다. struct에 붙은 #[optional]은 모든 필드에 적용됩니다.
+표시가 serde 속성과 맞는지는 openapi 테스트가 DTO를 직렬화/역직렬화해서 확인합니다.
+
+schema_object!(Mount {
+    #[rename = "type"]
+    mount_type,
+    source,
+});
+*/
+macro_rules! schema_object {
+    ($(#[$struct_attr:ident])* $type:ident $(as $name:literal)? {
+        $( $(#[$attr:ident $(= $value:literal)?])* $field:ident ),* $(,)?
+    }) => {
+        impl $crate::openapi::schema::ToSchema for $type {
+            fn schema(
+                registry: &mut $crate::openapi::schema::SchemaRegistry,
+            ) -> serde_json::Value {
+                #[allow(dead_code)]
+                fn exhaustive(value: &$type) {
+                    let $type { $($field: _),* } = value;
+                }
+
+                let name = {
+                    let _name = stringify!($type);
+                    $(let _name = $name;)?
+                    _name
+                };
+
+                registry.object(name, |registry| {
+                    let fields: Vec<$crate::openapi::schema::Field> = vec![$(
+                        $crate::openapi::schema::Field::of(
+                            registry,
+                            stringify!($field),
+                            |value: &$type| &value.$field,
+                        )
+                        $(.$attr($($value)?))*
+                    ),*];
+
+                    fields
+                        .into_iter()
+                        .map(|field| field $(.$struct_attr())*)
+                        .collect()
+                })
+            }
+        }
+    };
+}
+
+/*
+값이 없는 variant로만 된 enum의 문자열 schema를 구현합니다. 값은 serde로 직렬화해서 얻으므로 rename_all도 반영됩니다.
+*/
+macro_rules! schema_enum {
+    ($type:ident { $($variant:ident),* $(,)? }) => {
+        impl $crate::openapi::schema::ToSchema for $type {
+            fn schema(
+                registry: &mut $crate::openapi::schema::SchemaRegistry,
+            ) -> serde_json::Value {
+                #[allow(dead_code)]
+                fn exhaustive(value: &$type) {
+                    match value {
+                        $($type::$variant => {})*
+                    }
+                }
+
+                let values = vec![$(
+                    serde_json::to_value($type::$variant)
+                        .expect("unit variant serializes to a string")
+                ),*];
+
+                registry.enumeration(stringify!($type), values)
+            }
+        }
+    };
+}
+
+/*
+#[serde(transparent)] newtype은 안쪽 타입의 schema를 그대로 사용합니다.
+*/
+macro_rules! schema_transparent {
+    ($type:ident) => {
+        impl $crate::openapi::schema::ToSchema for $type {
+            fn schema(registry: &mut $crate::openapi::schema::SchemaRegistry) -> serde_json::Value {
+                $crate::openapi::schema::field_schema(registry, |value: &$type| &value.0)
+            }
+        }
+    };
+}
+
+pub(crate) use schema_enum;
+pub(crate) use schema_object;
+pub(crate) use schema_transparent;
+
+macro_rules! primitive_schema {
+    ($($type:ty => $schema:tt),* $(,)?) => {
+        $(
+            impl ToSchema for $type {
+                fn schema(_registry: &mut SchemaRegistry) -> Value {
+                    json!($schema)
+                }
+            }
+        )*
+    };
+}
+
+primitive_schema! {
+    String => { "type": "string" },
+    bool => { "type": "boolean" },
+    i32 => { "type": "integer", "format": "int32" },
+    i64 => { "type": "integer", "format": "int64" },
+    u32 => { "type": "integer", "format": "int32", "minimum": 0 },
+    u64 => { "type": "integer", "format": "int64", "minimum": 0 },
+    usize => { "type": "integer", "format": "int64", "minimum": 0 },
+    f64 => { "type": "number", "format": "double" },
+    chrono::DateTime<Utc> => { "type": "string", "format": "date-time" },
+    () => { "type": "null" },
+    Value => {},
+}
+
+impl<T: ToSchema> ToSchema for Option<T> {
+    fn schema(registry: &mut SchemaRegistry) -> Value {
+        let schema = T::schema(registry);
+
+        // 응답에서는 null로 내려가므로 null도 허용
+        match schema.get("type").and_then(Value::as_str) {
+            Some(schema_type) => {
+                let mut schema = schema.clone();
+                schema["type"] = json!([schema_type, "null"]);
+                schema
+            }
+            None => json!({ "anyOf": [schema, { "type": "null" }] }),
+        }
+    }
+
+    fn is_required() -> bool {
+        false
+    }
+}
+
+// 생략하면 현재 값을 유지하고, null이면 값을 지움
+impl<T: ToSchema> ToSchema for crate::types::patch::Patch<T> {
+    fn schema(registry: &mut SchemaRegistry) -> Value {
+        Option::<T>::schema(registry)
+    }
+
+    fn is_required() -> bool {
+        false
+    }
+}
+
+impl<T: ToSchema> ToSchema for Vec<T> {
+    fn schema(registry: &mut SchemaRegistry) -> Value {
+        json!({ "type": "array", "items": T::schema(registry) })
+    }
+}
+
+impl<T: ToSchema> ToSchema for BTreeMap<String, T> {
+    fn schema(registry: &mut SchemaRegistry) -> Value {
+        json!({ "type": "object", "additionalProperties": T::schema(registry) })
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt::Write;
+
+use serde_json::Value;
+
+use crate::routes;
+
+use super::{Operation, ResponseBody, SchemaFn, register_responses, schema::SchemaRegistry};
+
+// 생성한 클라이언트를 저장하는 위치 (web/src/api.ts가 사용)
+pub const CLIENT_PATH: &str = "web/src/generated/api.ts";
+
+/*
+OpenAPI 문서와 같은 route/DTO로 web에서 사용할 TypeScript 타입과 클라이언트를 만듭니다.
+HTTP 요청은 createClient에 넘기는 Transport가 보내므로 특정 HTTP 라이브러리에 묶이지 않습니다.
+*/
+pub fn client() -> String {
+    let router = routes::api_router();
+    let mut registry = SchemaRegistry::new();
+    let mut functions = String::new();
+
+    // 모든 오류 응답의 본문과 응답 DTO
+    register_responses(&mut registry, router.operations());
+
+    for operation in router.operations() {
+        write_function(&mut functions, &mut registry, operation);
+    }
+
+    let mut output = String::from(
+        "// Generated from the API routes and DTOs. Do not edit.\n\
+         // Regenerate: BATCHMAN_UPDATE_API_CLIENT=1 cargo test --lib openapi::typescript\n\n",
+    );
+
+    for (name, schema) in registry.into_schemas() {
+        match schema.get("enum") {
+            Some(_) => writeln!(output, "export type {} = {};\n", name, type_of(&schema)),
+            None => writeln!(
+                output,
+                "export interface {} {}\n",
+                name,
+                object_body(&schema, "")
+            ),
+        }
+        .unwrap();
+    }
+
+    output.push_str(
+        "export type Method = \"GET\" | \"POST\" | \"PATCH\" | \"DELETE\";\n\n\
+         export interface ApiRequest {\n  method: Method;\n  path: string; // path under /api\n  query?: object;\n  body?: unknown;\n}\n\n\
+         export interface Transport {\n  request<T>(request: ApiRequest): Promise<T>;\n}\n\n\
+         export const createClient = (transport: Transport) => ({\n",
+    );
+    output.push_str(&functions);
+    output.push_str("});\n\nexport type Client = ReturnType<typeof createClient>;\n");
+
+    output
+}
+
+fn write_function(output: &mut String, registry: &mut SchemaRegistry, operation: &Operation) {
+    let path_parameters = operation
+        .path
+        .split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .collect::<Vec<_>>();
+
+    let mut parameters = path_parameters
+        .iter()
+        .map(|name| format!("{}: number", name))
+        .collect::<Vec<_>>();
+    let mut request = vec![format!("method: \"{}\"", operation.method)];
+
+    let path = operation.path.replace('{', "${");
+    match path_parameters.is_empty() {
+        true => request.push(format!("path: \"{}\"", path)),
+        false => request.push(format!("path: `{}`", path)),
+    }
+
+    if let Some(query) = operation.query {
+        // 필수 query가 없으면 생략할 수 있음
+        let reference = registry.request(query);
+        let required = registry
+            .resolve(&reference)
+            .and_then(|schema| schema["required"].as_array())
+            .is_some_and(|required| !required.is_empty());
+
+        parameters.push(match required {
+            true => format!("query: {}", type_of(&reference)),
+            false => format!("query: {} = {{}}", type_of(&reference)),
+        });
+        request.push("query".to_string());
+    }
+
+    if let Some(body) = operation.request_body {
+        parameters.push(format!("body: {}", type_of(&registry.request(body))));
+        request.push("body".to_string());
+    }
+
+    let response = match &operation.response {
+        ResponseBody::Json(response) => schema_type(registry, *response),
+        ResponseBody::Content(_) => "string".to_string(),
+    };
+
+    writeln!(
+        output,
+        "  // {}\n  {}: ({}) =>\n    transport.request<{}>({{ {} }}),",
+        operation.summary,
+        operation.operation_id,
+        parameters.join(", "),
+        response,
+        request.join(", "),
+    )
+    .unwrap();
+}
+
+fn schema_type(registry: &mut SchemaRegistry, schema: SchemaFn) -> String {
+    type_of(&schema(registry))
+}
+
+// JSON schema를 TypeScript 타입으로 바꿉니다.
+fn type_of(schema: &Value) -> String {
+    if let Some(reference) = schema["$ref"].as_str() {
+        return reference
+            .trim_start_matches("#/components/schemas/")
+            .to_string();
+    }
+
+    if let Some(any_of) = schema["anyOf"].as_array() {
+        return any_of.iter().map(type_of).collect::<Vec<_>>().join(" | ");
+    }
+
+    if let Some(values) = schema["enum"].as_array() {
+        return values
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+
+    // ["string", "null"]
+    if let Some(types) = schema["type"].as_array() {
+        return types
+            .iter()
+            .map(|schema_type| {
+                let mut schema = schema.clone();
+                schema["type"] = schema_type.clone();
+                type_of(&schema)
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+
+    match schema["type"].as_str() {
+        Some("string") => "string".to_string(),
+        Some("integer" | "number") => "number".to_string(),
+        Some("boolean") => "boolean".to_string(),
+        Some("null") => "null".to_string(),
+        Some("array") => {
+            let items = type_of(&schema["items"]);
+            match items.contains(' ') {
+                true => format!("({})[]", items),
+                false => format!("{}[]", items),
+            }
+        }
+        Some("object") if schema.get("properties").is_some() => object_body(schema, "  "),
+        Some("object") => format!(
+            "Record<string, {}>",
+            schema
+                .get("additionalProperties")
+                .map(type_of)
+                .unwrap_or("unknown".to_string())
+        ),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn object_body(schema: &Value, indent: &str) -> String {
+    let required = schema["required"].as_array().cloned().unwrap_or_default();
+    let mut body = String::from("{\n");
+
+    for (name, property) in schema["properties"].as_object().into_iter().flatten() {
+        let optional = match required.contains(&Value::from(name.as_str())) {
+            true => "",
+            false => "?",
+        };
+        writeln!(
+            body,
+            "{}  {}{}: {};",
+            indent,
+            name,
+            optional,
+            type_of(property)
+        )
+        .unwrap();
+    }
+
+    body.push_str(indent);
+    body.push('}');
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+    체크인된 클라이언트가 현재 route/DTO와 같은지 확인합니다.
+    BATCHMAN_UPDATE_API_CLIENT=1이면 비교하지 않고 파일을 다시 씁니다.
+    */
+    #[test]
+    fn test_typescript_client_is_up_to_date() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(CLIENT_PATH);
+        let generated = client();
+
+        if std::env::var_os("BATCHMAN_UPDATE_API_CLIENT").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, generated).unwrap();
+            return;
+        }
+
+        let checked_in = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            checked_in == generated,
+            "{} is out of date. Run: BATCHMAN_UPDATE_API_CLIENT=1 cargo test --lib openapi::typescript",
+            CLIENT_PATH
+        );
+    }
+
+    #[test]
+    fn test_type_of() {
+        assert_eq!(
+            type_of(
+                &serde_json::json!({ "type": ["array", "null"], "items": { "type": "string" } })
+            ),
+            "string[] | null"
+        );
+        assert_eq!(
+            type_of(
+                &serde_json::json!({ "type": "object", "additionalProperties": { "$ref": "#/components/schemas/Mount" } })
+            ),
+            "Record<string, Mount>"
+        );
+        assert_eq!(
+            type_of(&serde_json::json!({ "enum": ["asc", "desc"] })),
+            "\"asc\" | \"desc\""
+        );
+    }
+}
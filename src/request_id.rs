@@ -0,0 +1,45 @@
+use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+
+// 요청 id를 주고받는 헤더입니다. 클라이언트가 보낸 값이 있다면 그대로 사용합니다.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// 클라이언트가 보낸 요청 id로 받아들이는 최대 길이
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/*
+처리 중인 HTTP 요청의 id를 반환합니다. 요청 처리 밖(백그라운드 루프 등)에서는 None입니다.
+*/
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/*
+요청마다 id를 정해서 처리하는 동안 current()로 꺼낼 수 있게 하고, 응답의 x-request-id 헤더로 돌려줍니다.
+*/
+pub async fn request_id_middleware(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| {
+            !value.is_empty()
+                && value.len() <= MAX_REQUEST_ID_LENGTH
+                && value.chars().all(|c| c.is_ascii_graphic())
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let mut response = REQUEST_ID
+        .scope(request_id.clone(), next.run(request))
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    response
+}
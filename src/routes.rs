@@ -0,0 +1,42 @@
+use axum::Extension;
+
+use crate::{
+    context::SharedContext,
+    domain,
+    openapi::{self, router::ApiRouter},
+};
+
+/*
+/api 아래의 모든 route입니다. 같은 목록으로 OpenAPI 문서(openapi::document)도 만듭니다.
+*/
+pub fn api_router() -> ApiRouter {
+    ApiRouter::new("system")
+        .get("/healthz", "Health check", healthz)
+        .get(
+            "/database-check",
+            "Check the database connection",
+            database_check,
+        )
+        .get(
+            "/openapi.json",
+            "This OpenAPI document",
+            openapi::get_openapi_document,
+        )
+        .get("/docs", "API reference page", openapi::get_api_docs)
+        .merge(domain::task_definition::routes::router::router())
+        .merge(domain::job::routes::router::router())
+        .merge(domain::node::routes::router::router())
+        .merge(domain::secret::routes::router::router())
+        .merge(domain::registry_credential::routes::router::router())
+        .merge(domain::schedule::routes::router::router())
+}
+
+pub async fn healthz() -> &'static str {
+    "Hello, World!"
+}
+
+pub async fn database_check(Extension(state): Extension<SharedContext>) -> &'static str {
+    state.connection.ping().await.unwrap();
+
+    "OK"
+}
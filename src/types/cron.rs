@@ -23,7 +23,7 @@ impl CronExpressionElement {
                     return false; // Step cannot be zero
                 }
 
-                (value - base) % step == 0
+                (value - base).is_multiple_of(*step)
             }
         }
     }
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Deserializer};
+
+/*
+PATCH 요청 본문의 필드입니다. Option<T>는 필드 생략과 null을 구분하지 못하므로, 지울 수 있는 필드에 사용합니다.
+- Missing: 필드를 생략함. 현재 값을 유지
+- Null: null을 보냄. 값을 지움
+- Value: 값을 바꿈
+필드를 생략할 수 있도록 struct에 #[serde(default)]가 필요합니다.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Patch<T> {
+    #[default]
+    Missing,
+    Null,
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    // 요청에 들어있는 새 값 (생략하거나 null이면 None)
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Patch::Value(value) => Some(value),
+            Patch::Missing | Patch::Null => None,
+        }
+    }
+
+    pub fn as_ref(&self) -> Patch<&T> {
+        match self {
+            Patch::Missing => Patch::Missing,
+            Patch::Null => Patch::Null,
+            Patch::Value(value) => Patch::Value(value),
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Patch<U> {
+        match self {
+            Patch::Missing => Patch::Missing,
+            Patch::Null => Patch::Null,
+            Patch::Value(value) => Patch::Value(f(value)),
+        }
+    }
+
+    // 현재 값에 요청을 적용한 결과
+    pub fn apply(self, current: Option<T>) -> Option<T> {
+        match self {
+            Patch::Missing => current,
+            Patch::Null => None,
+            Patch::Value(value) => Some(value),
+        }
+    }
+
+    // repository에 넘길 변경 내용 (None: 유지, Some(None): 지움)
+    pub fn into_update(self) -> Option<Option<T>> {
+        match self {
+            Patch::Missing => None,
+            Patch::Null => Some(None),
+            Patch::Value(value) => Some(Some(value)),
+        }
+    }
+}
+
+// 필드가 있으면 null 또는 값이고, 없으면 #[serde(default)]로 Missing이 됨
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            Some(value) => Patch::Value(value),
+            None => Patch::Null,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize, Default)]
+    #[serde(default)]
+    struct Body {
+        user: Patch<String>,
+    }
+
+    #[test]
+    fn test_patch_deserialize() {
+        let parse = |raw: &str| serde_json::from_str::<Body>(raw).unwrap().user;
+
+        assert_eq!(parse("{}"), Patch::Missing);
+        assert_eq!(parse(r#"{"user": null}"#), Patch::Null);
+        assert_eq!(
+            parse(r#"{"user": "batch"}"#),
+            Patch::Value("batch".to_string())
+        );
+
+        assert_eq!(Patch::Missing.apply(Some(1)), Some(1));
+        assert_eq!(Patch::Null.apply(Some(1)), None);
+        assert_eq!(Patch::Value(2).apply(Some(1)), Some(2));
+        assert_eq!(Patch::<i32>::Missing.into_update(), None);
+        assert_eq!(Patch::<i32>::Null.into_update(), Some(None));
+    }
+}
@@ -34,6 +34,24 @@ pub(crate) fn count_lines(file_path: &str) -> Result<usize, std::io::Error> {
     Ok(count)
 }
 
+/*
+목록을 id 내림차순으로 page_size + 1개 조회한 결과에서 다음 페이지가 있는지 확인합니다.
+다음 페이지가 있다면 page_size개로 자르고, 다음 요청의 after_id로 사용할 마지막 id를 반환합니다.
+*/
+pub(crate) fn take_page<T>(
+    items: &mut Vec<T>,
+    page_size: u64,
+    id: impl Fn(&T) -> i64,
+) -> Option<i64> {
+    if items.len() as u64 <= page_size {
+        return None;
+    }
+
+    items.truncate(page_size as usize);
+
+    items.last().map(id)
+}
+
 /*
 Log Tailing을 위한 유틸리티 객체
 */
@@ -100,7 +118,7 @@ mod tests {
                 let log_entry = format!("Log entry {}\n", i);
 
                 writer
-                    .write(log_entry.as_bytes())
+                    .write_all(log_entry.as_bytes())
                     .await
                     .expect("Failed to write to log file");
 
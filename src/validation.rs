@@ -0,0 +1,422 @@
+use std::fmt::Display;
+
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, FromRequestParts, Path, Request},
+    http::{HeaderMap, header, request::Parts},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{errors, openapi::schema::schema_object};
+
+// 목록 조회의 page_size 최대값
+pub const MAX_PAGE_SIZE: u16 = 1000;
+
+// 목록 조회의 page_number 최대값 ((page_number - 1) * page_size가 overflow되지 않도록 제한, 더 뒤의 페이지는 cursor로 조회)
+pub const MAX_PAGE_NUMBER: u32 = 1_000_000;
+
+// 이름 컬럼(varchar(255))의 최대 길이
+pub const MAX_NAME_LENGTH: usize = 255;
+
+/*
+검증에 실패한 요청 필드와 이유입니다. 400 VALIDATION_FAILED 응답의 details로 내려갑니다.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,   // field path (example: name, mounts[0].target, query)
+    pub message: String, // why the value was rejected
+}
+
+schema_object!(FieldError { field, message });
+
+/*
+요청 DTO의 검증 규칙입니다. ValidJson/ValidQuery로 받은 값은 handler에 넘어가기 전에 validate가 호출됩니다.
+*/
+pub trait Validate {
+    fn validate(&self, validator: &mut Validator);
+}
+
+/*
+필드 검증 결과를 모읍니다. 첫 실패에서 멈추지 않고 모든 필드의 오류를 함께 돌려줍니다.
+*/
+#[derive(Debug, Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn check(&mut self, field: &str, is_valid: bool, message: impl Into<String>) -> &mut Self {
+        if !is_valid {
+            self.errors.push(FieldError {
+                field: field.to_string(),
+                message: message.into(),
+            });
+        }
+        self
+    }
+
+    pub fn not_blank(&mut self, field: &str, value: &str) -> &mut Self {
+        self.check(field, !value.trim().is_empty(), "must not be empty")
+    }
+
+    // 값이 주어졌을 때만 검사합니다 (patch body 등)
+    pub fn optional_not_blank(&mut self, field: &str, value: Option<&str>) -> &mut Self {
+        match value {
+            Some(value) => self.not_blank(field, value),
+            None => self,
+        }
+    }
+
+    pub fn max_length(&mut self, field: &str, value: &str, max: usize) -> &mut Self {
+        self.check(
+            field,
+            value.chars().count() <= max,
+            format!("must be at most {} characters", max),
+        )
+    }
+
+    pub fn optional_max_length(
+        &mut self,
+        field: &str,
+        value: Option<&str>,
+        max: usize,
+    ) -> &mut Self {
+        match value {
+            Some(value) => self.max_length(field, value, max),
+            None => self,
+        }
+    }
+
+    pub fn min<T: PartialOrd + Display>(&mut self, field: &str, value: T, min: T) -> &mut Self {
+        let message = format!("must be greater than or equal to {}", min);
+        self.check(field, value >= min, message)
+    }
+
+    pub fn optional_min<T: PartialOrd + Display>(
+        &mut self,
+        field: &str,
+        value: Option<T>,
+        min: T,
+    ) -> &mut Self {
+        match value {
+            Some(value) => self.min(field, value, min),
+            None => self,
+        }
+    }
+
+    pub fn optional_range<T: PartialOrd + Display>(
+        &mut self,
+        field: &str,
+        value: Option<T>,
+        min: T,
+        max: T,
+    ) -> &mut Self {
+        match value {
+            Some(value) => {
+                let message = format!("must be between {} and {}", min, max);
+                self.check(field, min <= value && value <= max, message)
+            }
+            None => self,
+        }
+    }
+
+    /*
+    목록 조회 query의 page_number(1 ~ MAX_PAGE_NUMBER)와 page_size(1 ~ MAX_PAGE_SIZE)를 검사합니다.
+    */
+    pub fn pagination<T: PartialOrd + Display + From<u32>>(
+        &mut self,
+        page_number: Option<T>,
+        page_size: Option<T>,
+    ) -> &mut Self {
+        self.optional_range(
+            "page_number",
+            page_number,
+            T::from(1),
+            T::from(MAX_PAGE_NUMBER),
+        )
+        .optional_range(
+            "page_size",
+            page_size,
+            T::from(1),
+            T::from(u32::from(MAX_PAGE_SIZE)),
+        )
+    }
+
+    pub fn into_result(self) -> errors::Result<()> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors::Error::Validation(self.errors))
+        }
+    }
+}
+
+pub fn validate<T: Validate>(value: &T) -> errors::Result<()> {
+    let mut validator = Validator::new();
+    value.validate(&mut validator);
+    validator.into_result()
+}
+
+/*
+JSON 본문을 받아 검증하는 extractor입니다. 역직렬화 실패(필수 필드 누락, 타입 오류)도 필드 단위의 VALIDATION_FAILED로 응답합니다.
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for ValidJson<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Validate,
+{
+    type Rejection = errors::Error;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !has_json_content_type(request.headers()) {
+            return Err(single_error(
+                "content-type",
+                "expected request with `Content-Type: application/json`",
+            ));
+        }
+
+        let bytes = Bytes::from_request(request, state)
+            .await
+            .map_err(|rejection| single_error("body", rejection.body_text()))?;
+
+        let value = parse_json_body(&bytes)?;
+        validate(&value)?;
+
+        Ok(ValidJson(value))
+    }
+}
+
+/*
+query string을 받아 검증하는 extractor입니다. 필수 query 누락과 형식 오류는 해당 파라미터 이름으로 응답합니다.
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidQuery<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ValidQuery<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Validate,
+{
+    type Rejection = errors::Error;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let value = parse_query(parts.uri.query().unwrap_or_default())?;
+        validate(&value)?;
+
+        Ok(ValidQuery(value))
+    }
+}
+
+/*
+path 파라미터 extractor입니다. 형식 오류(/jobs/abc 등)를 다른 검증 오류와 같은 응답으로 맞춥니다.
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidPath<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ValidPath<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Send,
+{
+    type Rejection = errors::Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| single_error("path", rejection.body_text()))?;
+
+        Ok(ValidPath(value))
+    }
+}
+
+fn single_error(field: &str, message: impl Into<String>) -> errors::Error {
+    errors::Error::Validation(vec![FieldError {
+        field: field.to_string(),
+        message: message.into(),
+    }])
+}
+
+fn has_json_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    essence == "application/json"
+        || (essence.starts_with("application/") && essence.ends_with("+json"))
+}
+
+fn parse_json_body<T: DeserializeOwned>(bytes: &[u8]) -> errors::Result<T> {
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let value = serde_path_to_error::deserialize(&mut deserializer)
+        .map_err(|error| path_error("body", error))?;
+
+    // 값 뒤에 남은 내용이 있으면 잘못된 JSON
+    deserializer
+        .end()
+        .map_err(|error| single_error("body", strip_json_position(&error.to_string())))?;
+
+    Ok(value)
+}
+
+fn parse_query<T: DeserializeOwned>(query: &str) -> errors::Result<T> {
+    let deserializer =
+        serde_urlencoded::Deserializer::new(form_urlencoded::parse(query.as_bytes()));
+
+    serde_path_to_error::deserialize(deserializer).map_err(|error| path_error("query", error))
+}
+
+/*
+serde_path_to_error의 오류를 FieldError로 바꿉니다.
+필수 필드 누락은 serde가 부모 경로로 보고하므로 메시지에서 필드 이름을 꺼내 경로에 붙입니다.
+*/
+fn path_error<E: Display>(root: &str, error: serde_path_to_error::Error<E>) -> errors::Error {
+    let path = error.path().to_string();
+    let message = strip_json_position(&error.inner().to_string());
+
+    let parent = if path == "." { None } else { Some(path) };
+    let missing_field = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next());
+
+    let field = match (parent, missing_field) {
+        (Some(parent), Some(name)) => format!("{}.{}", parent, name),
+        (None, Some(name)) => name.to_string(),
+        (Some(parent), None) => parent,
+        (None, None) => root.to_string(),
+    };
+
+    single_error(&field, message)
+}
+
+// serde_json 메시지 끝의 " at line 1 column 10"을 제거
+fn strip_json_position(message: &str) -> String {
+    match message.rfind(" at line ") {
+        Some(index) => message[..index].to_string(),
+        None => message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Body {
+        name: String,
+        page_number: Option<u64>,
+        mounts: Option<Vec<Mount>>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Mount {
+        target: String,
+    }
+
+    impl Validate for Body {
+        fn validate(&self, validator: &mut Validator) {
+            validator
+                .not_blank("name", &self.name)
+                .pagination(self.page_number, None);
+        }
+    }
+
+    fn field_errors(result: errors::Result<Body>) -> Vec<FieldError> {
+        match result.and_then(|body| validate(&body).map(|_| body)) {
+            Err(errors::Error::Validation(errors)) => errors,
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_rules() {
+        let errors = field_errors(parse_json_body(br#"{"name": " ", "page_number": 0}"#));
+        assert_eq!(
+            errors,
+            vec![
+                FieldError {
+                    field: "name".to_string(),
+                    message: "must not be empty".to_string(),
+                },
+                FieldError {
+                    field: "page_number".to_string(),
+                    message: "must be between 1 and 1000000".to_string(),
+                },
+            ]
+        );
+
+        let body: Body = parse_json_body(br#"{"name": "a", "page_number": 1}"#).unwrap();
+        assert!(validate(&body).is_ok());
+
+        // offset 계산이 overflow되는 page_number는 거부
+        let errors = field_errors(parse_query(&format!("name=a&page_number={}", u64::MAX)));
+        assert_eq!(
+            errors,
+            vec![FieldError {
+                field: "page_number".to_string(),
+                message: "must be between 1 and 1000000".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_errors_have_field_path() {
+        let errors = field_errors(parse_json_body(br#"{"page_number": 1}"#));
+        assert_eq!(errors[0].field, "name");
+        assert_eq!(errors[0].message, "missing field `name`");
+
+        let errors = field_errors(parse_json_body(br#"{"name": "a", "mounts": [{}]}"#));
+        assert_eq!(errors[0].field, "mounts[0].target");
+
+        let errors = field_errors(parse_json_body(br#"{"name": "a", "page_number": -1}"#));
+        assert_eq!(errors[0].field, "page_number");
+
+        let errors = field_errors(parse_json_body(br#"{"name": "a"} x"#));
+        assert_eq!(errors[0].field, "body");
+
+        let errors = field_errors(parse_query("name=a&page_number=abc"));
+        assert_eq!(errors[0].field, "page_number");
+
+        let errors = field_errors(parse_query("page_number=1"));
+        assert_eq!(errors[0].field, "name");
+    }
+
+    #[test]
+    fn test_json_content_type() {
+        let mut headers = HeaderMap::new();
+        assert!(!has_json_content_type(&headers));
+
+        headers.insert(
+            header::CONTENT_TYPE,
+            "application/json; charset=utf-8".parse().unwrap(),
+        );
+        assert!(has_json_content_type(&headers));
+
+        headers.insert(
+            header::CONTENT_TYPE,
+            "application/problem+json".parse().unwrap(),
+        );
+        assert!(has_json_content_type(&headers));
+
+        headers.insert(header::CONTENT_TYPE, "text/plain".parse().unwrap());
+        assert!(!has_json_content_type(&headers));
+    }
+}
@@ -1,13 +1,25 @@
 import axios, { AxiosError } from "axios";
 import config from "./config";
+import * as generated from "./generated/api";
+
+export type FieldError = generated.FieldError;
 
 export class ErrorResponse {
   error_code?: string;
   message?: string;
-
-  constructor(error_code: string, message: string) {
+  request_id?: string; // 서버 로그에서 요청을 찾을 때 사용
+  details?: FieldError[]; // VALIDATION_FAILED일 때 필드별 오류
+
+  constructor(
+    error_code: string,
+    message: string,
+    request_id?: string,
+    details?: FieldError[],
+  ) {
     this.error_code = error_code;
     this.message = message;
+    this.request_id = request_id;
+    this.details = details;
   }
 }
 
@@ -17,188 +29,67 @@ export interface ApiResponse<T> {
   status_code: number;
 }
 
-// Task Definition 관련 타입
-export interface TaskDefinition {
-  id: number;
-  name: string;
-  description: string;
-  version: number;
-  image: string;
-  command: string | null;
-  args: string | null;
-  env: string | null;
-  memory_limit: number | null;
-  cpu_limit: number | null;
-  created_at: string;
-  enabled: boolean;
-  is_latest: boolean;
-}
-
-export interface ListTaskDefinitionsRequest {
-  page_number: number;
-  page_size: number;
-  name?: string;
-  task_definition_id?: number;
-  contains_name?: string;
-  is_latest_only?: boolean;
-}
-
-export interface ListTaskDefinitionsResponse {
-  task_definitions: TaskDefinition[];
-  total_count: number;
-}
-
-export interface CreateTaskDefinitionRequest {
-  name: string;
-  description: string;
-  image: string;
-  command?: string;
-  args?: string;
-  env?: string;
-  memory_limit?: number;
-  cpu_limit?: number;
-}
-
-export interface CreateTaskDefinitionResponse {
-  task_definition_id: number;
-}
-
-export interface PatchTaskDefinitionRequest {
-  image?: string;
-  command?: string;
-  args?: string;
-  env?: string;
-  memory_limit?: number;
-  cpu_limit?: number;
-}
-
-// Job 관련 타입
-export type JobStatus =
-  | "Pending"
-  | "Starting"
-  | "Running"
-  | "Finished"
-  | "Failed";
-
-export type ContainerType = "Docker";
-
-export interface Job {
-  id: number;
-  name: string;
-  task_definition_id: number;
-  task_definition_name?: string;
-  status: JobStatus;
-  submited_at: string | null;
-  started_at: string | null;
-  finished_at: string | null;
-  container_type: ContainerType;
-  container_id: string | null;
-  exit_code: number | null;
-  error_message: string | null;
-  log_expire_after: string | null;
-  log_expired: boolean;
-}
-
-export interface SubmitJobRequest {
-  task_definition_id: number;
-  job_name: string;
-  log_expire_after?: string;
-}
-
-export interface StopJobRequest {
+// 서버 DTO에서 생성한 타입 (generated/api.ts)의 화면용 이름
+export type {
+  Mount,
+  MountInput,
+  Ulimit,
+  ImagePullPolicy,
+  ListTaskDefinitionsResponse,
+  CreateTaskDefinitionBody as CreateTaskDefinitionRequest,
+  PatchTaskDefinitionBody as PatchTaskDefinitionRequest,
+  ListTaskDefinitionsQuery as ListTaskDefinitionsRequest,
+  ListTaskDefinitionsItem as TaskDefinition,
+  CreateDefinitionResponse as CreateTaskDefinitionResponse,
+  ListSecretsItem as Secret,
+  ListSecretsQuery as ListSecretsRequest,
+  ListSecretsResponse,
+  CreateSecretBody as CreateSecretRequest,
+  CreateSecretResponse,
+  PatchSecretBody as PatchSecretRequest,
+  ListRegistryCredentialsItem as RegistryCredential,
+  ListRegistryCredentialsQuery as ListRegistryCredentialsRequest,
+  ListRegistryCredentialsResponse,
+  CreateRegistryCredentialBody as CreateRegistryCredentialRequest,
+  CreateRegistryCredentialResponse,
+  PatchRegistryCredentialBody as PatchRegistryCredentialRequest,
+  JobStatusDto as JobStatus,
+  ExitReason,
+  ContainerType,
+  JobDto as Job,
+  SubmitJobBody as SubmitJobRequest,
+  SubmitJobResponse,
+  StopJobBody as StopJobRequest,
+  ListJobsQuery as ListJobsRequest,
+  ListJobsResponse,
+  PurgeJobsBody as PurgeJobsRequest,
+  PurgeJobsResponse,
+  TaskDefinitionJobStatsDto as TaskDefinitionJobStats,
+  JobStatsResponse,
+  JobLogDto as JobLog,
+  ListJobLogsResponse,
+  CountJobLogsResponse,
+  JobMetricDto as JobMetric,
+  ListJobMetricsResponse,
+  JobEventDto as JobEvent,
+  ListJobEventsResponse,
+  ListSchedulesItem as Schedule,
+  ListSchedulesQuery as ListSchedulesRequest,
+  ListSchedulesResponse,
+  CreateScheduleBody as CreateScheduleRequest,
+  PatchScheduleBody as PatchScheduleRequest,
+} from "./generated/api";
+
+export interface ListJobLogsRequest extends generated.ListJobLogsQuery {
   job_id: number;
 }
 
-export interface SubmitJobResponse {
-  job_id: number;
-}
-
-export interface ListJobsRequest {
-  page_number: number;
-  page_size: number;
-  job_id?: number;
-  status?: JobStatus;
-  contains_name?: string;
-}
-
-export interface ListJobsResponse {
-  jobs: Job[];
-  total_count: number;
-}
-
-// Job Log 관련 타입
-export interface JobLog {
-  index: number;
-  time: string;
-  message: string;
-}
-
-export interface ListJobLogsRequest {
-  job_id: number;
-  offset: number;
-  limit: number;
-}
-
-export interface ListJobLogsResponse {
-  logs: JobLog[];
-}
-
 export interface CountJobLogsRequest {
   job_id: number;
 }
 
-export interface CountJobLogsResponse {
-  count: number;
-}
-
-// Schedule 관련 타입
-export interface Schedule {
-  id: number;
-  name: string;
-  job_name: string;
-  cron_expression: string;
-  task_definition_id: number;
-  command: string | null;
-  timezone: string | null;
-  timezone_offset: number | null;
-  enabled: boolean;
-  created_at: string;
-}
-
-export interface ListSchedulesResponse {
-  schedules: Schedule[];
-  total_count: number;
-}
-
-export interface ListSchedulesRequest {
-  page_number?: number;
-  page_size?: number;
-  schedule_id?: number;
-  contains_name?: string;
-  name?: string;
-  enabled?: boolean;
-}
-
-export interface CreateScheduleRequest {
-  name: string;
-  job_name: string;
-  cron_expression: string;
-  task_definition_id: number;
-  command?: string;
-  timezone: string;
-  timezone_offset: number;
-  enabled: boolean;
-}
-
-export interface PatchScheduleRequest {
-  name?: string;
-  job_name?: string;
-  cron_expression?: string;
-  task_definition_id?: number;
-  command?: string;
-  timezone?: string;
-  timezone_offset?: number;
-  enabled?: boolean;
+export interface ListJobMetricsRequest extends generated.ListJobMetricsQuery {
+  job_id: number;
 }
 
 // API 클라이언트 생성
@@ -209,6 +100,18 @@ const api = axios.create({
   },
 });
 
+const client = generated.createClient({
+  request: async <T>({ method, path, query, body }: generated.ApiRequest) => {
+    const response = await api.request<T>({
+      method,
+      url: path,
+      params: query,
+      data: body,
+    });
+    return response.data;
+  },
+});
+
 // 에러 처리 헬퍼 함수
 const handleApiError = (error: AxiosError): ApiResponse<ErrorResponse> => {
   if (error.response) {
@@ -216,6 +119,8 @@ const handleApiError = (error: AxiosError): ApiResponse<ErrorResponse> => {
       response: new ErrorResponse(
         (error.response.data as any)?.error_code,
         (error.response.data as any)?.message,
+        (error.response.data as any)?.request_id,
+        (error.response.data as any)?.details,
       ),
       status_code: error.response.status,
     };
@@ -226,212 +131,102 @@ const handleApiError = (error: AxiosError): ApiResponse<ErrorResponse> => {
   };
 };
 
-// Task Definition API
-export const listTaskDefinitions = async (
-  request: ListTaskDefinitionsRequest,
-): Promise<ApiResponse<ListTaskDefinitionsResponse | ErrorResponse>> => {
+// 클라이언트 호출 결과를 ApiResponse로 변환
+const call = async <T>(
+  request: Promise<T>,
+): Promise<ApiResponse<T | ErrorResponse>> => {
   try {
-    const response = await api.get("/task-definitions", { params: request });
-    console.log("API request params:", request); // 디버깅용
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
+    return { response: await request, status_code: 200 };
   } catch (error) {
     return handleApiError(error as AxiosError);
   }
 };
 
-export const createTaskDefinition = async (
-  taskDefinition: CreateTaskDefinitionRequest,
-): Promise<ApiResponse<CreateTaskDefinitionResponse | ErrorResponse>> => {
-  try {
-    const response = await api.post("/task-definitions", taskDefinition);
-    console.log("API response:", response); // 디버깅용
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+// Task Definition API
+export const listTaskDefinitions = (
+  request: generated.ListTaskDefinitionsQuery,
+) => call(client.listTaskDefinitions(request));
+
+export const createTaskDefinition = (
+  taskDefinition: generated.CreateTaskDefinitionBody,
+) => call(client.createTaskDefinition(taskDefinition));
 
-export const patchTaskDefinition = async (
+export const patchTaskDefinition = (
   id: number,
-  taskDefinition: PatchTaskDefinitionRequest,
-): Promise<ApiResponse<void | ErrorResponse>> => {
-  try {
-    const response = await api.patch(`/task-definitions/${id}`, taskDefinition);
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+  taskDefinition: generated.PatchTaskDefinitionBody,
+) => call(client.patchTaskDefinition(id, taskDefinition));
 
-export const deleteTaskDefinition = async (
+export const deleteTaskDefinition = (id: number) =>
+  call(client.deleteTaskDefinition(id));
+
+// Secret API
+export const listSecrets = (request: generated.ListSecretsQuery) =>
+  call(client.listSecrets(request));
+
+export const createSecret = (request: generated.CreateSecretBody) =>
+  call(client.createSecret(request));
+
+export const patchSecret = (id: number, request: generated.PatchSecretBody) =>
+  call(client.patchSecret(id, request));
+
+export const deleteSecret = (id: number) => call(client.deleteSecret(id));
+
+// Registry Credential API
+export const listRegistryCredentials = (
+  request: generated.ListRegistryCredentialsQuery,
+) => call(client.listRegistryCredentials(request));
+
+export const createRegistryCredential = (
+  request: generated.CreateRegistryCredentialBody,
+) => call(client.createRegistryCredential(request));
+
+export const patchRegistryCredential = (
   id: number,
-): Promise<ApiResponse<void | ErrorResponse>> => {
-  try {
-    const response = await api.delete(`/task-definitions/${id}`);
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+  request: generated.PatchRegistryCredentialBody,
+) => call(client.patchRegistryCredential(id, request));
+
+export const deleteRegistryCredential = (id: number) =>
+  call(client.deleteRegistryCredential(id));
 
 // Job API
-export const submitJob = async (
-  request: SubmitJobRequest,
-): Promise<ApiResponse<SubmitJobResponse | ErrorResponse>> => {
-  try {
-    const response = await api.post("/jobs/submit", request);
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+export const submitJob = (request: generated.SubmitJobBody) =>
+  call(client.submitJob(request));
 
-export const stopJob = async (
-  request: StopJobRequest,
-): Promise<ApiResponse<void | ErrorResponse>> => {
-  try {
-    const response = await api.post("/jobs/stop", request);
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+export const stopJob = (request: generated.StopJobBody) =>
+  call(client.stopJob(request));
 
-export const listJobs = async (
-  request: ListJobsRequest,
-): Promise<ApiResponse<ListJobsResponse | ErrorResponse>> => {
-  try {
-    const queryParams = new URLSearchParams();
-    queryParams.append("page_number", String(request.page_number));
-    queryParams.append("page_size", String(request.page_size));
-
-    if (request.job_id) {
-      queryParams.append("job_id", String(request.job_id));
-    }
-    if (request.status) {
-      queryParams.append("status", request.status);
-    }
-    if (request.contains_name) {
-      queryParams.append("contains_name", request.contains_name);
-    }
-
-    const response = await api.get(`/jobs?${queryParams.toString()}`);
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+export const listJobs = (request: generated.ListJobsQuery) =>
+  call(client.listJobs(request));
+
+export const purgeJobs = (request: generated.PurgeJobsBody) =>
+  call(client.purgeJobs(request));
+
+export const getJobStats = (task_definition_id?: number) =>
+  call(client.getJobStats({ task_definition_id }));
 
 // Job Log API
-export const listJobLogs = async (
-  request: ListJobLogsRequest,
-): Promise<ApiResponse<ListJobLogsResponse | ErrorResponse>> => {
-  try {
-    const queryParams = new URLSearchParams();
-    queryParams.append("offset", String(request.offset));
-    queryParams.append("limit", String(request.limit));
+export const listJobLogs = ({ job_id, ...query }: ListJobLogsRequest) =>
+  call(client.listJobLogs(job_id, query));
 
-    const response = await api.get(
-      `/jobs/${request.job_id}/logs?${queryParams.toString()}`,
-    );
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+export const countJobLogs = (request: CountJobLogsRequest) =>
+  call(client.countJobLogs(request.job_id));
 
-export const countJobLogs = async (
-  request: CountJobLogsRequest,
-): Promise<ApiResponse<CountJobLogsResponse | ErrorResponse>> => {
-  try {
-    const response = await api.get(`/jobs/${request.job_id}/logs/count`);
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+export const listJobMetrics = ({ job_id, ...query }: ListJobMetricsRequest) =>
+  call(client.listJobMetrics(job_id, query));
+
+export const listJobEvents = (job_id: number) =>
+  call(client.listJobEvents(job_id));
 
 // Schedule API
-export const listSchedules = async (
-  request?: ListSchedulesRequest,
-): Promise<ApiResponse<ListSchedulesResponse | ErrorResponse>> => {
-  try {
-    const response = await api.get("/schedules", { params: request });
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+export const listSchedules = (request?: generated.ListSchedulesQuery) =>
+  call(client.listSchedules(request));
 
-export const createSchedule = async (
-  schedule: CreateScheduleRequest,
-): Promise<ApiResponse<number | ErrorResponse>> => {
-  try {
-    const response = await api.post("/schedules", schedule);
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+export const createSchedule = (schedule: generated.CreateScheduleBody) =>
+  call(client.createSchedule(schedule));
 
-export const patchSchedule = async (
+export const patchSchedule = (
   id: number,
-  schedule: PatchScheduleRequest,
-): Promise<ApiResponse<void | ErrorResponse>> => {
-  try {
-    const response = await api.patch(`/schedules/${id}`, schedule);
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+  schedule: generated.PatchScheduleBody,
+) => call(client.patchSchedule(id, schedule));
 
-export const deleteSchedule = async (
-  id: number,
-): Promise<ApiResponse<void | ErrorResponse>> => {
-  try {
-    const response = await api.delete(`/schedules/${id}`);
-    return {
-      response: response.data,
-      status_code: response.status,
-    };
-  } catch (error) {
-    return handleApiError(error as AxiosError);
-  }
-};
+export const deleteSchedule = (id: number) => call(client.deleteSchedule(id));
@@ -20,6 +20,7 @@ import AddIcon from '@mui/icons-material/Add';
 import DeleteIcon from '@mui/icons-material/Delete';
 import { CreateTaskDefinitionFormData } from '../types/taskDefinition';
 import { TaskDefinition, ErrorResponse } from '../api';
+import { formatCommand, fromEnvMap } from '../utils';
 
 interface CreateTaskDefinitionModalProps {
     open: boolean;
@@ -60,9 +61,7 @@ const CreateTaskDefinitionModal: React.FC<CreateTaskDefinitionModalProps> = ({
 
     useEffect(() => {
         if (open && baseTaskDefinition) {
-            const env = baseTaskDefinition.env
-                ? JSON.parse(baseTaskDefinition.env)
-                : [];
+            const env = fromEnvMap(baseTaskDefinition.env);
 
             // 메모리 단위 변환 로직
             let memoryValue = baseTaskDefinition.memory_limit || 1024;
@@ -77,14 +76,14 @@ const CreateTaskDefinitionModal: React.FC<CreateTaskDefinitionModalProps> = ({
             setFormData({
                 name: baseTaskDefinition.name,
                 image: baseTaskDefinition.image,
-                command: baseTaskDefinition.command || '',
+                command: formatCommand(baseTaskDefinition.command),
                 env: env.length > 0 ? env : [{ key: '', value: '' }],
                 resources: {
                     memory: {
                         value: memoryValue,
                         unit: memoryUnit,
                     },
-                    cpu: baseTaskDefinition.cpu_limit || 1024,
+                    cpu: baseTaskDefinition.cpu_shares || 1024,
                 },
                 description: baseTaskDefinition.description,
             });
@@ -32,6 +32,7 @@ import {
   ListTaskDefinitionsRequest,
 } from "../api";
 import { useNavigate } from "react-router-dom";
+import { formatCommand } from "../utils";
 
 interface JobCreateModalProps {
   open: boolean;
@@ -363,7 +364,7 @@ const JobCreateModal: React.FC<JobCreateModalProps> = ({
                 </Typography>
                 {selectedTaskDefinition.command && (
                   <Typography variant="body2" sx={{ mt: 1 }}>
-                    <strong>명령어:</strong> {selectedTaskDefinition.command}
+                    <strong>명령어:</strong> {formatCommand(selectedTaskDefinition.command)}
                   </Typography>
                 )}
               </Paper>
@@ -29,14 +29,20 @@ const JobTable: React.FC<JobTableProps> = ({
         switch (status) {
             case 'Pending':
                 return 'warning';
+            case 'Pulling':
+                return 'info';
             case 'Starting':
                 return 'info';
             case 'Running':
                 return 'primary';
-            case 'Finished':
+            case 'Succeeded':
                 return 'success';
             case 'Failed':
                 return 'error';
+            case 'Cancelling':
+                return 'warning';
+            case 'Cancelled':
+                return 'default';
             default:
                 return 'default';
         }
@@ -46,14 +52,20 @@ const JobTable: React.FC<JobTableProps> = ({
         switch (status) {
             case 'Pending':
                 return '대기중';
+            case 'Pulling':
+                return '이미지 받는중';
             case 'Starting':
                 return '시작중';
             case 'Running':
                 return '실행중';
-            case 'Finished':
+            case 'Succeeded':
                 return '완료';
             case 'Failed':
                 return '실패';
+            case 'Cancelling':
+                return '취소중';
+            case 'Cancelled':
+                return '취소됨';
             default:
                 return status;
         }
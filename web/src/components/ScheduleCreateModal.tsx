@@ -24,6 +24,7 @@ import {
     ListTaskDefinitionsResponse,
     ErrorResponse,
 } from '../api';
+import { formatCommand } from '../utils';
 
 interface ScheduleCreateModalProps {
     open: boolean;
@@ -231,7 +232,7 @@ const ScheduleCreateModal: React.FC<ScheduleCreateModalProps> = ({
                             </Typography>
                             {selectedTaskDefinition.command && (
                                 <Typography variant="body2">
-                                    기본 명령어: {selectedTaskDefinition.command}
+                                    기본 명령어: {formatCommand(selectedTaskDefinition.command)}
                                 </Typography>
                             )}
                         </Box>
@@ -18,6 +18,7 @@ import {
   Add as AddIcon,
 } from "@mui/icons-material";
 import { TaskDefinition } from "../api";
+import { formatCommand, fromEnvMap } from "../utils";
 import JobCreateModal from "./JobCreateModal";
 
 interface TaskDefinitionDetailModalProps {
@@ -37,7 +38,7 @@ const TaskDefinitionDetailModal: React.FC<TaskDefinitionDetailModalProps> = ({
 
   if (!taskDefinition) return null;
 
-  const env = taskDefinition.env ? JSON.parse(taskDefinition.env) : [];
+  const env = fromEnvMap(taskDefinition.env);
 
   const handleSubmitClick = () => {
     setIsJobSubmitModalOpen(true);
@@ -90,11 +91,7 @@ const TaskDefinitionDetailModal: React.FC<TaskDefinitionDetailModalProps> = ({
 
             <TextField
               label="명령어"
-              value={
-                taskDefinition.command
-                  ? taskDefinition.command.replace(/\\"/g, '"')
-                  : ""
-              }
+              value={formatCommand(taskDefinition.command)}
               fullWidth
               InputProps={{ readOnly: true }}
             />
@@ -158,7 +155,7 @@ const TaskDefinitionDetailModal: React.FC<TaskDefinitionDetailModalProps> = ({
               <Box sx={{ flex: 1 }}>
                 <TextField
                   label="CPU 제한"
-                  value={taskDefinition.cpu_limit || ""}
+                  value={taskDefinition.cpu_shares || ""}
                   fullWidth
                   InputProps={{ readOnly: true }}
                 />
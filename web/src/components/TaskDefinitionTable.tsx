@@ -16,6 +16,7 @@ import {
 import AddIcon from '@mui/icons-material/Add';
 import DeleteIcon from '@mui/icons-material/Delete';
 import { TaskDefinition } from '../api';
+import { formatCommand } from '../utils';
 
 interface TaskDefinitionTableProps {
     taskDefinitions: TaskDefinition[];
@@ -128,7 +129,7 @@ const TaskDefinitionTable: React.FC<TaskDefinitionTableProps> = ({
                                   </TableCell>
                                   <TableCell>{task.version}</TableCell>
                                   <TableCell>{task.image}</TableCell>
-                                  <TableCell>{task.command}</TableCell>
+                                  <TableCell>{formatCommand(task.command)}</TableCell>
                                   <TableCell>
                                       {new Date(
                                           task.created_at
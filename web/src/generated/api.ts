@@ -0,0 +1,698 @@
+// Generated from the API routes and DTOs. Do not edit.
+// Regenerate: BATCHMAN_UPDATE_API_CLIENT=1 cargo test --lib openapi::typescript
+
+export interface AppendNodeJobLogsBody {
+  logs: NodeJobLogLine[];
+  offset: number;
+}
+
+export interface AppendNodeJobLogsResponse {
+  next_offset: number;
+}
+
+export interface CancelJobDto {
+  container_id: string | null;
+  grace_period_seconds: number;
+  job_id: number;
+}
+
+export interface ClaimNodeJobsBody {
+  limit: number;
+}
+
+export interface ClaimNodeJobsResponse {
+  jobs: NodeJobAssignment[];
+}
+
+export type ContainerType = "Docker";
+
+export interface CountJobLogsResponse {
+  count: number;
+}
+
+export interface CreateDefinitionResponse {
+  task_definition_id: number;
+}
+
+export interface CreateRegistryCredentialBody {
+  password: string;
+  registry: string;
+  username: string;
+}
+
+export interface CreateRegistryCredentialResponse {
+  registry_credential_id: number;
+}
+
+export interface CreateScheduleBody {
+  command?: string | null;
+  cron_expression: string;
+  enabled: boolean;
+  job_name: string;
+  name: string;
+  task_definition_id: number;
+  timezone?: string | null;
+  timezone_offset?: number | null;
+}
+
+export interface CreateSecretBody {
+  description?: string | null;
+  name: string;
+  value: string;
+}
+
+export interface CreateSecretResponse {
+  secret_id: number;
+}
+
+export interface CreateTaskDefinitionBody {
+  args?: string[] | null;
+  command?: string[] | null;
+  cpu_limit?: number | null;
+  cpu_shares?: number | null;
+  cpus?: number | null;
+  cpuset_cpus?: string | null;
+  description: string;
+  entrypoint?: string[] | null;
+  env?: Record<string, string> | null;
+  image: string;
+  image_pull_policy?: ImagePullPolicy | null;
+  labels?: Record<string, string> | null;
+  memory_limit?: number | null;
+  memory_reservation?: number | null;
+  memory_swap?: number | null;
+  mounts?: MountInput[] | null;
+  name: string;
+  network?: string | null;
+  placement_constraints?: PlacementConstraintInput[] | null;
+  secrets?: Record<string, string> | null;
+  shm_size?: number | null;
+  stop_grace_period_seconds?: number | null;
+  ulimits?: Ulimit[] | null;
+  user?: string | null;
+  working_dir?: string | null;
+}
+
+export interface ErrorResponse {
+  details?: FieldError[];
+  error_code: string;
+  message: string;
+  request_id: string | null;
+}
+
+export type ExitReason = "Succeeded" | "NonZeroExit" | "OomKilled" | "KilledByUser" | "RuntimeError";
+
+export interface FieldError {
+  field: string;
+  message: string;
+}
+
+export type ImagePullPolicy = "Always" | "IfNotPresent" | "Never";
+
+export interface JobDto {
+  cancel_reason: string | null;
+  cancelled_by: string | null;
+  container_id: string | null;
+  container_type: ContainerType;
+  cpu_seconds: number | null;
+  created_at: string;
+  error_code: string | null;
+  error_message: string | null;
+  exit_code: number | null;
+  exit_reason: ExitReason | null;
+  finished_at: string | null;
+  id: number;
+  log_expire_after: string | null;
+  log_expired: boolean;
+  name: string;
+  peak_memory_bytes: number | null;
+  schedule_id: number | null;
+  started_at: string | null;
+  status: JobStatusDto;
+  stop_requested_at: string | null;
+  submited_at: string | null;
+  task_definition_id: number;
+  task_definition_name: string | null;
+  worker_id: string | null;
+}
+
+export interface JobEventDto {
+  created_at: string;
+  from_status: JobStatusDto | null;
+  id: number;
+  reason: string | null;
+  to_status: JobStatusDto;
+}
+
+export interface JobLogDto {
+  index: number;
+  message: string;
+  time: string;
+}
+
+export interface JobMetricDto {
+  cpu_percent: number;
+  cpu_seconds: number;
+  memory_limit_bytes: number;
+  memory_usage_bytes: number;
+  pids: number;
+  sampled_at: string;
+}
+
+export type JobSortBy = "id" | "name" | "status" | "submitted_at" | "started_at" | "finished_at" | "created_at";
+
+export interface JobStatsQuery {
+  task_definition_id?: number | null;
+}
+
+export interface JobStatsResponse {
+  task_definitions: TaskDefinitionJobStatsDto[];
+}
+
+export type JobStatusDto = "Pending" | "Pulling" | "Starting" | "Running" | "Succeeded" | "Failed" | "Cancelling" | "Cancelled";
+
+export interface ListJobEventsResponse {
+  events: JobEventDto[];
+}
+
+export interface ListJobLogsQuery {
+  limit: number;
+  offset: number;
+}
+
+export interface ListJobLogsResponse {
+  logs: JobLogDto[];
+}
+
+export interface ListJobMetricsQuery {
+  limit?: number | null;
+  since?: string | null;
+}
+
+export interface ListJobMetricsResponse {
+  cpu_seconds: number | null;
+  metrics: JobMetricDto[];
+  peak_memory_bytes: number | null;
+}
+
+export interface ListJobsQuery {
+  after_id?: number | null;
+  container_id?: string | null;
+  contains_name?: string | null;
+  exit_code?: number | null;
+  finished_after?: string | null;
+  finished_before?: string | null;
+  job_id?: number | null;
+  order?: SortOrder | null;
+  page_number?: number | null;
+  page_size?: number | null;
+  schedule_id?: number | null;
+  sort_by?: JobSortBy | null;
+  started_after?: string | null;
+  started_before?: string | null;
+  status?: string | null;
+  submitted_after?: string | null;
+  submitted_before?: string | null;
+  task_definition_id?: number | null;
+  task_definition_name?: string | null;
+}
+
+export interface ListJobsResponse {
+  jobs: JobDto[];
+  next_cursor: number | null;
+  total_count: number;
+}
+
+export interface ListNodesQuery {
+  name?: string | null;
+  node_id?: number | null;
+}
+
+export interface ListNodesResponse {
+  nodes: NodeDto[];
+}
+
+export interface ListRegistryCredentialsItem {
+  created_at: string;
+  id: number;
+  registry: string;
+  updated_at: string;
+  username: string;
+}
+
+export interface ListRegistryCredentialsQuery {
+  page_number?: number | null;
+  page_size?: number | null;
+  registry?: string | null;
+  registry_credential_id?: number | null;
+}
+
+export interface ListRegistryCredentialsResponse {
+  registry_credentials: ListRegistryCredentialsItem[];
+  total_count: number;
+}
+
+export interface ListSchedulesItem {
+  command: string | null;
+  created_at: string;
+  cron_expression: string;
+  enabled: boolean;
+  id: number;
+  job_name: string;
+  name: string;
+  task_definition_id: number;
+  timezone: string | null;
+  timezone_offset: number | null;
+}
+
+export interface ListSchedulesQuery {
+  after_id?: number | null;
+  contains_name?: string | null;
+  enabled?: boolean | null;
+  name?: string | null;
+  page_number?: number | null;
+  page_size?: number | null;
+  schedule_id?: number | null;
+}
+
+export interface ListSchedulesResponse {
+  next_cursor: number | null;
+  schedules: ListSchedulesItem[];
+  total_count: number;
+}
+
+export interface ListSecretsItem {
+  created_at: string;
+  description: string;
+  id: number;
+  name: string;
+  updated_at: string;
+}
+
+export interface ListSecretsQuery {
+  contains_name?: string | null;
+  page_number?: number | null;
+  page_size?: number | null;
+  secret_id?: number | null;
+}
+
+export interface ListSecretsResponse {
+  secrets: ListSecretsItem[];
+  total_count: number;
+}
+
+export interface ListTaskDefinitionsItem {
+  args: string[] | null;
+  command: string[] | null;
+  cpu_shares: number | null;
+  cpus: number | null;
+  cpuset_cpus: string | null;
+  created_at: string;
+  description: string;
+  enabled: boolean;
+  entrypoint: string[] | null;
+  env: Record<string, string> | null;
+  id: number;
+  image: string;
+  image_pull_policy: ImagePullPolicy | null;
+  is_latest: boolean;
+  labels: Record<string, string> | null;
+  memory_limit: number | null;
+  memory_reservation: number | null;
+  memory_swap: number | null;
+  mounts: Mount[] | null;
+  name: string;
+  network: string | null;
+  placement_constraints: PlacementConstraint[] | null;
+  secrets: Record<string, string> | null;
+  shm_size: number | null;
+  stop_grace_period_seconds: number | null;
+  ulimits: Ulimit[] | null;
+  user: string | null;
+  version: number;
+  working_dir: string | null;
+}
+
+export interface ListTaskDefinitionsQuery {
+  after_id?: number | null;
+  contains_name?: string | null;
+  is_latest_only?: boolean | null;
+  name?: string | null;
+  page_number?: number | null;
+  page_size?: number | null;
+  task_definition_id?: number | null;
+}
+
+export interface ListTaskDefinitionsResponse {
+  next_cursor: number | null;
+  task_definitions: ListTaskDefinitionsItem[];
+  total_count: number;
+}
+
+export interface Mount {
+  read_only: boolean;
+  source: string | null;
+  target: string;
+  type: MountType;
+}
+
+export interface MountInput {
+  read_only?: boolean;
+  source?: string | null;
+  target: string;
+  type: MountType;
+}
+
+export type MountType = "bind" | "volume" | "tmpfs";
+
+export interface NodeDto {
+  capacity: number;
+  drained_at: string | null;
+  id: number;
+  labels: Record<string, string>;
+  last_heartbeat_at: string;
+  name: string;
+  registered_at: string;
+  running_jobs: number;
+  status: NodeStatus;
+}
+
+export interface NodeHeartbeatBody {
+  job_ids?: number[];
+  running_jobs: number;
+}
+
+export interface NodeHeartbeatResponse {
+  cancel_jobs: CancelJobDto[];
+}
+
+export interface NodeJobAssignment {
+  job_id: number;
+  registry_auth: RegistryAuth | null;
+  secret_env: Record<string, string>;
+  task_definition: TaskDefinition;
+}
+
+export interface NodeJobLogLine {
+  message: string;
+  time: string;
+}
+
+export type NodeStatus = "Active" | "Draining";
+
+export interface PatchRegistryCredentialBody {
+  password?: string | null;
+  username?: string | null;
+}
+
+export interface PatchScheduleBody {
+  command?: string | null;
+  cron_expression?: string | null;
+  enabled?: boolean | null;
+  job_name?: string | null;
+  name?: string | null;
+  task_definition_id?: number | null;
+  timezone?: string | null;
+  timezone_offset?: number | null;
+}
+
+export interface PatchSecretBody {
+  description?: string | null;
+  value?: string | null;
+}
+
+export interface PatchTaskDefinitionBody {
+  args?: string[] | null;
+  command?: string[] | null;
+  cpu_limit?: number | null;
+  cpu_shares?: number | null;
+  cpus?: number | null;
+  cpuset_cpus?: string | null;
+  description?: string | null;
+  enabled?: boolean | null;
+  entrypoint?: string[] | null;
+  env?: Record<string, string> | null;
+  image?: string | null;
+  image_pull_policy?: ImagePullPolicy | null;
+  labels?: Record<string, string> | null;
+  memory_limit?: number | null;
+  memory_reservation?: number | null;
+  memory_swap?: number | null;
+  mounts?: MountInput[] | null;
+  network?: string | null;
+  placement_constraints?: PlacementConstraintInput[] | null;
+  secrets?: Record<string, string> | null;
+  shm_size?: number | null;
+  stop_grace_period_seconds?: number | null;
+  ulimits?: Ulimit[] | null;
+  user?: string | null;
+  working_dir?: string | null;
+}
+
+export interface PlacementConstraint {
+  key: string;
+  operator: PlacementOperator;
+  values: string[];
+}
+
+export interface PlacementConstraintInput {
+  key: string;
+  operator: PlacementOperator;
+  values?: string[];
+}
+
+export type PlacementOperator = "In" | "NotIn" | "Exists" | "DoesNotExist";
+
+export interface PurgeJobsBody {
+  archive?: boolean;
+  dry_run?: boolean;
+  finished_before?: string | null;
+  schedule_id?: number | null;
+  statuses?: JobStatusDto[] | null;
+  task_definition_id?: number | null;
+}
+
+export interface PurgeJobsResponse {
+  matched_count: number;
+  purged_count: number;
+}
+
+export interface RegisterNodeBody {
+  capacity: number;
+  labels: Record<string, string>;
+  name: string;
+}
+
+export interface RegisterNodeResponse {
+  heartbeat_timeout_seconds: number;
+  lease_duration_seconds: number;
+  node_id: number;
+}
+
+export interface RegistryAuth {
+  password: string;
+  registry: string;
+  username: string;
+}
+
+export interface ReportNodeJobBody {
+  container_id?: string | null;
+  error_code?: string | null;
+  error_message?: string | null;
+  exit_code?: number | null;
+  exit_reason?: ExitReason | null;
+  finished_at?: string | null;
+  reason?: string | null;
+  status: JobStatusDto;
+}
+
+export type SortOrder = "asc" | "desc";
+
+export interface StopJobBody {
+  grace_period_seconds?: number | null;
+  job_id: number;
+  reason?: string | null;
+  requested_by?: string | null;
+}
+
+export interface SubmitJobBody {
+  job_name: string;
+  log_expire_after?: string | null;
+  task_definition_id: number;
+}
+
+export interface SubmitJobResponse {
+  job_id: number;
+}
+
+export interface TaskDefinition {
+  args: string[] | null;
+  command: string[] | null;
+  cpu_shares: number | null;
+  cpuset_cpus: string | null;
+  created_at: string;
+  description: string;
+  enabled: boolean;
+  entrypoint: string[] | null;
+  env: Record<string, string> | null;
+  id: number;
+  image: string;
+  image_pull_policy: ImagePullPolicy | null;
+  is_latest: boolean;
+  labels: Record<string, string> | null;
+  memory_limit: number | null;
+  memory_reservation: number | null;
+  memory_swap: number | null;
+  mounts: Mount[] | null;
+  name: string;
+  nano_cpus: number | null;
+  network: string | null;
+  placement_constraints: PlacementConstraint[] | null;
+  secrets: Record<string, string> | null;
+  shm_size: number | null;
+  stop_grace_period_seconds: number | null;
+  ulimits: Ulimit[] | null;
+  user: string | null;
+  version: number;
+  working_dir: string | null;
+}
+
+export interface TaskDefinitionJobStatsDto {
+  removed_count: number;
+  status_counts: Record<string, number>;
+  task_definition_id: number;
+  task_definition_name: string | null;
+  total_count: number;
+}
+
+export interface Ulimit {
+  hard: number;
+  name: string;
+  soft: number;
+}
+
+export type Method = "GET" | "POST" | "PATCH" | "DELETE";
+
+export interface ApiRequest {
+  method: Method;
+  path: string; // path under /api
+  query?: object;
+  body?: unknown;
+}
+
+export interface Transport {
+  request<T>(request: ApiRequest): Promise<T>;
+}
+
+export const createClient = (transport: Transport) => ({
+  // Health check
+  healthz: () =>
+    transport.request<string>({ method: "GET", path: "/healthz" }),
+  // Check the database connection
+  databaseCheck: () =>
+    transport.request<string>({ method: "GET", path: "/database-check" }),
+  // This OpenAPI document
+  getOpenapiDocument: () =>
+    transport.request<unknown>({ method: "GET", path: "/openapi.json" }),
+  // API reference page
+  getApiDocs: () =>
+    transport.request<string>({ method: "GET", path: "/docs" }),
+  // List task definitions
+  listTaskDefinitions: (query: ListTaskDefinitionsQuery = {}) =>
+    transport.request<ListTaskDefinitionsResponse>({ method: "GET", path: "/task-definitions", query }),
+  // Create a task definition (a new version if the name exists)
+  createTaskDefinition: (body: CreateTaskDefinitionBody) =>
+    transport.request<CreateDefinitionResponse>({ method: "POST", path: "/task-definitions", body }),
+  // Update a task definition
+  patchTaskDefinition: (task_definition_id: number, body: PatchTaskDefinitionBody) =>
+    transport.request<null>({ method: "PATCH", path: `/task-definitions/${task_definition_id}`, body }),
+  // Delete a task definition
+  deleteTaskDefinition: (task_definition_id: number) =>
+    transport.request<null>({ method: "DELETE", path: `/task-definitions/${task_definition_id}` }),
+  // Submit a job
+  submitJob: (body: SubmitJobBody) =>
+    transport.request<SubmitJobResponse>({ method: "POST", path: "/jobs/submit", body }),
+  // Cancel a job
+  stopJob: (body: StopJobBody) =>
+    transport.request<null>({ method: "POST", path: "/jobs/stop", body }),
+  // List jobs
+  listJobs: (query: ListJobsQuery = {}) =>
+    transport.request<ListJobsResponse>({ method: "GET", path: "/jobs", query }),
+  // Archive or delete finished jobs
+  purgeJobs: (body: PurgeJobsBody) =>
+    transport.request<PurgeJobsResponse>({ method: "POST", path: "/jobs/purge", body }),
+  // Job counts per task definition
+  getJobStats: (query: JobStatsQuery = {}) =>
+    transport.request<JobStatsResponse>({ method: "GET", path: "/jobs/stats", query }),
+  // List job logs
+  listJobLogs: (job_id: number, query: ListJobLogsQuery) =>
+    transport.request<ListJobLogsResponse>({ method: "GET", path: `/jobs/${job_id}/logs`, query }),
+  // Count job log lines
+  countJobLogs: (job_id: number) =>
+    transport.request<CountJobLogsResponse>({ method: "GET", path: `/jobs/${job_id}/logs/count` }),
+  // List job resource usage samples
+  listJobMetrics: (job_id: number, query: ListJobMetricsQuery = {}) =>
+    transport.request<ListJobMetricsResponse>({ method: "GET", path: `/jobs/${job_id}/metrics`, query }),
+  // List job status changes
+  listJobEvents: (job_id: number) =>
+    transport.request<ListJobEventsResponse>({ method: "GET", path: `/jobs/${job_id}/events` }),
+  // Stream job logs (server-sent events)
+  tailJobLogs: (job_id: number) =>
+    transport.request<string>({ method: "GET", path: `/jobs/${job_id}/logs/tailing` }),
+  // List agent nodes
+  listNodes: (query: ListNodesQuery = {}) =>
+    transport.request<ListNodesResponse>({ method: "GET", path: "/nodes", query }),
+  // Register an agent
+  registerNode: (body: RegisterNodeBody) =>
+    transport.request<RegisterNodeResponse>({ method: "POST", path: "/nodes/register", body }),
+  // Agent heartbeat
+  heartbeatNode: (node_id: number, body: NodeHeartbeatBody) =>
+    transport.request<NodeHeartbeatResponse>({ method: "POST", path: `/nodes/${node_id}/heartbeat`, body }),
+  // Claim pending jobs for an agent
+  claimNodeJobs: (node_id: number, body: ClaimNodeJobsBody) =>
+    transport.request<ClaimNodeJobsResponse>({ method: "POST", path: `/nodes/${node_id}/claim`, body }),
+  // Report the status of a job running on an agent
+  reportNodeJob: (node_id: number, job_id: number, body: ReportNodeJobBody) =>
+    transport.request<null>({ method: "POST", path: `/nodes/${node_id}/jobs/${job_id}/report`, body }),
+  // Upload log lines of a job running on an agent
+  appendNodeJobLogs: (node_id: number, job_id: number, body: AppendNodeJobLogsBody) =>
+    transport.request<AppendNodeJobLogsResponse>({ method: "POST", path: `/nodes/${node_id}/jobs/${job_id}/logs`, body }),
+  // List secrets (values are never returned)
+  listSecrets: (query: ListSecretsQuery = {}) =>
+    transport.request<ListSecretsResponse>({ method: "GET", path: "/secrets", query }),
+  // Create a secret
+  createSecret: (body: CreateSecretBody) =>
+    transport.request<CreateSecretResponse>({ method: "POST", path: "/secrets", body }),
+  // Update a secret
+  patchSecret: (secret_id: number, body: PatchSecretBody) =>
+    transport.request<null>({ method: "PATCH", path: `/secrets/${secret_id}`, body }),
+  // Delete a secret
+  deleteSecret: (secret_id: number) =>
+    transport.request<null>({ method: "DELETE", path: `/secrets/${secret_id}` }),
+  // List registry credentials (passwords are never returned)
+  listRegistryCredentials: (query: ListRegistryCredentialsQuery = {}) =>
+    transport.request<ListRegistryCredentialsResponse>({ method: "GET", path: "/registry-credentials", query }),
+  // Create a registry credential
+  createRegistryCredential: (body: CreateRegistryCredentialBody) =>
+    transport.request<CreateRegistryCredentialResponse>({ method: "POST", path: "/registry-credentials", body }),
+  // Update a registry credential
+  patchRegistryCredential: (registry_credential_id: number, body: PatchRegistryCredentialBody) =>
+    transport.request<null>({ method: "PATCH", path: `/registry-credentials/${registry_credential_id}`, body }),
+  // Delete a registry credential
+  deleteRegistryCredential: (registry_credential_id: number) =>
+    transport.request<null>({ method: "DELETE", path: `/registry-credentials/${registry_credential_id}` }),
+  // List schedules
+  listSchedules: (query: ListSchedulesQuery = {}) =>
+    transport.request<ListSchedulesResponse>({ method: "GET", path: "/schedules", query }),
+  // Create a schedule
+  createSchedule: (body: CreateScheduleBody) =>
+    transport.request<null>({ method: "POST", path: "/schedules", body }),
+  // Update a schedule
+  patchSchedule: (schedule_id: number, body: PatchScheduleBody) =>
+    transport.request<null>({ method: "PATCH", path: `/schedules/${schedule_id}`, body }),
+  // Delete a schedule
+  deleteSchedule: (schedule_id: number) =>
+    transport.request<null>({ method: "DELETE", path: `/schedules/${schedule_id}` }),
+});
+
+export type Client = ReturnType<typeof createClient>;
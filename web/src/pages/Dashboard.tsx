@@ -1,12 +1,77 @@
-import React from 'react';
-import { Box, Typography, Paper } from '@mui/material';
+import React, { useEffect, useState } from 'react';
+import { Alert, Box, Typography, Paper } from '@mui/material';
+import {
+    ErrorResponse,
+    getJobStats,
+    JobStatsResponse,
+    JobStatus,
+} from '../api';
+
+interface JobCounts {
+    total: number;
+    running: number;
+    succeeded: number;
+    failed: number;
+}
+
+// 보관/삭제된 job도 포함한 task definition별 집계를 합산
+const sumJobCounts = (stats: JobStatsResponse): JobCounts => {
+    const countOf = (status: JobStatus) =>
+        stats.task_definitions.reduce(
+            (sum, task_definition) =>
+                sum + (task_definition.status_counts[status] ?? 0),
+            0,
+        );
+
+    return {
+        total: stats.task_definitions.reduce(
+            (sum, task_definition) => sum + task_definition.total_count,
+            0,
+        ),
+        running: countOf('Running'),
+        succeeded: countOf('Succeeded'),
+        failed: countOf('Failed'),
+    };
+};
 
 const Dashboard: React.FC = () => {
+    const [counts, setCounts] = useState<JobCounts>({
+        total: 0,
+        running: 0,
+        succeeded: 0,
+        failed: 0,
+    });
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        const fetchJobStats = async () => {
+            const result = await getJobStats();
+
+            if (result.status_code === 200) {
+                setCounts(sumJobCounts(result.response as JobStatsResponse));
+                setError(null);
+            } else {
+                const errorResponse = result.response as ErrorResponse;
+                setError(
+                    errorResponse.message ||
+                        '통계를 불러오는 중 오류가 발생했습니다.',
+                );
+            }
+        };
+
+        fetchJobStats();
+    }, []);
+
     return (
         <Box sx={{ p: 3 }}>
             <Typography variant="h4" gutterBottom>
                 대시보드
             </Typography>
+            {error && (
+                <Alert severity="error" sx={{ mb: 2 }}>
+                    {error}
+                </Alert>
+            )}
             <Box
                 sx={{
                     display: 'grid',
@@ -23,7 +88,7 @@ const Dashboard: React.FC = () => {
                         <Typography variant="h6" gutterBottom>
                             전체 태스크
                         </Typography>
-                        <Typography variant="h4">0</Typography>
+                        <Typography variant="h4">{counts.total}</Typography>
                     </Paper>
                 </Box>
                 <Box>
@@ -31,7 +96,7 @@ const Dashboard: React.FC = () => {
                         <Typography variant="h6" gutterBottom>
                             실행 중인 태스크
                         </Typography>
-                        <Typography variant="h4">0</Typography>
+                        <Typography variant="h4">{counts.running}</Typography>
                     </Paper>
                 </Box>
                 <Box>
@@ -39,7 +104,7 @@ const Dashboard: React.FC = () => {
                         <Typography variant="h6" gutterBottom>
                             성공한 태스크
                         </Typography>
-                        <Typography variant="h4">0</Typography>
+                        <Typography variant="h4">{counts.succeeded}</Typography>
                     </Paper>
                 </Box>
                 <Box>
@@ -47,7 +112,7 @@ const Dashboard: React.FC = () => {
                         <Typography variant="h6" gutterBottom>
                             실패한 태스크
                         </Typography>
-                        <Typography variant="h4">0</Typography>
+                        <Typography variant="h4">{counts.failed}</Typography>
                     </Paper>
                 </Box>
             </Box>
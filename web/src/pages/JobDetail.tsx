@@ -28,12 +28,15 @@ import VisibilityIcon from "@mui/icons-material/Visibility";
 import RestartAltIcon from "@mui/icons-material/RestartAlt";
 import {
   Job,
+  ExitReason,
   JobStatus,
   JobLog,
+  JobEvent,
   TaskDefinition,
   listJobs,
   listTaskDefinitions,
   listJobLogs,
+  listJobEvents,
   stopJob,
   submitJob,
   ErrorResponse,
@@ -48,6 +51,7 @@ const JobDetail: React.FC = () => {
     null,
   );
   const [logs, setLogs] = useState<JobLog[]>([]);
+  const [events, setEvents] = useState<JobEvent[]>([]);
   const [isLoading, setIsLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
   const [isStoppingJob, setIsStoppingJob] = useState(false);
@@ -208,9 +212,26 @@ const JobDetail: React.FC = () => {
     fetchJobDetail();
   }, [jobId]);
 
+  const fetchJobEvents = async () => {
+    if (!jobId) return;
+
+    try {
+      const eventsResult = await listJobEvents(parseInt(jobId));
+
+      if (eventsResult.response instanceof ErrorResponse) {
+        console.error("Failed to fetch job events:", eventsResult.response);
+      } else {
+        setEvents(eventsResult.response.events);
+      }
+    } catch (err) {
+      console.error("Failed to fetch job events:", err);
+    }
+  };
+
   useEffect(() => {
     if (job) {
       fetchJobLogs();
+      fetchJobEvents();
     }
   }, [job]);
 
@@ -218,14 +239,20 @@ const JobDetail: React.FC = () => {
     switch (status) {
       case "Pending":
         return "warning";
+      case "Pulling":
+        return "info";
       case "Starting":
         return "info";
       case "Running":
         return "primary";
-      case "Finished":
+      case "Succeeded":
         return "success";
       case "Failed":
         return "error";
+      case "Cancelling":
+        return "warning";
+      case "Cancelled":
+        return "default";
       default:
         return "default";
     }
@@ -235,29 +262,55 @@ const JobDetail: React.FC = () => {
     switch (status) {
       case "Pending":
         return "대기중";
+      case "Pulling":
+        return "이미지 받는중";
       case "Starting":
         return "시작중";
       case "Running":
         return "실행중";
-      case "Finished":
+      case "Succeeded":
         return "완료";
       case "Failed":
         return "실패";
+      case "Cancelling":
+        return "취소중";
+      case "Cancelled":
+        return "취소됨";
       default:
         return status;
     }
   };
 
+  const getExitReasonLabel = (exitReason: ExitReason) => {
+    switch (exitReason) {
+      case "Succeeded":
+        return "정상 종료";
+      case "NonZeroExit":
+        return "비정상 종료";
+      case "OomKilled":
+        return "메모리 초과 (OOM)";
+      case "KilledByUser":
+        return "사용자 취소";
+      case "RuntimeError":
+        return "런타임 오류";
+      default:
+        return exitReason;
+    }
+  };
+
   const canStopJob =
     job &&
     (job.status === "Pending" ||
+      job.status === "Pulling" ||
       job.status === "Starting" ||
       job.status === "Running");
 
   const canRetryJob =
     job &&
     taskDefinition &&
-    (job.status === "Finished" || job.status === "Failed");
+    (job.status === "Succeeded" ||
+      job.status === "Failed" ||
+      job.status === "Cancelled");
 
   if (isLoading) {
     return (
@@ -432,6 +485,51 @@ const JobDetail: React.FC = () => {
                       {job.exit_code !== null ? job.exit_code : "-"}
                     </TableCell>
                   </TableRow>
+                  <TableRow>
+                    <TableCell
+                      component="th"
+                      scope="row"
+                      sx={{ fontWeight: "bold" }}
+                    >
+                      종료 사유
+                    </TableCell>
+                    <TableCell>
+                      {job.exit_reason ? getExitReasonLabel(job.exit_reason) : "-"}
+                    </TableCell>
+                  </TableRow>
+                  <TableRow>
+                    <TableCell
+                      component="th"
+                      scope="row"
+                      sx={{ fontWeight: "bold" }}
+                    >
+                      최대 메모리 / CPU 시간
+                    </TableCell>
+                    <TableCell>
+                      {job.peak_memory_bytes !== null
+                        ? `${(job.peak_memory_bytes / 1024 / 1024).toFixed(1)} MB`
+                        : "-"}
+                      {" / "}
+                      {job.cpu_seconds !== null
+                        ? `${job.cpu_seconds.toFixed(1)} s`
+                        : "-"}
+                    </TableCell>
+                  </TableRow>
+                  {job.cancel_reason && (
+                    <TableRow>
+                      <TableCell
+                        component="th"
+                        scope="row"
+                        sx={{ fontWeight: "bold" }}
+                      >
+                        취소 사유
+                      </TableCell>
+                      <TableCell>
+                        {job.cancel_reason}
+                        {job.cancelled_by && ` (${job.cancelled_by})`}
+                      </TableCell>
+                    </TableRow>
+                  )}
                   {job.error_message && (
                     <TableRow>
                       <TableCell
@@ -556,6 +654,29 @@ const JobDetail: React.FC = () => {
                   </TableRow>
                 </TableBody>
               </Table>
+
+              <Typography variant="h6" gutterBottom sx={{ mt: 3 }}>
+                상태 변경 이력
+              </Typography>
+              <Divider sx={{ mb: 2 }} />
+              <Table size="small">
+                <TableBody>
+                  {events.map((event) => (
+                    <TableRow key={event.id}>
+                      <TableCell sx={{ whiteSpace: "nowrap" }}>
+                        {formatDate(event.created_at)}
+                      </TableCell>
+                      <TableCell sx={{ whiteSpace: "nowrap" }}>
+                        {event.from_status
+                          ? `${getStatusLabel(event.from_status)} → `
+                          : ""}
+                        {getStatusLabel(event.to_status)}
+                      </TableCell>
+                      <TableCell>{event.reason ?? "-"}</TableCell>
+                    </TableRow>
+                  ))}
+                </TableBody>
+              </Table>
             </CardContent>
           </Card>
         </Box>
@@ -180,10 +180,13 @@ const JobList: React.FC = () => {
           >
             <MenuItem value="">전체</MenuItem>
             <MenuItem value="Pending">대기중</MenuItem>
+            <MenuItem value="Pulling">이미지 받는중</MenuItem>
             <MenuItem value="Starting">시작중</MenuItem>
             <MenuItem value="Running">실행중</MenuItem>
-            <MenuItem value="Finished">완료</MenuItem>
+            <MenuItem value="Succeeded">완료</MenuItem>
             <MenuItem value="Failed">실패</MenuItem>
+            <MenuItem value="Cancelling">취소중</MenuItem>
+            <MenuItem value="Cancelled">취소됨</MenuItem>
           </Select>
         </FormControl>
         <Button
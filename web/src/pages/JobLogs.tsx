@@ -269,11 +269,12 @@ const JobLogs: React.FC = () => {
     switch (status) {
       case "Running":
         return "info";
-      case "Finished":
+      case "Succeeded":
         return "success";
       case "Failed":
         return "error";
       case "Pending":
+      case "Cancelling":
         return "warning";
       default:
         return "default";
@@ -33,7 +33,7 @@ import {
   createTaskDefinition,
   ErrorResponse,
 } from "../api";
-import { formatDate } from "../utils";
+import { formatCommand, formatDate, splitCommand, toEnvMap } from "../utils";
 import CreateTaskDefinitionModal from "../components/CreateTaskDefinitionModal";
 import JobCreateModal from "../components/JobCreateModal";
 import { CreateTaskDefinitionFormData } from "../types/taskDefinition";
@@ -155,13 +155,13 @@ const TaskDefinitionDetail: React.FC = () => {
         name: data.name, // 동일한 이름으로 새 버전 생성
         description: data.description,
         image: data.image,
-        command: data.command,
-        env: JSON.stringify(data.env),
+        command: splitCommand(data.command),
+        env: toEnvMap(data.env),
         memory_limit:
           data.resources.memory.unit === "g"
             ? data.resources.memory.value * 1024
             : data.resources.memory.value,
-        cpu_limit: data.resources.cpu,
+        cpu_shares: data.resources.cpu,
         args: undefined,
       });
 
@@ -196,13 +196,13 @@ const TaskDefinitionDetail: React.FC = () => {
 
       const result = await patchTaskDefinition(parseInt(taskDefinitionId), {
         image: data.image,
-        command: data.command,
-        env: JSON.stringify(data.env),
+        command: splitCommand(data.command),
+        env: toEnvMap(data.env),
         memory_limit:
           data.resources.memory.unit === "g"
             ? data.resources.memory.value * 1024
             : data.resources.memory.value,
-        cpu_limit: data.resources.cpu,
+        cpu_shares: data.resources.cpu,
       });
 
       if (result.response instanceof ErrorResponse) {
@@ -401,7 +401,7 @@ const TaskDefinitionDetail: React.FC = () => {
                       명령어
                     </TableCell>
                     <TableCell sx={{ wordBreak: "break-all" }}>
-                      {taskDefinition.command || "-"}
+                      {formatCommand(taskDefinition.command) || "-"}
                     </TableCell>
                   </TableRow>
                   <TableRow>
@@ -451,8 +451,8 @@ const TaskDefinitionDetail: React.FC = () => {
                       CPU 제한
                     </TableCell>
                     <TableCell>
-                      {taskDefinition.cpu_limit
-                        ? `${taskDefinition.cpu_limit}`
+                      {taskDefinition.cpu_shares
+                        ? `${taskDefinition.cpu_shares}`
                         : "-"}
                     </TableCell>
                   </TableRow>
@@ -28,6 +28,7 @@ import {
 } from '../api';
 import { useSearchParams } from 'react-router-dom';
 import SearchIcon from '@mui/icons-material/Search';
+import { splitCommand, toEnvMap } from '../utils';
 
 const TaskDefinitionList: React.FC = () => {
     const [searchParams, setSearchParams] = useSearchParams();
@@ -141,10 +142,10 @@ const TaskDefinitionList: React.FC = () => {
                 name: data.name,
                 description: data.description,
                 image: data.image,
-                command: data.command,
-                env: JSON.stringify(data.env),
+                command: splitCommand(data.command),
+                env: toEnvMap(data.env),
                 memory_limit: memoryLimitInMB,
-                cpu_limit: data.resources.cpu,
+                cpu_shares: data.resources.cpu,
                 args: undefined,
             });
 
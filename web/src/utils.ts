@@ -4,3 +4,70 @@ export const formatDate = (dateString: string | null) => {
 
     return new Date(dateString).toLocaleString('ko-KR');
 };
+
+// 쉘과 유사하게 따옴표를 고려하여 명령어 문자열을 argv 배열로 분리합니다.
+// 예: `sh -c "echo a, b"` => ["sh", "-c", "echo a, b"]
+export const splitCommand = (command: string): string[] => {
+    const args: string[] = [];
+    let current = '';
+    let quote: '"' | "'" | null = null;
+    let hasToken = false;
+
+    for (let i = 0; i < command.length; i++) {
+        const char = command[i];
+
+        if (quote) {
+            if (char === quote) {
+                quote = null;
+            } else if (char === '\\' && quote === '"' && i + 1 < command.length) {
+                current += command[++i];
+            } else {
+                current += char;
+            }
+        } else if (char === '"' || char === "'") {
+            quote = char;
+            hasToken = true;
+        } else if (char === '\\' && i + 1 < command.length) {
+            current += command[++i];
+            hasToken = true;
+        } else if (/\s/.test(char)) {
+            if (hasToken) {
+                args.push(current);
+                current = '';
+                hasToken = false;
+            }
+        } else {
+            current += char;
+            hasToken = true;
+        }
+    }
+
+    if (hasToken) {
+        args.push(current);
+    }
+
+    return args;
+};
+
+// argv 배열을 사람이 읽을 수 있는 명령어 문자열로 변환합니다.
+export const formatCommand = (command: string[] | null | undefined) => {
+    if (!command || command.length === 0) return '';
+
+    return command
+        .map((arg) =>
+            arg === '' || /[\s"'\\]/.test(arg)
+                ? `"${arg.replace(/(["\\])/g, '\\$1')}"`
+                : arg
+        )
+        .join(' ');
+};
+
+// [{ key, value }] 형식의 폼 데이터를 환경 변수 객체로 변환합니다.
+export const toEnvMap = (env: { key: string; value: string }[]) =>
+    Object.fromEntries(
+        env.filter((item) => item.key.trim() !== '').map((item) => [item.key, item.value])
+    );
+
+// 환경 변수 객체를 [{ key, value }] 형식으로 변환합니다.
+export const fromEnvMap = (env: Record<string, string> | null | undefined) =>
+    Object.entries(env ?? {}).map(([key, value]) => ({ key, value }));